@@ -1,7 +1,10 @@
+// - STD
+use std::fmt;
+
 // - internal
 use crate::{
 	header::ChunkFlags, 
-	io::calculate_xxhash, 
+	io::{calculate_xxhash, calculate_crc64}, 
 	Result,
 };
 
@@ -37,15 +40,48 @@ impl Chunk {
 		self.size
 	}
 
-	/// Checks the integrity of the chunk data by calculating the appropriate xxhash hash and comparing it with the given hash.
+	/// Checks the integrity of the chunk data by calculating the appropriate integrity hash (see [ChunkFlags::integrity])
+	/// and comparing it with the given hash.
 	///
-	/// Returns true if the xxhash hash is equal to the hash in the header, otherwise false. 
+	/// Returns true if the calculated hash is equal to the given hash, otherwise false. 
 	pub fn check_integrity(&self, original_hash: u64) -> Result<bool> {
-		let calculated_hash = calculate_xxhash(&self.data);
+		let calculated_hash = self.flags.integrity().calculate(&self.data);
 		Ok(calculated_hash == original_hash)
 	}
 }
 
+/// The algorithm, which is used to calculate the integrity hash of each chunk.
+/// The integrity hashes are stored in the [ChunkXxHashMap](crate::header::ChunkXxHashMap) and
+/// the appropriate algorithm is recorded in the [ChunkFlags] of each chunk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChunkIntegrity {
+	/// xxh3 (64 bit), the default algorithm.
+	#[default]
+	Xxh3,
+	/// CRC-64/XZ
+	Crc64,
+}
+
+impl ChunkIntegrity {
+	/// Calculates the integrity hash of the given data by using the appropriate algorithm.
+	pub fn calculate(&self, data: &[u8]) -> u64 {
+		match self {
+			ChunkIntegrity::Xxh3 => calculate_xxhash(data),
+			ChunkIntegrity::Crc64 => calculate_crc64(data),
+		}
+	}
+}
+
+impl fmt::Display for ChunkIntegrity {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let value = match self {
+			ChunkIntegrity::Xxh3 => "xxh3",
+			ChunkIntegrity::Crc64 => "CRC-64",
+		};
+		write!(f, "{value}")
+	}
+}
+
 /// This struct represents a prepared [Chunk] (encrypted and compressed).
 #[derive(Debug, Clone)]
 pub struct PreparedChunk {
@@ -99,6 +135,11 @@ impl PreparedChunk {
 	pub fn duplicated(&self) -> Option<u64> {
 		self.duplicated
 	}
+
+	/// Sets the error flag (e.g. if the chunk contains unreadable sectors of the input data).
+	pub(crate) fn set_error_flag(&mut self) {
+		self.flags.error = true;
+	}
 }
 
 #[derive(Debug, Clone)]
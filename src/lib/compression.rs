@@ -5,7 +5,12 @@ use std::fmt;
 
 
 // - internal
-use crate::Result;
+use crate::{
+	Result,
+	ZffError,
+	ZffErrorKind,
+	constants::*,
+};
 
 // - external
 #[cfg(feature = "serde")]
@@ -27,6 +32,13 @@ pub enum CompressionAlgorithm {
 	Zstd = 1,
 	/// LZ4 compression - encoded as 2 in the header. LZ4 frame format is used (not the LZ4 block format) for compression.
 	Lz4 = 2,
+	/// Brotli compression - encoded as 3 in the header. The compression level is used as brotli quality (0-11).
+	Brotli = 3,
+	/// Xz (LZMA2) compression - encoded as 4 in the header. The compression level is used as xz preset (0-9).
+	Xz = 4,
+	/// Gzip (DEFLATE) compression - encoded as 5 in the header. The compression level is used as gzip level (0-9).
+	/// Each compressed chunk is a complete gzip stream, which can be decompressed by external tools.
+	Gzip = 5,
 }
 
 impl From<&str> for CompressionAlgorithm {
@@ -35,6 +47,9 @@ impl From<&str> for CompressionAlgorithm {
 		match algorithm.as_str() {
 			"zstd" => CompressionAlgorithm::Zstd,
 			"lz4" => CompressionAlgorithm::Lz4,
+			"brotli" => CompressionAlgorithm::Brotli,
+			"xz" | "lzma" => CompressionAlgorithm::Xz,
+			"gzip" | "gz" | "deflate" => CompressionAlgorithm::Gzip,
 			_ => CompressionAlgorithm::None,
 		}
 	}
@@ -45,6 +60,9 @@ impl fmt::Display for CompressionAlgorithm {
     	let value = match self {
     		CompressionAlgorithm::Zstd => "Zstd",
     		CompressionAlgorithm::Lz4 => "Lz4",
+    		CompressionAlgorithm::Brotli => "Brotli",
+    		CompressionAlgorithm::Xz => "Xz",
+    		CompressionAlgorithm::Gzip => "Gzip",
     		CompressionAlgorithm::None => "None",
     	};
         write!(f, "{value}")
@@ -56,21 +74,58 @@ pub fn decompress_buffer<C>(buffer: &[u8], compression_algorithm: C) -> Result<V
 where
 	C: Borrow<CompressionAlgorithm>,
 {
-	match compression_algorithm.borrow() {
-    	CompressionAlgorithm::None => Ok(buffer.to_vec()),
-    	CompressionAlgorithm::Zstd => {
-    		let mut decompressed_buffer = Vec::new();
-			let mut decoder = zstd::stream::read::Decoder::new(buffer)?;
-			decoder.read_to_end(&mut decompressed_buffer)?;
-			Ok(decompressed_buffer)
-    	},
-    	CompressionAlgorithm::Lz4 => {
-    		let mut decompressed_buffer = Vec::new();
-			let mut decompressor = lz4_flex::frame::FrameDecoder::new(buffer);
-			decompressor.read_to_end(&mut decompressed_buffer)?;
-			Ok(decompressed_buffer)
-    	}
-    }
+	decompress_buffer_with_dictionary(buffer, compression_algorithm, None)
+}
+
+/// Decompresses a buffer with the given [CompressionAlgorithm] and the optional (zstd) dictionary.
+/// # Error
+/// Fails (besides the usual decompression errors) with [ZffErrorKind::MissingCompressionDictionary],
+/// if the zstd compressed data needs a dictionary, but no dictionary was given.
+pub fn decompress_buffer_with_dictionary<C>(buffer: &[u8], compression_algorithm: C, dictionary: Option<&[u8]>) -> Result<Vec<u8>>
+where
+	C: Borrow<CompressionAlgorithm>,
+{
+	let mut decompressed_buffer = Vec::new();
+	buffer_decoder(buffer, compression_algorithm.borrow(), dictionary)?.read_to_end(&mut decompressed_buffer)?;
+	Ok(decompressed_buffer)
+}
+
+/// Works like [decompress_buffer_with_dictionary], but the decompressed data must not be larger than the given maximum size
+/// (e.g. the chunk size of the appropriate object). The output buffer will be allocated only once (with the maximum size),
+/// so the memory usage is bounded - regardless of the content of the compressed data.
+/// # Error
+/// Fails with [ZffErrorKind::MalformedSegment], if the decompressed data is larger than the given maximum size.
+pub fn decompress_buffer_with_limit<C>(buffer: &[u8], compression_algorithm: C, dictionary: Option<&[u8]>, max_size: usize) -> Result<Vec<u8>>
+where
+	C: Borrow<CompressionAlgorithm>,
+{
+	let mut decompressed_buffer = Vec::with_capacity(max_size);
+	let decoder = buffer_decoder(buffer, compression_algorithm.borrow(), dictionary)?;
+	// reads one more byte to detect oversized data.
+	decoder.take(max_size as u64 + 1).read_to_end(&mut decompressed_buffer)?;
+	if decompressed_buffer.len() > max_size {
+		return Err(ZffError::new(ZffErrorKind::MalformedSegment, format!("{ERROR_DECOMPRESSED_DATA_EXCEEDS_LIMIT}{max_size}")));
+	}
+	Ok(decompressed_buffer)
+}
+
+// returns the appropriate decoder for the given compressed buffer.
+fn buffer_decoder<'a>(buffer: &'a [u8], compression_algorithm: &CompressionAlgorithm, dictionary: Option<&[u8]>) -> Result<Box<dyn Read + 'a>> {
+	match compression_algorithm {
+		CompressionAlgorithm::None => Ok(Box::new(buffer)),
+		CompressionAlgorithm::Zstd => {
+			if dictionary.is_none() {
+				if let Some(dictionary_id) = zstd::zstd_safe::get_dict_id_from_frame(buffer) {
+					return Err(ZffError::new(ZffErrorKind::MissingCompressionDictionary, format!("{ERROR_MISSING_ZSTD_DICTIONARY}{dictionary_id}")));
+				}
+			}
+			Ok(Box::new(zstd::stream::read::Decoder::with_dictionary(buffer, dictionary.unwrap_or_default())?))
+		},
+		CompressionAlgorithm::Lz4 => Ok(Box::new(lz4_flex::frame::FrameDecoder::new(buffer))),
+		CompressionAlgorithm::Brotli => Ok(Box::new(brotli::Decompressor::new(buffer, DEFAULT_BROTLI_BUFFER_SIZE))),
+		CompressionAlgorithm::Xz => Ok(Box::new(xz2::read::XzDecoder::new(buffer))),
+		CompressionAlgorithm::Gzip => Ok(Box::new(flate2::read::GzDecoder::new(buffer))),
+	}
 }
 
 /// Decompresses a reader with the given [CompressionAlgorithm].
@@ -89,5 +144,56 @@ where
 			let decompressor = lz4_flex::frame::FrameDecoder::new(input);
 			Ok(Box::new(decompressor))
 		},
+		CompressionAlgorithm::Brotli => {
+			let decompressor = brotli::Decompressor::new(input, DEFAULT_BROTLI_BUFFER_SIZE);
+			Ok(Box::new(decompressor))
+		},
+		CompressionAlgorithm::Xz => {
+			let decompressor = xz2::read::XzDecoder::new(input);
+			Ok(Box::new(decompressor))
+		},
+		CompressionAlgorithm::Gzip => {
+			let decompressor = flate2::read::GzDecoder::new(input);
+			Ok(Box::new(decompressor))
+		},
 	}
-}
\ No newline at end of file
+}
+
+/// Compresses the given buffer with brotli. The given level will be used as brotli quality
+/// (values greater than [BROTLI_MAX_QUALITY] will be clamped).
+pub(crate) fn compress_buffer_brotli(buffer: &[u8], level: u8) -> Result<Vec<u8>> {
+	let params = brotli::enc::BrotliEncoderParams {
+		quality: level.min(BROTLI_MAX_QUALITY) as i32,
+		lgwin: BROTLI_DEFAULT_LGWIN,
+		..Default::default()
+	};
+	let mut compressed_data = Vec::new();
+	brotli::BrotliCompress(&mut &buffer[..], &mut compressed_data, &params)?;
+	Ok(compressed_data)
+}
+
+/// Compresses the given buffer with xz (LZMA2). The given level will be used as xz preset
+/// (values greater than [XZ_MAX_PRESET] will be clamped).
+pub(crate) fn compress_buffer_xz(buffer: &[u8], level: u8) -> Result<Vec<u8>> {
+	let mut compressor = xz2::read::XzEncoder::new(buffer, level.min(XZ_MAX_PRESET) as u32);
+	let mut compressed_data = Vec::new();
+	compressor.read_to_end(&mut compressed_data)?;
+	Ok(compressed_data)
+}
+
+/// Compresses the given buffer with gzip. The given level will be used as gzip level
+/// (values greater than [GZIP_MAX_LEVEL] will be clamped).
+pub(crate) fn compress_buffer_gzip(buffer: &[u8], level: u8) -> Result<Vec<u8>> {
+	let mut compressor = flate2::read::GzEncoder::new(buffer, flate2::Compression::new(level.min(GZIP_MAX_LEVEL) as u32));
+	let mut compressed_data = Vec::new();
+	compressor.read_to_end(&mut compressed_data)?;
+	Ok(compressed_data)
+}
+
+/// Trains a zstd dictionary with the given samples (e.g. the content of some typical small files).  
+/// The returned dictionary could be used as [ZffCreationParameters::zstd_dictionary](crate::io::ZffCreationParameters).
+/// # Error
+/// Fails if the dictionary could not be trained (e.g. if there are not enough samples).
+pub fn train_zstd_dictionary(samples: &[Vec<u8>], dict_size: usize) -> Result<Vec<u8>> {
+	Ok(zstd::dict::from_samples(samples, dict_size)?)
+}
@@ -35,6 +35,8 @@ pub const HEADER_IDENTIFIER_CHUNK_XXHASH_MAP: u32 = 0x7a666669;
 pub const HEADER_IDENTIFIER_CHUNK_SAMEBYTES_MAP: u32 = 0x7a666653;
 /// The identifier of the [ChunkDeduplicationMap](crate::header::ChunkFlagMap).
 pub const HEADER_IDENTIFIER_CHUNK_DEDUPLICATION_MAP: u32 = 0x7a666644;
+/// The identifier of the [ChunkParityMap](crate::header::ChunkParityMap).
+pub const HEADER_IDENTIFIER_CHUNK_PARITY_MAP: u32 = 0x7a666652;
 /// The identifier of the [VirtualMappingInformation](crate::header::VirtualMappingInformation).
 pub const HEADER_IDENTIFIER_VIRTUAL_MAPPING_INFORMATION: u32 = 0x7a666676;
 /// The identifier of the [VirtualLayer](crate::header::VirtualObjectMap).
@@ -62,6 +64,22 @@ pub const ENCODING_KEY_EXAMINER_NAME: &str = "ex";
 pub const ENCODING_KEY_NOTES: &str = "no";
 /// Encoding key for the global description notes.
 pub const ENCODING_KEY_DESCRIPTION_NOTES: &str = "dn";
+/// Encoding key for the chunk parity map table of the segment footer and the main footer.
+pub const ENCODING_KEY_CHUNK_PARITY_MAPS: &str = "pm";
+/// Encoding key for the container-wide acquisition start timestamp of the main footer.
+pub const ENCODING_KEY_ACQUISITION_START: &str = "as";
+/// Encoding key for the container-wide acquisition end timestamp of the main footer.
+pub const ENCODING_KEY_ACQUISITION_END: &str = "ae";
+
+// Field names (used by DescriptionHeader::from_fields() and DescriptionHeader::to_fields())
+/// Field name for the case number.
+pub const DESCRIPTION_FIELD_CASE_NUMBER: &str = "case_number";
+/// Field name for the evidence number.
+pub const DESCRIPTION_FIELD_EVIDENCE_NUMBER: &str = "evidence_number";
+/// Field name for the examiner name.
+pub const DESCRIPTION_FIELD_EXAMINER_NAME: &str = "examiner_name";
+/// Field name for the notes.
+pub const DESCRIPTION_FIELD_NOTES: &str = "notes";
 
 // chunk header flags
 pub(crate) const ERROR_FLAG_VALUE: u8 = 1<<0;
@@ -71,10 +89,12 @@ pub(crate) const DUPLICATION_FLAG_VALUE: u8 = 1<<3;
 pub(crate) const ENCRYPTION_FLAG_VALUE: u8 = 1<<4;
 pub(crate) const EMPTY_FILE_FLAG_VALUE: u8 = 1<<5;
 pub(crate) const VIRTUAL_FLAG_VALUE: u8 = 1<<6;
+pub(crate) const CRC64_FLAG_VALUE: u8 = 1<<7;
 
 // object header flags
 pub(crate) const ENCRYPT_OBJECT_FLAG_VALUE: u8 = 1<<0;
 pub(crate) const SIGN_HASH_FLAG_VALUE: u8 = 1<<1;
+pub(crate) const PLAINTEXT_DESCRIPTION_FLAG_VALUE: u8 = 1<<2;
 
 // - Error messages
 pub(crate) const ERROR_HEADER_DECODER_UNKNOWN_HASH_TYPE: &str = "Unknown hash type value.";
@@ -86,30 +106,91 @@ pub(crate) const FILE_EXTENSION_PARSER_ERROR: &str = "Error while trying to pars
 pub(crate) const ERROR_HEADER_DECODER_HEADER_LENGTH: &str = "Unable to read header length from given data.";
 pub(crate) const ERROR_HEADER_DECODER_KEY_POSITION: &str = "Key not in position.";
 pub(crate) const ERROR_HEADER_DECODER_COMPRESSION_ALGORITHM: &str = "unknown compression algorithm value";
+pub(crate) const ERROR_INVALID_SIGNATURE_LENGTH: &str = "Invalid length of the signature: ";
+pub(crate) const ERROR_OBJECT_HEADER_BUILDER_MISSING_ENCRYPTION_KEY: &str = "The encryption header contains no (decrypted) encryption key.";
+pub(crate) const ERROR_OBJECT_HEADER_BUILDER_INVALID_CHUNK_SIZE: &str = "The chunk size must be greater than zero.";
+pub(crate) const ERROR_MISSING_ZSTD_DICTIONARY: &str = "The compressed data needs a zstd dictionary which is not available, dictionary id: ";
 pub(crate) const ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER: &str = "The read identifier does not match the header identifier.";
+pub(crate) const ERROR_CHUNKMAP_AUTHENTICATION_FAILED: &str = "The authentication of the decrypted data failed, the encrypted chunk map is corrupt: ";
+pub(crate) const ERROR_PEEK_CONTAINER_TOO_SHORT: &str = "The data is too short to contain a zff segment header.";
+pub(crate) const ERROR_PEEK_CONTAINER_UNKNOWN_MAGIC: &str = "The data does not start with a known zff header identifier: ";
+pub(crate) const ERROR_PEEK_CONTAINER_LEGACY_VERSION_PREFIX: &str = "This looks like a zff v";
+pub(crate) const ERROR_PEEK_CONTAINER_LEGACY_VERSION_SUFFIX: &str = " container; this build supports zff v3 containers only. \
+	Please use a zff release of the appropriate major version to read (or to convert) this container.";
+pub(crate) const ERROR_PEEK_CONTAINER_UNSUPPORTED_VERSION: &str = "Unsupported zff segment header version: ";
 pub(crate) const ERROR_MISSING_SEGMENT_MAIN_FOOTER: &str = "A segment with a valid zff main footer is missing.";
+pub(crate) const ERROR_MISSING_SEGMENT_MAIN_FOOTER_EXPECTED_SEGMENT: &str = " The main footer is stored in the last segment, which has to be segment number ";
+pub(crate) const ERROR_MISSING_SEGMENT_MAIN_FOOTER_SUPPLIED_SEGMENTS: &str = " or higher. Supplied segments: ";
 pub(crate) const ERROR_MISSING_OBJECT_HEADER_IN_SEGMENT: &str = "Missing object header in segment with following object number: ";
 pub(crate) const ERROR_MISSING_OBJECT_FOOTER_IN_SEGMENT: &str = "Missing object footer in segment with following object number: ";
 pub(crate) const ERROR_MISSING_FILE_NUMBER: &str = "Missing filenumber: ";
 pub(crate) const ERROR_INVALID_OBJECT_TYPE_FLAG_VALUE: &str = "Invalid object type flag value:";
 
 pub(crate) const ERROR_ZFFREADER_SEGMENT_NOT_FOUND: &str = "The segment of the chunk was not found.";
+pub(crate) const ERROR_ZFFREADER_MISSING_CHUNK: &str = "The segment of the following chunk is missing or unreadable: ";
+pub(crate) const ERROR_ZFFREADER_MISSING_CHUNK_XXHASH: &str = "Missing xxhash value for chunk number: ";
+pub(crate) const ERROR_ZFFREADER_MISSING_CHUNK_MAP: &str = "Missing chunk map for chunk number: ";
+pub(crate) const ERROR_ZFFREADER_CHUNK_INTEGRITY: &str = "The integrity check (xxhash) failed for chunk number: ";
 pub(crate) const ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT: &str = "Operation not available for encrypted objects.";
 pub(crate) const ERROR_ZFFREADER_OPERATION_PHYSICAL_OBJECT: &str = "Operation not available for physical objects.";
 pub(crate) const ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT: &str = "Operation not available for virtual objects.";
+pub(crate) const ERROR_ZFFREADER_OPERATION_LOGICAL_OBJECT: &str = "Operation not available for logical objects.";
+pub(crate) const ERROR_ZFFREADER_SIGNATURES_OF_ENCRYPTED_OBJECT: &str = "The signatures are calculated over the hash values of the plaintext data, \
+which are stored in the encrypted footers. The object has to be decrypted (the decryption password is required) to verify the signatures.";
+pub(crate) const ERROR_ZFFREADER_NO_HASH_SIGNATURES: &str = "The active object was created without hash signatures.";
+pub(crate) const ERROR_ZFFREADER_MISSING_CHUNK_PARITY: &str = "There is no parity data available for chunk number: ";
+pub(crate) const ERROR_ZFFREADER_UNRECOVERABLE_CHUNK: &str = "Too many corrupt chunks in the parity group of chunk number: ";
+pub(crate) const ERROR_ZFFREADER_MALFORMED_CHUNK_PARITY: &str = "The parity group of the following chunk is malformed: ";
+pub(crate) const ERROR_ZFFREADER_CHUNK_OF_INITIALIZED_OBJECT: &str = "The chunk does not belong to an initialized (and decrypted) physical or logical object: ";
+pub(crate) const ERROR_ZFFREADER_CHUNK_DATA: &str = "Could not read the data of chunk number ";
+pub(crate) const ERROR_BAD_SECTOR: &str = "Could not read the sector at offset ";
+pub(crate) const ERROR_BAD_SECTOR_ZERO_FILLED: &str = " - the bytes were filled with zeros.";
+pub(crate) const ERROR_INTERRUPTED_READ: &str = "The chunk was filled with zeros, because the read operation was interrupted too often. Number of retries: ";
 
 pub(crate) const ERROR_IO_NOT_SEEKABLE_NEGATIVE_POSITION: &str = "Unseekable position (position is negative).";
 
 pub(crate) const ERROR_ZFFREADER_MISSING_OBJECT: &str = "Missing object number in zffreader: ";
+pub(crate) const ERROR_ZFFREADER_VIRTUAL_OBJECT_MAP: &str = "No virtual mapping information found for offset ";
+#[cfg(feature = "async")]
+pub(crate) const ERROR_ASYNC_READER_OPERATION_IN_PROGRESS: &str = "Another operation of the async reader is still in progress.";
+
+pub(crate) const ERROR_ENCRYPTED_VIRTUAL_OBJECT: &str = "Encrypted virtual objects are not supported.";
+pub(crate) const ERROR_EMPTY_VIRTUAL_OBJECT_RANGE: &str = "A virtual object needs at least one range and each range needs a length greater than zero.";
+pub(crate) const ERROR_VIRTUAL_OBJECT_RANGE_OUT_OF_BOUNDS: &str = "The range exceeds the data of the passive object with the following object number: ";
+
+pub(crate) const ERROR_DESCRIPTION_FIELD_EMPTY_KEY: &str = "Empty keys are not allowed in the description header.";
+pub(crate) const ERROR_DESCRIPTION_FIELD_RESERVED_KEY: &str = "The following key is reserved for a predefined field of the description header: ";
+pub(crate) const ERROR_PARSE_HASH_TYPE: &str = "Unknown hash type: ";
+pub(crate) const ERROR_PARSE_HUMAN_READABLE_SIZE: &str = "Unable to parse the given size value: ";
+pub(crate) const ERROR_INVALID_PARITY_SHARDS: &str = "The number of data and parity chunks must be greater than zero and must not exceed 256 in total.";
+pub(crate) const ERROR_WRITER_AMBIGUOUS_OBJECT_NUMBER: &str = "The following object number is given for multiple objects, so the ranges of the virtual objects are ambiguous: ";
+pub(crate) const ERROR_WRITER_EXTEND_OBJECT_ENCODERS: &str = "The given object encoders cannot be used to extend an existing container.";
+pub(crate) const ERROR_WRITER_WRITE_TO_UNSUPPORTED_OUTPUT: &str = "Only new, non-segmented containers can be written to a single output.";
+pub(crate) const ERROR_WRITER_SEGMENTED_STREAM_EXTEND: &str = "An extension of an existing container cannot be streamed segment by segment.";
+pub(crate) const ERROR_TRANSCODE_ENCRYPTED_OBJECT: &str = "Encrypted objects cannot be transcoded, object number: ";
+pub(crate) const ERROR_TRANSCODE_SIGNED_OBJECT: &str = "Signed objects cannot be transcoded, object number: ";
+pub(crate) const ERROR_DECOMPRESSED_DATA_EXCEEDS_LIMIT: &str = "The decompressed data exceeds the maximum size of ";
+pub(crate) const ERROR_METADATA_EXTENDED_VALUE_MISMATCH_TYPE: &str = "The metadata extended value has an incompatible type: ";
+pub(crate) const ERROR_TRANSCODE_VIRTUAL_OBJECT: &str = "Virtual objects cannot be transcoded yet, object number: ";
+pub(crate) const ERROR_CHUNK_ENCODING_WORKERS_STOPPED: &str = "The chunk encoding worker threads have stopped unexpectedly while encoding the chunk with the following chunk number: ";
 
 // Default values
 pub(crate) const DEFAULT_LENGTH_HEADER_IDENTIFIER: usize = 4;
 pub(crate) const DEFAULT_LENGTH_VALUE_HEADER_LENGTH: usize = 8;
 
 pub(crate) const DEFAULT_WAIT_TIME_IO_INTERRUPT_RETRY: u64 = 6000; // in milliseconds
-pub(crate) const DEFAULT_NUMBER_OF_RETRIES_IO_INTERRUPT: u8 = 11;
+pub(crate) const DEFAULT_NUMBER_OF_RETRIES_IO_INTERRUPT: u32 = 11;
+pub(crate) const DEFAULT_WAIT_TIME_IO_ERROR_RETRY: u64 = 100; // in milliseconds
+/// The default number of retries of a failed read operation, used by the default [BadBlockStrategy](crate::io::BadBlockStrategy).
+pub const DEFAULT_NUMBER_OF_RETRIES_BAD_BLOCK: u8 = 3;
+/// The default sector size, used by the default [BadBlockStrategy](crate::io::BadBlockStrategy).
+pub const DEFAULT_SECTOR_SIZE: u64 = 512;
 
 pub(crate) const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024; // 1 MiB
+/// The default interval (in bytes) between two calls of the progress callback of [crate::io::zffwriter::ZffWriter::generate_files_with_progress].
+pub const DEFAULT_WRITER_PROGRESS_INTERVAL: u64 = 64 * 1024 * 1024; // 64 MiB
+/// The default interval (in chunks) between two calls of the progress callback of [crate::io::zffreader::ZffReader::verify_all_with_progress].
+pub const DEFAULT_VERIFY_PROGRESS_INTERVAL: u64 = 4096;
 
 /// The number of the first object in a zff container.
 pub const INITIAL_OBJECT_NUMBER: u64 = 1;
@@ -122,6 +203,26 @@ pub const INITIAL_SEGMENT_NUMBER: u64 = 1;
 
 /// The default compression ratio threshold
 pub const DEFAULT_COMPRESSION_RATIO_THRESHOLD: &str = "1.05";
+/// The compression ratio threshold to keep the compressed data of each chunk, regardless of the achieved compression ratio.
+pub const COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS: f32 = 0.0;
+/// The compression ratio threshold to store each chunk uncompressed without trying to compress it
+/// (the compression algorithm will be recorded in the [CompressionHeader](crate::header::CompressionHeader) anyway, e.g. for benchmarking).
+pub const COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS: f32 = f32::INFINITY;
+/// The default compression level (used by the [ObjectHeaderBuilder](crate::header::ObjectHeaderBuilder)).
+pub const DEFAULT_COMPRESSION_LEVEL: u8 = 3;
+/// The default target chunk size (used by the [ObjectHeaderBuilder](crate::header::ObjectHeaderBuilder)).
+pub const DEFAULT_CHUNK_SIZE: u64 = 32768;
+
+/// The maximum quality level of the brotli compression (higher levels will be clamped to this value).
+pub const BROTLI_MAX_QUALITY: u8 = 11;
+/// The (log2 of the) sliding window size, used by the brotli compression.
+pub(crate) const BROTLI_DEFAULT_LGWIN: i32 = 22;
+/// The internal buffer size of the brotli decompressor.
+pub(crate) const DEFAULT_BROTLI_BUFFER_SIZE: usize = 4096;
+/// The maximum preset level of the xz compression (higher levels will be clamped to this value).
+pub const XZ_MAX_PRESET: u8 = 9;
+/// The maximum level of the gzip compression (higher levels will be clamped to this value).
+pub const GZIP_MAX_LEVEL: u8 = 9;
 
 /// the default key length of a public signature key
 pub const ED25519_DALEK_PUBKEY_LEN: usize = PUBLIC_KEY_LENGTH;
@@ -147,42 +248,63 @@ pub const DEFAULT_HEADER_VERSION_HASH_HEADER: u8 = 2;
 pub const DEFAULT_HEADER_VERSION_SEGMENT_HEADER: u8 = 3;
 /// current header version for the [CompressionHeader](crate::header::CompressionHeader).
 pub const DEFAULT_HEADER_VERSION_COMPRESSION_HEADER: u8 = 1;
+/// header version for [CompressionHeader](crate::header::CompressionHeader)s, which contain a (zstd) dictionary.
+pub const HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY: u8 = 2;
 /// current header version for the [DescriptionHeader](crate::header::DescriptionHeader).
 pub const DEFAULT_HEADER_VERSION_DESCRIPTION_HEADER: u8 = 2;
 /// current header version for the [PBEHeader](crate::header::PBEHeader).
 pub const DEFAULT_HEADER_VERSION_PBE_HEADER: u8 = 3;
 /// current header version for the [EncryptionHeader](crate::header::EncryptionHeader).
 pub const DEFAULT_HEADER_VERSION_ENCRYPTION_HEADER: u8 = 3;
+/// header version for [EncryptionHeader](crate::header::EncryptionHeader)s, which contain a random nonce extension
+/// (see [EncryptionHeader::nonce_extension](crate::header::EncryptionHeader::nonce_extension)).
+pub const HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION: u8 = 4;
 /// current header version for the [FileHeader](crate::header::FileHeader).
 pub const DEFAULT_HEADER_VERSION_FILE_HEADER: u8 = 2;
 /// current header version for the [ObjectHeader](crate::header::ObjectHeader).
 pub const DEFAULT_HEADER_VERSION_OBJECT_HEADER: u8 = 2;
+/// header version for encrypted [ObjectHeader](crate::header::ObjectHeader)s, which contain an unencrypted copy of the
+/// [DescriptionHeader](crate::header::DescriptionHeader) (see [ObjectFlags::plaintext_description](crate::header::ObjectFlags::plaintext_description)).
+pub const HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION: u8 = 3;
 /// current header version for the [ChunkOffsetMap](crate::header::ChunkOffsetMap) structure.
 pub const DEFAULT_HEADER_VERSION_CHUNK_OFFSET_MAP: u8 = 1;
 /// current header version for the [ChunkSizeMap](crate::header::ChunkSizeMap) structure.
 pub const DEFAULT_HEADER_VERSION_CHUNK_SIZE_MAP: u8 = 1;
 /// current header version for the [ChunkFlagMap](crate::header::ChunkFlagMap) structure.
 pub const DEFAULT_HEADER_VERSION_CHUNK_FLAG_MAP: u8 = 1;
+/// header version for [ChunkFlagMap](crate::header::ChunkFlagMap)s, which contain chunks with CRC-64 integrity hashes.
+pub const HEADER_VERSION_CHUNK_FLAG_MAP_CRC64: u8 = 2;
 /// current header version for the [ChunkXxHashMap](crate::header::ChunkXxHashMap) structure.
 pub const DEFAULT_HEADER_VERSION_CHUNK_XXHASH_MAP: u8 = 1;
 /// current header version for the [ChunkSamebytesMap](crate::header::ChunkSamebytesMap) structure.
 pub const DEFAULT_HEADER_VERSION_CHUNK_SAMEBYTES_MAP: u8 = 1;
 /// current header version for the [ChunkDedeuplicationMap](crate::header::ChunkDedeuplicationMap) structure.
 pub const DEFAULT_HEADER_VERSION_CHUNK_DEDUPLICATION_MAP: u8 = 1;
+/// current header version for the [ChunkParityMap](crate::header::ChunkParityMap) structure.
+pub const DEFAULT_HEADER_VERSION_CHUNK_PARITY_MAP: u8 = 1;
 /// current header version for the [VirtualMappingInformation](crate::header::VirtualMappingInformation) structure.
 pub const DEFAULT_HEADER_VERSION_VIRTUAL_MAPPING_INFORMATION: u8 = 1;
 /// current header version for the [VirtualLayer](crate::header::VirtualObjectMap) structure.
 pub const DEFAULT_HEADER_VERSION_VIRTUAL_OBJECT_MAP: u8 = 1;
 /// current footer version for the [ObjectFooterPhysical](crate::footer::ObjectFooterPhysical).
 pub const DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL: u8 = 2;
+/// footer version for [ObjectFooterPhysical](crate::footer::ObjectFooterPhysical)s, which contain bad chunks or an error log.
+pub const FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS: u8 = 3;
 /// current footer version for the [ObjectFooterLogical](crate::footer::ObjectFooterLogical).
 pub const DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_LOGICAL: u8 = 2;
 /// current footer version for the [ObjectFooterVirtual](crate::footer::ObjectFooterVirtual) structure.
 pub const DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_VIRTUAL: u8 = 1;
 /// current footer version for the [SegmentFooter](crate::footer::SegmentFooter).
 pub const DEFAULT_FOOTER_VERSION_SEGMENT_FOOTER: u8 = 3;
+/// footer version for [SegmentFooter](crate::footer::SegmentFooter)s, which contain a chunk parity map table.
+pub const FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY: u8 = 4;
 /// current footer version for the [MainFooter](crate::footer::MainFooter).
 pub const DEFAULT_FOOTER_VERSION_MAIN_FOOTER: u8 = 2;
+/// footer version for [MainFooter](crate::footer::MainFooter)s, which contain a chunk parity map table.
+pub const FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY: u8 = 3;
+/// footer version for [MainFooter](crate::footer::MainFooter)s, which contain the acquisition timestamps
+/// (and optionally a chunk parity map table).
+pub const FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS: u8 = 4;
 /// current footer version for the [FileFooter](crate::footer::FileFooter).
 pub const DEFAULT_FOOTER_VERSION_FILE_FOOTER: u8 = 2;
 
@@ -201,12 +323,15 @@ pub const DEFAULT_CHUNKMAP_SIZE: u64 = 32768;
 pub(crate) const METADATA_EXT_KEY_DEVID: &str = "devid";
 #[cfg(target_family = "unix")]
 pub(crate) const METADATA_EXT_KEY_INODE: &str = "inode";
+/// The metadata key for the file mode.
 #[cfg(target_family = "unix")]
-pub(crate) const METADATA_EXT_KEY_MODE: &str = "mode";
+pub const METADATA_EXT_KEY_MODE: &str = "mode";
+/// The metadata key for the uid.
 #[cfg(target_family = "unix")]
-pub(crate) const METADATA_EXT_KEY_UID: &str = "uid";
+pub const METADATA_EXT_KEY_UID: &str = "uid";
+/// The metadata key for the gid.
 #[cfg(target_family = "unix")]
-pub(crate) const METADATA_EXT_KEY_GID: &str = "gid";
+pub const METADATA_EXT_KEY_GID: &str = "gid";
 #[cfg(target_os = "windows")]
 pub(crate) const METADATA_EXT_DW_FILE_ATTRIBUTES: &str = "dwFileAttributes";
 pub(crate) const METADATA_EXT_KEY_UNACCESSABLE_FILE: &str = "ua";
@@ -219,6 +344,9 @@ pub const METADATA_MTIME: &str = "mtime";
 pub const METADATA_CTIME: &str = "ctime";
 /// The metadata key for btime.
 pub const METADATA_BTIME: &str = "btime";
+/// The metadata key for the original path of the file (only stored, if set in the
+/// [ZffCreationParameters](crate::io::ZffCreationParameters::original_paths)). The path is stored as raw bytes.
+pub const METADATA_EXT_KEY_ORIGINAL_PATH: &str = "original_path";
 
 
 // - ChunkMap
@@ -19,7 +19,7 @@ use aes_gcm::{
 	Aes256Gcm, Aes128Gcm, Nonce as AesGcmNonce, KeyInit,
 	aead::Aead,
 };
-use chacha20poly1305::ChaCha20Poly1305;
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305, XNonce};
 use byteorder::{LittleEndian, WriteBytesExt};
 use rand::{rngs::OsRng, RngCore};
 use typenum::consts::U12;
@@ -48,6 +48,9 @@ pub enum EncryptionAlgorithm {
 	AES256GCM = 1,
 	/// Chacha20 stream cipher with Poly1305 universal hash function.
 	CHACHA20POLY1305 = 2,
+	/// XChacha20 stream cipher (Chacha20 with an extended 192-bit nonce) with Poly1305 universal hash function.  
+	/// Encoded with value 3.
+	XCHACHA20POLY1305 = 3,
 }
 
 impl fmt::Display for EncryptionAlgorithm {
@@ -56,6 +59,7 @@ impl fmt::Display for EncryptionAlgorithm {
     		EncryptionAlgorithm::AES128GCM => "AES128GCM",
     		EncryptionAlgorithm::AES256GCM => "AES256GCM",
     		EncryptionAlgorithm::CHACHA20POLY1305 => "CHACHA20POLY1305",
+    		EncryptionAlgorithm::XCHACHA20POLY1305 => "XCHACHA20POLY1305",
     	};
         write!(f, "{value}")
     }
@@ -114,7 +118,8 @@ impl fmt::Display for PBEScheme {
 /// trait to implement the zff encryption for the appropriate type.
 pub trait Encryption {
 	/// Encrypts the message, using the type specific nonce padding.
-	fn encrypt<K, M, A>(key: K, message: M, nonce_value: u64, algorithm: A) -> Result<Vec<u8>>
+	/// The nonce extension is only used by XChacha20-Poly1305 (see [EncryptionHeader](crate::header::EncryptionHeader)).
+	fn encrypt<K, M, A>(key: K, message: M, nonce_value: u64, nonce_extension: [u8; 16], algorithm: A) -> Result<Vec<u8>>
 	where
 		K: AsRef<[u8]>,
 		M: AsRef<[u8]>,
@@ -134,12 +139,17 @@ pub trait Encryption {
 			EncryptionAlgorithm::CHACHA20POLY1305 => {
 				let cipher = ChaCha20Poly1305::new_from_slice(key.as_ref())?;
 				Ok(cipher.encrypt(&nonce, message.as_ref())?)
-			}
+			},
+			EncryptionAlgorithm::XCHACHA20POLY1305 => {
+				let cipher = XChaCha20Poly1305::new_from_slice(key.as_ref())?;
+				Ok(cipher.encrypt(&Self::gen_crypto_extended_nonce(nonce_value, nonce_extension)?, message.as_ref())?)
+			},
 		}
 	}
 
 	/// Decrypts the message, using the type specific nonce padding.
-	fn decrypt<K, M, A>(key: K, message: M, nonce_value: u64, algorithm: A) -> Result<Vec<u8>>
+	/// The nonce extension is only used by XChacha20-Poly1305 (see [EncryptionHeader](crate::header::EncryptionHeader)).
+	fn decrypt<K, M, A>(key: K, message: M, nonce_value: u64, nonce_extension: [u8; 16], algorithm: A) -> Result<Vec<u8>>
 	where
 		K: AsRef<[u8]>,
 		M: AsRef<[u8]>,
@@ -159,7 +169,11 @@ pub trait Encryption {
 			EncryptionAlgorithm::CHACHA20POLY1305 => {
 				let cipher = ChaCha20Poly1305::new_from_slice(key.as_ref())?;
 				Ok(cipher.decrypt(&nonce, message.as_ref())?)
-			}
+			},
+			EncryptionAlgorithm::XCHACHA20POLY1305 => {
+				let cipher = XChaCha20Poly1305::new_from_slice(key.as_ref())?;
+				Ok(cipher.decrypt(&Self::gen_crypto_extended_nonce(nonce_value, nonce_extension)?, message.as_ref())?)
+			},
 		}
 	}
 
@@ -173,6 +187,18 @@ pub trait Encryption {
 		Ok(*Nonce::from_slice(&buffer))
 	}
 
+	/// Method to generate a 192-bit nonce (used by XChacha20-Poly1305) for the appropriate message type (using the given value).
+	/// The nonce is built like the 96-bit nonce, but the remaining 128-bit are filled with the random nonce extension of the object
+	/// (the type specific padding is set in the last byte). Objects with an older encryption header use a zeroed nonce extension.
+	fn gen_crypto_extended_nonce(nonce_value: u64, nonce_extension: [u8; 16]) -> Result<XNonce> {
+		let mut buffer = vec![];
+		buffer.write_u64::<LittleEndian>(nonce_value)?;
+		buffer.extend_from_slice(&nonce_extension);
+		let buffer_len = buffer.len();
+		buffer[buffer_len - 1] |= Self::crypto_nonce_padding();
+		Ok(*XNonce::from_slice(&buffer))
+	}
+
 	/// The appropriate, type specific padding value for the nonce (see official zff documentation).
 	fn crypto_nonce_padding() -> u8;
 
@@ -209,6 +235,14 @@ pub fn gen_random_iv() -> [u8; 16] {
 	iv
 }
 
+/// Generates a new random nonce extension as ```[u8; 16]``` for use in encryption header.
+pub fn gen_random_nonce_extension() -> [u8; 16] {
+	let mut nonce_extension = [0; 16];
+	let mut rng = OsRng;
+	rng.fill_bytes(&mut nonce_extension);
+	nonce_extension
+}
+
 /// Generates a new random salt as ```[u8; 32]``` for use in PBE header.
 pub fn gen_random_salt() -> [u8; 32] {
 	let mut salt = [0; 32];
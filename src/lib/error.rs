@@ -25,6 +25,9 @@ use redb::{
 	};
 use argon2::Error as Argon2Error;
 use cbc::cipher::block_padding::UnpadError as AesCbcError;
+use reed_solomon_erasure::Error as ReedSolomonError;
+#[cfg(target_family = "unix")]
+use posix_acl::ACLError;
 
 /// The main error-type of this crate.
 #[derive(Debug)]
@@ -123,8 +126,11 @@ pub enum ZffErrorKind {
 	InvalidFlagValue,
 	/// Error will be returned, if you try to use a invalid key length for the preferred encryption algorithm.
 	InvalidEncryptionKeySize,
-	/// Error will be returned, if the appropriate segment is missing in the zff image.
-	MissingSegment,
+	/// Error will be returned, if the appropriate segment is missing in the zff image. Contains the number of the missing segment.
+	MissingSegment(u64),
+	/// Error will be returned, if the appropriate chunk is not available (e.g. because the segment of the chunk is missing or unreadable).
+	/// Contains the number of the missing chunk.
+	MissingChunk(u64),
 	/// Error will be returned, if the appropriate segment is malformed (e.g. the object header is missing)
 	MalformedSegment,
 	/// Error will be returned, if the header is malformed.
@@ -161,6 +167,20 @@ pub enum ZffErrorKind {
 	BinarySearchError,
 	/// Error will be returned if the segmentation has not finished.
 	SegmentNotFinished,
+	/// Error will be returned, if the compressed data needs a (zstd) dictionary, which is not available.
+	MissingCompressionDictionary,
+	/// Error will be returned, if an object cannot be transcoded (e.g. because the object is encrypted or signed).
+	UnsupportedTranscoding,
+	/// Error will be returned, if the calculation of the parity data or the reconstruction of the data failed.
+	ReedSolomonError,
+	/// Error will be returned, if a chunk cannot be reconstructed by using the parity data
+	/// (e.g. if there is no parity data or too many chunks of the parity group are corrupt).
+	/// Contains the number of the chunk.
+	UnrecoverableChunk(u64),
+	/// Error will be returned, if a POSIX ACL cannot be read or written.
+	AclError,
+	/// Error will be returned, if the authentication of decrypted data fails (e.g. if an encrypted chunk map is corrupt).
+	IntegrityError,
 }
 
 impl fmt::Display for ZffErrorKind {
@@ -208,7 +228,8 @@ impl fmt::Display for ZffErrorKind {
 			ZffErrorKind::NoSignatureFoundAtChunk => "NoSignatureFoundAtChunk",
 			ZffErrorKind::InvalidFlagValue => "InvalidFlagValue",
 			ZffErrorKind::InvalidEncryptionKeySize => "InvalidEncryptionKeySize",
-			ZffErrorKind::MissingSegment => "MissingSegment",
+			ZffErrorKind::MissingSegment(_) => "MissingSegment",
+			ZffErrorKind::MissingChunk(_) => "MissingChunk",
 			ZffErrorKind::MalformedSegment => "MalformedSegment",
 			ZffErrorKind::MalformedHeader => "MalformedHeader",
 			ZffErrorKind::UnknownObjectTypeValue => "UnknownObjectTypeValue",
@@ -227,6 +248,12 @@ impl fmt::Display for ZffErrorKind {
 			ZffErrorKind::MissmatchIdentifier => "MissmatchIdentifier",
 			ZffErrorKind::BinarySearchError => "BinarySearchError",
 			ZffErrorKind::SegmentNotFinished => "SegmentNotFinished",
+			ZffErrorKind::MissingCompressionDictionary => "MissingCompressionDictionary",
+			ZffErrorKind::UnsupportedTranscoding => "UnsupportedTranscoding",
+			ZffErrorKind::ReedSolomonError => "ReedSolomonError",
+			ZffErrorKind::UnrecoverableChunk(_) => "UnrecoverableChunk",
+			ZffErrorKind::AclError => "AclError",
+			ZffErrorKind::IntegrityError => "IntegrityError",
 		};
 	write!(f, "{}", err_msg)
 	}
@@ -359,9 +386,46 @@ impl ZffError {
 	}
 }
 
+impl ZffErrorKind {
+	// returns the appropriate std::io::ErrorKind (used by the conversion into a std::io::Error).
+	fn io_error_kind(&self) -> io::ErrorKind {
+		match self {
+			ZffErrorKind::IoError(e) => e.kind(),
+			ZffErrorKind::ReadEOF | ZffErrorKind::EmptyFile(_) => io::ErrorKind::UnexpectedEof,
+			ZffErrorKind::MissingObjectNumber
+			| ZffErrorKind::MissingFileNumber
+			| ZffErrorKind::MissingSegment(_)
+			| ZffErrorKind::MissingChunk(_)
+			| ZffErrorKind::InvalidChunkNumber
+			| ZffErrorKind::DataDecodeChunkNumberNotInSegment
+			| ZffErrorKind::ValueNotInMap => io::ErrorKind::NotFound,
+			ZffErrorKind::HeaderDecodeError
+			| ZffErrorKind::HeaderDecodeMismatchIdentifier
+			| ZffErrorKind::HeaderDecoderKeyNotOnPosition
+			| ZffErrorKind::MalformedSegment
+			| ZffErrorKind::MalformedHeader
+			| ZffErrorKind::MissmatchIdentifier
+			| ZffErrorKind::UnknownObjectTypeValue
+			| ZffErrorKind::UnknownMetadataExtendedType
+			| ZffErrorKind::InvalidFlagValue
+			| ZffErrorKind::FromUtf8Error
+			| ZffErrorKind::Lz4Error
+			| ZffErrorKind::UnrecoverableChunk(_)
+			| ZffErrorKind::IntegrityError => io::ErrorKind::InvalidData,
+			ZffErrorKind::InvalidOption | ZffErrorKind::Seek => io::ErrorKind::InvalidInput,
+			ZffErrorKind::UnsupportedVersion | ZffErrorKind::UnsupportedTranscoding => io::ErrorKind::Unsupported,
+			ZffErrorKind::OutOfMemory => io::ErrorKind::OutOfMemory,
+			_ => io::ErrorKind::Other,
+		}
+	}
+}
+
+/// Converts the [ZffError] into a [std::io::Error] with the appropriate [std::io::ErrorKind]
+/// (e.g. [ZffErrorKind::ReadEOF] to [std::io::ErrorKind::UnexpectedEof] or [ZffErrorKind::MissingChunk] to [std::io::ErrorKind::NotFound]).  
+/// A [ZffErrorKind::IoError] keeps the kind of the contained error. The [ZffError] itself will be used as the inner error.
 impl From<ZffError> for std::io::Error {
 	fn from(e: ZffError) -> std::io::Error {
-		std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+		std::io::Error::new(e.kind.io_error_kind(), e)
 	}
 }
 
@@ -482,6 +546,12 @@ impl From<TryReserveError> for ZffError {
 	}
 }
 
+impl From<ReedSolomonError> for ZffError {
+	fn from(e: ReedSolomonError) -> ZffError {
+		ZffError::new(ZffErrorKind::ReedSolomonError, e.to_string())
+	}
+}
+
 impl From<InvalidLength> for ZffError {
 	fn from(e: InvalidLength) -> ZffError {
 		ZffError::new(ZffErrorKind::InvalidEncryptionKeySize, e.to_string())
@@ -502,4 +572,11 @@ impl std::error::Error for ZffError {
 			_ => None,
 		}
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(target_family = "unix")]
+impl From<ACLError> for ZffError {
+	fn from(e: ACLError) -> ZffError {
+		ZffError::new(ZffErrorKind::AclError, e.to_string())
+	}
+}
@@ -15,7 +15,7 @@ use crate::{
 };
 use crate::{
 	Result,
-	io::buffer_chunk,
+	io::{buffer_chunk, buffer_chunk_with_strategy, IoInterruptRetries},
 	HeaderCoding,
 	ValueEncoder,
 	ZffError,
@@ -23,6 +23,7 @@ use crate::{
 	EncodingThreadPoolManager,
 	Signature,
 	chunking,
+	same_bytes_chunking,
 };
 
 #[cfg(feature = "log")]
@@ -85,6 +86,8 @@ pub struct FileEncoder {
 	acquisition_start: u64,
 	acquisition_end: u64,
 	filetype_encoding_information: FileTypeEncodingInformation,
+	/// Defines how interrupted read operations of the underlying file will be retried.
+	io_interrupt_retries: IoInterruptRetries,
 }
 
 impl FileEncoder {
@@ -98,7 +101,8 @@ impl FileEncoder {
 		signing_key: Option<SigningKey>,
 		encryption_information: Option<EncryptionInformation>,
 		current_chunk_number: u64,
-		filetype_encoding_information: FileTypeEncodingInformation) -> Result<FileEncoder> {
+		filetype_encoding_information: FileTypeEncodingInformation,
+		io_interrupt_retries: IoInterruptRetries) -> Result<FileEncoder> {
 		
 		Ok(Self {
 			file_header,
@@ -113,9 +117,16 @@ impl FileEncoder {
 			acquisition_start: 0,
 			acquisition_end: 0,
 			filetype_encoding_information,
+			io_interrupt_retries,
 		})
 	}
 
+	/// sets the first chunk number of this file (has to be called before the first chunk will be read).
+	pub(crate) fn set_initial_chunk_number(&mut self, chunk_number: u64) {
+		self.initial_chunk_number = chunk_number;
+		self.current_chunk_number = chunk_number;
+	}
+
 	/// returns the underlying encoded header
 	pub fn get_encoded_header(&mut self) -> Vec<u8> {
 		if self.acquisition_start == 0 {
@@ -178,7 +189,7 @@ impl FileEncoder {
 				}	
 			},
 			FileTypeEncodingInformation::File => {
-				let buffered_chunk = buffer_chunk(&mut self.underlying_file, chunk_size)?;
+				let buffered_chunk = buffer_chunk_with_strategy(&mut self.underlying_file, chunk_size, None, &self.io_interrupt_retries)?;
 				self.read_bytes_underlying_data += buffered_chunk.bytes_read;
 				buffered_chunk
 			},
@@ -220,21 +231,26 @@ impl FileEncoder {
 
 		let mut encoding_thread_pool_manager = self.encoding_thread_pool_manager.borrow_mut();
 
-		encoding_thread_pool_manager.update(buffered_chunk.buffer);
+		let samebyte = encoding_thread_pool_manager.update_same_bytes_checked(buffered_chunk.buffer, buf_len, chunk_size as u64);
 
-		let encryption_algorithm = self.encryption_information.as_ref().map(|encryption_information| &encryption_information.algorithm);
-		let encryption_key = self.encryption_information.as_ref().map(|encryption_information| &encryption_information.encryption_key);
 
-	    let chunk = chunking(
-			&mut encoding_thread_pool_manager,
-			self.current_chunk_number,
-			buf_len,
-			chunk_size as u64,
-			deduplication_map,
-			encryption_key,
-			encryption_algorithm,
-			empty_file_flag
-		)?;
+	    let chunk = match samebyte {
+			Some(samebyte) => same_bytes_chunking(
+				&encoding_thread_pool_manager,
+				self.current_chunk_number,
+				samebyte,
+				self.encryption_information.as_ref(),
+			)?,
+			None => chunking(
+				&mut encoding_thread_pool_manager,
+				self.current_chunk_number,
+				buf_len,
+				chunk_size as u64,
+				deduplication_map,
+				self.encryption_information.as_ref(),
+				empty_file_flag
+			)?,
+		};
 
 		self.current_chunk_number += 1;
 	    Ok(chunk)
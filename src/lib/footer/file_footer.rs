@@ -26,7 +26,10 @@ use crate::header::{
 
 // - external
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{
+	Deserialize,
+	Serialize,
+};
 
 
 /// The file footer is written at the end of each acquired file.
@@ -35,6 +38,7 @@ use serde::Serialize;
 /// hash values, or size information.
 /// The general structure of the file footer is the same for all file types.
 #[derive(Debug,Clone,Eq,PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct FileFooter {
 	/// the appropriate file number.
@@ -99,7 +103,7 @@ impl FileFooter {
 	{
 		let mut vec = Vec::new();
 		let encryption_information = encryption_information.borrow();
-		let mut encoded_footer = self.encode_encrypted_footer(&encryption_information.encryption_key, &encryption_information.algorithm)?;
+		let mut encoded_footer = self.encode_encrypted_footer(&encryption_information.encryption_key, encryption_information.nonce_extension, &encryption_information.algorithm)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = 4 + 8 + (encoded_footer.len() as u64); //4 bytes identifier + 8 bytes for length + length itself
 		vec.append(&mut identifier.to_be_bytes().to_vec());
@@ -109,7 +113,7 @@ impl FileFooter {
 		Ok(vec)
 	}
 
-	fn encode_encrypted_footer<K, A>(&self, key: K, algorithm: A) -> Result<Vec<u8>>
+	fn encode_encrypted_footer<K, A>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<Vec<u8>>
 	where
 		K: AsRef<[u8]>,
 		A: Borrow<EncryptionAlgorithm>,
@@ -124,6 +128,7 @@ impl FileFooter {
 		let encrypted_data = FileFooter::encrypt(
 			key, data_to_encrypt,
 			self.file_number,
+			nonce_extension,
 			algorithm
 			)?;
 		vec.append(&mut encrypted_data.encode_directly());
@@ -152,6 +157,7 @@ impl FileFooter {
 			&encryption_information.borrow().encryption_key, 
 			encrypted_data, 
 			file_number, 
+			encryption_information.borrow().nonce_extension,
 			algorithm)?;
 		let mut cursor = Cursor::new(decrypted_data);
 		let (acquisition_start, acquisition_end, hash_header, first_chunk_number, number_of_chunks, length_of_data) = Self::decode_inner_content(&mut cursor)?;
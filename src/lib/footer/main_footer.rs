@@ -9,9 +9,12 @@ use crate::{
 	HeaderCoding,
 	ValueDecoder,
 	ValueEncoder,
+	ZffError,
 	ZffErrorKind,
 	FOOTER_IDENTIFIER_MAIN_FOOTER,
-	ENCODING_KEY_DESCRIPTION_NOTES, constants::DEFAULT_FOOTER_VERSION_MAIN_FOOTER,
+	ENCODING_KEY_DESCRIPTION_NOTES, ENCODING_KEY_CHUNK_PARITY_MAPS, constants::DEFAULT_FOOTER_VERSION_MAIN_FOOTER,
+	constants::{FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY, FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS},
+	ENCODING_KEY_ACQUISITION_START, ENCODING_KEY_ACQUISITION_END,
 };
 
 // - external
@@ -46,14 +49,23 @@ pub struct MainFooter {
 	pub chunk_samebytes_maps: BTreeMap<u64, u64>, //<highest chunk number, segment number>
 	/// The segment numbers where the appropriate chunkmap can be found.
 	pub chunk_dedup_maps: BTreeMap<u64, u64>, //<highest chunk number, segment number>
+	/// The segment numbers where the appropriate chunk parity map can be found
+	/// (only available, if the container was created with parity data).
+	pub chunk_parity_maps: BTreeMap<u64, u64>, //<highest chunk number, segment number>
 	/// some optional (globally) description notes for the container.
 	pub description_notes: Option<String>,
+	/// The timestamp (unix time), when the acquisition of the container started.
+	/// The description header of an object is written before the dumped data, so the container-wide acquisition timestamps
+	/// are stored in the main footer (not available in containers, which were created by older versions).
+	pub acquisition_start: Option<u64>,
+	/// The timestamp (unix time), when the acquisition of the container was finished (see acquisition_start).
+	pub acquisition_end: Option<u64>,
 	/// offset in the current segment, where the footer starts.
 	pub footer_offset: u64,
 }
 
 impl MainFooter {
-	/// creates a new MainFooter with a given values.
+	/// creates a new MainFooter with a given values (without a chunk parity map table and acquisition timestamps).
 	pub fn new(
 		number_of_segments: u64,
 		object_header: BTreeMap<u64, u64>,
@@ -76,7 +88,10 @@ impl MainFooter {
 			chunk_xxhash_maps,
 			chunk_samebytes_maps,
 			chunk_dedup_maps,
+			chunk_parity_maps: BTreeMap::new(),
 			description_notes,
+			acquisition_start: None,
+			acquisition_end: None,
 			footer_offset,
 		}
 	}
@@ -126,6 +141,31 @@ impl MainFooter {
 		Some(self.description_notes.as_ref()?)
 	}
 
+	/// sets the timestamp (unix time), when the acquisition of the container started.
+	pub fn set_acquisition_start(&mut self, start: u64) {
+		self.acquisition_start = Some(start);
+	}
+
+	/// returns the timestamp (unix time), when the acquisition of the container started, if available.
+	pub fn acquisition_start(&self) -> Option<u64> {
+		self.acquisition_start
+	}
+
+	/// sets the timestamp (unix time), when the acquisition of the container was finished.
+	pub fn set_acquisition_end(&mut self, end: u64) {
+		self.acquisition_end = Some(end);
+	}
+
+	/// returns the timestamp (unix time), when the acquisition of the container was finished, if available.
+	pub fn acquisition_end(&self) -> Option<u64> {
+		self.acquisition_end
+	}
+
+	/// returns the duration of the acquisition in seconds, if both timestamps are available.
+	pub fn acquisition_duration(&self) -> Option<u64> {
+		self.acquisition_end?.checked_sub(self.acquisition_start?)
+	}
+
 	/// Returns a reference of the global chunkmap table.
 	pub fn chunk_offset_maps(&self) -> &BTreeMap<u64, u64> {
 		&self.chunk_offset_maps
@@ -155,6 +195,11 @@ impl MainFooter {
 	pub fn chunk_dedup_maps(&self) -> &BTreeMap<u64, u64> {
 		&self.chunk_dedup_maps
 	}
+
+	/// Returns a reference of the global chunk parity table.
+	pub fn chunk_parity_maps(&self) -> &BTreeMap<u64, u64> {
+		&self.chunk_parity_maps
+	}
 }
 
 impl HeaderCoding for MainFooter {
@@ -169,8 +214,16 @@ impl HeaderCoding for MainFooter {
 	}
 
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = Vec::new();
-		vec.append(&mut Self::version().encode_directly());
+		// footers with a chunk parity map table or acquisition timestamps use a separate version,
+		// so older readers will report these footers as unsupported.
+		let footer_version = if self.acquisition_start.is_some() || self.acquisition_end.is_some() {
+			FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS
+		} else if !self.chunk_parity_maps.is_empty() {
+			FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY
+		} else {
+			Self::version()
+		};
+		let mut vec = vec![footer_version];
 		vec.append(&mut self.number_of_segments.encode_directly());
 		vec.append(&mut self.object_header.encode_directly());
 		vec.append(&mut self.object_footer.encode_directly());
@@ -183,13 +236,29 @@ impl HeaderCoding for MainFooter {
 		if let Some(description_notes) = &self.description_notes {
 			vec.append(&mut description_notes.encode_for_key(ENCODING_KEY_DESCRIPTION_NOTES));
 		};
+		// the table is part of all footers of the appropriate versions (the footers with acquisition timestamps contain the table
+		// even if it is empty), to keep the encoding of containers without parity data and timestamps unchanged.
+		if footer_version != Self::version() {
+			vec.append(&mut self.chunk_parity_maps.encode_for_key(ENCODING_KEY_CHUNK_PARITY_MAPS));
+		}
+		if let Some(acquisition_start) = self.acquisition_start {
+			vec.append(&mut acquisition_start.encode_for_key(ENCODING_KEY_ACQUISITION_START));
+		}
+		if let Some(acquisition_end) = self.acquisition_end {
+			vec.append(&mut acquisition_end.encode_for_key(ENCODING_KEY_ACQUISITION_END));
+		}
 		vec.append(&mut self.footer_offset.encode_directly());
 		vec
 	}
 
 	fn decode_content(data: Vec<u8>) -> Result<MainFooter> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let footer_version = u8::decode_directly(&mut cursor)?;
+		if footer_version != Self::version()
+			&& footer_version != FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY
+			&& footer_version != FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS {
+			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, footer_version.to_string()));
+		}
 		let number_of_segments = u64::decode_directly(&mut cursor)?;
 		let object_header = BTreeMap::<u64, u64>::decode_directly(&mut cursor)?;
 		let object_footer = BTreeMap::<u64, u64>::decode_directly(&mut cursor)?;
@@ -210,8 +279,19 @@ impl HeaderCoding for MainFooter {
 				_ => return Err(e)
 			},
 		};
+		let chunk_parity_maps = if footer_version == Self::version() {
+			BTreeMap::new()
+		} else {
+			BTreeMap::<u64, u64>::decode_for_key(&mut cursor, ENCODING_KEY_CHUNK_PARITY_MAPS)?
+		};
+		let (acquisition_start, acquisition_end) = if footer_version == FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS {
+			(decode_optional_timestamp(&mut cursor, ENCODING_KEY_ACQUISITION_START)?,
+			decode_optional_timestamp(&mut cursor, ENCODING_KEY_ACQUISITION_END)?)
+		} else {
+			(None, None)
+		};
 		let footer_offset = u64::decode_directly(&mut cursor)?;
-		Ok(MainFooter::new(
+		let mut main_footer = MainFooter::new(
 			number_of_segments, 
 			object_header, 
 			object_footer, 
@@ -222,7 +302,11 @@ impl HeaderCoding for MainFooter {
 			chunk_samebytes_maps,
 			chunk_dedup_maps,
 			description_notes, 
-			footer_offset))
+			footer_offset);
+		main_footer.chunk_parity_maps = chunk_parity_maps;
+		main_footer.acquisition_start = acquisition_start;
+		main_footer.acquisition_end = acquisition_end;
+		Ok(main_footer)
 	}
 
 	fn struct_name() -> &'static str {
@@ -230,6 +314,21 @@ impl HeaderCoding for MainFooter {
 	}
 }
 
+// a footer with acquisition timestamps could contain only one of both timestamps.
+fn decode_optional_timestamp(cursor: &mut Cursor<Vec<u8>>, key: &str) -> Result<Option<u64>> {
+	let position = cursor.position();
+	match u64::decode_for_key(cursor, key) {
+		Ok(value) => Ok(Some(value)),
+		Err(e) => match e.get_kind() {
+			ZffErrorKind::HeaderDecoderKeyNotOnPosition => {
+				cursor.set_position(position);
+				Ok(None)
+			},
+			_ => Err(e)
+		},
+	}
+}
+
 // - implement fmt::Display
 impl fmt::Display for MainFooter {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
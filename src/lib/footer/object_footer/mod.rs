@@ -50,6 +50,7 @@ use serde::{
 /// Each object contains its own object footer.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum ObjectFooter {
 	/// A physical object contains a [ObjectFooterPhysical].
 	Physical(ObjectFooterPhysical),
@@ -63,7 +64,7 @@ impl ObjectFooter {
 	/// returns the version of the object footer.
 	pub fn version(&self) -> u8 {
 		match self {
-			ObjectFooter::Physical(_) => ObjectFooterPhysical::version(),
+			ObjectFooter::Physical(footer) => footer.encoding_version(),
 			ObjectFooter::Logical(_) => ObjectFooterLogical::version(),
 			ObjectFooter::Virtual(_) => ObjectFooterVirtual::version(),
 		}
@@ -80,6 +81,8 @@ impl ObjectFooter {
 			1
 		} else if identifier == ObjectFooterLogical::identifier() {
 			2
+		} else if identifier == ObjectFooterVirtual::identifier() {
+			3
 		} else {
 			0
 		}
@@ -109,6 +112,12 @@ impl ObjectFooter {
 				data.read_exact(&mut content_buffer)?;
 				Ok(ObjectFooter::Logical(ObjectFooterLogical::decode_content(content_buffer)?))
 			},
+			3 => {
+				let length = Self::decode_header_length(data)? as usize;
+				let mut content_buffer = vec![0u8; length-DEFAULT_LENGTH_HEADER_IDENTIFIER-DEFAULT_LENGTH_VALUE_HEADER_LENGTH];
+				data.read_exact(&mut content_buffer)?;
+				Ok(ObjectFooter::Virtual(ObjectFooterVirtual::decode_content(content_buffer)?))
+			},
 			_ => Err(ZffError::new(ZffErrorKind::HeaderDecodeMismatchIdentifier, ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER)),
 		}
 	}
@@ -189,7 +198,7 @@ impl EncryptedObjectFooter {
 	/// returns the version of the object footer.
 	pub fn version(&self) -> u8 {
 		match self {
-			EncryptedObjectFooter::Physical(_) => ObjectFooterPhysical::version(),
+			EncryptedObjectFooter::Physical(footer) => footer.footer_version,
 			EncryptedObjectFooter::Logical(_) => ObjectFooterLogical::version(),
 		}
 	}
@@ -238,29 +247,29 @@ impl EncryptedObjectFooter {
 	}
 
 	/// tries to decrypt the ObjectFooter. If an error occures, the EncryptedObjectFooter is still available.
-	pub fn decrypt<A, K>(&self, key: K, algorithm: A) -> Result<ObjectFooter>
+	pub fn decrypt<A, K>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooter>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
 		match self {
 			EncryptedObjectFooter::Physical(encrypted_inner_footer) => {
-				let decrypted_footer = encrypted_inner_footer.decrypt(key, algorithm)?;
+				let decrypted_footer = encrypted_inner_footer.decrypt(key, nonce_extension, algorithm)?;
 				Ok(ObjectFooter::from(decrypted_footer))
 			},
 			EncryptedObjectFooter::Logical(encrypted_inner_footer) => {
-				let decrypted_footer = encrypted_inner_footer.decrypt(key, algorithm)?;
+				let decrypted_footer = encrypted_inner_footer.decrypt(key, nonce_extension, algorithm)?;
 				Ok(ObjectFooter::from(decrypted_footer))
 			}
 		}
 	}
 
 	/// tries to decrypt the ObjectFooter. Consumes the EncryptedObjectFooter, regardless of whether an error occurs or not.
-	pub fn decrypt_and_consume<A, K>(self, key: K, algorithm: A) -> Result<ObjectFooter>
+	pub fn decrypt_and_consume<A, K>(self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooter>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		self.decrypt(key, algorithm)
+		self.decrypt(key, nonce_extension, algorithm)
 	}
 }
\ No newline at end of file
@@ -169,6 +169,7 @@ impl ObjectFooterLogical {
 			&encryption_information.borrow().encryption_key, 
 			self.encode_content(), 
 			self.object_number, 
+			encryption_information.borrow().nonce_extension,
 			&encryption_information.borrow().algorithm)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = (
@@ -293,12 +294,12 @@ impl EncryptedObjectFooterLogical {
 	}
 
 	/// Tries to decrypt the ObjectFooter. If an error occures, the EncryptedObjectFooterPhysical is still available.
-	pub fn decrypt<A, K>(&self, key: K, algorithm: A) -> Result<ObjectFooterLogical>
+	pub fn decrypt<A, K>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterLogical>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		let content = ObjectFooter::decrypt(key, &self.encrypted_data, self.object_number, algorithm.borrow())?;
+		let content = ObjectFooter::decrypt(key, &self.encrypted_data, self.object_number, nonce_extension, algorithm.borrow())?;
 		let mut cursor = Cursor::new(content);
 		let (acquisition_start,
 			acquisition_end,
@@ -319,12 +320,12 @@ impl EncryptedObjectFooterLogical {
 	}
 
 	/// Tries to decrypt the ObjectFooter. Consumes the EncryptedObjectFooterPhysical, regardless of whether an error occurs or not.
-	pub fn decrypt_and_consume<A, K>(self, key: K, algorithm: A) -> Result<ObjectFooterLogical>
+	pub fn decrypt_and_consume<A, K>(self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterLogical>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		self.decrypt(key, algorithm)
+		self.decrypt(key, nonce_extension, algorithm)
 	}
 }
 
@@ -1,4 +1,8 @@
-use crate::constants::DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL;
+use crate::constants::{
+	DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL,
+	FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS,
+	METADATA_EXT_TYPE_IDENTIFIER_VEC,
+};
 
 use super::*;
 
@@ -10,8 +14,11 @@ use super::*;
 /// - the first chunk number, which is used for this physical dump
 /// - the total number of chunks, used for this physical dump
 /// - a hash header with the appropriate hash values of the underlying physical dump
+/// - the numbers of the chunks, which contain unreadable (skipped) sectors (if recorded).
+/// - an error log with the read errors, which occurred during the acquisition.
 #[derive(Debug,Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub struct ObjectFooterPhysical {
 	/// The object number of the footer.
 	pub object_number: u64,
@@ -27,6 +34,11 @@ pub struct ObjectFooterPhysical {
 	pub number_of_chunks: u64,
 	/// The appropriate [crate::header::HashHeader].
 	pub hash_header: HashHeader,
+	/// The numbers of the chunks, which contain unreadable sectors of the input data (see [crate::io::BadBlockStrategy]).
+	pub bad_chunks: Vec<u64>,
+	/// The read errors of the input data, which occurred during the acquisition
+	/// (e.g. the unreadable sectors, which were filled with zeros).
+	pub error_log: Vec<AcquisitionErrorEntry>,
 }
 
 impl ObjectFooterPhysical {
@@ -47,6 +59,18 @@ impl ObjectFooterPhysical {
 			first_chunk_number,
 			number_of_chunks,
 			hash_header,
+			bad_chunks: Vec::new(),
+			error_log: Vec::new(),
+		}
+	}
+
+	/// returns the version, which is used to encode this footer. Footers with bad chunks or an error log use a
+	/// separate version, so older readers will report these footers as unsupported (instead of silently ignoring the acquisition errors).
+	pub fn encoding_version(&self) -> u8 {
+		if self.bad_chunks.is_empty() && self.error_log.is_empty() {
+			Self::version()
+		} else {
+			FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS
 		}
 	}
 
@@ -58,6 +82,10 @@ impl ObjectFooterPhysical {
 		vec.append(&mut self.first_chunk_number.encode_directly());
 		vec.append(&mut self.number_of_chunks.encode_directly());
 		vec.append(&mut self.hash_header.encode_directly());
+		if self.encoding_version() == FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS {
+			vec.append(&mut self.bad_chunks.encode_directly());
+			vec.append(&mut self.error_log.encode_directly());
+		}
 		vec
 	}
 
@@ -71,18 +99,19 @@ impl ObjectFooterPhysical {
 			&encryption_information.borrow().encryption_key, 
 			self.encode_content(), 
 			self.object_number, 
+			encryption_information.borrow().nonce_extension,
 			&encryption_information.borrow().algorithm)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = (
 			DEFAULT_LENGTH_HEADER_IDENTIFIER +
 			DEFAULT_LENGTH_VALUE_HEADER_LENGTH + 
-			Self::version().encode_directly().len() +
+			self.encoding_version().encode_directly().len() +
 			self.object_number.encode_directly().len() +
 			true.encode_directly().len() +
 			encrypted_content.encode_directly().len()) as u64; //4 bytes identifier + 8 bytes for length + length itself
 		vec.append(&mut identifier.to_be_bytes().to_vec());
 		vec.append(&mut encoded_header_length.encode_directly());
-		vec.append(&mut Self::version().encode_directly());
+		vec.append(&mut self.encoding_version().encode_directly());
 		vec.append(&mut self.object_number.encode_directly());
 		vec.append(&mut true.encode_directly()); // encryption flag
 		vec.append(&mut encrypted_content.encode_directly());
@@ -90,13 +119,16 @@ impl ObjectFooterPhysical {
 		Ok(vec)
 	}
 
-	pub(crate) fn decode_inner_content<R: Read>(data: &mut R) -> Result<(
+	#[allow(clippy::type_complexity)]
+	pub(crate) fn decode_inner_content(data: &mut Cursor<Vec<u8>>, footer_version: u8) -> Result<(
 		u64, //acquisition_start
 		u64, //acquisition_end
 		u64, //length_of_data
 		u64, //first_chunk_number
 		u64, //number_of_chunks
-		HashHeader // hash_header
+		HashHeader, // hash_header
+		Vec<u64>, // bad_chunks
+		Vec<AcquisitionErrorEntry>, // error_log
 		)> {
 		let acquisition_start = u64::decode_directly(data)?;
 		let acquisition_end = u64::decode_directly(data)?;
@@ -104,17 +136,82 @@ impl ObjectFooterPhysical {
 		let first_chunk_number = u64::decode_directly(data)?;
 		let number_of_chunks = u64::decode_directly(data)?;
 		let hash_header = HashHeader::decode_directly(data)?;
+		let (bad_chunks, error_log) = if footer_version == FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS {
+			(Vec::<u64>::decode_directly(data)?, Vec::<AcquisitionErrorEntry>::decode_directly(data)?)
+		} else {
+			(Vec::new(), Vec::new())
+		};
 		Ok((
 			acquisition_start,
 			acquisition_end,
 			length_of_data,
 			first_chunk_number,
 			number_of_chunks,
-			hash_header
+			hash_header,
+			bad_chunks,
+			error_log,
 			))
 	}
 }
 
+/// An entry of the error log of a physical object (see [ObjectFooterPhysical::error_log]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct AcquisitionErrorEntry {
+	/// The number of the affected chunk.
+	pub chunk_number: u64,
+	/// The description of the error.
+	pub message: String,
+}
+
+impl AcquisitionErrorEntry {
+	/// creates a new [AcquisitionErrorEntry] with the given values.
+	pub fn new<S: Into<String>>(chunk_number: u64, message: S) -> Self {
+		Self {
+			chunk_number,
+			message: message.into(),
+		}
+	}
+}
+
+impl fmt::Display for AcquisitionErrorEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "chunk {}: {}", self.chunk_number, self.message)
+	}
+}
+
+impl ValueEncoder for Vec<AcquisitionErrorEntry> {
+	fn encode_directly(&self) -> Vec<u8> {
+		let mut vec = Vec::new();
+		vec.append(&mut (self.len() as u64).encode_directly());
+		for entry in self {
+			vec.append(&mut entry.chunk_number.encode_directly());
+			vec.append(&mut entry.message.encode_directly());
+		}
+		vec
+	}
+
+	fn identifier(&self) -> u8 {
+		METADATA_EXT_TYPE_IDENTIFIER_VEC
+	}
+}
+
+impl ValueDecoder for Vec<AcquisitionErrorEntry> {
+	type Item = Vec<AcquisitionErrorEntry>;
+
+	fn decode_directly<R: Read>(data: &mut R) -> Result<Vec<AcquisitionErrorEntry>> {
+		let length = u64::decode_directly(data)?;
+		let mut vec = Vec::new();
+		for _ in 0..length {
+			let chunk_number = u64::decode_directly(data)?;
+			let message = String::decode_directly(data)?;
+			vec.push(AcquisitionErrorEntry::new(chunk_number, message));
+		}
+		Ok(vec)
+	}
+}
+
 // - implement fmt::Display
 impl fmt::Display for ObjectFooterPhysical {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -131,7 +228,7 @@ impl HeaderCoding for ObjectFooterPhysical {
 		FOOTER_IDENTIFIER_OBJECT_FOOTER_PHYSICAL
 	}
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = vec![Self::version()];
+		let mut vec = vec![self.encoding_version()];
 		vec.append(&mut self.object_number.encode_directly());
 		vec.append(&mut false.encode_directly()); // encryption flag
 		vec.append(&mut self.encode_content());
@@ -139,7 +236,7 @@ impl HeaderCoding for ObjectFooterPhysical {
 	}
 	fn decode_content(data: Vec<u8>) -> Result<ObjectFooterPhysical> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let footer_version = decode_footer_version(&mut cursor)?;
 		let object_number = u64::decode_directly(&mut cursor)?;
 		let encryption_flag = bool::decode_directly(&mut cursor)?;
 		if encryption_flag {
@@ -150,15 +247,20 @@ impl HeaderCoding for ObjectFooterPhysical {
 			length_of_data, 
 			first_chunk_number, 
 			number_of_chunks, 
-			hash_header) = Self::decode_inner_content(&mut cursor)?;
-		Ok(ObjectFooterPhysical::new(
+			hash_header,
+			bad_chunks,
+			error_log) = Self::decode_inner_content(&mut cursor, footer_version)?;
+		let mut footer = ObjectFooterPhysical::new(
 			object_number,
 			acquisition_start, 
 			acquisition_end, 
 			length_of_data, 
 			first_chunk_number, 
 			number_of_chunks, 
-			hash_header))
+			hash_header);
+		footer.bad_chunks = bad_chunks;
+		footer.error_log = error_log;
+		Ok(footer)
 	}
 
 	fn struct_name() -> &'static str {
@@ -175,48 +277,57 @@ pub struct EncryptedObjectFooterPhysical {
 	pub object_number: u64,
 	/// The underlying data in encrypted form.
 	pub encrypted_data: Vec<u8>,
+	/// The version of the footer (see [ObjectFooterPhysical::encoding_version]).
+	pub footer_version: u8,
 }
 
 impl EncryptedObjectFooterPhysical {
-	/// Creates a new [EncryptedObjectFooterPhysical] by the given values.
+	/// Creates a new [EncryptedObjectFooterPhysical] by the given values
+	/// (with the default footer version, use [EncryptedObjectFooterPhysical::footer_version] for footers with acquisition errors).
 	pub fn new(object_number: u64, encrypted_data: Vec<u8>) -> Self {
 		Self {
 			object_number,
 			encrypted_data,
+			footer_version: DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL,
 		}
 	}
 
 	/// Tries to decrypt the ObjectFooter. If an error occures, the EncryptedObjectFooterPhysical is still available.
-	pub fn decrypt<A, K>(&self, key: K, algorithm: A) -> Result<ObjectFooterPhysical>
+	pub fn decrypt<A, K>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterPhysical>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		let content = ObjectFooter::decrypt(key, &self.encrypted_data, self.object_number, algorithm.borrow())?;
+		let content = ObjectFooter::decrypt(key, &self.encrypted_data, self.object_number, nonce_extension, algorithm.borrow())?;
 		let mut cursor = Cursor::new(content);
 		let (acquisition_start, 
 			acquisition_end, 
 			length_of_data, 
 			first_chunk_number, 
 			number_of_chunks, 
-			hash_header) = ObjectFooterPhysical::decode_inner_content(&mut cursor)?;
-		Ok(ObjectFooterPhysical::new(
+			hash_header,
+			bad_chunks,
+			error_log) = ObjectFooterPhysical::decode_inner_content(&mut cursor, self.footer_version)?;
+		let mut footer = ObjectFooterPhysical::new(
 			self.object_number,
 			acquisition_start,
 			acquisition_end,
 			length_of_data,
 			first_chunk_number,
 			number_of_chunks,
-			hash_header))
+			hash_header);
+		footer.bad_chunks = bad_chunks;
+		footer.error_log = error_log;
+		Ok(footer)
 	}
 
 	/// Tries to decrypt the ObjectFooter. Consumes the EncryptedObjectFooterPhysical, regardless of whether an error occurs or not.
-	pub fn decrypt_and_consume<A, K>(self, key: K, algorithm: A) -> Result<ObjectFooterPhysical>
+	pub fn decrypt_and_consume<A, K>(self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterPhysical>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		self.decrypt(key, algorithm)
+		self.decrypt(key, nonce_extension, algorithm)
 	}
 }
 
@@ -236,7 +347,7 @@ impl HeaderCoding for EncryptedObjectFooterPhysical {
 		FOOTER_IDENTIFIER_OBJECT_FOOTER_PHYSICAL
 	}
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = vec![Self::version()];
+		let mut vec = vec![self.footer_version];
 		vec.append(&mut self.object_number.encode_directly());
 		vec.append(&mut true.encode_directly()); // encryption flag
 		vec.append(&mut self.encrypted_data.encode_directly());
@@ -244,19 +355,31 @@ impl HeaderCoding for EncryptedObjectFooterPhysical {
 	}
 	fn decode_content(data: Vec<u8>) -> Result<Self> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let footer_version = decode_footer_version(&mut cursor)?;
 		let object_number = u64::decode_directly(&mut cursor)?;
 		let encryption_flag = bool::decode_directly(&mut cursor)?;
 		if !encryption_flag {
 			return Err(ZffError::new(ZffErrorKind::NoEncryptionDetected, ""));
 		}
 		let encrypted_data = Vec::<u8>::decode_directly(&mut cursor)?;
-		Ok(Self::new(
+		let mut footer = Self::new(
 			object_number,
-			encrypted_data))
+			encrypted_data);
+		footer.footer_version = footer_version;
+		Ok(footer)
 	}
 
 	fn struct_name() -> &'static str {
 		"EncryptedObjectFooterPhysical"
 	}
-}
\ No newline at end of file
+}
+
+// footers with bad chunks or an error log use a separate version (see [ObjectFooterPhysical::encoding_version]).
+fn decode_footer_version(cursor: &mut Cursor<Vec<u8>>) -> Result<u8> {
+	let footer_version = u8::decode_directly(cursor)?;
+	if footer_version != DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL
+		&& footer_version != FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS {
+		return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, footer_version.to_string()));
+	}
+	Ok(footer_version)
+}
@@ -11,6 +11,7 @@ use super::*;
 /// - a hash header with the appropriate hash values of the underlying physical dump
 #[derive(Debug,Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub struct ObjectFooterVirtual {
 	/// The object number of the footer.
 	pub object_number: u64,
@@ -64,6 +65,7 @@ impl ObjectFooterVirtual {
 			&encryption_information.borrow().encryption_key, 
 			self.encode_content(), 
 			self.object_number, 
+			encryption_information.borrow().nonce_extension,
 			&encryption_information.borrow().algorithm)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = (
@@ -176,30 +178,30 @@ impl EncryptedObjectFooterVirtual {
 	}
 
 	/// Tries to decrypt the ObjectFooter. If an error occures, the EncryptedObjectFooterVirtual is still available.
-	pub fn decrypt<A, K>(&self, key: K, algorithm: A) -> Result<ObjectFooterVirtual>
+	pub fn decrypt<A, K>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterVirtual>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		self.inner_decrypt(key, algorithm)
+		self.inner_decrypt(key, nonce_extension, algorithm)
 	}
 
 	/// Tries to decrypt the ObjectFooter. Consumes the EncryptedObjectFooterPhysical, regardless of whether an error occurs or not.
-	pub fn decrypt_and_consume<A, K>(self, key: K, algorithm: A) -> Result<ObjectFooterVirtual>
+	pub fn decrypt_and_consume<A, K>(self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterVirtual>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
-		self.inner_decrypt(key, algorithm)
+		self.inner_decrypt(key, nonce_extension, algorithm)
 	}
 
-	fn inner_decrypt<A, K>(&self, key: K, algorithm: A) -> Result<ObjectFooterVirtual>
+	fn inner_decrypt<A, K>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<ObjectFooterVirtual>
 	where
 		A: Borrow<EncryptionAlgorithm>,
 		K: AsRef<[u8]>,
 	{
 		let content = ObjectFooter::decrypt(
-			key, &self.encrypted_data, self.object_number, algorithm.borrow())?;
+			key, &self.encrypted_data, self.object_number, nonce_extension, algorithm.borrow())?;
 		let mut cursor = Cursor::new(content);
 		let (creation_timestamp, 
 			passive_objects, 
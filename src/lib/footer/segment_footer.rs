@@ -5,7 +5,8 @@ use std::fmt;
 
 // - internal
 use crate::{
-	HeaderCoding, Result, ValueDecoder, ValueEncoder, DEFAULT_FOOTER_VERSION_SEGMENT_FOOTER, FOOTER_IDENTIFIER_SEGMENT_FOOTER, INITIAL_CHUNK_NUMBER
+	HeaderCoding, Result, ValueDecoder, ValueEncoder, ZffError, ZffErrorKind, DEFAULT_FOOTER_VERSION_SEGMENT_FOOTER, FOOTER_IDENTIFIER_SEGMENT_FOOTER, INITIAL_CHUNK_NUMBER,
+	ENCODING_KEY_CHUNK_PARITY_MAPS, FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY,
 };
 
 // - external
@@ -43,6 +44,9 @@ pub struct SegmentFooter {
 	pub chunk_samebytes_map_table: BTreeMap<u64, u64>, //<highest chunk number, offset>
 	/// [BTreeMap] containing the chunk number and the appropriate offset of the chunkmaps.
 	pub chunk_dedup_map_table: BTreeMap<u64, u64>, //<highest chunk number, offset>
+	/// [BTreeMap] containing the chunk number and the appropriate offset of the chunk parity maps
+	/// (only available, if the container was created with parity data).
+	pub chunk_parity_map_table: BTreeMap<u64, u64>, //<highest chunk number, offset>
 	/// The first chunk number which was used in this segment.
 	pub first_chunk_number: u64,
 	/// The offset where the footer starts.
@@ -69,12 +73,13 @@ impl SegmentFooter {
 			chunk_xxhash_map_table: BTreeMap::new(),
 			chunk_samebytes_map_table: BTreeMap::new(),
 			chunk_dedup_map_table: BTreeMap::new(),
+			chunk_parity_map_table: BTreeMap::new(),
 			first_chunk_number: INITIAL_CHUNK_NUMBER,
 			footer_offset: 0,
 		}
 	}
 
-	/// creates a new SegmentFooter.
+	/// creates a new SegmentFooter (without a chunk parity map table).
 	pub fn new(
 		length_of_segment: u64, 
 		object_header_offsets: HashMap<u64, u64>, 
@@ -97,6 +102,7 @@ impl SegmentFooter {
 			chunk_xxhash_map_table,
 			chunk_samebytes_map_table,
 			chunk_dedup_map_table,
+			chunk_parity_map_table: BTreeMap::new(),
 			first_chunk_number,
 			footer_offset,
 		}
@@ -146,8 +152,12 @@ impl HeaderCoding for SegmentFooter {
 	}
 
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = Vec::new();
-		vec.append(&mut Self::version().encode_directly());
+		// footers with a chunk parity map table use a separate version, so older readers will report these footers as unsupported.
+		let mut vec = if self.chunk_parity_map_table.is_empty() {
+			vec![Self::version()]
+		} else {
+			vec![FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY]
+		};
 		vec.append(&mut self.length_of_segment.encode_directly());
 		vec.append(&mut self.object_header_offsets.encode_directly());
 		vec.append(&mut self.object_footer_offsets.encode_directly());
@@ -157,6 +167,10 @@ impl HeaderCoding for SegmentFooter {
 		vec.append(&mut self.chunk_xxhash_map_table.encode_directly());
 		vec.append(&mut self.chunk_samebytes_map_table.encode_directly());
 		vec.append(&mut self.chunk_dedup_map_table.encode_directly());
+		// the table is optional to keep the encoding of containers without parity data unchanged.
+		if !self.chunk_parity_map_table.is_empty() {
+			vec.append(&mut self.chunk_parity_map_table.encode_for_key(ENCODING_KEY_CHUNK_PARITY_MAPS));
+		}
 		vec.append(&mut self.first_chunk_number.encode_directly());
 		vec.append(&mut self.footer_offset.encode_directly());
 		vec
@@ -164,7 +178,10 @@ impl HeaderCoding for SegmentFooter {
 
 	fn decode_content(data: Vec<u8>) -> Result<SegmentFooter> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let footer_version = u8::decode_directly(&mut cursor)?;
+		if footer_version != Self::version() && footer_version != FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY {
+			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, footer_version.to_string()));
+		}
 		let length_of_segment = u64::decode_directly(&mut cursor)?;
 		let object_header_offsets = HashMap::<u64, u64>::decode_directly(&mut cursor)?;
 		let object_footer_offsets = HashMap::<u64, u64>::decode_directly(&mut cursor)?;
@@ -174,9 +191,14 @@ impl HeaderCoding for SegmentFooter {
 		let chunk_xxhash_map_table = BTreeMap::<u64, u64>::decode_directly(&mut cursor)?;
 		let chunk_samebytes_map_table = BTreeMap::<u64, u64>::decode_directly(&mut cursor)?;
 		let chunk_dedup_map_table = BTreeMap::<u64, u64>::decode_directly(&mut cursor)?;
+		let chunk_parity_map_table = if footer_version == FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY {
+			BTreeMap::<u64, u64>::decode_for_key(&mut cursor, ENCODING_KEY_CHUNK_PARITY_MAPS)?
+		} else {
+			BTreeMap::new()
+		};
 		let first_chunk_number = u64::decode_directly(&mut cursor)?;
 		let footer_offset = u64::decode_directly(&mut cursor)?;
-		Ok(SegmentFooter::new(
+		let mut segment_footer = SegmentFooter::new(
 			length_of_segment, 
 			object_header_offsets, 
 			object_footer_offsets, 
@@ -187,7 +209,9 @@ impl HeaderCoding for SegmentFooter {
 			chunk_samebytes_map_table,
 			chunk_dedup_map_table,
 			first_chunk_number, 
-			footer_offset))
+			footer_offset);
+		segment_footer.chunk_parity_map_table = chunk_parity_map_table;
+		Ok(segment_footer)
 	}
 
 	fn struct_name() -> &'static str {
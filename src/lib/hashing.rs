@@ -1,5 +1,17 @@
 // - STD
 use std::fmt;
+use std::io::{Read, Write, ErrorKind};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+// - internal
+use crate::{
+	ZffError,
+	ZffErrorKind,
+	Result,
+	constants::{ERROR_PARSE_HASH_TYPE, DEFAULT_BUFFER_SIZE},
+};
 
 // - external
 use blake3::Hasher as Blake3;
@@ -7,6 +19,7 @@ use blake2::Blake2b512;
 use sha2::{Sha256, Sha512};
 use sha3::Sha3_256;
 use digest::{DynDigest, Digest};
+use crossbeam::channel::{bounded, Sender};
 #[cfg(feature = "serde")]
 use serde::{
 	Deserialize,
@@ -35,6 +48,11 @@ pub enum HashType {
 }
 
 impl HashType {
+	/// Returns all implemented hash types (e.g. to build the possible values of a command line argument).
+	pub fn all() -> &'static [HashType] {
+		&[HashType::Blake2b512, HashType::SHA256, HashType::SHA512, HashType::SHA3_256, HashType::Blake3]
+	}
+
 	/// returns the default length of the appropriate hash (as bits).
 	pub fn default_len(&self) -> usize {
 		match self {
@@ -60,6 +78,21 @@ impl fmt::Display for HashType {
 	}
 }
 
+/// Parses the canonical name of a hash type (as returned by the [Display](fmt::Display) implementation).
+/// The parsing is case insensitive and ignores dashes and underscores, so common spellings like "blake2b-512"
+/// or "sha3-256" will be accepted, too.
+impl FromStr for HashType {
+	type Err = ZffError;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		let normalized = s.to_lowercase().replace(['-', '_'], "");
+		match HashType::all().iter().find(|hash_type| hash_type.to_string().to_lowercase().replace('_', "") == normalized) {
+			Some(hash_type) => Ok(hash_type.clone()),
+			None => Err(ZffError::new(ZffErrorKind::ParseError, format!("{ERROR_PARSE_HASH_TYPE}{s}"))),
+		}
+	}
+}
+
 /// structure contains serveral methods to handle hashing
 #[derive(Debug,Clone)]
 pub struct Hash;
@@ -76,12 +109,144 @@ impl Hash {
 		}
 	}
 
+	/// returns the hash of the given data, calculated with the given [HashType].  
+	/// The returned bytes are the same as the hash values, which are stored in the container.
+	pub fn hash_bytes(hash_type: &HashType, data: &[u8]) -> Vec<u8> {
+		let mut hasher = Self::new_hasher(hash_type);
+		hasher.update(data);
+		hasher.finalize().to_vec()
+	}
+
+	/// returns the hash of all data of the given reader (read until EOF), calculated with the given [HashType].
+	/// # Error
+	/// Fails if the data could not be read from the given reader.
+	pub fn hash_reader<R: Read>(hash_type: &HashType, mut reader: R) -> Result<Vec<u8>> {
+		let mut hasher = Self::new_hasher(hash_type);
+		let mut buffer = vec![0u8; DEFAULT_BUFFER_SIZE];
+		loop {
+			match reader.read(&mut buffer) {
+				Ok(0) => break,
+				Ok(n) => hasher.update(&buffer[..n]),
+				Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+				Err(e) => return Err(e.into()),
+			}
+		}
+		Ok(hasher.finalize().to_vec())
+	}
+
 	/// returns the default hashtype of zff.
 	pub fn default_hashtype() -> HashType {
 		HashType::Blake3
 	}
 }
 
+/// Calculates the hashes of all written data with several [HashType]s at once, so the data has to be read only once
+/// (e.g. to check the stored hash values of an object, see [ZffReader::calculate_hash_values](crate::io::zffreader::ZffReader::calculate_hash_values)).  
+/// If more than one thread is used, the hashers will be distributed across the threads (at most one thread per hash type)
+/// and each written buffer will be passed to all threads. Otherwise, the hashers will be updated sequentially.
+pub struct MultiHasher {
+	hash_types: Vec<HashType>,
+	inner: MultiHasherInner,
+}
+
+// the result of a hashing thread.
+type HashWorkerResult = Vec<(usize, Vec<u8>)>; //<index of the hash type, hash>
+
+enum MultiHasherInner {
+	Sequential(Vec<Box<dyn DynDigest>>),
+	Threaded {
+		senders: Vec<Sender<Arc<Vec<u8>>>>,
+		workers: Vec<JoinHandle<HashWorkerResult>>,
+	},
+}
+
+impl MultiHasher {
+	/// Returns a new [MultiHasher] for the given [HashType]s, which uses the given number of threads.
+	pub fn new(hash_types: Vec<HashType>, n_threads: usize) -> Self {
+		let n_threads = n_threads.min(hash_types.len());
+		let inner = if n_threads <= 1 {
+			MultiHasherInner::Sequential(hash_types.iter().map(Hash::new_hasher).collect())
+		} else {
+			let mut groups = vec![Vec::new(); n_threads];
+			for (index, hash_type) in hash_types.iter().enumerate() {
+				groups[index % n_threads].push((index, hash_type.clone()));
+			}
+			let mut senders = Vec::new();
+			let mut workers = Vec::new();
+			for group in groups {
+				let (sender, receiver) = bounded::<Arc<Vec<u8>>>(2);
+				senders.push(sender);
+				workers.push(std::thread::spawn(move || {
+					let mut hashers = group.into_iter()
+						.map(|(index, hash_type)| (index, Hash::new_hasher(&hash_type)))
+						.collect::<Vec<_>>();
+					// the loop ends, when the sender was dropped.
+					for buffer in receiver {
+						for (_, hasher) in hashers.iter_mut() {
+							hasher.update(&buffer);
+						}
+					}
+					hashers.into_iter().map(|(index, hasher)| (index, hasher.finalize().to_vec())).collect()
+				}));
+			}
+			MultiHasherInner::Threaded { senders, workers }
+		};
+		Self {
+			hash_types,
+			inner,
+		}
+	}
+
+	/// Returns the [HashType]s of this [MultiHasher].
+	pub fn hash_types(&self) -> &[HashType] {
+		&self.hash_types
+	}
+
+	/// Returns the hashes of the written data (in the order of the [HashType]s).
+	pub fn finalize(self) -> Vec<(HashType, Vec<u8>)> {
+		let hashes = match self.inner {
+			MultiHasherInner::Sequential(hashers) => hashers.into_iter().map(|hasher| hasher.finalize().to_vec()).collect(),
+			MultiHasherInner::Threaded { senders, workers } => {
+				drop(senders);
+				let mut hashes = vec![Vec::new(); self.hash_types.len()];
+				for worker in workers {
+					// the worker threads only panic, if one of the hashers panics.
+					let results = worker.join().unwrap_or_else(|e| std::panic::resume_unwind(e));
+					for (index, hash) in results {
+						hashes[index] = hash;
+					}
+				}
+				hashes
+			},
+		};
+		self.hash_types.into_iter().zip(hashes).collect()
+	}
+}
+
+impl Write for MultiHasher {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		match &mut self.inner {
+			MultiHasherInner::Sequential(hashers) => {
+				for hasher in hashers.iter_mut() {
+					hasher.update(buf);
+				}
+			},
+			MultiHasherInner::Threaded { senders, .. } => {
+				let buffer = Arc::new(buf.to_vec());
+				for sender in senders.iter() {
+					// the receivers live until the senders are dropped.
+					let _ = sender.send(Arc::clone(&buffer));
+				}
+			},
+		}
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
 #[cfg(feature = "log")]
 pub(crate) fn hashes_to_log(object_no: u64, file_no: Option<u64>, values: &Vec<crate::header::HashValue>) {
 	for value in values {
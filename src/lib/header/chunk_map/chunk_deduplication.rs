@@ -9,7 +9,7 @@ use crate::{
 };
 
 // - external
-use redb::Database;
+use redb::{Database, Durability, TableError};
 use blake3::Hash as Blake3Hash;
 
 /// The [ChunkDeduplicationMap] stores the chunk size of the appropriate chunk.
@@ -81,14 +81,14 @@ impl ChunkMap for ChunkDeduplicationMap {
 		}
 	}
 
-	fn decrypt_and_decode<K, A, D>(key: K, encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
+	fn decrypt_and_decode<K, A, D>(key: K, nonce_extension: [u8; 16], encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
     where
     K: AsRef<[u8]>, 
     A: Borrow<EncryptionAlgorithm>, 
     D: Read,
     Self: Sized {
 		let structure_data = Self::inner_structure_data(data)?;
-		let enc_buffer = ChunkDeduplicationMap::decrypt(key, structure_data, chunk_no, encryption_algorithm.borrow())?;
+		let enc_buffer = Self::decrypt_structure_data(key, structure_data, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
 		let mut reader = Cursor::new(enc_buffer);
 		let map = BTreeMap::decode_directly(&mut reader)?;
 		Ok(Self::with_data(map))
@@ -98,14 +98,14 @@ impl ChunkMap for ChunkDeduplicationMap {
 		self.chunkmap.encode_directly()
 	}
 
-	fn encrypt_encoded_map<K, A>(&self, key: K, encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
+	fn encrypt_encoded_map<K, A>(&self, key: K, nonce_extension: [u8; 16], encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
 		where
 		K: AsRef<[u8]>,
 		A: Borrow<EncryptionAlgorithm>,
 		Self: HeaderCoding, {
 		let mut vec = Vec::new();
 		let encoded_map = Self::encode_map(self);
-		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, encryption_algorithm.borrow())?;
+		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
 		let mut encoded_version = Self::version().encode_directly();
 		let identifier = Self::identifier();
 		let encoded_header_length = (
@@ -203,6 +203,8 @@ impl Default for DeduplicationChunkMap {
 
 impl DeduplicationChunkMap {
 	/// Creates a new [DeduplicationChunkMap] with a Redb by given path.
+	/// The entries will be stored on disk, so the memory usage does not grow with the number of chunks
+	/// (e.g. for very large acquisitions with small chunks). If the Redb already exists, the existing entries will be kept.
 	/// May fail if the Redb can not be created at the given Path.
 	pub fn new_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
 		let db = Database::create(path.as_ref())?;
@@ -219,7 +221,7 @@ impl DeduplicationChunkMap {
 		DeduplicationChunkMap::InMemory(HashMap::new())
 	}
 
-	/// Adds an entry to the deduplication map.
+	/// Adds an entry to the deduplication map. If the map already contains the given hash, the existing chunk number will be kept.
 	pub fn append_entry(&mut self, chunk_no: u64, blak3_hash: Blake3Hash) -> Result<()> {
 		match self {
 			DeduplicationChunkMap::InMemory(map) => {
@@ -230,10 +232,14 @@ impl DeduplicationChunkMap {
 				Ok(())
 			},
 			DeduplicationChunkMap::Redb(db) => {
-				let write_txn = db.begin_write()?;
+				let mut write_txn = db.begin_write()?;
+				// the map is only used while the container will be created, so each entry does not have to be synced to disk.
+				write_txn.set_durability(Durability::Eventual);
 			    {
 			        let mut table = write_txn.open_table(CHUNK_MAP_TABLE)?;
-			        table.insert(blak3_hash.as_bytes(), chunk_no)?;
+			        if table.get(blak3_hash.as_bytes())?.is_none() {
+			        	table.insert(blak3_hash.as_bytes(), chunk_no)?;
+			        }
 			    }
 			    write_txn.commit()?;
 				Ok(())
@@ -252,7 +258,12 @@ impl DeduplicationChunkMap {
 			},
 			DeduplicationChunkMap::Redb(db) => {
 			let read_txn = db.begin_read()?;
-    		let table = read_txn.open_table(CHUNK_MAP_TABLE)?;
+    		let table = match read_txn.open_table(CHUNK_MAP_TABLE) {
+    			Ok(table) => table,
+    			// the table will be created by the first entry.
+    			Err(TableError::TableDoesNotExist(_)) => return Err(ZffError::new_not_in_map_error()),
+    			Err(e) => return Err(e.into()),
+    		};
     		let value = table.get(blak3_hash.borrow().as_bytes())?.ok_or(ZffError::new_not_in_map_error())?.value();
     		Ok(value)
 			}
@@ -5,6 +5,7 @@ use super::*;
 use crate::{
     HEADER_IDENTIFIER_CHUNK_FLAG_MAP,
 	DEFAULT_HEADER_VERSION_CHUNK_FLAG_MAP,
+	HEADER_VERSION_CHUNK_FLAG_MAP_CRC64,
 	ERROR_FLAG_VALUE,
 	COMPRESSION_FLAG_VALUE,
 	SAME_BYTES_FLAG_VALUE,
@@ -12,6 +13,8 @@ use crate::{
 	ENCRYPTION_FLAG_VALUE,
 	EMPTY_FILE_FLAG_VALUE,
 	VIRTUAL_FLAG_VALUE,
+	CRC64_FLAG_VALUE,
+	ChunkIntegrity,
 	METADATA_EXT_TYPE_IDENTIFIER_U8,
 };
 
@@ -34,6 +37,8 @@ pub struct ChunkFlags {
 	pub empty_file: bool,
 	/// is set, if the chunk is a virtual chunk.
 	pub virtual_chunk: bool,
+	/// is set, if the integrity hash of the chunk is a CRC-64 checksum (otherwise, the xxh3 hash is used).
+	pub crc64: bool,
 }
 
 impl From<u8> for ChunkFlags {
@@ -46,6 +51,7 @@ impl From<u8> for ChunkFlags {
 			encryption: flag_values & ENCRYPTION_FLAG_VALUE != 0,
 			empty_file: flag_values & EMPTY_FILE_FLAG_VALUE != 0,
 			virtual_chunk: flag_values & VIRTUAL_FLAG_VALUE != 0,
+			crc64: flag_values & CRC64_FLAG_VALUE != 0,
 		}
 	}
 }
@@ -74,9 +80,39 @@ impl ChunkFlags {
 		if self.encryption { flag_value |= ENCRYPTION_FLAG_VALUE; }
 		if self.empty_file { flag_value |= EMPTY_FILE_FLAG_VALUE; }
 		if self.virtual_chunk { flag_value |= VIRTUAL_FLAG_VALUE; }
+		if self.crc64 { flag_value |= CRC64_FLAG_VALUE; }
 		flag_value
 	}
 
+	/// Returns the names of all flags, which are set (e.g. "compression" or "same_bytes").
+	pub fn names(&self) -> Vec<&'static str> {
+		let flags = [
+			(self.error, "error"),
+			(self.compression, "compression"),
+			(self.same_bytes, "same_bytes"),
+			(self.duplicate, "duplicate"),
+			(self.encryption, "encryption"),
+			(self.empty_file, "empty_file"),
+			(self.virtual_chunk, "virtual_chunk"),
+			(self.crc64, "crc64"),
+		];
+		flags.into_iter().filter(|(set, _)| *set).map(|(_, name)| name).collect()
+	}
+
+	/// Returns the [ChunkIntegrity] algorithm, which was used to calculate the integrity hash of the chunk.
+	pub fn integrity(&self) -> ChunkIntegrity {
+		if self.crc64 {
+			ChunkIntegrity::Crc64
+		} else {
+			ChunkIntegrity::Xxh3
+		}
+	}
+
+	/// Sets the appropriate flag for the given [ChunkIntegrity] algorithm.
+	pub fn set_integrity(&mut self, integrity: ChunkIntegrity) {
+		self.crc64 = integrity == ChunkIntegrity::Crc64;
+	}
+
 	fn struct_name() -> &'static str {
 		"ChunkHeaderFlags"
 	}
@@ -92,6 +128,7 @@ impl ValueEncoder for ChunkFlags {
 		if self.encryption { flag_value |= ENCRYPTION_FLAG_VALUE; }
 		if self.empty_file { flag_value |= EMPTY_FILE_FLAG_VALUE; }
 		if self.virtual_chunk { flag_value |= VIRTUAL_FLAG_VALUE; }
+		if self.crc64 { flag_value |= CRC64_FLAG_VALUE; }
 		flag_value.encode_directly()
 	}
 
@@ -118,6 +155,18 @@ pub struct ChunkFlagsMap {
 	target_size: usize,
 }
 
+impl ChunkFlagsMap {
+	// maps with CRC-64 flagged chunks use a separate version, so older readers will report these maps as unsupported
+	// (instead of checking the CRC-64 checksums as xxh3 hashes).
+	fn encoding_version(&self) -> u8 {
+		if self.chunkmap.values().any(|flags| flags.crc64) {
+			HEADER_VERSION_CHUNK_FLAG_MAP_CRC64
+		} else {
+			Self::version()
+		}
+	}
+}
+
 impl Default for ChunkFlagsMap {
 	fn default() -> Self {
 		Self::new_empty()
@@ -179,14 +228,18 @@ impl ChunkMap for ChunkFlagsMap {
 		}
 	}
 
-	fn decrypt_and_decode<K, A, D>(key: K, encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
+	fn is_supported_version(version: u8) -> bool {
+		version == Self::version() || version == HEADER_VERSION_CHUNK_FLAG_MAP_CRC64
+	}
+
+	fn decrypt_and_decode<K, A, D>(key: K, nonce_extension: [u8; 16], encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
     where
     K: AsRef<[u8]>, 
     A: Borrow<EncryptionAlgorithm>, 
     D: Read,
     Self: Sized {
 		let structure_data = Self::inner_structure_data(data)?;
-		let enc_buffer = ChunkFlagsMap::decrypt(key, structure_data, chunk_no, encryption_algorithm.borrow())?;
+		let enc_buffer = Self::decrypt_structure_data(key, structure_data, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
 		let mut reader = Cursor::new(enc_buffer);
 		let map = BTreeMap::decode_directly(&mut reader)?;
 		Ok(Self::with_data(map))
@@ -196,15 +249,15 @@ impl ChunkMap for ChunkFlagsMap {
 		self.chunkmap.encode_directly()
 	}
 
-	fn encrypt_encoded_map<K, A>(&self, key: K, encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
+	fn encrypt_encoded_map<K, A>(&self, key: K, nonce_extension: [u8; 16], encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
 		where
 		K: AsRef<[u8]>,
 		A: Borrow<EncryptionAlgorithm>,
 		Self: HeaderCoding, {
 		let mut vec = Vec::new();
 		let encoded_map = Self::encode_map(self);
-		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, encryption_algorithm.borrow())?;
-		let mut encoded_version = Self::version().encode_directly();
+		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
+		let mut encoded_version = self.encoding_version().encode_directly();
 		let identifier = Self::identifier();
 		let encoded_header_length = (
 			DEFAULT_LENGTH_HEADER_IDENTIFIER + 
@@ -233,14 +286,17 @@ impl HeaderCoding for ChunkFlagsMap {
 	fn encode_header(&self) -> Vec<u8> {
 		let mut vec = Vec::new();
 
-		vec.append(&mut Self::version().encode_directly());
+		vec.append(&mut self.encoding_version().encode_directly());
 		vec.append(&mut self.chunkmap.encode_directly());
 		vec
 	}
 
 	fn decode_content(data: Vec<u8>) -> Result<Self> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let version = u8::decode_directly(&mut cursor)?;
+		if !Self::is_supported_version(version) {
+			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, version.to_string()));
+		}
 		let chunkmap = BTreeMap::<u64, ChunkFlags>::decode_directly(&mut cursor)?;
 		Ok(Self::with_data(chunkmap))
 	}
@@ -76,14 +76,14 @@ impl ChunkMap for ChunkOffsetMap {
 		}
 	}
 
-	fn decrypt_and_decode<K, A, D>(key: K, encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
+	fn decrypt_and_decode<K, A, D>(key: K, nonce_extension: [u8; 16], encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
     where
     K: AsRef<[u8]>, 
     A: Borrow<EncryptionAlgorithm>, 
     D: Read,
     Self: Sized {
 		let structure_data = Self::inner_structure_data(data)?;
-		let enc_buffer = Self::decrypt(key, structure_data, chunk_no, encryption_algorithm.borrow())?;
+		let enc_buffer = Self::decrypt_structure_data(key, structure_data, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
 		let mut reader = Cursor::new(enc_buffer);
 		let map = BTreeMap::decode_directly(&mut reader)?;
 		Ok(Self::with_data(map))
@@ -93,14 +93,14 @@ impl ChunkMap for ChunkOffsetMap {
 		self.chunkmap.encode_directly()
 	}
 
-	fn encrypt_encoded_map<K, A>(&self, key: K, encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
+	fn encrypt_encoded_map<K, A>(&self, key: K, nonce_extension: [u8; 16], encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
 		where
 		K: AsRef<[u8]>,
 		A: Borrow<EncryptionAlgorithm>,
 		Self: HeaderCoding, {
 		let mut vec = Vec::new();
 		let encoded_map = Self::encode_map(self);
-		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, encryption_algorithm.borrow())?;
+		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
 		let mut encoded_version = Self::version().encode_directly();
 		let identifier = Self::identifier();
 		let encoded_header_length = (
@@ -0,0 +1,307 @@
+// - parent
+use super::*;
+
+// - internal
+use crate::{
+    HEADER_IDENTIFIER_CHUNK_PARITY_MAP,
+	DEFAULT_HEADER_VERSION_CHUNK_PARITY_MAP,
+	METADATA_EXT_TYPE_IDENTIFIER_BYTEARRAY,
+	ERROR_INVALID_PARITY_SHARDS,
+};
+
+// - external
+use reed_solomon_erasure::galois_8::ReedSolomon;
+
+/// The [ChunkParityBlock] contains the Reed-Solomon parity data of a group of consecutive chunks (a parity group).
+///
+/// The stored (compressed and/or encrypted) data of each chunk of the group is used as a data shard. All shards
+/// are padded with zeros to the size of the largest chunk of the group (the shard size). The parity block can be used
+/// to reconstruct as many corrupt (or missing) chunks of the group as it contains parity shards.
+#[derive(Debug,Clone,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChunkParityBlock {
+	/// The number of chunks of the parity group (the last chunk of the group is the key of the appropriate map entry).
+	pub number_of_chunks: u64,
+	/// The size of each shard.
+	pub shard_size: u64,
+	/// The parity shards (each shard has the shard size).
+	pub parity_shards: Vec<Vec<u8>>,
+}
+
+impl ChunkParityBlock {
+	/// Calculates the parity block of the given (stored) chunk data by using the given number of parity shards.
+	/// Returns None, if all chunks are empty (there is nothing to reconstruct).
+	/// # Error
+	/// Fails if there are no chunks, no parity shards or more than 256 shards in total.
+	pub fn calculate(chunks: &[Vec<u8>], parity_shards: usize) -> Result<Option<Self>> {
+		let codec = ReedSolomon::new(chunks.len(), parity_shards)?;
+		let shard_size = chunks.iter().map(|chunk| chunk.len()).max().unwrap_or(0);
+		if shard_size == 0 {
+			return Ok(None);
+		}
+		let mut shards = Vec::with_capacity(chunks.len() + parity_shards);
+		for chunk in chunks {
+			let mut shard = chunk.clone();
+			shard.resize(shard_size, 0);
+			shards.push(shard);
+		}
+		shards.resize(chunks.len() + parity_shards, vec![0; shard_size]);
+		codec.encode(&mut shards)?;
+		Ok(Some(Self {
+			number_of_chunks: chunks.len() as u64,
+			shard_size: shard_size as u64,
+			parity_shards: shards.split_off(chunks.len()),
+		}))
+	}
+
+	/// Reconstructs the missing chunks of the parity group.
+	/// The chunks have to be given in ascending order of their chunk numbers, corrupt (or missing) chunks have to be None.
+	/// Returns the data of all chunks of the group, padded to the shard size
+	/// (the original sizes are stored in the [ChunkSizeMap]).
+	/// # Error
+	/// Fails if the number of the given chunks does not match or if too many chunks are missing.
+	pub fn reconstruct(&self, chunks: Vec<Option<Vec<u8>>>) -> Result<Vec<Vec<u8>>> {
+		if chunks.len() as u64 != self.number_of_chunks {
+			return Err(ZffError::new(ZffErrorKind::ReedSolomonError, ERROR_INVALID_PARITY_SHARDS));
+		}
+		let number_of_chunks = chunks.len();
+		let codec = ReedSolomon::new(number_of_chunks, self.parity_shards.len())?;
+		let shard_size = self.shard_size as usize;
+		let mut shards = chunks.into_iter().map(|chunk| match chunk {
+			// a chunk which is larger than the shard size cannot be intact.
+			Some(chunk) if chunk.len() > shard_size => None,
+			Some(mut chunk) => {
+				chunk.resize(shard_size, 0);
+				Some(chunk)
+			},
+			None => None,
+		}).collect::<Vec<_>>();
+		shards.extend(self.parity_shards.iter().cloned().map(Some));
+		codec.reconstruct_data(&mut shards)?;
+		Ok(shards.into_iter().take(number_of_chunks).map(|shard| shard.unwrap_or_default()).collect())
+	}
+
+	// the encoded size of this block.
+	fn encoded_size(&self) -> usize {
+		8 + 8 + 8 + self.parity_shards.len() * self.shard_size as usize //8 bytes for the number of chunks, 8 bytes for the shard size, 8 bytes for the number of parity shards
+	}
+}
+
+impl ValueEncoder for ChunkParityBlock {
+	fn encode_directly(&self) -> Vec<u8> {
+		let mut vec = Vec::new();
+		vec.append(&mut self.number_of_chunks.encode_directly());
+		vec.append(&mut self.shard_size.encode_directly());
+		vec.append(&mut (self.parity_shards.len() as u64).encode_directly());
+		for shard in &self.parity_shards {
+			vec.extend_from_slice(shard);
+		}
+		vec
+	}
+
+	fn identifier(&self) -> u8 {
+		METADATA_EXT_TYPE_IDENTIFIER_BYTEARRAY
+	}
+}
+
+impl ValueDecoder for ChunkParityBlock {
+	type Item = Self;
+
+	fn decode_directly<R: Read>(data: &mut R) -> Result<Self> {
+		let number_of_chunks = u64::decode_directly(data)?;
+		let shard_size = u64::decode_directly(data)?;
+		let number_of_parity_shards = u64::decode_directly(data)?;
+		let mut parity_shards = Vec::new();
+		parity_shards.try_reserve(number_of_parity_shards as usize)?;
+		for _ in 0..number_of_parity_shards {
+			let mut shard = Vec::new();
+			shard.try_reserve(shard_size as usize)?;
+			shard.resize(shard_size as usize, 0);
+			data.read_exact(&mut shard)?;
+			parity_shards.push(shard);
+		}
+		Ok(Self {
+			number_of_chunks,
+			shard_size,
+			parity_shards,
+		})
+	}
+}
+
+/// The [ChunkParityMap] stores the [ChunkParityBlock]s of the parity groups (the key is the last chunk number of the appropriate group).
+#[derive(Debug,Clone,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct ChunkParityMap {
+	chunkmap: BTreeMap<u64, ChunkParityBlock>, //<last chunk no of the group, parity block>
+	target_size: usize,
+}
+
+impl Default for ChunkParityMap {
+	fn default() -> Self {
+		Self::new_empty()
+	}
+}
+
+impl ChunkParityMap {
+	/// Returns the [ChunkParityBlock] of the parity group, which contains the given chunk (if available in this map).
+	pub fn parity_block_of_chunk(&self, chunk_no: u64) -> Option<(u64, &ChunkParityBlock)> {
+		let (last_chunk_no, parity_block) = self.chunkmap.range(chunk_no..).next()?;
+		if last_chunk_no - chunk_no < parity_block.number_of_chunks {
+			Some((*last_chunk_no, parity_block))
+		} else {
+			None
+		}
+	}
+}
+
+impl ChunkMap for ChunkParityMap {
+	type Value = ChunkParityBlock;
+
+	/// returns a new [ChunkParityMap] with the given values.
+	fn with_data(chunkmap: BTreeMap<u64, Self::Value>) -> Self {
+		Self {
+			chunkmap,
+			target_size: 0,
+		}
+	}
+
+	/// returns a new, empty [ChunkParityMap] with the given values.
+	fn new_empty() -> Self {
+		Self {
+			chunkmap: BTreeMap::new(),
+			target_size: 0,
+		}
+	}
+
+	fn flush(&mut self) -> BTreeMap<u64, Self::Value> {
+		std::mem::take(&mut self.chunkmap)
+	}
+
+	fn current_size(&self) -> usize {
+		match self.chunkmap.first_key_value() {
+			Some(_) => self.chunkmap.values().map(|block| 8 + block.encoded_size()).sum::<usize>() + 8, //8 -> 8bytes for the chunk no
+			None => 0,
+		}
+	}
+
+	fn chunkmap(&self) -> &BTreeMap<u64, Self::Value> {
+		&self.chunkmap
+	}
+
+	fn set_target_size(&mut self, target_size: usize) {
+		self.target_size = target_size
+	}
+
+	fn add_chunk_entry<V: Borrow<Self::Value>>(&mut self, chunk_no: u64, value: V) -> bool {
+		if self.is_full() {
+			false
+		} else {
+			self.chunkmap.entry(chunk_no).or_insert(value.borrow().clone());
+			true
+		}
+	}
+
+	// the size of a parity block depends on the chunk sizes, so the map is full if the target size is reached
+	// (an empty map accepts each block).
+	fn is_full(&self) -> bool {
+		!self.chunkmap.is_empty() && self.target_size <= self.current_size()
+	}
+
+	fn decrypt_and_decode<K, A, D>(key: K, nonce_extension: [u8; 16], encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self>
+    where
+    K: AsRef<[u8]>,
+    A: Borrow<EncryptionAlgorithm>,
+    D: Read,
+    Self: Sized {
+		let structure_data = Self::inner_structure_data(data)?;
+		let enc_buffer = Self::decrypt_structure_data(key, structure_data, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
+		let mut reader = Cursor::new(enc_buffer);
+		let map = BTreeMap::decode_directly(&mut reader)?;
+		Ok(Self::with_data(map))
+	}
+
+	fn encode_map(&self) -> Vec<u8> {
+		self.chunkmap.encode_directly()
+	}
+
+	fn encrypt_encoded_map<K, A>(&self, key: K, nonce_extension: [u8; 16], encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
+		where
+		K: AsRef<[u8]>,
+		A: Borrow<EncryptionAlgorithm>,
+		Self: HeaderCoding, {
+		let mut vec = Vec::new();
+		let encoded_map = Self::encode_map(self);
+		let mut encrypted_map = Self::encrypt(key, encoded_map, chunk_no, nonce_extension, encryption_algorithm.borrow())?;
+		let mut encoded_version = Self::version().encode_directly();
+		let identifier = Self::identifier();
+		let encoded_header_length = (
+			DEFAULT_LENGTH_HEADER_IDENTIFIER +
+			DEFAULT_LENGTH_VALUE_HEADER_LENGTH +
+			encrypted_map.len() +
+			encoded_version.len()) as u64;
+		vec.append(&mut identifier.to_be_bytes().to_vec());
+		vec.append(&mut encoded_header_length.to_le_bytes().to_vec());
+		vec.append(&mut encoded_version);
+		vec.append(&mut encrypted_map);
+		Ok(vec)
+	}
+}
+
+impl HeaderCoding for ChunkParityMap {
+	type Item = ChunkParityMap;
+
+	fn identifier() -> u32 {
+		HEADER_IDENTIFIER_CHUNK_PARITY_MAP
+	}
+
+	fn version() -> u8 {
+		DEFAULT_HEADER_VERSION_CHUNK_PARITY_MAP
+	}
+
+	fn encode_header(&self) -> Vec<u8> {
+		let mut vec = Vec::new();
+
+		vec.append(&mut Self::version().encode_directly());
+		vec.append(&mut self.chunkmap.encode_directly());
+		vec
+	}
+
+	fn decode_content(data: Vec<u8>) -> Result<Self> {
+		let mut cursor = Cursor::new(data);
+		Self::check_version(&mut cursor)?;
+		let chunkmap = BTreeMap::<u64, ChunkParityBlock>::decode_directly(&mut cursor)?;
+		Ok(Self::with_data(chunkmap))
+	}
+
+	fn struct_name() -> &'static str {
+		"ChunkParityMap"
+	}
+}
+
+// - implement fmt::Display
+impl fmt::Display for ChunkParityMap {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", Self::struct_name())
+	}
+}
+
+impl Encryption for ChunkParityMap {
+	fn crypto_nonce_padding() -> u8 {
+		0b01111111
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ChunkParityMap {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(Self::struct_name(), 2)?;
+        for (key, value) in &self.chunkmap {
+        	state.serialize_field(string_to_str(key.to_string()), &value)?;
+        }
+        state.end()
+    }
+}
@@ -13,6 +13,7 @@ mod chunk_size;
 mod chunk_xxhash;
 mod chunk_same_bytes;
 mod chunk_deduplication;
+mod chunk_parity;
 
 // - use
 pub use chunk_flags::*;
@@ -21,6 +22,7 @@ pub use chunk_size::*;
 pub use chunk_xxhash::*;
 pub use chunk_same_bytes::*;
 pub use chunk_deduplication::*;
+pub use chunk_parity::*;
 
 // - internal
 use crate::{
@@ -35,6 +37,7 @@ use crate::{
     DEFAULT_LENGTH_HEADER_IDENTIFIER,
     DEFAULT_LENGTH_VALUE_HEADER_LENGTH,
     ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER,
+    ERROR_CHUNKMAP_AUTHENTICATION_FAILED,
 };
 
 #[cfg(feature = "serde")]
@@ -68,6 +71,8 @@ pub enum ChunkMapType {
 	SamebytesMap = 4,
 	/// The deduplication map.
 	DeduplicationMap = 5,
+	/// The parity map.
+	ParityMap = 6,
 }
 
 impl fmt::Display for ChunkMapType {
@@ -79,6 +84,7 @@ impl fmt::Display for ChunkMapType {
 			ChunkMapType::XxHashMap => "XxHashMap",
 			ChunkMapType::SamebytesMap => "SamebytesMap",
 			ChunkMapType::DeduplicationMap => "DeduplicationMap",
+			ChunkMapType::ParityMap => "ParityMap",
     	};
         write!(f, "{value}")
     }
@@ -100,6 +106,8 @@ pub struct ChunkMaps {
 	pub same_bytes_map: ChunkSamebytesMap,
 	/// The deduplication map.
 	pub duplicate_chunks: ChunkDeduplicationMap,
+	/// The parity map.
+	pub parity_map: ChunkParityMap,
 }
 
 impl ChunkMaps {
@@ -110,7 +118,8 @@ impl ChunkMaps {
 		self.flags_map.chunkmap().is_empty() && 
 		self.xxhash_map.chunkmap().is_empty() && 
 		self.same_bytes_map.chunkmap().is_empty() && 
-		self.duplicate_chunks.chunkmap().is_empty()
+		self.duplicate_chunks.chunkmap().is_empty() && 
+		self.parity_map.chunkmap().is_empty()
 	}
 }
 
@@ -151,6 +160,14 @@ pub trait ChunkMap {
     /// Returns a reference to the inner map
 	fn chunkmap(&self) -> &BTreeMap<u64, Self::Value>;
 
+    /// Returns true, if the given version of an encoded chunk map can be decoded.
+    fn is_supported_version(version: u8) -> bool
+    where
+        Self: HeaderCoding,
+    {
+        version == Self::version()
+    }
+
     /// Returns the apprpropriate (encrypted) structure data
     fn inner_structure_data<D: Read>(data: &mut D) -> Result<Vec<u8>>
     where 
@@ -161,7 +178,7 @@ pub trait ChunkMap {
 		}
 		let header_length = Self::decode_header_length(data)? as usize;
 		let version = u8::decode_directly(data)?;
-		if version != Self::version() {
+		if !Self::is_supported_version(version) {
 			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, version.to_string()));
 		}
 		let mut structure_content = vec![0u8; header_length-DEFAULT_LENGTH_HEADER_IDENTIFIER-DEFAULT_LENGTH_VALUE_HEADER_LENGTH-1];
@@ -169,8 +186,25 @@ pub trait ChunkMap {
         Ok(structure_content)
     }
 
+    /// Decrypts the (encrypted) structure data of the chunk map by using the given key.
+    /// All supported encryption algorithms are authenticated, so a failed authentication of the data
+    /// (e.g. a corrupt encrypted chunk map) will be returned as [ZffErrorKind::IntegrityError].
+    fn decrypt_structure_data<K, A>(key: K, structure_data: Vec<u8>, chunk_no: u64, nonce_extension: [u8; 16], encryption_algorithm: A) -> Result<Vec<u8>>
+    where
+    K: AsRef<[u8]>,
+    A: Borrow<EncryptionAlgorithm>,
+    Self: HeaderCoding + Encryption,
+    {
+        match Self::decrypt(key, structure_data, chunk_no, nonce_extension, encryption_algorithm) {
+            Err(e) if matches!(e.kind, ZffErrorKind::EncryptionError) => Err(ZffError::new(
+                ZffErrorKind::IntegrityError,
+                format!("{ERROR_CHUNKMAP_AUTHENTICATION_FAILED}{} (chunk number {chunk_no})", Self::struct_name()))),
+            result => result,
+        }
+    }
+
     /// Decrypts and decodes the chunk map by using the given key.
-    fn decrypt_and_decode<K, A, D>(key: K, encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
+    fn decrypt_and_decode<K, A, D>(key: K, nonce_extension: [u8; 16], encryption_algorithm: A, data: &mut D, chunk_no: u64) -> Result<Self> 
     where
     K: AsRef<[u8]>, 
     A: Borrow<EncryptionAlgorithm>, 
@@ -181,21 +215,21 @@ pub trait ChunkMap {
 	fn encode_map(&self) -> Vec<u8>;
 
 	/// Encrypts the inner encoded map
-	fn encrypt_encoded_map<K, A>(&self, key: K, encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
+	fn encrypt_encoded_map<K, A>(&self, key: K, nonce_extension: [u8; 16], encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
     where
     K: AsRef<[u8]>,
     A: Borrow<EncryptionAlgorithm>,
     Self: HeaderCoding;
 
     /// Encodes and encrypts the appropriate chunk map
-    fn encode_and_encrypt<K, A>(&self, key: K, encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
+    fn encode_and_encrypt<K, A>(&self, key: K, nonce_extension: [u8; 16], encryption_algorithm: A, chunk_no: u64) -> Result<Vec<u8>>
     where
     K: AsRef<[u8]>,
     A: Borrow<EncryptionAlgorithm>,
     Self: HeaderCoding,
 	{
 		let mut vec = Vec::new();
-		let mut encrypted_map = self.encrypt_encoded_map(key, encryption_algorithm, chunk_no)?;
+		let mut encrypted_map = self.encrypt_encoded_map(key, nonce_extension, encryption_algorithm, chunk_no)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = (DEFAULT_LENGTH_HEADER_IDENTIFIER + DEFAULT_LENGTH_VALUE_HEADER_LENGTH + encrypted_map.len()) as u64; //4 bytes identifier + 8 bytes for length + length itself
 		vec.append(&mut identifier.to_be_bytes().to_vec());
@@ -2,11 +2,17 @@
 use std::io::Cursor;
 use std::fmt;
 
-use crate::constants::DEFAULT_HEADER_VERSION_COMPRESSION_HEADER;
+use crate::constants::{
+	DEFAULT_HEADER_VERSION_COMPRESSION_HEADER,
+	HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY,
+	COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS,
+	COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS,
+};
 // - internal
 use crate::{
 	Result,
 	ZffError,
+	ZffErrorKind,
 	HeaderCoding,
 	ValueEncoder,
 	ValueDecoder,
@@ -36,8 +42,14 @@ pub struct CompressionHeader {
 	pub algorithm: CompressionAlgorithm,
 	/// The compression level.
 	pub level: u8,
-	/// The compression threshold.
+	/// The compression threshold. The compressed data of a chunk will only be stored, if the compression ratio
+	/// (uncompressed size / compressed size) is not below this threshold. Use
+	/// [COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS](crate::constants::COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS) (or any other value <= 0.0)
+	/// to keep the compressed data of each chunk and
+	/// [COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS](crate::constants::COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS) to skip the compression.
 	pub threshold: f32,
+	/// The optional (zstd) dictionary, which is used to compress and decompress the chunks.
+	pub dictionary: Option<Vec<u8>>,
 }
 
 impl CompressionHeader {
@@ -47,6 +59,27 @@ impl CompressionHeader {
 			algorithm: compression_algo,
 			level,
 			threshold,
+			dictionary: None,
+		}
+	}
+
+	/// Returns true, if the compressed data of each chunk will be kept (regardless of the achieved compression ratio).
+	pub fn always_compress(&self) -> bool {
+		self.threshold <= COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS
+	}
+
+	/// Returns true, if the chunks will be stored uncompressed without trying to compress them.
+	pub fn never_compress(&self) -> bool {
+		self.threshold == COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS
+	}
+
+	/// returns a new compression header with the given values and the given (zstd) dictionary.
+	pub fn new_with_dictionary(compression_algo: CompressionAlgorithm, level: u8, threshold: f32, dictionary: Vec<u8>) -> CompressionHeader {
+		Self {
+			algorithm: compression_algo,
+			level,
+			threshold,
+			dictionary: Some(dictionary),
 		}
 	}
 }
@@ -63,24 +96,44 @@ impl HeaderCoding for CompressionHeader {
 	}
 
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = vec![Self::version(), self.algorithm.clone() as u8, self.level];
+		// headers with a dictionary use a separate version, so older readers will report these headers as unsupported
+		// (instead of failing to decompress the chunks).
+		let version = if self.dictionary.is_some() {
+			HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY
+		} else {
+			Self::version()
+		};
+		let mut vec = vec![version, self.algorithm.clone() as u8, self.level];
 		vec.append(&mut self.threshold.encode_directly());
+		if let Some(dictionary) = &self.dictionary {
+			vec.append(&mut dictionary.encode_directly());
+		}
 		
 		vec
 	}
 
 	fn decode_content(data: Vec<u8>) -> Result<CompressionHeader> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let version = u8::decode_directly(&mut cursor)?;
+		if version != Self::version() && version != HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY {
+			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, version.to_string()));
+		}
 		let algorithm = match u8::decode_directly(&mut cursor) {
 			Ok(0) => CompressionAlgorithm::None,
 			Ok(1) => CompressionAlgorithm::Zstd,
 			Ok(2) => CompressionAlgorithm::Lz4,
+			Ok(3) => CompressionAlgorithm::Brotli,
+			Ok(4) => CompressionAlgorithm::Xz,
+			Ok(5) => CompressionAlgorithm::Gzip,
 			_ => return Err(ZffError::new_header_decode_error(ERROR_HEADER_DECODER_COMPRESSION_ALGORITHM))
 		};
 		let level = u8::decode_directly(&mut cursor)?;
 		let threshold = f32::decode_directly(&mut cursor)?;
-		Ok(CompressionHeader::new(algorithm, level, threshold))
+		let mut compression_header = CompressionHeader::new(algorithm, level, threshold);
+		if version == HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY {
+			compression_header.dictionary = Some(Vec::<u8>::decode_directly(&mut cursor)?);
+		}
+		Ok(compression_header)
 	}
 
 	fn struct_name() -> &'static str {
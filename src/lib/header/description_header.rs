@@ -27,7 +27,12 @@ use serde::{
 		Serializer,
 		SerializeStruct,
 	},
-	Deserialize,
+	de::{
+		Deserialize,
+		Deserializer,
+		MapAccess,
+		Visitor,
+	},
 };
 
 /// The description header contains all data, which describes the dumped data in den appropriate object (e.g. case number, examiner name or acquisition date).
@@ -53,7 +58,6 @@ use serde::{
 /// assert_eq!(Some("ph0llux"), description_header.examiner_name());
 /// ```
 #[derive(Debug,Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub struct DescriptionHeader {
 	/// The inner identifier map.
 	pub identifier_map: HashMap<String, String>
@@ -75,6 +79,42 @@ impl Serialize for DescriptionHeader {
     }
 }
 
+// the fields are serialized flat (next to the version), see the Serialize implementation above.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DescriptionHeader {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct DescriptionHeaderVisitor;
+
+        impl<'de> Visitor<'de> for DescriptionHeaderVisitor {
+            type Value = DescriptionHeader;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of the version and the description fields")
+            }
+
+            fn visit_map<M>(self, mut map: M) -> std::result::Result<DescriptionHeader, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let mut identifier_map = HashMap::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "version" {
+                        map.next_value::<u8>()?;
+                    } else {
+                        identifier_map.insert(key, map.next_value::<String>()?);
+                    }
+                }
+                Ok(DescriptionHeader::new(identifier_map))
+            }
+        }
+
+        deserializer.deserialize_map(DescriptionHeaderVisitor)
+    }
+}
+
 impl DescriptionHeader {
 	/// creates a new, empty header, which can be filled by the set_*-methods.
 	/// All fields will be initialized with ```None``` or ```0```.
@@ -152,6 +192,68 @@ impl DescriptionHeader {
 	pub fn identifier_map(&self) -> &HashMap<String, String> {
 		&self.identifier_map
 	}
+
+	/// Creates a new [DescriptionHeader] by the given (human readable) fields.
+	/// 
+	/// The keys ```case_number```, ```evidence_number```, ```examiner_name``` and ```notes``` will be mapped to the appropriate
+	/// predefined fields. All other keys will be stored as custom key-value pairs.
+	/// # Error
+	/// Fails if a key is empty or if a custom key collides with the encoding key of a predefined field
+	/// (e.g. [ENCODING_KEY_CASE_NUMBER](crate::constants::ENCODING_KEY_CASE_NUMBER)).
+	/// # Example
+	/// ```
+	/// use std::collections::HashMap;
+	/// use zff::header::DescriptionHeader;
+	/// 
+	/// let mut fields = HashMap::new();
+	/// fields.insert("case_number", "1");
+	/// fields.insert("examiner_name", "ph0llux");
+	/// fields.insert("location", "lab 2");
+	/// 
+	/// let description_header = DescriptionHeader::from_fields(fields.clone()).unwrap();
+	/// assert_eq!(Some("1"), description_header.case_number());
+	/// assert_eq!(Some(&String::from("lab 2")), description_header.identifier_map().get("location"));
+	/// 
+	/// let round_trip = description_header.to_fields();
+	/// assert_eq!(fields.len(), round_trip.len());
+	/// for (key, value) in fields {
+	///     assert_eq!(Some(&String::from(value)), round_trip.get(key));
+	/// }
+	/// ```
+	pub fn from_fields(fields: HashMap<&str, &str>) -> Result<DescriptionHeader> {
+		let mut description_header = DescriptionHeader::new_empty();
+		for (key, value) in fields {
+			match key {
+				"" => return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_DESCRIPTION_FIELD_EMPTY_KEY)),
+				DESCRIPTION_FIELD_CASE_NUMBER => description_header.set_case_number(value),
+				DESCRIPTION_FIELD_EVIDENCE_NUMBER => description_header.set_evidence_number(value),
+				DESCRIPTION_FIELD_EXAMINER_NAME => description_header.set_examiner_name(value),
+				DESCRIPTION_FIELD_NOTES => description_header.set_notes(value),
+				ENCODING_KEY_CASE_NUMBER | ENCODING_KEY_EVIDENCE_NUMBER | ENCODING_KEY_EXAMINER_NAME | ENCODING_KEY_NOTES => {
+					return Err(ZffError::new(ZffErrorKind::InvalidOption, format!("{ERROR_DESCRIPTION_FIELD_RESERVED_KEY}{key}")));
+				},
+				_ => description_header.custom_identifier_value(key, value),
+			}
+		}
+		Ok(description_header)
+	}
+
+	/// Returns all key-value pairs of this header as a flat map, using the (human readable) field names
+	/// for the predefined fields (see [DescriptionHeader::from_fields]).
+	pub fn to_fields(&self) -> HashMap<String, String> {
+		let mut fields = HashMap::new();
+		for (key, value) in &self.identifier_map {
+			let key = match key.as_str() {
+				ENCODING_KEY_CASE_NUMBER => DESCRIPTION_FIELD_CASE_NUMBER,
+				ENCODING_KEY_EVIDENCE_NUMBER => DESCRIPTION_FIELD_EVIDENCE_NUMBER,
+				ENCODING_KEY_EXAMINER_NAME => DESCRIPTION_FIELD_EXAMINER_NAME,
+				ENCODING_KEY_NOTES => DESCRIPTION_FIELD_NOTES,
+				other => other,
+			};
+			fields.insert(key.to_string(), value.clone());
+		}
+		fields
+	}
 }
 
 impl HeaderCoding for DescriptionHeader {
@@ -21,6 +21,7 @@ use crate::{
 	HEADER_IDENTIFIER_ENCRYPTION_HEADER,
 	ERROR_HEADER_DECODER_UNKNOWN_ENCRYPTION_ALGORITHM,
 	DEFAULT_HEADER_VERSION_ENCRYPTION_HEADER,
+	HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION,
 };
 
 // - external
@@ -40,14 +41,18 @@ pub struct EncryptionInformation {
 	pub encryption_key: Vec<u8>,
 	/// The used [crate::encryption::EncryptionAlgorithm].
 	pub algorithm: EncryptionAlgorithm,
+	/// The nonce extension of the appropriate [EncryptionHeader].
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_array"))]
+	pub nonce_extension: [u8; 16],
 }
 
 impl EncryptionInformation {
 	/// Creates a new [EncryptionInformation] by the given values.
-	pub fn new(key: Vec<u8>, algorithm: EncryptionAlgorithm) -> Self {
+	pub fn new(key: Vec<u8>, algorithm: EncryptionAlgorithm, nonce_extension: [u8; 16]) -> Self {
 		Self {
 			encryption_key: key,
-			algorithm
+			algorithm,
+			nonce_extension,
 		}
 	}
 }
@@ -62,7 +67,8 @@ impl TryFrom<ObjectHeader> for EncryptionInformation {
 					None => Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, "")),
 					Some(key) => Ok(EncryptionInformation {
 						encryption_key: key,
-						algorithm: enc_header.algorithm
+						algorithm: enc_header.algorithm,
+						nonce_extension: enc_header.nonce_extension,
 					}),
 				}
 			}
@@ -80,7 +86,8 @@ impl TryFrom<&ObjectHeader> for EncryptionInformation {
 					None => Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, "")),
 					Some(key) => Ok(EncryptionInformation {
 						encryption_key: key,
-						algorithm: enc_header.algorithm.clone()
+						algorithm: enc_header.algorithm.clone(),
+						nonce_extension: enc_header.nonce_extension,
 					}),
 				}
 			}
@@ -121,12 +128,17 @@ pub struct EncryptionHeader {
 	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_base64", deserialize_with = "crate::helper::base64_to_buffer"))]
 	pub encrypted_encryption_key: Vec<u8>,
 	/// The decrypted encryption key.
-	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::option_buffer_to_base64"))]
-	pub decrypted_encryption_key: Option<Vec<u8>>
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::option_buffer_to_base64", deserialize_with = "crate::helper::option_base64_to_buffer"))]
+	pub decrypted_encryption_key: Option<Vec<u8>>,
+	/// The random nonce extension of this object, which fills the remaining 128-bit of the 192-bit nonce
+	/// used by [XChacha20-Poly1305](crate::encryption::EncryptionAlgorithm::XCHACHA20POLY1305).
+	/// Encryption headers of older versions have no nonce extension (so a zeroed nonce extension is used).
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_array"))]
+	pub nonce_extension: [u8; 16],
 }
 
 impl EncryptionHeader {
-	/// creates a new encryption header by the given values.
+	/// creates a new encryption header by the given values (and a new random nonce extension).
 	pub fn new(
 		pbe_header: PBEHeader,
 		algorithm: EncryptionAlgorithm,
@@ -137,6 +149,7 @@ impl EncryptionHeader {
 			algorithm,
 			encrypted_encryption_key,
 			decrypted_encryption_key: None,
+			nonce_extension: gen_random_nonce_extension(),
 		}
 	}
 
@@ -188,8 +201,8 @@ impl EncryptionHeader {
 					match self.pbe_header.encryption_scheme {
 						PBEScheme::AES128CBC => decrypt_scrypt_aes128cbc(
 							logn,
-							p,
 							r,
+							p,
 							&salt,
 							&self.pbe_header.pbencryption_nonce,
 							&password,
@@ -197,8 +210,8 @@ impl EncryptionHeader {
 							),
 						PBEScheme::AES256CBC => decrypt_scrypt_aes256cbc(
 							logn,
-							p,
 							r,
+							p,
 							&salt,
 							&self.pbe_header.pbencryption_nonce,
 							&password,
@@ -255,27 +268,43 @@ impl HeaderCoding for EncryptionHeader {
 	}
 
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = vec![Self::version()];
+		let mut vec = vec![HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION];
 		vec.append(&mut self.pbe_header.encode_directly());
 		vec.push(self.algorithm.clone() as u8);
 		vec.append(&mut self.encrypted_encryption_key.encode_directly());
+		vec.append(&mut self.nonce_extension.encode_directly());
 		vec
 	}
 
 	fn decode_content(data: Vec<u8>) -> Result<EncryptionHeader> {
 		let mut cursor = Cursor::new(data);
-		Self::check_version(&mut cursor)?;
+		let version = u8::decode_directly(&mut cursor)?;
+		if version != Self::version() && version != HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION {
+			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, version.to_string()));
+		}
 		let pbe_header = PBEHeader::decode_directly(&mut cursor)?;
 		let encryption_algorithm = match u8::decode_directly(&mut cursor)? {
 			0 => EncryptionAlgorithm::AES128GCM,
 			1 => EncryptionAlgorithm::AES256GCM,
 			2 => EncryptionAlgorithm::CHACHA20POLY1305,
+			3 => EncryptionAlgorithm::XCHACHA20POLY1305,
 			_ => return Err(ZffError::new_header_decode_error(ERROR_HEADER_DECODER_UNKNOWN_ENCRYPTION_ALGORITHM)),
 		};
 		let key_length = u64::decode_directly(&mut cursor)? as usize;
 		let mut encryption_key = vec![0u8; key_length];
 		cursor.read_exact(&mut encryption_key)?;
-		Ok(EncryptionHeader::new(pbe_header, encryption_algorithm, encryption_key))
+		// older encryption headers have no nonce extension, so the (zeroed) nonce of these versions is used.
+		let mut nonce_extension = [0u8; 16];
+		if version == HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION {
+			cursor.read_exact(&mut nonce_extension)?;
+		}
+		Ok(EncryptionHeader {
+			pbe_header,
+			algorithm: encryption_algorithm,
+			encrypted_encryption_key: encryption_key,
+			decrypted_encryption_key: None,
+			nonce_extension,
+		})
 	}
 
 	fn struct_name() -> &'static str {
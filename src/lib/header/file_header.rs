@@ -4,6 +4,9 @@ use std::io::{Cursor, Read};
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::any::Any;
+use std::path::{Path, PathBuf};
+#[cfg(target_family = "unix")]
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
 
 // - internal
 use crate::{
@@ -39,6 +42,8 @@ use crate::{
 	METADATA_EXT_TYPE_IDENTIFIER_F64,
 	METADATA_EXT_TYPE_IDENTIFIER_VEC,
 	METADATA_EXT_TYPE_IDENTIFIER_BOOL,
+	METADATA_EXT_KEY_ORIGINAL_PATH,
+	ERROR_METADATA_EXTENDED_VALUE_MISMATCH_TYPE,
 };
 
 use crate::header::EncryptionInformation;
@@ -49,7 +54,6 @@ use ordered_float::OrderedFloat;
 use serde::{
 	Deserialize,
 	Serialize,
-	ser::{Serializer, SerializeMap, SerializeSeq},
 };
 
 /// Defines all file types, which are implemented for zff files.
@@ -181,6 +185,22 @@ impl FileHeader {
 		}
 	}
 
+	/// Returns the original path of the file, if the path was stored in the metadata of this file
+	/// (see [METADATA_EXT_KEY_ORIGINAL_PATH](crate::constants::METADATA_EXT_KEY_ORIGINAL_PATH)).
+	pub fn original_path(&self) -> Option<PathBuf> {
+		match self.metadata_ext.get(METADATA_EXT_KEY_ORIGINAL_PATH) {
+			Some(MetadataExtendedValue::ByteArray(bytes)) => Some(path_from_bytes(bytes)),
+			_ => None,
+		}
+	}
+
+	/// Stores the given path as original path in the metadata of this file.
+	/// On unix systems, the raw bytes of the path will be stored (so non-UTF8 paths will be preserved), otherwise the path
+	/// will be stored as UTF-8 string.
+	pub fn set_original_path<P: AsRef<Path>>(&mut self, path: P) {
+		self.metadata_ext.insert(METADATA_EXT_KEY_ORIGINAL_PATH.to_string(), MetadataExtendedValue::ByteArray(path_to_bytes(path.as_ref())));
+	}
+
 	/// encodes the file header to a ```Vec<u8>```. The encryption flag of the appropriate object header has to be set to 2.
 	/// # Error
 	/// The method returns an error, if the encryption fails.
@@ -190,7 +210,7 @@ impl FileHeader {
 	{
 		let mut vec = Vec::new();
 		let encryption_information = encryption_information.borrow();
-		let mut encoded_header = self.encode_encrypted_header(&encryption_information.encryption_key, &encryption_information.algorithm)?;
+		let mut encoded_header = self.encode_encrypted_header(&encryption_information.encryption_key, encryption_information.nonce_extension, &encryption_information.algorithm)?;
 		let identifier = HEADER_IDENTIFIER_FILE_HEADER;
 		let encoded_header_length = 4 + 8 + (encoded_header.len() as u64); //4 bytes identifier + 8 bytes for length + length itself
 		vec.append(&mut identifier.to_be_bytes().to_vec());
@@ -200,7 +220,7 @@ impl FileHeader {
 		Ok(vec)
 	}
 
-	fn encode_encrypted_header<K, A>(&self, key: K, algorithm: A) -> Result<Vec<u8>>
+	fn encode_encrypted_header<K, A>(&self, key: K, nonce_extension: [u8; 16], algorithm: A) -> Result<Vec<u8>>
 	where
 		K: AsRef<[u8]>,
 		A: Borrow<EncryptionAlgorithm>,
@@ -215,6 +235,7 @@ impl FileHeader {
 		let encrypted_data = FileHeader::encrypt(
 			key, data_to_encrypt,
 			self.file_number,
+			nonce_extension,
 			algorithm
 			)?;
 		vec.append(&mut encrypted_data.encode_directly());
@@ -253,6 +274,7 @@ impl FileHeader {
 			&encryption_information.borrow().encryption_key, 
 			encrypted_data, 
 			file_number, 
+			encryption_information.borrow().nonce_extension,
 			algorithm)?;
 		let mut cursor = Cursor::new(decrypted_data);
 		let (file_type,
@@ -341,8 +363,13 @@ impl Encryption for FileHeader {
 }
 
 /// This is a wrapper enum for all possible values of the metadata extended values.
+///
+/// If the serde feature is enabled, each value will be serialized with its type,
+/// e.g. `{"type":"u64","value":123}` or `{"type":"bytearray","value":[1,2,3]}`, so the values can be deserialized losslessly.
 #[derive(Debug,Clone,Eq,PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value", rename_all = "lowercase"))]
 pub enum MetadataExtendedValue {
 	/// represents a uint8 value
 	U8(u8),
@@ -378,51 +405,6 @@ pub enum MetadataExtendedValue {
 	Bool(bool),
 }
 
-#[cfg(feature = "serde")]
-impl Serialize for MetadataExtendedValue {
-	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
-	where
-		S: Serializer,
-	{
-		match self {
-			MetadataExtendedValue::U8(value) => serializer.serialize_u8(*value),
-			MetadataExtendedValue::U16(value) => serializer.serialize_u16(*value),
-			MetadataExtendedValue::U32(value) => serializer.serialize_u32(*value),
-			MetadataExtendedValue::U64(value) => serializer.serialize_u64(*value),
-			MetadataExtendedValue::I8(value) => serializer.serialize_i8(*value),
-			MetadataExtendedValue::I16(value) => serializer.serialize_i16(*value),
-			MetadataExtendedValue::I32(value) => serializer.serialize_i32(*value),
-			MetadataExtendedValue::I64(value) => serializer.serialize_i64(*value),
-			MetadataExtendedValue::String(value) => serializer.serialize_str(value),
-			MetadataExtendedValue::Hashmap(value) => {
-				let mut state = serializer.serialize_map(Some(value.len()))?;
-				for (key, value) in value {
-					state.serialize_entry(key, value)?;
-				}
-				state.end()
-			},
-			MetadataExtendedValue::BTreeMap(value) => {
-				let mut state = serializer.serialize_map(Some(value.len()))?;
-				for (key, value) in value {
-					state.serialize_entry(key, value)?;
-				}
-				state.end()
-			},
-			MetadataExtendedValue::ByteArray(value) => serializer.serialize_bytes(value),
-			MetadataExtendedValue::F32(value) => serializer.serialize_f32(value.into_inner()),
-			MetadataExtendedValue::F64(value) => serializer.serialize_f64(value.into_inner()),
-			MetadataExtendedValue::Vector(value) => {
-				let mut state = serializer.serialize_seq(Some(value.len()))?;
-				for value in value {
-					state.serialize_element(value)?;
-				}
-				state.end()
-			},
-			MetadataExtendedValue::Bool(value) => serializer.serialize_bool(*value),
-		}
-	}
-}
-
 impl MetadataExtendedValue {
 	/// returns the inner value.
 	pub fn into_any(self) -> Box<dyn Any> {
@@ -467,6 +449,117 @@ impl MetadataExtendedValue {
 			MetadataExtendedValue::Bool(value) => Box::new(value),
 		}
 	}
+
+	/// returns the inner value as u64, if the value is an integer, which fits into an u64.
+	pub fn as_u64(&self) -> Option<u64> {
+		match self {
+			MetadataExtendedValue::U8(value) => Some(*value as u64),
+			MetadataExtendedValue::U16(value) => Some(*value as u64),
+			MetadataExtendedValue::U32(value) => Some(*value as u64),
+			MetadataExtendedValue::U64(value) => Some(*value),
+			MetadataExtendedValue::I8(value) => u64::try_from(*value).ok(),
+			MetadataExtendedValue::I16(value) => u64::try_from(*value).ok(),
+			MetadataExtendedValue::I32(value) => u64::try_from(*value).ok(),
+			MetadataExtendedValue::I64(value) => u64::try_from(*value).ok(),
+			_ => None,
+		}
+	}
+
+	/// returns the inner value as i64, if the value is an integer, which fits into an i64.
+	pub fn as_i64(&self) -> Option<i64> {
+		match self {
+			MetadataExtendedValue::U8(value) => Some(*value as i64),
+			MetadataExtendedValue::U16(value) => Some(*value as i64),
+			MetadataExtendedValue::U32(value) => Some(*value as i64),
+			MetadataExtendedValue::U64(value) => i64::try_from(*value).ok(),
+			MetadataExtendedValue::I8(value) => Some(*value as i64),
+			MetadataExtendedValue::I16(value) => Some(*value as i64),
+			MetadataExtendedValue::I32(value) => Some(*value as i64),
+			MetadataExtendedValue::I64(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// returns the inner value as string slice, if the value is a string.
+	pub fn as_string(&self) -> Option<&str> {
+		match self {
+			MetadataExtendedValue::String(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// returns the inner value as bool, if the value is a bool.
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			MetadataExtendedValue::Bool(value) => Some(*value),
+			_ => None,
+		}
+	}
+}
+
+impl TryFrom<MetadataExtendedValue> for u64 {
+	type Error = ZffError;
+
+	/// converts each integer value, which fits into an u64 (see [MetadataExtendedValue::as_u64]).
+	fn try_from(value: MetadataExtendedValue) -> Result<Self> {
+		value.as_u64().ok_or_else(|| mismatch_type_error(&value))
+	}
+}
+
+impl TryFrom<MetadataExtendedValue> for i64 {
+	type Error = ZffError;
+
+	/// converts each integer value, which fits into an i64 (see [MetadataExtendedValue::as_i64]).
+	fn try_from(value: MetadataExtendedValue) -> Result<Self> {
+		value.as_i64().ok_or_else(|| mismatch_type_error(&value))
+	}
+}
+
+impl TryFrom<MetadataExtendedValue> for String {
+	type Error = ZffError;
+
+	fn try_from(value: MetadataExtendedValue) -> Result<Self> {
+		match value {
+			MetadataExtendedValue::String(value) => Ok(value),
+			value => Err(mismatch_type_error(&value)),
+		}
+	}
+}
+
+impl TryFrom<MetadataExtendedValue> for Vec<u8> {
+	type Error = ZffError;
+
+	fn try_from(value: MetadataExtendedValue) -> Result<Self> {
+		match value {
+			MetadataExtendedValue::ByteArray(value) => Ok(value),
+			value => Err(mismatch_type_error(&value)),
+		}
+	}
+}
+
+#[cfg(target_family = "unix")]
+fn path_to_bytes(path: &Path) -> Vec<u8> {
+	path.as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(not(target_family = "unix"))]
+fn path_to_bytes(path: &Path) -> Vec<u8> {
+	path.to_string_lossy().as_bytes().to_vec()
+}
+
+#[cfg(target_family = "unix")]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+	PathBuf::from(std::ffi::OsString::from_vec(bytes.to_vec()))
+}
+
+#[cfg(not(target_family = "unix"))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+	PathBuf::from(String::from_utf8_lossy(bytes).to_string())
+}
+
+// returns the error for a failed conversion of the given value.
+fn mismatch_type_error(value: &MetadataExtendedValue) -> ZffError {
+	ZffError::new(ZffErrorKind::MissmatchIdentifier, format!("{ERROR_METADATA_EXTENDED_VALUE_MISMATCH_TYPE}{value:?}"))
 }
 
 impl ValueEncoder for Vec<MetadataExtendedValue> {
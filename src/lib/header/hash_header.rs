@@ -24,7 +24,9 @@ use ed25519_dalek::SIGNATURE_LENGTH;
 #[cfg(feature = "serde")]
 use serde::{
 	Serialize,
+	Deserialize,
 	ser::{Serializer, SerializeStruct},
+	de::{Deserializer, Error as DeserializeError},
 };
 #[cfg(feature = "serde")]
 use base64::{Engine, engine::general_purpose::STANDARD as base64engine};
@@ -56,6 +58,25 @@ impl Serialize for HashHeader {
     }
 }
 
+/// The hash values will be ordered by their [HashType], as the serialized hash values are keyed by the hash type.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for HashHeader {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct SerializedHashHeader {
+        	hash: HashMap<String, HashValue>,
+        }
+
+        let header = SerializedHashHeader::deserialize(deserializer)?;
+        let mut hashes = header.hash.into_values().collect::<Vec<_>>();
+        hashes.sort_by_key(|hashvalue| hashvalue.hash_type.clone() as u8);
+        Ok(HashHeader::new(hashes))
+    }
+}
+
 impl HashHeader {
 	/// creates a new HashHeader by given values/hashes.
 	pub fn new(hashes: Vec<HashValue>) -> HashHeader {
@@ -233,4 +254,32 @@ impl Serialize for HashValue {
         }
         state.end()
     }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for HashValue {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct SerializedHashValue {
+        	hash_type: String,
+        	hash: String,
+        	#[serde(default)]
+        	ed25519_signature: Option<String>,
+        }
+
+        let value = SerializedHashValue::deserialize(deserializer)?;
+        let hash_type = value.hash_type.parse::<HashType>().map_err(|e| DeserializeError::custom(e.to_string()))?;
+        let hash = hex::decode(value.hash).map_err(|e| DeserializeError::custom(e.to_string()))?;
+        let ed25519_signature = match value.ed25519_signature {
+        	None => None,
+        	Some(signature) => {
+        		let signature = base64engine.decode(signature).map_err(|e| DeserializeError::custom(e.to_string()))?;
+        		Some(signature.try_into().map_err(|signature: Vec<u8>| DeserializeError::invalid_length(signature.len(), &"a signature of 64 bytes"))?)
+        	},
+        };
+        Ok(HashValue::new(hash_type, hash, ed25519_signature))
+    }
 }
\ No newline at end of file
@@ -26,6 +26,14 @@ use crate::{
 	ERROR_INVALID_OBJECT_TYPE_FLAG_VALUE,
 	ENCRYPT_OBJECT_FLAG_VALUE,
 	SIGN_HASH_FLAG_VALUE,
+	PLAINTEXT_DESCRIPTION_FLAG_VALUE,
+	HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION,
+	DEFAULT_CHUNK_SIZE,
+	DEFAULT_COMPRESSION_LEVEL,
+	DEFAULT_COMPRESSION_RATIO_THRESHOLD,
+	ERROR_OBJECT_HEADER_BUILDER_MISSING_ENCRYPTION_KEY,
+	ERROR_OBJECT_HEADER_BUILDER_INVALID_CHUNK_SIZE,
+	CompressionAlgorithm,
 };
 
 use crate::header::{
@@ -45,6 +53,7 @@ use serde::{
 /// Holds the appropriate object flags:
 /// - the encryption flag, if the appropriate object is encrypted.
 /// - the sign hash flag, if the appropriate calculated hash value was signed.
+/// - the plaintext description flag, if the description header of an encrypted object is (additionally) stored unencrypted.
 /// - the passive object flag, if this object should not handled as an active object
 #[derive(Debug,Clone,Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize))]
@@ -54,6 +63,10 @@ pub struct ObjectFlags {
 	pub encryption: bool,
 	/// this flag is set, if signatures are available for this object.
 	pub sign_hash: bool,
+	/// this flag is set, if the [DescriptionHeader] of an encrypted object is also stored unencrypted in the object header,
+	/// so the description can be read without the decryption password (the chunk data and all other metadata stay encrypted).
+	/// This flag has no effect for unencrypted objects.
+	pub plaintext_description: bool,
 }
 
 impl From<u8> for ObjectFlags {
@@ -61,6 +74,7 @@ impl From<u8> for ObjectFlags {
 		Self {
 			encryption: flag_values & ENCRYPT_OBJECT_FLAG_VALUE != 0,
 			sign_hash: flag_values & SIGN_HASH_FLAG_VALUE != 0,
+			plaintext_description: flag_values & PLAINTEXT_DESCRIPTION_FLAG_VALUE != 0,
 		}
 	}
 }
@@ -114,6 +128,17 @@ impl ObjectHeader {
 		}
 	}
 
+	/// returns a new [ObjectHeaderBuilder] for an object with the given object number and [ObjectType].
+	pub fn builder(object_number: u64, object_type: ObjectType) -> ObjectHeaderBuilder {
+		ObjectHeaderBuilder::new(object_number, object_type)
+	}
+
+	/// replaces the embedded [crate::header::DescriptionHeader] by the given one.
+	pub fn set_description(&mut self, description_header: DescriptionHeader) -> &mut Self {
+		self.description_header = description_header;
+		self
+	}
+
 	/// checks if a signature method was used. Returns true if and false if not.
 	pub fn has_hash_signatures(&self) -> bool {
 		self.flags.sign_hash
@@ -150,15 +175,26 @@ impl ObjectHeader {
 		};
 
 		let mut vec = Vec::new();
-		vec.append(&mut Self::version().encode_directly());
+		if self.flags.plaintext_description {
+			vec.append(&mut HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION.encode_directly());
+		} else {
+			vec.append(&mut Self::version().encode_directly());
+		}
 		vec.append(&mut self.object_number.encode_directly());
 		let mut flags: u8 = 0;
 		flags += ENCRYPT_OBJECT_FLAG_VALUE;
 		if self.flags.sign_hash {
 			flags += SIGN_HASH_FLAG_VALUE;
 		};
+		if self.flags.plaintext_description {
+			flags += PLAINTEXT_DESCRIPTION_FLAG_VALUE;
+		};
 		vec.append(&mut flags.encode_directly());
 		vec.append(&mut encryption_header.encode_directly());
+		// the encrypted content contains the description header in any case.
+		if self.flags.plaintext_description {
+			vec.append(&mut self.description_header.encode_directly());
+		}
 
 		let mut data_to_encrypt = Vec::new();
 		data_to_encrypt.append(&mut self.encode_content());
@@ -166,6 +202,7 @@ impl ObjectHeader {
 		let encrypted_data = ObjectHeader::encrypt(
 			key, data_to_encrypt,
 			self.object_number,
+			encryption_header.nonce_extension,
 			&encryption_header.algorithm)?;
 		vec.append(&mut encrypted_data.encode_directly());
 		Ok(vec)
@@ -194,17 +231,22 @@ impl ObjectHeader {
 		let mut header_content = vec![0u8; header_length-DEFAULT_LENGTH_HEADER_IDENTIFIER-DEFAULT_LENGTH_VALUE_HEADER_LENGTH];
 		data.read_exact(&mut header_content)?;
 		let mut cursor = Cursor::new(header_content);
-		Self::check_version(&mut cursor)?;
+		let header_version = u8::decode_directly(&mut cursor)?;
 		let object_number = u64::decode_directly(&mut cursor)?;
 		let flags = ObjectFlags::from(u8::decode_directly(&mut cursor)?);
 		if !flags.encryption {
 			return Err(ZffError::new(ZffErrorKind::NoEncryptionDetected, ""));
 		}
+		check_encrypted_header_version(header_version, &flags)?;
 		let mut encryption_header = EncryptionHeader::decode_directly(&mut cursor)?;
+		if flags.plaintext_description {
+			// the description header is also part of the encrypted content.
+			DescriptionHeader::decode_directly(&mut cursor)?;
+		}
 		let encrypted_data = Vec::<u8>::decode_directly(&mut cursor)?;
 		let encryption_key = encryption_header.decrypt_encryption_key(password)?;
 		let algorithm = &encryption_header.algorithm;
-		let decrypted_data = Self::decrypt(encryption_key, encrypted_data, object_number, algorithm)?;
+		let decrypted_data = Self::decrypt(encryption_key, encrypted_data, object_number, encryption_header.nonce_extension, algorithm)?;
 		let mut cursor = Cursor::new(decrypted_data);
 		let (chunk_size,
 			compression_header,
@@ -233,6 +275,7 @@ impl ObjectHeader {
 		let object_type = match u8::decode_directly(inner_content)? {
 			0 => ObjectType::Physical,
 			1 => ObjectType::Logical,
+			2 => ObjectType::Virtual,
 			value => return Err(ZffError::new(ZffErrorKind::InvalidFlagValue, format!("{ERROR_INVALID_OBJECT_TYPE_FLAG_VALUE}{value}"))),
 		};
 		let inner_content = (
@@ -244,6 +287,123 @@ impl ObjectHeader {
 	}
 }
 
+/// A builder for an [ObjectHeader].
+/// The [ObjectFlags] will be set automatically by the given values; the following defaults will be used, if not set:
+/// - chunk size: [DEFAULT_CHUNK_SIZE](crate::constants::DEFAULT_CHUNK_SIZE)
+/// - compression: zstd with [DEFAULT_COMPRESSION_LEVEL](crate::constants::DEFAULT_COMPRESSION_LEVEL) and
+///   [DEFAULT_COMPRESSION_RATIO_THRESHOLD](crate::constants::DEFAULT_COMPRESSION_RATIO_THRESHOLD)
+/// - an empty [DescriptionHeader], no encryption and no hash signatures.
+/// # Example
+/// ```
+/// use zff::header::{ObjectHeader, ObjectType, CompressionHeader};
+/// use zff::CompressionAlgorithm;
+///
+/// let object_header = ObjectHeader::builder(1, ObjectType::Physical)
+///     .chunk_size(65536)
+///     .compression(CompressionHeader::new(CompressionAlgorithm::Lz4, 1, 1.05))
+///     .build()
+///     .unwrap();
+/// assert_eq!(object_header.chunk_size, 65536);
+/// assert!(!object_header.flags.encryption);
+/// ```
+#[derive(Debug,Clone)]
+pub struct ObjectHeaderBuilder {
+	object_number: u64,
+	object_type: ObjectType,
+	encryption_header: Option<EncryptionHeader>,
+	chunk_size: u64,
+	compression_header: Option<CompressionHeader>,
+	description_header: Option<DescriptionHeader>,
+	sign_hash: bool,
+	plaintext_description: bool,
+}
+
+impl ObjectHeaderBuilder {
+	/// returns a new [ObjectHeaderBuilder] for an object with the given object number and [ObjectType].
+	pub fn new(object_number: u64, object_type: ObjectType) -> Self {
+		Self {
+			object_number,
+			object_type,
+			encryption_header: None,
+			chunk_size: DEFAULT_CHUNK_SIZE,
+			compression_header: None,
+			description_header: None,
+			sign_hash: false,
+			plaintext_description: false,
+		}
+	}
+
+	/// sets the target chunk size.
+	pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+		self.chunk_size = chunk_size;
+		self
+	}
+
+	/// sets the [CompressionHeader].
+	pub fn compression(mut self, compression_header: CompressionHeader) -> Self {
+		self.compression_header = Some(compression_header);
+		self
+	}
+
+	/// sets the [EncryptionHeader] (the encryption flag will be set automatically).
+	/// The [EncryptionHeader] has to contain the decrypted encryption key.
+	pub fn encryption(mut self, encryption_header: EncryptionHeader) -> Self {
+		self.encryption_header = Some(encryption_header);
+		self
+	}
+
+	/// sets the [DescriptionHeader].
+	pub fn description(mut self, description_header: DescriptionHeader) -> Self {
+		self.description_header = Some(description_header);
+		self
+	}
+
+	/// sets the sign hash flag.
+	pub fn sign_hash(mut self, sign_hash: bool) -> Self {
+		self.sign_hash = sign_hash;
+		self
+	}
+
+	/// sets the plaintext description flag: the [DescriptionHeader] of an encrypted object will also be stored unencrypted
+	/// (see [ObjectFlags::plaintext_description]). The flag will only be set, if an [EncryptionHeader] is given.
+	pub fn plaintext_description(mut self, plaintext_description: bool) -> Self {
+		self.plaintext_description = plaintext_description;
+		self
+	}
+
+	/// builds the [ObjectHeader].
+	/// # Error
+	/// Fails if the chunk size is zero or if the given [EncryptionHeader] contains no decrypted encryption key.
+	pub fn build(self) -> Result<ObjectHeader> {
+		if self.chunk_size == 0 {
+			return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_OBJECT_HEADER_BUILDER_INVALID_CHUNK_SIZE));
+		}
+		if let Some(encryption_header) = &self.encryption_header {
+			if encryption_header.get_encryption_key_ref().is_none() {
+				return Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, ERROR_OBJECT_HEADER_BUILDER_MISSING_ENCRYPTION_KEY));
+			}
+		}
+		let compression_header = self.compression_header.unwrap_or_else(|| CompressionHeader::new(
+			CompressionAlgorithm::Zstd,
+			DEFAULT_COMPRESSION_LEVEL,
+			// the default value is a valid float, so this should never fail.
+			DEFAULT_COMPRESSION_RATIO_THRESHOLD.parse().unwrap_or(1.05)));
+		let flags = ObjectFlags {
+			encryption: self.encryption_header.is_some(),
+			sign_hash: self.sign_hash,
+			plaintext_description: self.plaintext_description && self.encryption_header.is_some(),
+		};
+		Ok(ObjectHeader::new(
+			self.object_number,
+			self.encryption_header,
+			self.chunk_size,
+			compression_header,
+			self.description_header.unwrap_or_else(DescriptionHeader::new_empty),
+			self.object_type,
+			flags))
+	}
+}
+
 impl Encryption for ObjectHeader {
 	fn crypto_nonce_padding() -> u8 {
 		0b00010000
@@ -291,6 +451,9 @@ impl HeaderCoding for ObjectHeader {
 		if self.flags.sign_hash {
 			flags += SIGN_HASH_FLAG_VALUE;
 		};
+		if self.flags.plaintext_description {
+			flags += PLAINTEXT_DESCRIPTION_FLAG_VALUE;
+		};
 		vec.append(&mut flags.encode_directly());
 		if let Some(encryption_header) = &self.encryption_header {
 			vec.append(&mut encryption_header.encode_directly())
@@ -353,6 +516,8 @@ pub enum ObjectType {
 	Physical = 0,
 	/// An object, containing logical files.
 	Logical = 1,
+	/// An object, which links ranges of other objects.
+	Virtual = 2,
 }
 
 impl fmt::Display for ObjectType {
@@ -360,6 +525,7 @@ impl fmt::Display for ObjectType {
 		let msg = match self {
 			ObjectType::Physical => "Physical",
 			ObjectType::Logical => "Logical",
+			ObjectType::Virtual => "Virtual",
 		};
 		write!(f, "{}", msg)
 	}
@@ -380,6 +546,8 @@ pub struct EncryptedObjectHeader {
 	pub flags: ObjectFlags,
 	/// the [crate::header::EncryptionHeader].
 	pub encryption_header: EncryptionHeader,
+	/// the unencrypted [DescriptionHeader], if the plaintext description flag is set (see [ObjectFlags::plaintext_description]).
+	pub description_header: Option<DescriptionHeader>,
 	/// the encrypted blob with the other header values.
 	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_buffer"))]
 	pub encrypted_content: Vec<u8>
@@ -397,10 +565,16 @@ impl EncryptedObjectHeader {
 			object_number,
 			flags,
 			encryption_header,
+			description_header: None,
 			encrypted_content,
 		}
 	}
 
+	/// returns the unencrypted [DescriptionHeader], if the plaintext description flag is set (see [ObjectFlags::plaintext_description]).
+	pub fn description_header(&self) -> Option<&DescriptionHeader> {
+		self.description_header.as_ref()
+	}
+
 	/// Decodes the length of the header.
 	pub fn decode_header_length<R: Read>(data: &mut R) -> Result<u64> {
 		match data.read_u64::<LittleEndian>() {
@@ -416,7 +590,7 @@ impl EncryptedObjectHeader {
 	{
 		let encryption_key = self.encryption_header.decrypt_encryption_key(password)?;
 		let algorithm = &self.encryption_header.algorithm;
-		let decrypted_data = Self::decrypt(encryption_key, &self.encrypted_content, self.object_number, algorithm)?;
+		let decrypted_data = Self::decrypt(encryption_key, &self.encrypted_content, self.object_number, self.encryption_header.nonce_extension, algorithm)?;
 		let mut cursor = Cursor::new(decrypted_data);
 		let (chunk_size,
 			compression_header,
@@ -454,15 +628,28 @@ impl HeaderCoding for EncryptedObjectHeader {
 	}
 
 	fn encode_header(&self) -> Vec<u8> {
-		let mut vec = vec![Self::version()];
+		let mut vec = if self.flags.plaintext_description {
+			vec![HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION]
+		} else {
+			vec![Self::version()]
+		};
 		vec.append(&mut self.object_number.encode_directly());
 		let mut flags: u8 = 0;
 		flags += ENCRYPT_OBJECT_FLAG_VALUE;
 		if self.flags.sign_hash {
 			flags += SIGN_HASH_FLAG_VALUE;
 		};
+		if self.flags.plaintext_description {
+			flags += PLAINTEXT_DESCRIPTION_FLAG_VALUE;
+		};
 		vec.append(&mut flags.encode_directly());
 		vec.append(&mut self.encryption_header.encode_directly());
+		if self.flags.plaintext_description {
+			match &self.description_header {
+				Some(description_header) => vec.append(&mut description_header.encode_directly()),
+				None => vec.append(&mut DescriptionHeader::new_empty().encode_directly()),
+			}
+		}
 		vec.append(&mut self.encrypted_content.encode_directly());
 		vec
 	}
@@ -470,21 +657,26 @@ impl HeaderCoding for EncryptedObjectHeader {
 	fn decode_content(data: Vec<u8>) -> Result<Self> {
 		let mut cursor = Cursor::new(data);
 		let header_version = u8::decode_directly(&mut cursor)?;
-		if header_version != DEFAULT_HEADER_VERSION_OBJECT_HEADER {
-			return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, header_version.to_string()))
-		};
 		let object_number = u64::decode_directly(&mut cursor)?;
 		let flags = ObjectFlags::from(u8::decode_directly(&mut cursor)?);
 		if !flags.encryption {
 			return Err(ZffError::new(ZffErrorKind::HeaderDecodeError, ""));
 		}
+		check_encrypted_header_version(header_version, &flags)?;
 		let encryption_header = EncryptionHeader::decode_directly(&mut cursor)?;
+		let description_header = if flags.plaintext_description {
+			Some(DescriptionHeader::decode_directly(&mut cursor)?)
+		} else {
+			None
+		};
 		let encrypted_data = Vec::<u8>::decode_directly(&mut cursor)?;
-		Ok(Self::new(
+		let mut encrypted_object_header = Self::new(
 			object_number,
 			flags,
 			encryption_header,
-			encrypted_data))
+			encrypted_data);
+		encrypted_object_header.description_header = description_header;
+		Ok(encrypted_object_header)
 	}
 
 	fn struct_name() -> &'static str {
@@ -496,4 +688,18 @@ impl Encryption for EncryptedObjectHeader {
 	fn crypto_nonce_padding() -> u8 {
 		0b00010000
 	}
-}
\ No newline at end of file
+}
+
+// checks the version of an encrypted object header: headers with an unencrypted description header
+// use a separate version, so older readers will report these headers as unsupported.
+fn check_encrypted_header_version(header_version: u8, flags: &ObjectFlags) -> Result<()> {
+	let expected_version = if flags.plaintext_description {
+		HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION
+	} else {
+		DEFAULT_HEADER_VERSION_OBJECT_HEADER
+	};
+	if header_version != expected_version {
+		return Err(ZffError::new(ZffErrorKind::UnsupportedVersion, header_version.to_string()));
+	}
+	Ok(())
+}
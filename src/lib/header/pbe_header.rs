@@ -23,6 +23,7 @@ use crate::{
 	ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER_KDF,
 	ERROR_HEADER_DECODER_UNKNOWN_PBE_SCHEME,
 	ERROR_HEADER_DECODER_UNKNOWN_KDF_SCHEME,
+	ERROR_HEADER_DECODER_HEADER_LENGTH,
 	METADATA_EXT_TYPE_IDENTIFIER_UNKNOWN,
 };
 
@@ -51,7 +52,7 @@ pub struct PBEHeader {
 	/// The kdf parameters.
 	pub kdf_parameters: KDFParameters,
 	/// The nonce used for the encryption of the encryption key.
-	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex"))]
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_array"))]
 	pub pbencryption_nonce: [u8; 16],
 }
 
@@ -96,6 +97,7 @@ impl HeaderCoding for PBEHeader {
 		let kdf_scheme = match u8::decode_directly(&mut cursor)? {
 			0 => KDFScheme::PBKDF2SHA256,
 			1 => KDFScheme::Scrypt,
+			2 => KDFScheme::Argon2id,
 			_ => return Err(ZffError::new_header_decode_error(ERROR_HEADER_DECODER_UNKNOWN_KDF_SCHEME))
 		};
 		let encryption_scheme = match u8::decode_directly(&mut cursor)? {
@@ -156,7 +158,11 @@ impl ValueDecoder for KDFParameters {
 	fn decode_directly<R: Read>(data: &mut R) -> Result<KDFParameters> {
 		let identifier = data.read_u32::<BigEndian>()?;
 		let size = u64::decode_directly(data)?;
-		let mut params = vec![0u8; (size-12) as usize];
+		let params_size = match size.checked_sub(12) {
+			Some(params_size) => params_size,
+			None => return Err(ZffError::new_header_decode_error(ERROR_HEADER_DECODER_HEADER_LENGTH)),
+		};
+		let mut params = vec![0u8; params_size as usize];
 		data.read_exact(&mut params)?;
 		
 		let mut params_cursor = Cursor::new(params);
@@ -175,6 +181,14 @@ impl ValueDecoder for KDFParameters {
 			params_cursor.read_exact(&mut salt)?;
 			let parameters = ScryptParameters::new(logn, r, p, salt);
 			Ok(KDFParameters::ScryptParameters(parameters))
+		} else if identifier == Argon2idParameters::identifier() {
+			let mem_cost = u32::decode_directly(&mut params_cursor)?;
+			let lanes = u32::decode_directly(&mut params_cursor)?;
+			let iterations = u32::decode_directly(&mut params_cursor)?;
+			let mut salt = [0; 32];
+			params_cursor.read_exact(&mut salt)?;
+			let parameters = Argon2idParameters::new(mem_cost, lanes, iterations, salt);
+			Ok(KDFParameters::Argon2idParameters(parameters))
 		} else {
 			Err(ZffError::new(ZffErrorKind::HeaderDecodeMismatchIdentifier, ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER_KDF))
 		}
@@ -189,7 +203,7 @@ pub struct PBKDF2SHA256Parameters {
 	/// The iterations to use.
 	pub iterations: u32,
 	/// The salt value.
-	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex"))]
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_array"))]
 	pub salt: [u8; 32],
 }
 
@@ -249,7 +263,7 @@ pub struct ScryptParameters {
 	/// The p parameter for Scrypt.
 	pub p: u32,
 	/// The used salt.
-	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex"))]
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_array"))]
 	pub salt: [u8; 32],
 }
 
@@ -305,7 +319,7 @@ impl HeaderCoding for ScryptParameters {
 }
 
 
-/// struct to store the parameters for the KDF Scrypt.
+/// struct to store the parameters for the KDF Argon2id.
 #[derive(Debug,Clone,Eq,PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize))]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -317,11 +331,12 @@ pub struct Argon2idParameters {
 	/// The iterations value for Argon2id.
 	pub iterations: u32,
 	/// The used salt.
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::buffer_to_hex", deserialize_with = "crate::helper::hex_to_array"))]
 	pub salt: [u8; 32],
 }
 
 impl Argon2idParameters {
-	/// returns a new [ScryptParameters] with the given values.
+	/// returns a new [Argon2idParameters] with the given values.
 	pub fn new(mem_cost: u32, lanes: u32, iterations: u32, salt: [u8; 32]) -> Argon2idParameters {
 		Self {
 			mem_cost,
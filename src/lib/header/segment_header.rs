@@ -29,7 +29,7 @@ use serde::{
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SegmentHeader {
 	/// the unique identifier. Segments at the same group (=same zff container) should have the same identifier.
-	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::as_hex"))]
+	#[cfg_attr(feature = "serde", serde(serialize_with = "crate::helper::as_hex", deserialize_with = "crate::helper::hex_to_u64"))]
 	pub unique_identifier: u64,
 	/// the appropriate segment number.
 	pub segment_number: u64,
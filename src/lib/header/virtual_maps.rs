@@ -71,6 +71,7 @@ impl VirtualMappingInformation {
 			&encryption_information.borrow().encryption_key, 
 			self.encode_content(), 
 			offset, 
+			encryption_information.borrow().nonce_extension,
 			&encryption_information.borrow().algorithm)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = (
@@ -106,6 +107,7 @@ impl VirtualMappingInformation {
 			&encryption_information.borrow().encryption_key, 
 			encrypted_data, 
 			offset, 
+			encryption_information.borrow().nonce_extension,
 			algorithm)?;
 		let mut cursor = Cursor::new(decrypted_data);
 		let (object_number,
@@ -228,6 +230,7 @@ impl VirtualObjectMap {
 			&encryption_information.borrow().encryption_key, 
 			self.encode_content(), 
 			object_number, 
+			encryption_information.borrow().nonce_extension,
 			&encryption_information.borrow().algorithm)?;
 		let identifier = Self::identifier();
 		let encoded_header_length = (
@@ -266,6 +269,7 @@ impl VirtualObjectMap {
 			&encryption_information.borrow().encryption_key, 
 			encrypted_data,
 			object_number, 
+			encryption_information.borrow().nonce_extension,
 			algorithm)?;
 		let mut cursor = Cursor::new(decrypted_data);
 		let offsetmaps = Self::decode_inner_content(&mut cursor)?;
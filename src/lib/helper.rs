@@ -3,10 +3,9 @@ use std::collections::{BTreeMap, BTreeSet};
 
 // internal
 use crate::{Result, ZffError, ZffErrorKind};
+use crate::constants::ERROR_PARSE_HUMAN_READABLE_SIZE;
 
 // external
-#[cfg(feature = "log")]
-use log::debug;
 #[cfg(feature = "serde")]
 use hex::FromHex;
 #[cfg(feature = "serde")]
@@ -27,46 +26,13 @@ where
     s.serialize_str(&format!("0x{:X}", x))
 }
 
-pub(crate) fn find_vmi_offset(offset_maps: &BTreeSet<BTreeMap<u64, (u64, u64)>>, offset: u64) -> Option<(u64, u64)> {
-    let set_index = binary_search_for_map_in_set(offset_maps, offset).ok()?;
-    let map = offset_maps.iter().nth(set_index)?;
-    let (_, (segment_no, offset)) = map.range(..=offset).next_back()?;
-    Some((*segment_no, *offset))
+pub(crate) fn find_vmi_offset(offset_maps: &BTreeSet<BTreeMap<u64, (u64, u64)>>, offset: u64) -> Option<(u64, u64, u64)> {
+    // the maps contain disjoint offset ranges, so the set is ordered by the lowest offset of each map.
+    let map = offset_maps.iter().rev().find(|map| map.keys().next().map_or(false, |lowest_offset| *lowest_offset <= offset))?;
+    let (mapping_offset, (segment_no, vmi_offset)) = map.range(..=offset).next_back()?;
+    Some((*mapping_offset, *segment_no, *vmi_offset))
 }
 
-fn binary_search_for_map_in_set(set: &BTreeSet<BTreeMap<u64, (u64, u64)>>, offset: u64) -> Result<usize> {
-    // The zombie counter is used to prevent infinite loops (if the set is malformed, etc.)
-    let mut zombie_counter = 0;
-    let mut low = 0;
-    let mut high = set.len() - 1;
-    while low <= high {
-        if zombie_counter > 1000 {
-            #[cfg(feature = "log")]
-            debug!("Malformed VMI map. Exiting.");
-            return Err(ZffError::new(ZffErrorKind::BinarySearchError, "zombie counter exceeded 1000 iterations. Exiting."));
-        }
-        let mid = (low + high) / 2;
-        let lowest_offset = set.iter().nth(mid).unwrap().keys().next().unwrap();
-        let highest_offset = set.iter().nth(mid).unwrap().keys().next_back().unwrap();
-        if lowest_offset <= &offset && highest_offset >= &offset {
-            // returns the appropriate set index
-            return Ok(mid);
-        } else if lowest_offset > &offset {
-            // search left
-            high = mid - 1;
-            zombie_counter += 1;
-        } else {
-            // search right
-            low = mid + 1;
-            zombie_counter += 1;
-        }
-    }
-    #[cfg(feature = "log")]
-    debug!("Empty map");
-    Err(ZffError::new(ZffErrorKind::BinarySearchError, "Empty map"))
-}
-
-
 #[cfg(feature = "serde")]
 /// Serializes `buffer` to a lowercase hex string.
 pub fn buffer_to_hex<T, S>(buffer: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -86,6 +52,28 @@ pub fn hex_to_buffer<'de, D>(deserializer: D) -> std::result::Result<Vec<u8>, D:
     .and_then(|string| Vec::from_hex(string).map_err(|err| Error::custom(err.to_string())))
 }
 
+#[cfg(feature = "serde")]
+/// Deserializes a lowercase hex string to a fixed size byte array.
+pub fn hex_to_array<'de, D, const N: usize>(deserializer: D) -> std::result::Result<[u8; N], D::Error>
+  where D: serde::Deserializer<'de>
+{
+  use serde::de::Error;
+  hex_to_buffer(deserializer)?
+    .try_into()
+    .map_err(|buffer: Vec<u8>| Error::custom(format!("expected {N} bytes, found {} bytes", buffer.len())))
+}
+
+#[cfg(feature = "serde")]
+/// Deserializes a hex string with a "0x" prefix (as serialized by `as_hex`) to a u64.
+pub(crate) fn hex_to_u64<'de, D>(deserializer: D) -> std::result::Result<u64, D::Error>
+  where D: serde::Deserializer<'de>
+{
+  use serde::de::Error;
+  let string = String::deserialize(deserializer)?;
+  let hex_value = string.strip_prefix("0x").unwrap_or(&string);
+  u64::from_str_radix(hex_value, 16).map_err(|err| Error::custom(err.to_string()))
+}
+
 #[cfg(feature = "serde")]
 /// Serializes `buffer` to a lowercase base64 string.
 pub fn buffer_to_base64<T, S>(buffer: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -117,6 +105,18 @@ pub fn base64_to_buffer<'de, D>(deserializer: D) -> std::result::Result<Vec<u8>,
     String::deserialize(deserializer).and_then(|string| base64engine.decode(string).map_err(|err| Error::custom(err.to_string())))
 }
 
+#[cfg(feature = "serde")]
+/// Deserializes a lowercase base64 Option<String> to an `Option<Vec<u8>>`.
+pub fn option_base64_to_buffer<'de, D>(deserializer: D) -> std::result::Result<Option<Vec<u8>>, D::Error>
+  where D: serde::Deserializer<'de>
+{
+    use serde::de::Error;
+    match Option::<String>::deserialize(deserializer)? {
+        Some(string) => base64engine.decode(string).map(Some).map_err(|err| Error::custom(err.to_string())),
+        None => Ok(None),
+    }
+}
+
 
 /// Returns the segment number of a given chunk number.
 pub fn get_segment_of_chunk_no(chunk_no: u64, global_chunkmap: &BTreeMap<u64, u64>) -> Option<u64> {
@@ -141,3 +141,44 @@ pub fn get_segment_of_chunk_no(chunk_no: u64, global_chunkmap: &BTreeMap<u64, u6
     // so we should return None.
     None
 }
+
+/// Parses a human readable size value (e.g. "4G", "200M" or "64KiB") to the appropriate number of bytes.
+/// 
+/// The suffixes K, M, G and T (optionally followed by a B) use decimal semantics (1K = 1000 bytes),
+/// the suffixes KiB, MiB, GiB and TiB use binary semantics (1KiB = 1024 bytes). Suffixes are case-insensitive.
+/// A bare number without any suffix will be treated as raw bytes.
+/// # Example
+/// ```
+/// use zff::helper::parse_human_readable_size;
+/// 
+/// assert_eq!(parse_human_readable_size("512").unwrap(), 512);
+/// assert_eq!(parse_human_readable_size("64K").unwrap(), 64_000);
+/// assert_eq!(parse_human_readable_size("64KiB").unwrap(), 65_536);
+/// assert_eq!(parse_human_readable_size("200m").unwrap(), 200_000_000);
+/// assert_eq!(parse_human_readable_size("4G").unwrap(), 4_000_000_000);
+/// assert_eq!(parse_human_readable_size("1T").unwrap(), 1_000_000_000_000);
+/// assert!(parse_human_readable_size("4GB extra").is_err());
+/// assert!(parse_human_readable_size("G").is_err());
+/// ```
+pub fn parse_human_readable_size(value: &str) -> Result<u64> {
+    let parse_error = || ZffError::new(ZffErrorKind::ParseError, format!("{ERROR_PARSE_HUMAN_READABLE_SIZE}{value}"));
+
+    let value_trimmed = value.trim();
+    let suffix_start = value_trimmed.find(|c: char| !c.is_ascii_digit()).unwrap_or(value_trimmed.len());
+    let (number, suffix) = value_trimmed.split_at(suffix_start);
+    let number = number.parse::<u64>().map_err(|_| parse_error())?;
+
+    let multiplier: u64 = match suffix.trim_start().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1000,
+        "m" | "mb" => 1000_u64.pow(2),
+        "g" | "gb" => 1000_u64.pow(3),
+        "t" | "tb" => 1000_u64.pow(4),
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return Err(parse_error()),
+    };
+    number.checked_mul(multiplier).ok_or_else(parse_error)
+}
@@ -5,14 +5,17 @@
 pub mod zffreader;
 /// provides [ZffWriter] which implements the [Read](std::io::Read) trait to obtain a Read-Stream for a zff container.
 pub mod zffwriter;
+/// provides [restore_file_metadata](crate::io::restore::restore_file_metadata) to apply the stored metadata of a file to the extracted file.
+pub mod restore;
 
 // - STD
-use std::io::{Read, copy as io_copy};
+use std::io::{Read, Seek, SeekFrom, copy as io_copy};
+use std::fmt;
 use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::fs::{Metadata, read_link, File, read_dir};
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[cfg(target_family = "unix")]
 use std::os::unix::fs::MetadataExt;
@@ -31,15 +34,21 @@ use crate::{
     ZffErrorKind,
     ObjectEncoder,
     CompressionAlgorithm,
+    compression::{compress_buffer_brotli, compress_buffer_xz, compress_buffer_gzip},
     PhysicalObjectEncoder,
     LogicalObjectEncoder,
+    VirtualObjectEncoder,
+    VirtualObjectRange,
+    PassiveObjectInformation,
     PreparedData,
+    ChunkIntegrity,
     hashing::HashType,
     constants::*,
 };
 
 // - external
 use xxhash_rust::xxh3::xxh3_64;
+use crc::{Crc, CRC_64_XZ};
 use ed25519_dalek::SigningKey;
 #[cfg(target_family = "unix")]
 use time::OffsetDateTime;
@@ -95,10 +104,295 @@ pub struct ZffCreationParameters {
 	/// If set, the chunkmaps will not grow larger than the given size. Otherwise, the default size 32k will be used.
 	pub chunkmap_size: Option<u64>, //default is 32k
 	/// Optional [DeduplicationChunkMap] to ensure a chunk deduplication (and safe some disk space).
+	/// Use [DeduplicationChunkMap::new_in_memory_map] to keep the map in memory or [DeduplicationChunkMap::new_from_path]
+	/// to store the map in a Redb (at cost of I/O, but with bounded memory usage for very large acquisitions).
 	pub deduplication_chunkmap: Option<DeduplicationChunkMap>,
 	/// Will be used as a unique identifier, to assign each segment to the appropriate zff container.
 	/// If the [ZffWriter](zffwriter::ZffWriter) will be extend an existing Zff container, this value will be ignored.
-	pub unique_identifier: u64
+	pub unique_identifier: u64,
+	/// If set (and greater than 1), the chunks of physical objects will be compressed, hashed (xxhash) and encrypted
+	/// by the given number of worker threads in parallel.
+	/// Otherwise (and for logical objects), each chunk will be encoded separately.
+	pub encoding_threads: Option<usize>,
+	/// An optional zstd dictionary (e.g. created by [train_zstd_dictionary](crate::train_zstd_dictionary)), which will be used to compress
+	/// the chunks of all zstd compressed objects. The dictionary will be stored in the appropriate [CompressionHeader].
+	pub zstd_dictionary: Option<Vec<u8>>,
+	/// The algorithm, which will be used to calculate the integrity hash of each chunk (xxh3 by default).
+	/// The appropriate algorithm will be recorded in the [ChunkFlags](crate::header::ChunkFlags) of each chunk.
+	pub chunk_integrity: ChunkIntegrity,
+	/// If set, the [ZffWriter](zffwriter::ZffWriter) will limit the rate of the input data (e.g. of a live acquisition) to the given
+	/// number of bytes per second (measured against the wall-clock time since the first chunk was read),
+	/// by sleeping between the chunk reads. The output data will not be affected.
+	pub max_read_bytes_per_sec: Option<u64>,
+	/// If set, the [ZffWriter](zffwriter::ZffWriter) will retry failed read operations of the input data of physical objects
+	/// and will skip unreadable sectors (reported by a [BadBlockReader]) instead of aborting the acquisition.
+	/// Otherwise, only interrupted read operations will be retried.
+	pub bad_block_strategy: Option<BadBlockStrategy>,
+	/// If set, the [ZffWriter](zffwriter::ZffWriter) will calculate Reed-Solomon parity data for each group of the given number
+	/// of consecutive chunks of an object (number of data chunks, number of parity shards). The parity data will be stored in the
+	/// [ChunkParityMap](crate::header::ChunkParityMap)s and can be used to reconstruct as many corrupt chunks of a group
+	/// as parity shards were calculated (see [ZffReader::reconstruct_chunk](zffreader::ZffReader::reconstruct_chunk)).  
+	/// The number of data chunks and parity shards must be greater than zero and must not exceed 256 in total.
+	pub parity_shards: Option<(usize, usize)>,
+	/// The hash types, which will be used to hash each file of the logical objects.
+	/// The appropriate hash values will be stored in the [FileFooter](crate::footer::FileFooter) of each file
+	/// (see [ZffReader::current_file_hashes](zffreader::ZffReader::current_file_hashes)).
+	/// If empty, the files will be hashed by the hash types of the container.
+	pub per_file_hashes: Vec<HashType>,
+	/// The total size of the input data of all physical objects (e.g. the size of the acquired block device), if known.  
+	/// The input data is read by the [Read] trait, so the [ZffWriter](zffwriter::ZffWriter) cannot determine the size itself.
+	/// If set, the reported [ZffWriteProgress](zffwriter::ZffWriteProgress) contains the total size and is able to
+	/// calculate the percentage of the already read input data. Otherwise, only the number of the read bytes is available.
+	pub total_input_size: Option<u64>,
+	/// The number of retries of an interrupted read operation of the input data (see [std::io::ErrorKind::Interrupted]).
+	/// If the read operation is still interrupted after the last retry, the appropriate chunk will be zeroed and marked with the error flag.
+	/// If None, the default number of 11 retries will be used.
+	pub io_interrupt_retries: Option<u32>,
+	/// The time (in milliseconds) to wait before an interrupted read operation will be retried. If None, the default of 6000ms will be used.
+	pub io_interrupt_wait_ms: Option<u64>,
+	/// If set, the original path of each file of the logical objects will be stored in the metadata of the file
+	/// (see [METADATA_EXT_KEY_ORIGINAL_PATH] and [FileHeader::original_path]).
+	/// Otherwise, only the filename will be stored and the path can only be reconstructed by the parent file numbers.
+	pub original_paths: Option<OriginalPathFormat>,
+}
+
+/// Defines the format of the original paths, which will be stored for the files of logical objects
+/// (see [ZffCreationParameters::original_paths]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OriginalPathFormat {
+    /// The paths will be stored as they were traversed (i.e. relative paths remain relative to the working directory,
+    /// if the appropriate input path of the logical object was relative).
+    AsGiven,
+    /// Relative paths will be joined with the current working directory. Symlinks will not be resolved.
+    Absolute,
+}
+
+/// Defines how interrupted read operations of the input data (see [std::io::ErrorKind::Interrupted]) will be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoInterruptRetries {
+    /// The number of retries of an interrupted read operation.
+    pub retries: u32,
+    /// The time (in milliseconds) to wait before each retry.
+    pub wait_time_ms: u64,
+}
+
+impl IoInterruptRetries {
+    /// Returns the [IoInterruptRetries] of the given [ZffCreationParameters] (the unset values will be replaced by the defaults).
+    pub fn with_parameters(params: &ZffCreationParameters) -> Self {
+        let default = Self::default();
+        Self {
+            retries: params.io_interrupt_retries.unwrap_or(default.retries),
+            wait_time_ms: params.io_interrupt_wait_ms.unwrap_or(default.wait_time_ms),
+        }
+    }
+}
+
+impl Default for IoInterruptRetries {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_NUMBER_OF_RETRIES_IO_INTERRUPT,
+            wait_time_ms: DEFAULT_WAIT_TIME_IO_INTERRUPT_RETRY,
+        }
+    }
+}
+
+/// Defines how read errors of the input data of a physical object (e.g. bad sectors of a failing drive) will be handled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadBlockStrategy {
+    /// The number of retries of a failed read operation.
+    pub retries: u8,
+    /// The sector size of the input device. A [BadBlockReader] re-reads the data sector by sector after a failed
+    /// read operation, to preserve the readable sectors around a bad sector.
+    pub sector_size: u64,
+    /// If set, the numbers of the chunks which contain skipped sectors will be recorded in the
+    /// [ObjectFooterPhysical](crate::footer::ObjectFooterPhysical).
+    pub record_bad_chunks: bool,
+}
+
+impl Default for BadBlockStrategy {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_NUMBER_OF_RETRIES_BAD_BLOCK,
+            sector_size: DEFAULT_SECTOR_SIZE,
+            record_bad_chunks: true,
+        }
+    }
+}
+
+/// The error which will be returned by a [BadBlockReader], if a sector could not be read and was skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadSectorError {
+    /// The offset of the skipped bytes in the underlying data.
+    pub offset: u64,
+    /// The number of skipped bytes.
+    pub length: u64,
+}
+
+impl fmt::Display for BadSectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{ERROR_BAD_SECTOR}{} (length: {})", self.offset, self.length)
+    }
+}
+
+impl std::error::Error for BadSectorError {}
+
+/// A reader for (seekable) input data with bad sectors (e.g. a raw block device of a failing drive).  
+/// A failed read operation will be retried; if it still fails, the data will be re-read sector by sector.
+/// An unreadable sector will be skipped and reported by an [std::io::Error], which contains a [BadSectorError].
+/// The [ZffWriter](zffwriter::ZffWriter) fills the skipped bytes with zeros and sets the error flag of the appropriate chunk,
+/// if a [BadBlockStrategy] is set in the [ZffCreationParameters].
+pub struct BadBlockReader<R: Read + Seek> {
+    inner: R,
+    retries: u8,
+    sector_size: u64,
+    // the data will be read sector by sector up to this offset (after a failed read operation).
+    sectorwise_until: u64,
+}
+
+impl<R: Read + Seek> BadBlockReader<R> {
+    /// Returns a new [BadBlockReader] for the given input data, which uses the retries and the sector size of the given [BadBlockStrategy].
+    pub fn new(inner: R, bad_block_strategy: &BadBlockStrategy) -> Self {
+        Self {
+            inner,
+            retries: bad_block_strategy.retries,
+            sector_size: bad_block_strategy.sector_size.max(1),
+            sectorwise_until: 0,
+        }
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_with_retries(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut retries = 0;
+        loop {
+            match self.inner.read(buf) {
+                Ok(r) => return Ok(r),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => return Err(e),
+                Err(e) => if retries < self.retries {
+                    #[cfg(feature = "log")]
+                    debug!("Read operation failed: {e}. Retry reading ({} of {}).", retries + 1, self.retries);
+                    retries += 1;
+                } else {
+                    return Err(e);
+                },
+            }
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for BadBlockReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let position = self.inner.stream_position()?;
+        if position >= self.sectorwise_until {
+            match self.read_with_retries(buf) {
+                Ok(r) => return Ok(r),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => return Err(e),
+                Err(_) => self.sectorwise_until = position + buf.len() as u64,
+            }
+        }
+        // re-read the data up to the end of the current sector.
+        let sector_end = (position / self.sector_size + 1) * self.sector_size;
+        let length = buf.len().min((sector_end - position) as usize);
+        match self.read_with_retries(&mut buf[..length]) {
+            Ok(r) => Ok(r),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => Err(e),
+            Err(e) => {
+                #[cfg(feature = "log")]
+                warn!("Could not read the sector at offset {position}: {e}. The sector will be skipped.");
+                self.inner.seek(SeekFrom::Start(position + length as u64))?;
+                Err(std::io::Error::new(e.kind(), BadSectorError { offset: position, length: length as u64 }))
+            }
+        }
+    }
+}
+
+/// A reader which chains multiple input readers in the given order to a single, continuous input stream
+/// (e.g. the parts `disk.001`, `disk.002`, ... of a previously split image, which should be stored as one physical object).  
+/// The end of the stream is only reported after the last reader is exhausted, so the byte offsets (and the chunk numbers) stay continuous.
+pub struct ChainedReader<R: Read> {
+    readers: VecDeque<R>,
+}
+
+impl<R: Read> ChainedReader<R> {
+    /// Returns a new [ChainedReader], which reads the data of the given readers in the given order.
+    pub fn new(readers: Vec<R>) -> Self {
+        Self {
+            readers: readers.into(),
+        }
+    }
+
+    /// Returns the number of remaining (not completely read) readers.
+    pub fn remaining_readers(&self) -> usize {
+        self.readers.len()
+    }
+}
+
+impl ChainedReader<File> {
+    /// Opens the files of the given paths and returns a [ChainedReader], which reads the files in the given order.
+    /// # Error
+    /// Fails if one of the files could not be opened.
+    pub fn open<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            files.push(File::open(path)?);
+        }
+        Ok(Self::new(files))
+    }
+}
+
+impl<R: Read> Read for ChainedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while let Some(reader) = self.readers.front_mut() {
+            match reader.read(buf)? {
+                0 => { self.readers.pop_front(); },
+                r => return Ok(r),
+            }
+        }
+        Ok(0)
+    }
+}
+
+/// Limits the rate of the read input data by sleeping after each chunk, if the data was read faster than the target rate.
+/// The rate will be measured against the wall-clock time since the first chunk was read.
+#[derive(Debug)]
+pub(crate) struct ReadThrottle {
+    max_bytes_per_sec: u64,
+    start: Option<Instant>,
+    bytes_read: u64,
+}
+
+impl ReadThrottle {
+    /// Returns a new [ReadThrottle] with the given target rate (or None, if the rate is zero).
+    pub fn new(max_bytes_per_sec: u64) -> Option<Self> {
+        if max_bytes_per_sec == 0 {
+            return None;
+        }
+        Some(Self {
+            max_bytes_per_sec,
+            start: None,
+            bytes_read: 0,
+        })
+    }
+
+    /// Adds the given number of read bytes and sleeps until the read data does not exceed the target rate anymore.
+    pub fn consume(&mut self, bytes: u64) {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        self.bytes_read += bytes;
+        let target_duration = Duration::from_secs_f64(self.bytes_read as f64 / self.max_bytes_per_sec as f64);
+        let elapsed = start.elapsed();
+        if target_duration > elapsed {
+            sleep(target_duration - elapsed);
+        }
+    }
 }
 
 #[derive(Default, Debug)]
@@ -106,6 +400,7 @@ pub(crate) struct BufferedChunk {
     pub buffer: Vec<u8>,
     pub bytes_read: u64,
     pub error_flag: bool,
+    pub error_messages: Vec<String>, // the descriptions of the read errors (e.g. the skipped sectors)
 }
 
 impl BufferedChunk {
@@ -114,6 +409,7 @@ impl BufferedChunk {
             buffer: vec![0; chunk_size],
             bytes_read: 0,
             error_flag: false,
+            error_messages: Vec::new(),
         }
     } 
 }
@@ -125,9 +421,27 @@ pub(crate) fn buffer_chunk<R>(
 	) -> Result<BufferedChunk> 
 where
 	R: Read,
+{
+    buffer_chunk_with_strategy(input, chunk_size, None, &IoInterruptRetries::default())
+}
+
+// same as buffer_chunk, but handles the read errors by using the given bad block strategy (if set):
+// failed read operations will be retried and the bytes of skipped sectors will be filled with zeros.
+// Interrupted read operations will be retried like defined by the given io interrupt retries.
+pub(crate) fn buffer_chunk_with_strategy<R>(
+	input: &mut R,
+	chunk_size: usize,
+	bad_block_strategy: Option<&BadBlockStrategy>,
+	io_interrupt_retries: &IoInterruptRetries,
+	) -> Result<BufferedChunk>
+where
+	R: Read,
 {
     let mut buffered_chunk = BufferedChunk::with_chunksize(chunk_size);
     let mut interrupt_retries = 0;
+    let mut error_retries = 0;
+    let mut zero_chunk = false;
+    let mut skipped_sectors: Vec<BadSectorError> = Vec::new();
 
     while (buffered_chunk.bytes_read as usize) < chunk_size {
         let r = match input.read(&mut buffered_chunk.buffer[buffered_chunk.bytes_read as usize..]) {
@@ -136,14 +450,15 @@ where
                 //the Error::io::ErrorKind::Interrupted guarantees 
                 // that the read operation can be retried (see https://doc.rust-lang.org/std/io/trait.Read.html#tymethod.read)
         		std::io::ErrorKind::Interrupted => {
-                    if interrupt_retries < DEFAULT_NUMBER_OF_RETRIES_IO_INTERRUPT {
+                    if interrupt_retries < io_interrupt_retries.retries {
                         #[cfg(feature = "log")]
-                        warn!("Read operation was interrupted. Retry reading ({} of {}).", interrupt_retries, DEFAULT_NUMBER_OF_RETRIES_IO_INTERRUPT);
-                        sleep(Duration::from_millis(DEFAULT_WAIT_TIME_IO_INTERRUPT_RETRY));
+                        warn!("Read operation was interrupted. Retry reading ({} of {}).", interrupt_retries, io_interrupt_retries.retries);
+                        sleep(Duration::from_millis(io_interrupt_retries.wait_time_ms));
                         interrupt_retries += 1;
                         continue;
                     } else {
                         buffered_chunk.error_flag = true;
+                        zero_chunk = true;
                         #[cfg(feature = "log")] {
                             warn!("The read operation was interrupted {} times.", interrupt_retries);
                             warn!("The appropriate chunk will be marked with error flag and the content will be zeroed");
@@ -151,7 +466,32 @@ where
                         chunk_size
                     }
                 },
-        		_ => return Err(ZffError::from(e)),
+                _ => match bad_block_strategy {
+                    Some(bad_block_strategy) => {
+                        if let Some(bad_sector) = e.get_ref().and_then(|inner| inner.downcast_ref::<BadSectorError>()) {
+                            // the sector was skipped by the reader: the appropriate bytes will be filled with zeros.
+                            buffered_chunk.error_flag = true;
+                            let skipped = bad_sector.length.min(chunk_size as u64 - buffered_chunk.bytes_read) as usize;
+                            let start = buffered_chunk.bytes_read as usize;
+                            buffered_chunk.buffer[start..start + skipped].fill(0);
+                            // adjacent skipped sectors will be reported as one range.
+                            match skipped_sectors.last_mut() {
+                                Some(last) if last.offset + last.length == bad_sector.offset => last.length += skipped as u64,
+                                _ => skipped_sectors.push(BadSectorError { offset: bad_sector.offset, length: skipped as u64 }),
+                            }
+                            skipped
+                        } else if error_retries < bad_block_strategy.retries {
+                            #[cfg(feature = "log")]
+                            warn!("Read operation failed: {e}. Retry reading ({} of {}).", error_retries + 1, bad_block_strategy.retries);
+                            sleep(Duration::from_millis(DEFAULT_WAIT_TIME_IO_ERROR_RETRY));
+                            error_retries += 1;
+                            continue;
+                        } else {
+                            return Err(ZffError::from(e));
+                        }
+                    },
+                    None => return Err(ZffError::from(e)),
+                },
         	},
         };
         if r == 0 {
@@ -162,10 +502,12 @@ where
     if buffered_chunk.bytes_read as usize != chunk_size {
         buffered_chunk.buffer = buffered_chunk.buffer[..buffered_chunk.bytes_read as usize].to_vec();
     }
-    if buffered_chunk.error_flag {
+    if zero_chunk {
         buffered_chunk.buffer = vec![0; chunk_size];
         buffered_chunk.bytes_read = chunk_size as u64;
+        buffered_chunk.error_messages.push(format!("{ERROR_INTERRUPTED_READ}{interrupt_retries}"));
     }
+    buffered_chunk.error_messages.extend(skipped_sectors.iter().map(|sector| format!("{sector}{ERROR_BAD_SECTOR_ZERO_FILLED}")));
 
     /*let buffered_chunk.buffer = if bytes_read == chunk_size {
         buffered_chunk.buffer
@@ -181,20 +523,46 @@ pub fn calculate_xxhash(buffer: &[u8]) -> u64 {
     xxh3_64(buffer)
 }
 
+/// calculates a CRC-64 (CRC-64/XZ) checksum for the given bytes.
+pub fn calculate_crc64(buffer: &[u8]) -> u64 {
+    const CRC64: Crc<u64> = Crc::<u64>::new(&CRC_64_XZ);
+    CRC64.checksum(buffer)
+}
+
 /// This function takes the buffered bytes and tries to compress them. 
 /// 
 /// If the compression rate is greater than the threshold value of the given
 /// [CompressionHeader], the function returns a tuple of compressed bytes and the flag, if the bytes was compressed or not.
-pub fn compress_buffer(buf: Vec<u8>, chunk_size: usize, compression_header: &CompressionHeader) -> Result<(Vec<u8>, bool)> {
+/// The bytes will not be compressed at all, if the threshold is set to
+/// [COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS](crate::constants::COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS).
+#[deprecated(note = "the chunk size is not used anymore, use compress_chunk() instead")]
+pub fn compress_buffer(buf: Vec<u8>, _chunk_size: usize, compression_header: &CompressionHeader) -> Result<(Vec<u8>, bool)> {
+    compress_chunk(buf, compression_header)
+}
+
+/// This function takes the buffered bytes and tries to compress them. 
+/// 
+/// If the compression rate is greater than the threshold value of the given
+/// [CompressionHeader], the function returns a tuple of compressed bytes and the flag, if the bytes was compressed or not.
+/// The bytes will not be compressed at all, if the threshold is set to
+/// [COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS](crate::constants::COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS).  
+/// The size of the zstd compression buffer is bounded by the zstd compress bound of the input data, so (in contrast to
+/// [compress_buffer]) no chunk size is needed.
+pub fn compress_chunk(buf: Vec<u8>, compression_header: &CompressionHeader) -> Result<(Vec<u8>, bool)> {
     let mut compression_flag = false;
     let compression_threshold = compression_header.threshold;
+    if compression_header.never_compress() {
+        return Ok((buf, compression_flag));
+    }
 
     match compression_header.algorithm {
         CompressionAlgorithm::None => Ok((buf, compression_flag)),
         CompressionAlgorithm::Zstd => {
             let compression_level = compression_header.level as i32;
-            let mut stream = zstd::stream::read::Encoder::new(buf.as_slice(), compression_level)?;
-            let buffered_chunk = buffer_chunk(&mut stream, chunk_size * compression_header.level as usize)?;
+            let dictionary = compression_header.dictionary.as_deref().unwrap_or_default();
+            let mut stream = zstd::stream::read::Encoder::with_dictionary(buf.as_slice(), compression_level, dictionary)?;
+            // the compressed data will never be larger than the zstd compress bound of the input data.
+            let buffered_chunk = buffer_chunk(&mut stream, zstd::zstd_safe::compress_bound(buf.len()))?;
             if (buf.len() as f32 / buffered_chunk.buffer.len() as f32) < compression_threshold {
                 Ok((buf, compression_flag))
             } else {
@@ -213,7 +581,34 @@ pub fn compress_buffer(buf: Vec<u8>, chunk_size: usize, compression_header: &Com
                 compression_flag = true;
                 Ok((compressed_data, compression_flag))
             }
-        }
+        },
+        CompressionAlgorithm::Brotli => {
+            let compressed_data = compress_buffer_brotli(&buf, compression_header.level)?;
+            if (buf.len() as f32 / compressed_data.len() as f32) < compression_threshold {
+                Ok((buf, compression_flag))
+            } else {
+                compression_flag = true;
+                Ok((compressed_data, compression_flag))
+            }
+        },
+        CompressionAlgorithm::Xz => {
+            let compressed_data = compress_buffer_xz(&buf, compression_header.level)?;
+            if (buf.len() as f32 / compressed_data.len() as f32) < compression_threshold {
+                Ok((buf, compression_flag))
+            } else {
+                compression_flag = true;
+                Ok((compressed_data, compression_flag))
+            }
+        },
+        CompressionAlgorithm::Gzip => {
+            let compressed_data = compress_buffer_gzip(&buf, compression_header.level)?;
+            if (buf.len() as f32 / compressed_data.len() as f32) < compression_threshold {
+                Ok((buf, compression_flag))
+            } else {
+                compression_flag = true;
+                Ok((compressed_data, compression_flag))
+            }
+        },
     }
 }
 
@@ -360,7 +755,7 @@ pub(crate) fn get_posix_acls(acl: &PosixACL, default_acls: Option<&PosixACL>) ->
     };
     if let Some(default_acls) = default_acls {
         for entry in default_acls.entries() {
-            if let Some((key, value)) = gen_acl_key_value(false, &entry) {
+            if let Some((key, value)) = gen_acl_key_value(true, &entry) {
                 metadata_ext_map.insert(key, value.into());
             }
         };
@@ -444,9 +839,9 @@ fn gen_acl_key_value(default: bool, entry: &ACLEntry) -> Option<(String, String)
 #[cfg(target_family = "unix")]
 fn gen_acl_key_uid(default: bool, uid: u32) -> String {
     let start = if default {
-        ACL_PREFIX
-    } else {
         ACL_DEFAULT_PREFIX
+    } else {
+        ACL_PREFIX
     };
     format!("{start}:user:{uid}")
 }
@@ -454,9 +849,9 @@ fn gen_acl_key_uid(default: bool, uid: u32) -> String {
 #[cfg(target_family = "unix")]
 fn gen_acl_key_gid(default: bool, gid: u32) -> String {
     let start = if default {
-        ACL_PREFIX
-    } else {
         ACL_DEFAULT_PREFIX
+    } else {
+        ACL_PREFIX
     };
     format!("{start}:group:{gid}")
 }
@@ -464,19 +859,24 @@ fn gen_acl_key_gid(default: bool, gid: u32) -> String {
 #[cfg(target_family = "unix")]
 fn gen_acl_mask(default: bool) -> String {
     let start = if default {
-        ACL_PREFIX
-    } else {
         ACL_DEFAULT_PREFIX
+    } else {
+        ACL_PREFIX
     };
     format!("{start}:mask")
 }
 
 /// This function sets up the [ObjectEncoder] for the physical objects.
+#[allow(clippy::too_many_arguments)]
 fn setup_physical_object_encoder<R: Read>(
 	physical_objects: HashMap<ObjectHeader, R>,
 	hash_types: &Vec<HashType>,
 	signature_key_bytes: &Option<Vec<u8>>,
 	chunk_number: u64,
+	encoding_threads: Option<usize>,
+	chunk_integrity: ChunkIntegrity,
+	bad_block_strategy: &Option<BadBlockStrategy>,
+	io_interrupt_retries: IoInterruptRetries,
 	object_encoder: &mut Vec<ObjectEncoder<R>>) -> Result<()> {
 	for (object_header, stream) in physical_objects {
 		let encoder = PhysicalObjectEncoder::new(
@@ -484,18 +884,38 @@ fn setup_physical_object_encoder<R: Read>(
 			stream,
 			hash_types.to_owned(),
 			signature_key_bytes.clone(),
-			chunk_number)?;
+			chunk_number,
+			encoding_threads,
+			chunk_integrity,
+			bad_block_strategy.clone(),
+			io_interrupt_retries)?;
 		object_encoder.push(ObjectEncoder::Physical(Box::new(encoder)));
 	}
 	Ok(())
 }
 
+/// This function sets up the [ObjectEncoder] for the virtual objects.
+fn setup_virtual_object_encoder<R: Read>(
+	virtual_objects: HashMap<ObjectHeader, Vec<VirtualObjectRange>>,
+	chunk_number: u64,
+	object_encoder: &mut Vec<ObjectEncoder<R>>) -> Result<()> {
+	for (object_header, ranges) in virtual_objects {
+		let encoder = VirtualObjectEncoder::new(object_header, ranges, chunk_number)?;
+		object_encoder.push(ObjectEncoder::Virtual(Box::new(encoder)));
+	}
+	Ok(())
+}
+
 /// This function sets up the [ObjectEncoder] for the logical objects.
+#[allow(clippy::too_many_arguments)]
 fn setup_logical_object_encoder<R: Read>(
     logical_objects: HashMap<ObjectHeader, Vec<PathBuf>>,
     hash_types: &Vec<HashType>,
     signature_key_bytes: &Option<Vec<u8>>,
     chunk_number: u64,
+    chunk_integrity: ChunkIntegrity,
+    io_interrupt_retries: IoInterruptRetries,
+    original_paths: Option<OriginalPathFormat>,
     object_encoder: &mut Vec<ObjectEncoder<R>>) -> Result<()> {
     for (logical_object_header, input_files) in logical_objects {
         #[cfg(feature = "log")]
@@ -507,18 +927,25 @@ fn setup_logical_object_encoder<R: Read>(
             input_files,
             hash_types,
             signature_key_bytes,
-            chunk_number)?;
+            chunk_number,
+            chunk_integrity,
+            io_interrupt_retries,
+            original_paths)?;
         object_encoder.push(ObjectEncoder::Logical(Box::new(lobj)));
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn setup_logical_object(
     logical_object_header: ObjectHeader,
     input_files: Vec<PathBuf>,
     hash_types: &Vec<HashType>,
     signature_key_bytes: &Option<Vec<u8>>,
-    chunk_number: u64) -> Result<LogicalObjectEncoder> {
+    chunk_number: u64,
+    chunk_integrity: ChunkIntegrity,
+    io_interrupt_retries: IoInterruptRetries,
+    original_paths: Option<OriginalPathFormat>) -> Result<LogicalObjectEncoder> {
 
     let mut current_file_number = 0;
     let mut parent_file_number = 0;
@@ -633,6 +1060,10 @@ fn setup_logical_object(
         }
     }
 
+    if let Some(original_paths) = original_paths {
+        store_original_paths(&mut files, original_paths)?;
+    }
+
     #[cfg(target_family = "unix")]
     let hardlink_map = transform_hardlink_map(hardlink_map, &mut files)?;
 
@@ -648,11 +1079,27 @@ fn setup_logical_object(
         symlink_real_paths,
         hardlink_map,
         directory_children,
-        chunk_number)?;
+        chunk_number,
+        chunk_integrity,
+        io_interrupt_retries)?;
     Ok(log_obj)
 }
 
 
+fn store_original_paths(files: &mut [(PathBuf, FileHeader)], format: OriginalPathFormat) -> Result<()> {
+    let current_dir = match format {
+        OriginalPathFormat::AsGiven => None,
+        OriginalPathFormat::Absolute => Some(std::env::current_dir()?),
+    };
+    for (path, file_header) in files.iter_mut() {
+        match &current_dir {
+            Some(current_dir) if path.is_relative() => file_header.set_original_path(current_dir.join(path.as_path())),
+            _ => file_header.set_original_path(path.as_path()),
+        }
+    }
+    Ok(())
+}
+
 fn check_and_get_metadata<P: AsRef<Path>>(path: P) -> Result<Metadata> {
 	match std::fs::symlink_metadata(path.as_ref()) {
 		Ok(metadata) => Ok(metadata),
@@ -749,41 +1196,80 @@ fn transform_hardlink_map(hardlink_map: HashMap<u64, HashMap<u64, u64>>, files:
 fn prepare_object_header<R: Read>(
     physical_objects: &mut HashMap<ObjectHeader, R>, // <ObjectHeader, input_data stream>
 	logical_objects: &mut HashMap<ObjectHeader, Vec<PathBuf>>, //<ObjectHeader, input_files>,
-    extender_parameter: &Option<ZffExtenderParameter>
+    virtual_objects: &mut HashMap<ObjectHeader, Vec<VirtualObjectRange>>, //<ObjectHeader, ranges of the passive objects>,
+    extender_parameter: &Option<ZffExtenderParameter>,
+    zstd_dictionary: &Option<Vec<u8>>,
 ) -> Result<()> {
     let mut next_object_number = match &extender_parameter {
         None => INITIAL_OBJECT_NUMBER,
         Some(params) => params.next_object_no,
     };
 
-    let mut modify_map_phy = HashMap::new();
+    // the object numbers are assigned in the order physical objects, logical objects, virtual objects
+    // (each ordered by the given object numbers, so the assignment does not depend on the order of the HashMaps).
+    let mut assigned_object_numbers = HashMap::new(); // <given object number, assigned object number>
+    let mut ambiguous_object_number = None;
+
+    let mut modify_vec_phy = physical_objects.drain().collect::<Vec<_>>();
+    modify_vec_phy.sort_by_key(|(header, _)| header.object_number);
     // check if all necessary stuff is available in object header and modify them (if needed)
-    for (mut header, reader) in physical_objects.drain() {
+    for (header, _) in &mut modify_vec_phy {
         // check if all EncryptionHeader are contain a decrypted encryption key.
-        check_encryption_key_in_header(&header)?;
+        check_encryption_key_in_header(header)?;
         // modifies the appropriate object numbers to the right values.
+        if assigned_object_numbers.insert(header.object_number, next_object_number).is_some() {
+            ambiguous_object_number = Some(header.object_number);
+        }
         header.object_number = next_object_number;
         next_object_number += 1;
-
-        modify_map_phy.insert(header, reader);
+        set_zstd_dictionary(header, zstd_dictionary);
     }
-    physical_objects.extend(modify_map_phy);
+    physical_objects.extend(modify_vec_phy);
 
-    let mut modify_map_log = HashMap::new();
-    for (mut header, input_files) in logical_objects.drain() {
+    let mut modify_vec_log = logical_objects.drain().collect::<Vec<_>>();
+    modify_vec_log.sort_by_key(|(header, _)| header.object_number);
+    for (header, _) in &mut modify_vec_log {
         //check if all EncryptionHeader are contain a decrypted encryption key.
-        check_encryption_key_in_header(&header)?;        
+        check_encryption_key_in_header(header)?;
+        // modifies the appropriate object numbers to the right values.
+        if assigned_object_numbers.insert(header.object_number, next_object_number).is_some() {
+            ambiguous_object_number = Some(header.object_number);
+        }
+        header.object_number = next_object_number;
+        next_object_number += 1;
+        set_zstd_dictionary(header, zstd_dictionary);
+    }
+    logical_objects.extend(modify_vec_log);
+
+    if let (Some(object_number), false) = (ambiguous_object_number, virtual_objects.is_empty()) {
+        return Err(ZffError::new(ZffErrorKind::InvalidOption, format!("{ERROR_WRITER_AMBIGUOUS_OBJECT_NUMBER}{object_number}")));
+    }
+
+    let mut modify_vec_virt = virtual_objects.drain().collect::<Vec<_>>();
+    modify_vec_virt.sort_by_key(|(header, _)| header.object_number);
+    for (header, ranges) in &mut modify_vec_virt {
         // modifies the appropriate object numbers to the right values.
         header.object_number = next_object_number;
         next_object_number += 1;
-        
-        modify_map_log.insert(header, input_files);
+        // the ranges refer to the given object numbers of the new objects (or to the objects of the extended container).
+        for range in ranges {
+            if let Some(assigned_object_number) = assigned_object_numbers.get(&range.object_number) {
+                range.object_number = *assigned_object_number;
+            }
+        }
     }
-    logical_objects.extend(modify_map_log);
+    virtual_objects.extend(modify_vec_virt);
 
     Ok(())
 }
 
+// sets the given zstd dictionary for zstd compressed objects (a dictionary, which is already set in the header, will not be replaced).
+fn set_zstd_dictionary(object_header: &mut ObjectHeader, zstd_dictionary: &Option<Vec<u8>>) {
+    if object_header.compression_header.algorithm == CompressionAlgorithm::Zstd && object_header.compression_header.dictionary.is_none() {
+        object_header.compression_header.dictionary.clone_from(zstd_dictionary);
+    }
+}
+
 fn check_encryption_key_in_header(object_header: &ObjectHeader) -> Result<()> {
     if let Some(encryption_header) = &object_header.encryption_header {
         if encryption_header.get_encryption_key_ref().is_none() {
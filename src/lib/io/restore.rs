@@ -0,0 +1,182 @@
+// - STD
+use std::collections::HashMap;
+use std::path::Path;
+use std::fs;
+
+#[cfg(target_family = "unix")]
+use std::os::unix::fs::PermissionsExt;
+
+// - internal
+use crate::{
+	Result,
+	header::MetadataExtendedValue,
+	constants::*,
+};
+
+// - external
+use filetime::{FileTime, set_symlink_file_times};
+#[cfg(target_family = "unix")]
+use posix_acl::{PosixACL, Qualifier};
+#[cfg(target_family = "unix")]
+use nix::{
+	unistd::{fchownat, FchownatFlags, Uid, Gid},
+	errno::Errno,
+};
+
+#[cfg(feature = "log")]
+use log::warn;
+
+#[cfg(target_family = "windows")]
+const FILE_ATTRIBUTE_READONLY: u32 = 0x1;
+
+/// Applies the given extended metadata of a file (e.g. read by
+/// [ZffReader::current_file_metadata](crate::io::zffreader::ZffReader::current_file_metadata)) to the file at the given path.
+/// This reverses the acquisition of the metadata of the files of logical objects:
+/// - the uid and gid (the ownership can only be restored by privileged users, otherwise it will be skipped),
+/// - the extended attributes,
+/// - the file mode and the POSIX ACLs (not for symlinks),
+/// - the atime and mtime (the ctime and btime cannot be set),
+/// - the readonly attribute of the dwFileAttributes on Windows systems (the other attributes will be ignored).
+///
+/// Unknown and malformed values will be ignored.
+/// # Error
+/// Fails if the file does not exist or the appropriate metadata cannot be written.
+pub fn restore_file_metadata(path: &Path, metadata_ext: &HashMap<String, MetadataExtendedValue>) -> Result<()> {
+	let is_symlink = fs::symlink_metadata(path)?.file_type().is_symlink();
+
+	#[cfg(target_family = "unix")]
+	{
+		restore_ownership(path, metadata_ext)?;
+		restore_xattrs(path, metadata_ext)?;
+		if !is_symlink {
+			restore_mode_and_acls(path, metadata_ext)?;
+		}
+	}
+
+	#[cfg(target_family = "windows")]
+	if !is_symlink {
+		if let Some(attributes) = get_u64(metadata_ext, METADATA_EXT_DW_FILE_ATTRIBUTES) {
+			let mut permissions = fs::metadata(path)?.permissions();
+			permissions.set_readonly(attributes as u32 & FILE_ATTRIBUTE_READONLY != 0);
+			fs::set_permissions(path, permissions)?;
+		}
+	}
+
+	// the timestamps will be restored at last, as the other changes could touch them.
+	let atime = get_u64(metadata_ext, METADATA_ATIME);
+	let mtime = get_u64(metadata_ext, METADATA_MTIME);
+	if atime.is_some() || mtime.is_some() {
+		let metadata = fs::symlink_metadata(path)?;
+		let atime = match atime {
+			Some(atime) => FileTime::from_unix_time(atime as i64, 0),
+			None => FileTime::from_last_access_time(&metadata),
+		};
+		let mtime = match mtime {
+			Some(mtime) => FileTime::from_unix_time(mtime as i64, 0),
+			None => FileTime::from_last_modification_time(&metadata),
+		};
+		set_symlink_file_times(path, atime, mtime)?;
+	}
+
+	Ok(())
+}
+
+fn get_u64(metadata_ext: &HashMap<String, MetadataExtendedValue>, key: &str) -> Option<u64> {
+	metadata_ext.get(key).and_then(|value| value.as_u64())
+}
+
+#[cfg(target_family = "unix")]
+fn restore_ownership(path: &Path, metadata_ext: &HashMap<String, MetadataExtendedValue>) -> Result<()> {
+	let uid = get_u64(metadata_ext, METADATA_EXT_KEY_UID).and_then(|uid| u32::try_from(uid).ok()).map(Uid::from_raw);
+	let gid = get_u64(metadata_ext, METADATA_EXT_KEY_GID).and_then(|gid| u32::try_from(gid).ok()).map(Gid::from_raw);
+	if uid.is_none() && gid.is_none() {
+		return Ok(());
+	}
+	match fchownat(None, path, uid, gid, FchownatFlags::NoFollowSymlink) {
+		Ok(_) => Ok(()),
+		Err(Errno::EPERM) => {
+			#[cfg(feature = "log")]
+			warn!("The ownership of {} cannot be restored without the appropriate privileges.", path.display());
+			Ok(())
+		},
+		Err(e) => Err(std::io::Error::from(e).into()),
+	}
+}
+
+// the extended attributes are the only values (beside the original path), which are stored as byte arrays.
+#[cfg(target_family = "unix")]
+fn restore_xattrs(path: &Path, metadata_ext: &HashMap<String, MetadataExtendedValue>) -> Result<()> {
+	for (key, value) in metadata_ext {
+		if key == METADATA_EXT_KEY_ORIGINAL_PATH {
+			continue;
+		}
+		if let MetadataExtendedValue::ByteArray(value) = value {
+			xattr::set(path, key, value)?;
+		}
+	}
+	Ok(())
+}
+
+#[cfg(target_family = "unix")]
+fn restore_mode_and_acls(path: &Path, metadata_ext: &HashMap<String, MetadataExtendedValue>) -> Result<()> {
+	let mode = match get_u64(metadata_ext, METADATA_EXT_KEY_MODE) {
+		Some(mode) => {
+			let mode = mode as u32 & 0o7777;
+			fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+			mode
+		},
+		None => fs::metadata(path)?.permissions().mode(),
+	};
+
+	// older containers store the access ACL entries with the default prefix, too. As only directories can have
+	// default ACLs, these entries will be restored as access ACL entries for all other files.
+	let is_dir = fs::metadata(path)?.is_dir();
+	let mut acl_entries = Vec::new();
+	let mut default_acl_entries = Vec::new();
+	for (key, value) in metadata_ext {
+		let (entries, entry) = if let Some(entry) = key.strip_prefix(&format!("{ACL_DEFAULT_PREFIX}:")) {
+			if is_dir {
+				(&mut default_acl_entries, entry)
+			} else {
+				(&mut acl_entries, entry)
+			}
+		} else if let Some(entry) = key.strip_prefix(&format!("{ACL_PREFIX}:")) {
+			(&mut acl_entries, entry)
+		} else {
+			continue;
+		};
+		let perm = match value {
+			MetadataExtendedValue::String(perm) => perm.parse::<u32>().ok(),
+			value => value.as_u64().and_then(|perm| u32::try_from(perm).ok()),
+		};
+		if let (Some(qualifier), Some(perm)) = (parse_acl_qualifier(entry), perm) {
+			entries.push((qualifier, perm));
+		}
+	}
+
+	// the owner, group and other entries are figured by the mode, the mask will be recalculated.
+	if !acl_entries.is_empty() {
+		let mut acl = PosixACL::new(mode);
+		for (qualifier, perm) in acl_entries {
+			acl.set(qualifier, perm);
+		}
+		acl.write_acl(path)?;
+	}
+	if !default_acl_entries.is_empty() {
+		let mut acl = PosixACL::new(mode);
+		for (qualifier, perm) in default_acl_entries {
+			acl.set(qualifier, perm);
+		}
+		acl.write_default_acl(path)?;
+	}
+	Ok(())
+}
+
+#[cfg(target_family = "unix")]
+fn parse_acl_qualifier(entry: &str) -> Option<Qualifier> {
+	match entry.split_once(':') {
+		Some(("user", uid)) => uid.parse().ok().map(Qualifier::User),
+		Some(("group", gid)) => gid.parse().ok().map(Qualifier::Group),
+		_ => None,
+	}
+}
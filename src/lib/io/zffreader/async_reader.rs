@@ -0,0 +1,189 @@
+// - STD
+use std::io::{Read, Write, Seek, SeekFrom};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::pin::Pin;
+use std::future::Future;
+use std::task::{Context, Poll, ready};
+
+// - internal
+use crate::{
+	Result,
+	ZffError,
+	ZffErrorKind,
+	header::DescriptionHeader,
+	constants::ERROR_ASYNC_READER_OPERATION_IN_PROGRESS,
+};
+
+use super::{ZffReader, ObjectType};
+
+// - external
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+use tokio::task::{JoinHandle, spawn_blocking};
+
+/// An asynchronous wrapper of a [ZffReader], which implements [AsyncRead] and [AsyncSeek]
+/// (only available by using the "async" feature).
+/// All operations of the underlying (blocking) [ZffReader] will be executed by the blocking thread pool of the
+/// tokio runtime, so all methods (and the read and seek operations) have to be called within a tokio runtime.
+/// Like the [ZffReader], the read and seek operations refer to the active object (or the active file of a logical object).
+/// To read or seek across object boundaries, the appropriate object has to be set as active by using
+/// [AsyncZffReader::set_active_object] (or [AsyncZffReader::set_active_file]) first.
+pub struct AsyncZffReader<R: Read + Seek + Send + 'static> {
+	inner: Arc<Mutex<ZffReader<R>>>,
+	pending_read: Option<JoinHandle<std::io::Result<Vec<u8>>>>,
+	pending_seek: Option<JoinHandle<std::io::Result<u64>>>,
+	// data, which was read by the blocking thread, but did not fit into the buffer of the last read.
+	read_remainder: Vec<u8>,
+}
+
+impl<R: Read + Seek + Send + 'static> AsyncZffReader<R> {
+	/// Returns a new [AsyncZffReader] for the given [ZffReader].
+	pub fn new(reader: ZffReader<R>) -> Self {
+		Self {
+			inner: Arc::new(Mutex::new(reader)),
+			pending_read: None,
+			pending_seek: None,
+			read_remainder: Vec::new(),
+		}
+	}
+
+	/// Creates a new [AsyncZffReader] with the given segment readers (see [ZffReader::with_reader]).
+	pub async fn with_reader(reader_vec: Vec<R>) -> Result<Self> {
+		let reader = spawn_blocking(move || ZffReader::with_reader(reader_vec)).await.map_err(join_error)??;
+		Ok(Self::new(reader))
+	}
+
+	/// Returns the underlying [ZffReader].
+	/// # Error
+	/// Fails if a read or seek operation is still in progress.
+	pub fn into_inner(self) -> Result<ZffReader<R>> {
+		match Arc::try_unwrap(self.inner) {
+			Ok(inner) => inner.into_inner().map_err(|e| ZffError::new(ZffErrorKind::Custom, e.to_string())),
+			Err(_) => Err(ZffError::new(ZffErrorKind::Custom, ERROR_ASYNC_READER_OPERATION_IN_PROGRESS)),
+		}
+	}
+
+	/// Executes the given operation with the underlying [ZffReader] in the blocking thread pool.
+	async fn execute<T, F>(&self, operation: F) -> Result<T>
+	where
+		T: Send + 'static,
+		F: FnOnce(&mut ZffReader<R>) -> Result<T> + Send + 'static,
+	{
+		let inner = Arc::clone(&self.inner);
+		spawn_blocking(move || operation(&mut *lock(&inner)?)).await.map_err(join_error)?
+	}
+
+	/// Works like [ZffReader::list_objects].
+	pub async fn list_objects(&self) -> Result<BTreeMap<u64, ObjectType>> {
+		self.execute(|reader| reader.list_objects()).await
+	}
+
+	/// Works like [ZffReader::object_descriptions].
+	pub async fn object_descriptions(&self) -> Result<BTreeMap<u64, DescriptionHeader>> {
+		self.execute(|reader| Ok(reader.object_descriptions())).await
+	}
+
+	/// Works like [ZffReader::initialize_object].
+	pub async fn initialize_object(&self, object_number: u64) -> Result<()> {
+		self.execute(move |reader| reader.initialize_object(object_number)).await
+	}
+
+	/// Works like [ZffReader::initialize_objects_all].
+	pub async fn initialize_objects_all(&self) -> Result<()> {
+		self.execute(|reader| reader.initialize_objects_all()).await
+	}
+
+	/// Works like [ZffReader::set_active_object].
+	pub async fn set_active_object(&self, object_number: u64) -> Result<()> {
+		self.execute(move |reader| reader.set_active_object(object_number)).await
+	}
+
+	/// Works like [ZffReader::set_active_file].
+	pub async fn set_active_file(&self, file_number: u64) -> Result<()> {
+		self.execute(move |reader| reader.set_active_file(file_number)).await
+	}
+
+	/// Works like [ZffReader::read_range].
+	pub async fn read_range(&self, object_number: u64, offset: u64, length: usize) -> Result<Vec<u8>> {
+		self.execute(move |reader| reader.read_range(object_number, offset, length)).await
+	}
+
+	/// Works like [ZffReader::extract_file], but returns the given writer (after the data was written) in addition
+	/// to the number of written bytes.
+	pub async fn extract_file<W: Write + Send + 'static>(&self, file_number: u64, output: W) -> Result<(u64, W)> {
+		self.execute(move |reader| {
+			let mut output = output;
+			let written_bytes = reader.extract_file(file_number, &mut output)?;
+			Ok((written_bytes, output))
+		}).await
+	}
+}
+
+impl<R: Read + Seek + Send + 'static> AsyncRead for AsyncZffReader<R> {
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+		let this = self.get_mut();
+		if !this.read_remainder.is_empty() {
+			let length = this.read_remainder.len().min(buf.remaining());
+			buf.put_slice(&this.read_remainder[..length]);
+			this.read_remainder.drain(..length);
+			return Poll::Ready(Ok(()));
+		}
+		if this.pending_read.is_none() {
+			let inner = Arc::clone(&this.inner);
+			let length = buf.remaining();
+			this.pending_read = Some(spawn_blocking(move || {
+				let mut buffer = vec![0; length];
+				let read_bytes = lock(&inner)?.read(&mut buffer)?;
+				buffer.truncate(read_bytes);
+				Ok(buffer)
+			}));
+		}
+		// unwrap is safe here, because the pending read was set above.
+		let result = ready!(Pin::new(this.pending_read.as_mut().unwrap()).poll(cx));
+		this.pending_read = None;
+		let mut data = result.map_err(join_error)??;
+		// the buffer could be smaller than at the start of the read operation.
+		let length = data.len().min(buf.remaining());
+		buf.put_slice(&data[..length]);
+		this.read_remainder = data.split_off(length);
+		Poll::Ready(Ok(()))
+	}
+}
+
+impl<R: Read + Seek + Send + 'static> AsyncSeek for AsyncZffReader<R> {
+	fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+		let this = self.get_mut();
+		if this.pending_seek.is_some() {
+			return Err(std::io::Error::new(std::io::ErrorKind::Other, ERROR_ASYNC_READER_OPERATION_IN_PROGRESS));
+		}
+		// the position of the underlying reader is ahead of the remaining read data.
+		let position = match position {
+			SeekFrom::Current(offset) => SeekFrom::Current(offset - this.read_remainder.len() as i64),
+			position => position,
+		};
+		this.read_remainder.clear();
+		let inner = Arc::clone(&this.inner);
+		this.pending_seek = Some(spawn_blocking(move || lock(&inner)?.seek(position)));
+		Ok(())
+	}
+
+	fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+		let this = self.get_mut();
+		// returns the current position, if no seek operation was started.
+		if this.pending_seek.is_none() {
+			Pin::new(&mut *this).start_seek(SeekFrom::Current(0))?;
+		}
+		// unwrap is safe here, because the pending seek was set above.
+		let result = ready!(Pin::new(this.pending_seek.as_mut().unwrap()).poll(cx));
+		this.pending_seek = None;
+		Poll::Ready(result.map_err(join_error)?)
+	}
+}
+
+fn lock<R: Read + Seek>(inner: &Mutex<ZffReader<R>>) -> std::io::Result<MutexGuard<'_, ZffReader<R>>> {
+	inner.lock().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+}
+
+fn join_error(error: tokio::task::JoinError) -> std::io::Error {
+	std::io::Error::new(std::io::ErrorKind::Other, error)
+}
@@ -0,0 +1,89 @@
+// - STD
+use std::collections::{HashMap, BTreeMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A bounded LRU cache for the decoded data of chunks (see [ZffReader::set_chunk_cache_capacity](crate::io::zffreader::ZffReader::set_chunk_cache_capacity)).
+/// All clones of a [ChunkCache] share the same (thread-safe) cache. A capacity of 0 disables the cache.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChunkCache {
+	inner: Arc<Mutex<ChunkCacheInner>>,
+}
+
+#[derive(Debug, Default)]
+struct ChunkCacheInner {
+	capacity: usize, // the maximum number of cached chunks
+	access_counter: u64,
+	entries: HashMap<u64, (u64, Vec<u8>)>, //<chunk number, (last access, decoded chunk data)>
+	access_order: BTreeMap<u64, u64>, //<last access, chunk number>
+}
+
+impl ChunkCacheInner {
+	fn next_access(&mut self) -> u64 {
+		self.access_counter += 1;
+		self.access_counter
+	}
+
+	// removes the least recently used chunks until the number of cached chunks fits into the capacity.
+	fn evict(&mut self) {
+		while self.entries.len() > self.capacity {
+			let (_, chunk_number) = match self.access_order.pop_first() {
+				Some(entry) => entry,
+				None => break,
+			};
+			self.entries.remove(&chunk_number);
+		}
+	}
+}
+
+impl ChunkCache {
+	// the cached data is still valid, even if an other thread panicked while holding the lock.
+	fn lock(&self) -> MutexGuard<'_, ChunkCacheInner> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	/// Returns the maximum number of cached chunks.
+	pub(crate) fn capacity(&self) -> usize {
+		self.lock().capacity
+	}
+
+	/// Sets the maximum number of cached chunks. The least recently used chunks will be removed, if necessary.
+	pub(crate) fn set_capacity(&self, capacity: usize) {
+		let mut inner = self.lock();
+		inner.capacity = capacity;
+		inner.evict();
+	}
+
+	/// Returns the number of cached chunks.
+	pub(crate) fn len(&self) -> usize {
+		self.lock().entries.len()
+	}
+
+	/// Returns a copy of the cached data of the given chunk and marks the chunk as recently used.
+	pub(crate) fn get(&self, chunk_number: u64) -> Option<Vec<u8>> {
+		let mut inner = self.lock();
+		if inner.capacity == 0 {
+			return None;
+		}
+		let access = inner.next_access();
+		let (last_access, data) = inner.entries.get_mut(&chunk_number)?;
+		let previous_access = std::mem::replace(last_access, access);
+		let data = data.clone();
+		inner.access_order.remove(&previous_access);
+		inner.access_order.insert(access, chunk_number);
+		Some(data)
+	}
+
+	/// Caches the decoded data of the given chunk (has no effect, if the cache is disabled).
+	pub(crate) fn insert(&self, chunk_number: u64, data: &[u8]) {
+		let mut inner = self.lock();
+		if inner.capacity == 0 {
+			return;
+		}
+		let access = inner.next_access();
+		if let Some((previous_access, _)) = inner.entries.insert(chunk_number, (access, data.to_vec())) {
+			inner.access_order.remove(&previous_access);
+		}
+		inner.access_order.insert(access, chunk_number);
+		inner.evict();
+	}
+}
@@ -0,0 +1,76 @@
+// - internal
+use crate::{
+	CompressionAlgorithm,
+	hashing::HashType,
+	header::DescriptionHeader,
+};
+
+use super::ObjectType;
+
+// - external
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// A short summary of a single object, used by the [ContainerReport].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ObjectSummary {
+	/// The appropriate object number.
+	pub object_number: u64,
+	/// The type of the object (Note: The type of encrypted objects will always be [ObjectType::Encrypted]).
+	pub object_type: ObjectType,
+	/// Returns true if the object is encrypted.
+	pub encrypted: bool,
+}
+
+impl ObjectSummary {
+	/// Returns a new [ObjectSummary] with the given values.
+	pub fn new(object_number: u64, object_type: ObjectType, encrypted: bool) -> Self {
+		Self {
+			object_number,
+			object_type,
+			encrypted,
+		}
+	}
+}
+
+/// A report of the main properties of a zff container, which can be created by using [ZffReader::inspect](super::ZffReader::inspect)
+/// without initializing the objects.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ContainerReport {
+	/// The number of segments (as stored in the [MainFooter](crate::footer::MainFooter)).
+	pub segment_count: u64,
+	/// The unique identifier of the container.
+	pub unique_identifier: u64,
+	/// A short summary of each object.
+	pub object_summary: Vec<ObjectSummary>,
+	/// All compression algorithms used by the (unencrypted) objects.
+	pub compression_algorithms: Vec<CompressionAlgorithm>,
+	/// All hash types used by the (unencrypted) physical objects.
+	pub hash_types: Vec<HashType>,
+	/// Returns true if at least one (unencrypted) object uses signed hash values.
+	pub has_signature: bool,
+	/// The [DescriptionHeader] of the first unencrypted object, if available.
+	pub description: Option<DescriptionHeader>,
+	/// The total number of chunks in this container.
+	pub total_chunk_count: u64,
+	/// The zff format version of the container.
+	pub format_version: u8,
+}
+
+/// The basic information of a zff container, which can be detected by peeking at the start of the first segment
+/// (see [peek_container_info](super::peek_container_info)), without decoding the footers of the container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ContainerInfo {
+	/// The zff format version of the container (the version of the [SegmentHeader](crate::header::SegmentHeader)).
+	pub format_version: u8,
+	/// The unique identifier of the container.
+	pub unique_identifier: u64,
+	/// The segment number of the peeked segment.
+	pub segment_number: u64,
+	/// Returns true if the first object of the segment is encrypted
+	/// or None, if the segment does not start with an object header (e.g. the following segments of a container).
+	pub first_object_encrypted: Option<bool>,
+}
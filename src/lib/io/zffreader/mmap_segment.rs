@@ -0,0 +1,51 @@
+// - STD
+use std::io::{Read, Seek, SeekFrom, Cursor};
+use std::fs::File;
+use std::path::Path;
+
+// - internal
+use crate::Result;
+
+// - external
+use memmap2::Mmap;
+
+/// A memory-mapped segment file, which implements [Read] and [Seek] over the mapped pages
+/// (only available by using the "mmap" feature, see [ZffReader::with_mmap](super::ZffReader::with_mmap)).
+#[derive(Debug)]
+pub struct MmapSegment {
+	inner: Cursor<Mmap>,
+}
+
+impl MmapSegment {
+	/// Maps the segment file of the given path into the memory.
+	/// Note: The file must not be modified or truncated while it is mapped.
+	/// # Error
+	/// Fails if the file could not be opened or mapped.
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+		let file = File::open(path)?;
+		// SAFETY: the map is read-only; modifying or truncating the underlying file while it is mapped
+		// is not supported (see the documentation of this method and of ZffReader::with_mmap).
+		#[allow(unsafe_code)]
+		let mmap = unsafe { Mmap::map(&file)? };
+		Ok(Self {
+			inner: Cursor::new(mmap),
+		})
+	}
+
+	/// Returns the mapped data.
+	pub fn as_slice(&self) -> &[u8] {
+		self.inner.get_ref()
+	}
+}
+
+impl Read for MmapSegment {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		self.inner.read(buf)
+	}
+}
+
+impl Seek for MmapSegment {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		self.inner.seek(pos)
+	}
+}
@@ -1,33 +1,52 @@
 // - STD
 use std::fmt;
 use std::borrow::Borrow;
-use std::io::{Read, Seek, SeekFrom};
-use std::collections::{HashMap, BTreeMap};
+use std::io::{Read, Write, Seek, SeekFrom, Cursor};
+use std::collections::{HashMap, BTreeMap, BTreeSet};
 use std::sync::Arc;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 
 // - modules
 mod zffobjectreader;
 mod redb_handling;
+mod container_report;
+mod chunk_cache;
+#[cfg(feature = "async")]
+mod async_reader;
+#[cfg(feature = "mmap")]
+mod mmap_segment;
 
 // - re-exports
 pub use zffobjectreader::*;
+pub use container_report::*;
+#[cfg(feature = "async")]
+pub use async_reader::*;
+#[cfg(feature = "mmap")]
+pub use mmap_segment::*;
 pub(crate) use redb_handling::*;
+pub(crate) use chunk_cache::*;
 
 // - internal
 use crate::{
 	Segment,
 	HeaderCoding,
 	ValueDecoder,
+	Signature,
+	MultiHasher,
 	footer::{
 		FileFooter,
 		SegmentFooter,
 		ObjectFooter,
+		AcquisitionErrorEntry,
 	},
 	helper::get_segment_of_chunk_no,
 	header::{
 		EncryptionInformation, 
 		SegmentHeader, 
+		DescriptionHeader,
 		ObjectType as HeaderObjectType,
+		ObjectFlags,
 		ChunkFlags,
 		ChunkOffsetMap,
 		ChunkSizeMap,
@@ -35,9 +54,15 @@ use crate::{
 		ChunkXxHashMap,
 		ChunkSamebytesMap,
 		ChunkDeduplicationMap,
+		ChunkParityMap,
+		ChunkParityBlock,
 		ChunkMap,
+		VirtualObjectMap,
+		HashValue,
 	},
 	ChunkContent,
+	Chunk,
+	decode_chunk_content,
 };
 
 use super::*;
@@ -46,9 +71,12 @@ use super::*;
 use redb::{Database, ReadableTable};
 #[cfg(feature = "log")]
 use log::debug;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 /// Defines the recognized object type (used by the [ZffReader]). 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum ObjectType {
 	/// Physical object
 	Physical,
@@ -72,6 +100,155 @@ impl fmt::Display for ObjectType {
     }
 }
 
+/// A warning, which will be returned by [ZffReader::with_reader_lossy] for each segment which is unreadable or missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SegmentWarning {
+	/// The given reader (identified by the index of the reader vec) could not be parsed and was skipped.
+	/// Contains the segment number, if the segment header could be decoded, and the appropriate error message.
+	Unreadable {
+		/// The index of the skipped reader.
+		reader_index: usize,
+		/// The segment number (if the segment header could be decoded).
+		segment_number: Option<u64>,
+		/// The appropriate error message.
+		error: String,
+	},
+	/// The segment with the given segment number is not available.
+	/// Contains the ranges (first and last chunk number, inclusive) of the chunks which are stored in this segment.
+	Missing {
+		/// The number of the missing segment.
+		segment_number: u64,
+		/// The ranges of the missing chunks (first and last chunk number, inclusive).
+		chunk_ranges: Vec<(u64, u64)>,
+	},
+}
+
+impl fmt::Display for SegmentWarning {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SegmentWarning::Unreadable { reader_index, segment_number: Some(segment_number), error } => 
+				write!(f, "segment {segment_number} (reader {reader_index}) is unreadable: {error}"),
+			SegmentWarning::Unreadable { reader_index, segment_number: None, error } => 
+				write!(f, "reader {reader_index} is unreadable: {error}"),
+			SegmentWarning::Missing { segment_number, chunk_ranges } => {
+				let ranges = chunk_ranges.iter().map(|(first, last)| format!("{first}-{last}")).collect::<Vec<_>>();
+				write!(f, "segment {segment_number} is missing (chunks: {})", ranges.join(", "))
+			},
+		}
+	}
+}
+
+/// The number of chunks with the appropriate [ChunkFlags] set, which will be returned by [ZffReader::chunk_flag_statistics].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChunkFlagStats {
+	/// the total number of counted chunks.
+	pub chunks: u64,
+	/// the number of chunks with a read error (e.g. bad sectors during the acquisition).
+	pub error: u64,
+	/// the number of compressed chunks.
+	pub compression: u64,
+	/// the number of chunks, which contain the same bytes.
+	pub same_bytes: u64,
+	/// the number of duplicated chunks.
+	pub duplicate: u64,
+	/// the number of encrypted chunks.
+	pub encryption: u64,
+	/// the number of placeholder chunks of empty files.
+	pub empty_file: u64,
+	/// the number of virtual chunks.
+	pub virtual_chunk: u64,
+}
+
+/// The decoded chunk maps of all chunks of a container, which will be returned by [ZffReader::export_chunk_maps].  
+/// The key of each map is the appropriate chunk number.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChunkMapsExport {
+	/// the offset of each chunk in the appropriate segment.
+	pub offsets: BTreeMap<u64, u64>,
+	/// the (stored) size of each chunk.
+	pub sizes: BTreeMap<u64, u64>,
+	/// the names of the flags, which are set for each chunk (see [ChunkFlags::names]).
+	pub flags: BTreeMap<u64, Vec<String>>,
+	/// the stored integrity hash (xxhash or CRC-64) of each chunk.
+	pub integrity_hashes: BTreeMap<u64, u64>,
+}
+
+/// A stored hash value with a missing or invalid signature, which will be returned by [ZffReader::verify_all_stored].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct InvalidHashSignature {
+	/// the number of the appropriate file (only available for logical objects).
+	pub file_number: Option<u64>,
+	/// the [HashType] of the appropriate hash value.
+	pub hash_type: HashType,
+}
+
+/// The progress of a chunk verification, which will be reported by [ZffReader::verify_all_with_progress].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerifyProgress {
+	/// the number of chunks, which were read (and passed to the verification threads) so far.
+	pub chunks_processed: u64,
+	/// the total number of chunks of the verified object.
+	pub total_chunks: u64,
+	/// the number of corrupt chunks found so far.
+	pub corrupt_chunks: u64,
+}
+
+/// The segments, which contain the appropriate parts of an object (see [ZffReader::object_segments]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ObjectSegmentInfo {
+	/// the number of the segment, which contains the object header.
+	pub header_segment: u64,
+	/// the number of the segment, which contains the object footer.
+	pub footer_segment: u64,
+	/// the numbers of the segments, which contain the chunks of the object
+	/// (for virtual objects, the chunks of the appropriate passive objects).
+	pub chunk_segments: BTreeSet<u64>,
+}
+
+impl ChunkFlagStats {
+	// counts the given flags.
+	fn add(&mut self, flags: &ChunkFlags) {
+		self.chunks += 1;
+		self.error += flags.error as u64;
+		self.compression += flags.compression as u64;
+		self.same_bytes += flags.same_bytes as u64;
+		self.duplicate += flags.duplicate as u64;
+		self.encryption += flags.encryption as u64;
+		self.empty_file += flags.empty_file as u64;
+		self.virtual_chunk += flags.virtual_chunk as u64;
+	}
+}
+
+/// An iterator over the decoded data of all chunks of an object, which will be returned by [ZffReader::object_chunks].  
+/// Yields the chunk number and the decoded data of each chunk in ascending order of the chunk numbers
+/// (same byte chunks and duplicated chunks are resolved to the appropriate data).
+/// Errors of individual chunks are yielded as ```Err``` items, so the caller can decide whether to continue with the next chunk.
+pub struct ObjectChunks<'a, R: Read + Seek> {
+	reader: &'a mut ZffReader<R>,
+	chunks: std::vec::IntoIter<(u64, u64)>, // (chunk number, number of the object which contains the chunk)
+	decoding_information: HashMap<u64, (Option<EncryptionInformation>, CompressionHeader, u64)>, // <object number, (encryption information, compression header, chunk size)>
+}
+
+impl<R: Read + Seek> Iterator for ObjectChunks<'_, R> {
+	type Item = Result<(u64, Vec<u8>)>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (chunk_number, object_number) = self.chunks.next()?;
+		let (enc_info, compression_header, chunk_size) = &self.decoding_information[&object_number];
+		Some(self.reader.decoded_chunk_data(chunk_number, enc_info, compression_header, *chunk_size).map(|data| (chunk_number, data)))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.chunks.size_hint()
+	}
+}
+
 /// The preloaded chunkmaps which can be used by the [ZffReader] to speed up the reading process.
 #[derive(Debug, Default)]
 pub(crate) struct PreloadedChunkMapsInMemory {
@@ -137,7 +314,10 @@ impl PreloadedChunkMaps {
 	fn set_mode_redb(&mut self, mut db: Database) -> Result<()> {
 		match self {
 			PreloadedChunkMaps::None => *self = PreloadedChunkMaps::Redb(db),
-			PreloadedChunkMaps::InMemory(map) => convert_in_memory_preloaded_chunkmaps_into_redb(&mut db, map)?,
+			PreloadedChunkMaps::InMemory(map) => {
+				convert_in_memory_preloaded_chunkmaps_into_redb(&mut db, map)?;
+				*self = PreloadedChunkMaps::Redb(db);
+			},
 			PreloadedChunkMaps::Redb(old_db) => {
 				copy_redb(old_db, &mut db)?;
 				*self = PreloadedChunkMaps::Redb(db);
@@ -146,11 +326,117 @@ impl PreloadedChunkMaps {
 		Ok(())
 	}
 
+	/// Returns the Redb of a redb-based preloaded chunkmap and resets the preloaded chunkmap to none.
+	/// An in-memory preloaded chunkmap will be kept.
+	fn take_redb(&mut self) -> Option<Database> {
+		match std::mem::take(self) {
+			PreloadedChunkMaps::Redb(db) => Some(db),
+			other => {
+				*self = other;
+				None
+			},
+		}
+	}
+
 	fn get_samebyte(&self, chunk_no: u64) -> Option<u8> {
 		extract_samebyte_from_preloaded_chunkmap(&self, chunk_no)
 	}
 }
 
+/// Opens the segment files of the given paths and returns them sorted by the segment number of the appropriate [SegmentHeader]
+/// (the file names and the order of the given paths are not relevant, e.g. for paths found by a glob pattern).  
+/// The returned files can be used directly by [ZffReader::with_reader].
+/// # Error
+/// Fails if a file could not be opened or if the [SegmentHeader] of a file could not be decoded
+/// (e.g. with [ZffErrorKind::UnsupportedVersion] for segments of older zff versions).
+pub fn open_segments_from_paths<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<File>> {
+	let mut segments = Vec::with_capacity(paths.len());
+	for path in paths {
+		let mut file = File::open(path)?;
+		check_format_version(&mut file)?;
+		file.rewind()?;
+		let segment_header = SegmentHeader::decode_directly(&mut file)?;
+		file.rewind()?;
+		segments.push((segment_header.segment_number, file));
+	}
+	segments.sort_by_key(|(segment_number, _)| *segment_number);
+	Ok(segments.into_iter().map(|(_, file)| file).collect())
+}
+
+/// Peeks at the start of the given (first) segment and returns the detected [ContainerInfo].
+/// In contrast to [ZffReader::with_reader], only the [SegmentHeader] and the flags of the following object header will be read,
+/// so this can be used to check quickly if the given data is a supported zff container.
+/// The position of the reader will be restored afterwards.
+/// # Error
+/// Fails with
+///   - [ZffErrorKind::ReadEOF], if the data is too short to contain a [SegmentHeader].
+///   - [ZffErrorKind::HeaderDecodeMismatchIdentifier], if the data does not start with a zff header identifier.
+///   - [ZffErrorKind::UnsupportedVersion], if the container was created by an older (or newer) zff version
+///     (the error message contains the detected version, e.g. "This looks like a zff v2 container; [...]").
+pub fn peek_container_info<R: Read + Seek>(reader: &mut R) -> Result<ContainerInfo> {
+	let position = reader.stream_position()?;
+	reader.seek(SeekFrom::Start(0))?;
+	let container_info = peek_container_info_from_start(reader);
+	reader.seek(SeekFrom::Start(position))?;
+	container_info
+}
+
+// reads the identifier and the version of the segment header at the current position of the reader
+// and returns the format version, if the version is supported.
+fn check_format_version<R: Read>(reader: &mut R) -> Result<u8> {
+	let too_short = |e: std::io::Error| match e.kind() {
+		std::io::ErrorKind::UnexpectedEof => ZffError::new(ZffErrorKind::ReadEOF, ERROR_PEEK_CONTAINER_TOO_SHORT),
+		_ => ZffError::from(e),
+	};
+	// identifier, header length and version
+	let mut prefix = [0u8; 13];
+	reader.read_exact(&mut prefix).map_err(too_short)?;
+	let identifier = u32::from_be_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]);
+	if identifier != SegmentHeader::identifier() {
+		return Err(ZffError::new(ZffErrorKind::HeaderDecodeMismatchIdentifier, format!("{ERROR_PEEK_CONTAINER_UNKNOWN_MAGIC}{identifier:#010x}")));
+	}
+	// the segment header of zff v3 uses the same identifier as the main header of zff v1 and v2.
+	let format_version = prefix[12];
+	match format_version {
+		version if version == SegmentHeader::version() => Ok(format_version),
+		1 | 2 => Err(ZffError::new(
+			ZffErrorKind::UnsupportedVersion,
+			format!("{ERROR_PEEK_CONTAINER_LEGACY_VERSION_PREFIX}{format_version}{ERROR_PEEK_CONTAINER_LEGACY_VERSION_SUFFIX}"))),
+		_ => Err(ZffError::new(ZffErrorKind::UnsupportedVersion, format!("{ERROR_PEEK_CONTAINER_UNSUPPORTED_VERSION}{format_version}"))),
+	}
+}
+
+fn peek_container_info_from_start<R: Read + Seek>(reader: &mut R) -> Result<ContainerInfo> {
+	let format_version = check_format_version(reader)?;
+
+	reader.seek(SeekFrom::Start(0))?;
+	let segment_header = match SegmentHeader::decode_directly(reader) {
+		Ok(segment_header) => segment_header,
+		Err(e) => match e.get_kind() {
+			ZffErrorKind::IoError(io_error) if io_error.kind() == std::io::ErrorKind::UnexpectedEof => {
+				return Err(ZffError::new(ZffErrorKind::ReadEOF, ERROR_PEEK_CONTAINER_TOO_SHORT));
+			},
+			_ => return Err(e),
+		},
+	};
+
+	// identifier, header length, version, object number and flags of the following object header (if available).
+	let mut object_header_prefix = [0u8; 22];
+	let first_object_encrypted = match reader.read_exact(&mut object_header_prefix) {
+		Ok(_) if u32::from_be_bytes([object_header_prefix[0], object_header_prefix[1], object_header_prefix[2], object_header_prefix[3]]) == HEADER_IDENTIFIER_OBJECT_HEADER => {
+			Some(ObjectFlags::from(object_header_prefix[21]).encryption)
+		},
+		_ => None,
+	};
+
+	Ok(ContainerInfo {
+		format_version,
+		unique_identifier: segment_header.unique_identifier,
+		segment_number: segment_header.segment_number,
+		first_object_encrypted,
+	})
+}
+
 /// The [ZffReader] can be used to read the data of a zff container in a proper way.  
 /// It implements [std::io::Read] and [std::io::Seek] to ensure a wide range of possible use.
 /// # Example
@@ -183,6 +469,44 @@ pub struct ZffReader<R: Read + Seek> {
 	chunk_maps: PreloadedChunkMaps,
 	active_object: u64, //the number of the active object.
 	global_chunkmap: Arc<BTreeMap<u64, u64>>,
+	verify_on_read: bool, // checks the integrity (xxhash) of each read chunk
+	zero_fill_missing_chunks: bool, // fills chunks of missing segments with zeros instead of returning an error
+	chunk_cache: ChunkCache, // caches the decoded data of recently read chunks
+}
+
+impl ZffReader<Cursor<Vec<u8>>> {
+	/// Initializes the [ZffReader] with a single segment, which will be read from the given (non-seekable) stream
+	/// (e.g. stdin or a network socket).  
+	/// The whole segment will be buffered in memory to provide the seek semantics, which are needed to read the container.
+	/// Therefore, all operations of the [ZffReader] (e.g. random seeking or listing the objects) are available,
+	/// but this method should only be used for segments which fit into the memory.
+	/// # Error
+	/// Fails if the stream could not be read or if the buffered data is not a valid (last) segment of a zff container
+	/// (see [ZffReader::with_reader]).
+	pub fn with_buffered_stream<S: Read>(mut stream: S) -> Result<Self> {
+		let mut buffer = Vec::new();
+		stream.read_to_end(&mut buffer)?;
+		Self::with_reader(vec![Cursor::new(buffer)])
+	}
+}
+
+#[cfg(feature = "mmap")]
+impl ZffReader<MmapSegment> {
+	/// Initializes the [ZffReader] with the segment files of the given paths, which will be memory-mapped
+	/// (only available by using the "mmap" feature).  
+	/// The chunk data will be copied directly from the mapped pages instead of using a read syscall for each chunk,
+	/// which speeds up random access workloads (e.g. carving or timeline building).
+	/// The order of the given paths is not relevant.  
+	/// Note: The segment files must not be modified or truncated while they are mapped.
+	/// # Error
+	/// Fails if a file could not be opened or mapped, or if the segments are not a valid zff container (see [ZffReader::with_reader]).
+	pub fn with_mmap<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+		let mut segments = Vec::with_capacity(paths.len());
+		for path in paths {
+			segments.push(MmapSegment::open(path)?);
+		}
+		Self::with_reader(segments)
+	}
 }
 
 impl<R: Read + Seek> ZffReader<R> {
@@ -191,6 +515,12 @@ impl<R: Read + Seek> ZffReader<R> {
 	/// [SegmentFooter] and [MainFooter].  
 	/// This method will **not** initizalize the objects itself! This has to be done by using the
 	/// initialize_object() or initialize_objects_all() methods.
+	/// # Error
+	/// Fails if a segment could not be decoded or if no segment contains the [MainFooter]. In the latter case, the error
+	/// is of the kind [ZffErrorKind::MissingSegment] with the expected number of the last segment and the error message
+	/// lists the supplied segment numbers.  
+	/// Containers of older zff versions (v1 and v2) use an other layout and will be rejected with an error of the kind
+	/// [ZffErrorKind::UnsupportedVersion], which contains the detected version (see [peek_container_info]).
 	pub fn with_reader(reader_vec: Vec<R>) -> Result<Self> {
 		#[cfg(feature = "log")]
 		debug!("Initialize ZffReader with {} segments.", reader_vec.len());
@@ -199,6 +529,10 @@ impl<R: Read + Seek> ZffReader<R> {
 		let mut main_footer = None;
 
 		for mut reader in reader_vec {
+			// older containers (zff v1 and v2) use an other layout, which can not be read by this reader.
+			let position = reader.stream_position()?;
+			check_format_version(&mut reader)?;
+			reader.seek(SeekFrom::Start(position))?;
 			let segment_header = SegmentHeader::decode_directly(&mut reader)?;
 			let segment_footer = match try_find_footer(&mut reader)? {
 				Footer::MainAndSegment((main, segment)) => {
@@ -216,21 +550,199 @@ impl<R: Read + Seek> ZffReader<R> {
 		
 		let main_footer = match main_footer {
 			Some(footer) => footer,
-			None => return Err(ZffError::new(ZffErrorKind::MissingSegment, ERROR_MISSING_SEGMENT_MAIN_FOOTER)),
+			None => return Err(missing_main_footer_error(&segments)),
 		};
 
+		Ok(Self::with_segments(segments, main_footer))
+	}
+
+	/// Works like [ZffReader::with_reader], but initializes all objects of the container and tries to decrypt all encrypted objects
+	/// with the given password (see [ZffReader::decrypt_all_objects]). The returned [ZffReader] is ready to read the data of all objects,
+	/// which could be decrypted.
+	/// Objects which could not be decrypted with the given password (e.g. which were encrypted with a different password)
+	/// remain encrypted and can be decrypted later by using [ZffReader::decrypt_object].
+	/// # Error
+	/// Fails if the [ZffReader] could not be initialized (see [ZffReader::with_reader]) or if an object could not be initialized
+	/// (see [ZffReader::initialize_objects_all]).
+	pub fn with_reader_and_password<P: AsRef<[u8]>>(reader_vec: Vec<R>, decryption_password: P) -> Result<Self> {
+		let mut reader = Self::with_reader(reader_vec)?;
+		reader.initialize_objects_all()?;
+		for (_object_number, result) in reader.decrypt_all_objects(decryption_password) {
+			if let Err(_e) = result {
+				#[cfg(feature = "log")]
+				debug!("Could not decrypt object {_object_number}: {_e}");
+			}
+		}
+		Ok(reader)
+	}
+
+	/// Works like [ZffReader::with_reader], but skips all segments which could not be parsed (e.g. truncated segments
+	/// or segments with an unreadable footer) instead of returning an error. This is useful for the partial recovery of damaged containers.  
+	/// Returns the [ZffReader] and a [SegmentWarning] for each skipped reader and each missing segment (incl. the ranges of the missing chunks).  
+	/// Objects, whose header and footer are available, can be initialized and read as usual (by using [ZffReader::initialize_object]).
+	/// Reading a chunk of a missing segment will return an error of the kind [ZffErrorKind::MissingChunk],
+	/// unless zero filling is enabled (see [ZffReader::set_zero_fill_missing_chunks]).
+	/// # Error
+	/// Fails if the segment which contains the [MainFooter] is missing or unreadable.
+	pub fn with_reader_lossy(reader_vec: Vec<R>) -> Result<(Self, Vec<SegmentWarning>)> {
+		#[cfg(feature = "log")]
+		debug!("Initialize ZffReader (lossy) with {} segments.", reader_vec.len());
+
+		let mut segments = HashMap::new();
+		let mut main_footer = None;
+		let mut warnings = Vec::new();
+
+		for (reader_index, mut reader) in reader_vec.into_iter().enumerate() {
+			let segment_header = match SegmentHeader::decode_directly(&mut reader) {
+				Ok(header) => header,
+				Err(e) => {
+					warnings.push(SegmentWarning::Unreadable { reader_index, segment_number: None, error: e.to_string() });
+					continue;
+				},
+			};
+			let segment_number = segment_header.segment_number;
+			let segment_footer = match try_find_footer(&mut reader) {
+				Ok(Footer::MainAndSegment((main, segment))) => {
+					main_footer = Some(main);
+					segment
+				},
+				Ok(Footer::Segment(segment_footer)) => segment_footer,
+				Err(e) => {
+					warnings.push(SegmentWarning::Unreadable { reader_index, segment_number: Some(segment_number), error: e.to_string() });
+					continue;
+				},
+			};
+
+			let segment = Segment::with_header_and_data(segment_header, reader, segment_footer);
+			segments.insert(segment_number, segment);
+		}
+
+		let main_footer = match main_footer {
+			Some(footer) => footer,
+			None => return Err(missing_main_footer_error(&segments)),
+		};
+
+		// determine the chunk ranges of the missing segments by using the global chunkmap (<last chunk number, segment number>).
+		let mut missing_chunk_ranges: BTreeMap<u64, Vec<(u64, u64)>> = BTreeMap::new();
+		let mut first_chunk_number = 1;
+		for (last_chunk_number, segment_number) in main_footer.chunk_offset_maps() {
+			if !segments.contains_key(segment_number) {
+				let ranges = missing_chunk_ranges.entry(*segment_number).or_default();
+				match ranges.last_mut() {
+					Some((_, last)) if *last + 1 == first_chunk_number => *last = *last_chunk_number,
+					_ => ranges.push((first_chunk_number, *last_chunk_number)),
+				}
+			}
+			first_chunk_number = last_chunk_number + 1;
+		}
+		for segment_number in 1..=main_footer.number_of_segments() {
+			if !segments.contains_key(&segment_number) {
+				let chunk_ranges = missing_chunk_ranges.remove(&segment_number).unwrap_or_default();
+				warnings.push(SegmentWarning::Missing { segment_number, chunk_ranges });
+			}
+		}
+
+		Ok((Self::with_segments(segments, main_footer), warnings))
+	}
+
+	fn with_segments(segments: HashMap<u64, Segment<R>>, main_footer: MainFooter) -> Self {
 		let global_chunkmap = Arc::new(main_footer.chunk_offset_maps().clone());
 
-		Ok(Self {
+		Self {
 			segments,
 			object_reader: HashMap::new(),
 			main_footer,
 			chunk_maps: PreloadedChunkMaps::default(),
 			active_object: 0,
 			global_chunkmap,
+			verify_on_read: false,
+			zero_fill_missing_chunks: false,
+			chunk_cache: ChunkCache::default(),
+		}
+	}
+
+	/// Creates a [ContainerReport] of the given segments, without initializing the objects.  
+	/// Only the [SegmentHeader]s, the [MainFooter], the object headers and the footers of physical objects
+	/// will be decoded (the files of logical objects will not be enumerated).
+	/// # Error
+	/// Fails if
+	///   - a segment is missing which should contain an object header
+	///   - there is a decoding error (e.g. corrupted segment)
+	pub fn inspect(mut reader_vec: Vec<R>) -> Result<ContainerReport> {
+		// all segments of a container are stored with the same format version.
+		let format_version = reader_vec.first_mut().map(Self::detect_format_version).transpose()?;
+		let mut zffreader = Self::with_reader(reader_vec)?;
+
+		let unique_identifier = match zffreader.segments.values().next() {
+			Some(segment) => segment.header().unique_identifier,
+			None => return Err(ZffError::new(ZffErrorKind::MissingSegment(1), ERROR_MISSING_SEGMENT_MAIN_FOOTER)),
+		};
+
+		let mut object_summary = Vec::new();
+		let mut compression_algorithms = Vec::new();
+		let mut hash_types = Vec::new();
+		let mut has_signature = false;
+		let mut description = None;
+
+		for (object_number, object_type) in zffreader.list_objects()? {
+			let encrypted = object_type == ObjectType::Encrypted;
+			if !encrypted {
+				let header_segment_no = zffreader.main_footer.object_header().get(&object_number).copied().unwrap_or_default();
+				let object_header = zffreader.segment_mut_ref(header_segment_no)?.read_object_header(object_number)?;
+				if !compression_algorithms.contains(&object_header.compression_header.algorithm) {
+					compression_algorithms.push(object_header.compression_header.algorithm.clone());
+				}
+				has_signature |= object_header.has_hash_signatures();
+				if description.is_none() {
+					description = Some(object_header.description_header.clone());
+				}
+				if object_type == ObjectType::Physical {
+					let footer_segment_no = zffreader.main_footer.object_footer().get(&object_number).copied().unwrap_or_default();
+					if let ObjectFooter::Physical(footer) = zffreader.segment_mut_ref(footer_segment_no)?.read_object_footer(object_number)? {
+						for hash_value in footer.hash_header.hashes {
+							if !hash_types.contains(hash_value.hash_type()) {
+								hash_types.push(hash_value.hash_type().clone());
+							}
+						}
+					}
+				}
+			}
+			object_summary.push(ObjectSummary::new(object_number, object_type, encrypted));
+		}
+
+		Ok(ContainerReport {
+			segment_count: zffreader.main_footer.number_of_segments(),
+			unique_identifier,
+			object_summary,
+			compression_algorithms,
+			hash_types,
+			has_signature,
+			description,
+			total_chunk_count: zffreader.number_of_chunks(),
+			format_version: format_version.unwrap_or_else(SegmentHeader::version),
 		})
 	}
 
+	/// Detects the zff format version of the given (first) segment.
+	/// The version is read from the header at the start of the segment (which is the [SegmentHeader] in zff v3
+	/// and the main header in zff v2). The position of the reader will be restored afterwards.
+	/// Only zff v3 containers can be read by the [ZffReader]: there is no reader for zff v1 or v2 containers in this crate,
+	/// opening them fails with an [UnsupportedVersion](ZffErrorKind::UnsupportedVersion) error.
+	/// # Error
+	/// Fails if the reader does not start with a valid zff header identifier or if the data could not be read.
+	pub fn detect_format_version(reader: &mut R) -> Result<u8> {
+		let position = reader.stream_position()?;
+		reader.seek(SeekFrom::Start(0))?;
+		let version = if SegmentHeader::check_identifier(reader) {
+			SegmentHeader::decode_header_length(reader)?;
+			u8::decode_directly(reader)
+		} else {
+			Err(ZffError::new(ZffErrorKind::HeaderDecodeMismatchIdentifier, ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER))
+		};
+		reader.seek(SeekFrom::Start(position))?;
+		version
+	}
+
 	/// Lists all objects which are inside the zff container (even if they are uninitialized).
 	/// Returns a BTreeMap, which contains the appropriate object number and the object type.
 	/// # Error
@@ -243,12 +755,13 @@ impl<R: Read + Seek> ZffReader<R> {
 		for (object_number, segment_number) in self.main_footer.object_header() {
 			let segment = match self.segments.get_mut(segment_number) {
 				Some(segment) => segment,
-				None => return Err(ZffError::new(ZffErrorKind::MissingSegment, segment_number.to_string())),
+				None => return Err(ZffError::new(ZffErrorKind::MissingSegment(*segment_number), segment_number.to_string())),
 			};
 			if let Ok(obj_header) = segment.read_object_header(*object_number) {
 				let obj_type = match obj_header.object_type {
 					HeaderObjectType::Physical => ObjectType::Physical,
 					HeaderObjectType::Logical => ObjectType::Logical,
+					HeaderObjectType::Virtual => ObjectType::Virtual,
 				};
 				map.insert(*object_number, obj_type);
 			} else {
@@ -275,6 +788,22 @@ impl<R: Read + Seek> ZffReader<R> {
 		map
 	}
 
+	/// Lists all initialized objects (see [ZffReader::initialize_objects_all]) with their object types, including virtual objects
+	/// and encrypted objects which are not decrypted yet (as [ObjectType::Encrypted]).  
+	/// Unlike [ZffReader::list_objects], this method does not read any object header from the segments
+	/// and therefore does not need mutable access to the reader.
+	pub fn list_initialized_objects(&self) -> BTreeMap<u64, ObjectType> {
+		self.object_reader.iter().map(|(object_number, reader)| {
+			let object_type = match reader {
+				ZffObjectReader::Encrypted(_) => ObjectType::Encrypted,
+				ZffObjectReader::Physical(_) => ObjectType::Physical,
+				ZffObjectReader::Logical(_) => ObjectType::Logical,
+				ZffObjectReader::Virtual(_) => ObjectType::Virtual,
+			};
+			(*object_number, object_type)
+		}).collect()
+	}
+
 	///  Sets an appropriate object as active to read or seek from this object.
 	///  # Error
 	///  This method fails, if the appropriate object number not exists in this zff container.
@@ -309,7 +838,7 @@ impl<R: Read + Seek> ZffReader<R> {
 		let object_reader = initialize_object_reader(
 			object_number, &mut self.segments, &self.main_footer, Arc::clone(&self.global_chunkmap))?;
 		self.object_reader.insert(object_number, object_reader);
-		Ok(())
+		self.apply_read_options(object_number)
 	}
 
 	/// Same as initialize_object(), but will initialize **all** objects of this zff container.
@@ -319,6 +848,724 @@ impl<R: Read + Seek> ZffReader<R> {
 		let object_reader_map = initialize_object_reader_all(
 			&mut self.segments, &self.main_footer, Arc::clone(&self.global_chunkmap))?;
 		self.object_reader = object_reader_map;
+		for object_number in self.object_reader.keys().copied().collect::<Vec<_>>() {
+			self.apply_read_options(object_number)?;
+		}
+		Ok(())
+	}
+
+	/// Enables or disables the integrity check of each chunk while reading (disabled by default).  
+	/// If enabled, the xxhash value of each read chunk will be calculated and compared with the stored xxhash value.
+	/// The read methods will return an error which contains the appropriate chunk number, if the values do not match.  
+	/// The xxhash maps of all initialized (and unencrypted) objects will be preloaded by using this method (objects which
+	/// will be initialized or decrypted later will be handled automatically).
+	/// # Error
+	/// May fail if the xxhash maps could not be preloaded (e.g. corrupted or missing segments).
+	pub fn set_verify_on_read(&mut self, verify: bool) -> Result<()> {
+		self.verify_on_read = verify;
+		for object_number in self.object_reader.keys().copied().collect::<Vec<_>>() {
+			self.apply_read_options(object_number)?;
+		}
+		Ok(())
+	}
+
+	/// Enables or disables the zero filling of missing chunks (disabled by default).  
+	/// If enabled, chunks of missing or unreadable segments (see [ZffReader::with_reader_lossy]) will be read as zeros.
+	/// Otherwise, the read methods will return an error of the kind [ZffErrorKind::MissingChunk].
+	pub fn set_zero_fill_missing_chunks(&mut self, zero_fill: bool) {
+		self.zero_fill_missing_chunks = zero_fill;
+		for object_reader in self.object_reader.values_mut() {
+			object_reader.set_zero_fill_missing_chunks(zero_fill);
+		}
+	}
+
+	/// Sets the maximum number of chunks, whose decoded data will be cached (the cache is disabled by default).
+	/// The least recently used chunks will be removed from the cache, if the capacity is reached.
+	/// The cache is shared by all objects and avoids the repeated decryption and decompression of the same chunks
+	/// (e.g. if a parser reads the same regions again and again). Duplicated chunks and same bytes chunks will be cached, too.
+	/// A capacity of 0 disables the cache and removes all cached chunks. The memory usage of the cache is about
+	/// the given capacity multiplied by the chunk size.
+	pub fn set_chunk_cache_capacity(&mut self, capacity: usize) {
+		self.chunk_cache.set_capacity(capacity);
+	}
+
+	/// Returns the maximum number of cached chunks (see [ZffReader::set_chunk_cache_capacity]).
+	pub fn chunk_cache_capacity(&self) -> usize {
+		self.chunk_cache.capacity()
+	}
+
+	/// Returns the number of currently cached chunks (see [ZffReader::set_chunk_cache_capacity]).
+	pub fn cached_chunks(&self) -> usize {
+		self.chunk_cache.len()
+	}
+
+	// sets the verify_on_read and zero fill mode (and the chunk cache) of the appropriate object reader and preloads the xxhash map if necessary.
+	fn apply_read_options(&mut self, object_number: u64) -> Result<()> {
+		let object_reader = match self.object_reader.get_mut(&object_number) {
+			Some(reader) => reader,
+			None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		};
+		object_reader.set_verify_on_read(self.verify_on_read);
+		object_reader.set_zero_fill_missing_chunks(self.zero_fill_missing_chunks);
+		object_reader.set_chunk_cache(self.chunk_cache.clone());
+		if self.verify_on_read && !matches!(object_reader, ZffObjectReader::Encrypted(_)) {
+			self.preload_chunk_xxhash_map_per_object(object_number)?;
+			// the flags contain the algorithm of the integrity hash.
+			self.preload_chunk_flags_map_per_object(object_number)?;
+		}
+		Ok(())
+	}
+
+	/// Returns the first bytes (up to the given length) of the appropriate object, without changing the active object
+	/// or the reader position (e.g. to read the partition table or the boot sector of a physical object).  
+	/// For logical objects, the first bytes of the first regular file (the file with the lowest file number) will be returned.  
+	/// If the object is not initialized yet, it will be initialized temporarily.
+	/// # Error
+	/// May fail if
+	///   - the appropriate object number does not exist.
+	///   - the object is an encrypted or a virtual object.
+	///   - the logical object does not contain any regular files.
+	///   - the decoding or reading of the data fails (e.g. corrupted or missing segments)
+	pub fn get_first_bytes(&mut self, object_number: u64, length: usize) -> Result<Vec<u8>> {
+		let mut temporary_reader;
+		let object_reader = match self.object_reader.get_mut(&object_number) {
+			Some(reader) => reader,
+			None => {
+				temporary_reader = initialize_object_reader(
+					object_number, &mut self.segments, &self.main_footer, Arc::clone(&self.global_chunkmap))?;
+				&mut temporary_reader
+			}
+		};
+		match object_reader {
+			ZffObjectReader::Physical(reader) => Ok(reader.first_bytes(length, &mut self.segments, &self.chunk_maps)?),
+			ZffObjectReader::Logical(reader) => reader.first_bytes(length, &mut self.segments, &self.chunk_maps),
+			ZffObjectReader::Virtual(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			ZffObjectReader::Encrypted(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+		}
+	}
+
+	/// Reads up to the given length of bytes of the given object, starting at the given offset.  
+	/// For logical objects, the range will be read from the active file of the object.  
+	/// Neither the active object nor the reader positions will be changed. If the object is not initialized yet,
+	/// it will be initialized temporarily. If the range exceeds the end of the data, fewer bytes will be returned.
+	/// # Error
+	/// May fail if
+	///   - the appropriate object number does not exist.
+	///   - the object is an encrypted or a virtual object.
+	///   - the decoding or reading of the data fails (e.g. corrupted or missing segments)
+	pub fn read_range(&mut self, object_number: u64, offset: u64, length: usize) -> Result<Vec<u8>> {
+		let mut temporary_reader;
+		let object_reader = match self.object_reader.get_mut(&object_number) {
+			Some(reader) => reader,
+			None => {
+				temporary_reader = initialize_object_reader(
+					object_number, &mut self.segments, &self.main_footer, Arc::clone(&self.global_chunkmap))?;
+				&mut temporary_reader
+			}
+		};
+		match object_reader {
+			ZffObjectReader::Physical(reader) => Ok(reader.read_range(offset, length, &mut self.segments, &self.chunk_maps)?),
+			ZffObjectReader::Logical(reader) => reader.read_range(offset, length, &mut self.segments, &self.chunk_maps),
+			ZffObjectReader::Virtual(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			ZffObjectReader::Encrypted(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+		}
+	}
+
+	/// Writes the complete data of the given physical object (e.g. to create a raw/dd image) to the given writer
+	/// and returns the number of written bytes, which is equal to the length of the original data.  
+	/// The optional progress callback will be called with the number of written bytes after each chunk.  
+	/// Neither the active object nor the reader positions will be changed. If the object is not initialized yet,
+	/// it will be initialized temporarily.
+	/// # Error
+	/// May fail if
+	///   - the appropriate object number does not exist.
+	///   - the object is not a physical object (or is still encrypted).
+	///   - a chunk could not be read or decoded (the error contains the appropriate chunk number).
+	///   - the writing to the output fails.
+	pub fn export_physical_object(
+		&mut self,
+		object_number: u64,
+		mut output: impl Write,
+		progress: Option<&mut dyn FnMut(u64)>) -> Result<u64> {
+		let temporary_reader;
+		let object_reader = match self.object_reader.get(&object_number) {
+			Some(reader) => reader,
+			None => {
+				temporary_reader = initialize_object_reader(
+					object_number, &mut self.segments, &self.main_footer, Arc::clone(&self.global_chunkmap))?;
+				&temporary_reader
+			}
+		};
+		match object_reader {
+			ZffObjectReader::Physical(reader) => reader.export(&mut output, &mut self.segments, &self.chunk_maps, progress),
+			ZffObjectReader::Logical(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_LOGICAL_OBJECT)),
+			ZffObjectReader::Virtual(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			ZffObjectReader::Encrypted(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+		}
+	}
+
+	/// Writes the data of the file with the appropriate file number of the active (logical) object to the given writer
+	/// and returns the number of written bytes. Only the data up to the length of the file will be written.  
+	/// The active file and the reader positions will not be changed.
+	/// # Error
+	/// May fail if
+	///   - no object was set as active.
+	///   - the active object is not a "logical" object.
+	///   - the appropriate file number does not exist.
+	///   - the decoding, reading or writing of the data fails (e.g. corrupted or missing segments)
+	pub fn extract_file(&mut self, file_number: u64, mut output: impl Write) -> Result<u64> {
+		match self.object_reader.get_mut(&self.active_object) {
+			Some(ZffObjectReader::Logical(reader)) => reader.extract_file(file_number, &mut output, &mut self.segments, &self.chunk_maps),
+			Some(ZffObjectReader::Physical(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_PHYSICAL_OBJECT)),
+			Some(ZffObjectReader::Encrypted(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+			Some(ZffObjectReader::Virtual(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			None => Err(ZffError::new(ZffErrorKind::MissingObjectNumber, self.active_object.to_string())),
+		}
+	}
+
+	/// Verifies the integrity of all chunks of the active object by comparing the stored xxhash values
+	/// with the xxhash values of the decrypted and decompressed chunk data.  
+	/// Uses one worker thread per available CPU core (see [ZffReader::verify_all_with_threads]).  
+	/// Returns the numbers of the corrupt chunks (sorted ascending) or an empty Vec, if all chunks are intact.
+	/// # Error
+	/// May fail if
+	///   - no object was set as active.
+	///   - the active object is a virtual or an encrypted object.
+	///   - the reading of the chunk maps or the chunk data fails (e.g. missing segments).
+	pub fn verify_all(&mut self) -> Result<Vec<u64>> {
+		let n_threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+		self.verify_all_with_threads(n_threads)
+	}
+
+	/// Works like [ZffReader::verify_all], but uses the given number of worker threads
+	/// (at least one worker thread will be used).  
+	/// The chunk data is read sequentially from the segments, while the decryption, decompression and
+	/// the calculation of the xxhash values is distributed across the worker threads.
+	pub fn verify_all_with_threads(&mut self, n_threads: usize) -> Result<Vec<u64>> {
+		self.verify_all_with_threads_and_progress(n_threads, |_| {})
+	}
+
+	/// Works like [ZffReader::verify_all], but calls the given callback with the current [VerifyProgress]
+	/// every [DEFAULT_VERIFY_PROGRESS_INTERVAL](crate::constants::DEFAULT_VERIFY_PROGRESS_INTERVAL) chunks
+	/// and once after the verification has finished.
+	pub fn verify_all_with_progress<F>(&mut self, callback: F) -> Result<Vec<u64>>
+	where
+		F: FnMut(VerifyProgress),
+	{
+		let n_threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+		self.verify_all_with_threads_and_progress(n_threads, callback)
+	}
+
+	/// Works like [ZffReader::verify_all_with_threads], but calls the given callback with the current [VerifyProgress]
+	/// (see [ZffReader::verify_all_with_progress]).
+	pub fn verify_all_with_threads_and_progress<F>(&mut self, n_threads: usize, mut callback: F) -> Result<Vec<u64>>
+	where
+		F: FnMut(VerifyProgress),
+	{
+		let object_header = match self.object_reader.get(&self.active_object) {
+			Some(ZffObjectReader::Physical(reader)) => reader.object_header_ref().clone(),
+			Some(ZffObjectReader::Logical(reader)) => reader.object_header_ref().clone(),
+			Some(ZffObjectReader::Encrypted(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+			Some(ZffObjectReader::Virtual(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, self.active_object.to_string())),
+		};
+		let enc_info = EncryptionInformation::try_from(&object_header).ok();
+		let compression_header = &object_header.compression_header;
+		let chunk_size = object_header.chunk_size;
+		let chunk_numbers = get_chunks_of_unencrypted_object(&self.object_reader, self.active_object)?;
+		let total_chunks = chunk_numbers.len() as u64;
+		self.preload_chunk_xxhash_map_per_object(self.active_object)?;
+
+		let (job_sender, job_receiver) = crossbeam::channel::bounded::<ChunkVerificationJob>(n_threads.max(1) * 2);
+		let (corrupt_sender, corrupt_receiver) = crossbeam::channel::unbounded::<u64>();
+
+		std::thread::scope(|scope| {
+			for _ in 0..n_threads.max(1) {
+				let job_receiver = job_receiver.clone();
+				let corrupt_sender = corrupt_sender.clone();
+				let enc_info = &enc_info;
+				scope.spawn(move || {
+					for job in job_receiver {
+						let chunk_number = job.chunk_number;
+						if !job.verify(enc_info, compression_header, chunk_size) {
+							// the receiver lives until all worker threads are finished.
+							let _ = corrupt_sender.send(chunk_number);
+						}
+					}
+				});
+			}
+			// the sender will be dropped at the end of this method (also in case of error), which stops the worker threads.
+			self.send_chunk_verification_jobs(chunk_numbers, &enc_info, job_sender, |chunks_processed| {
+				callback(VerifyProgress { chunks_processed, total_chunks, corrupt_chunks: corrupt_receiver.len() as u64 });
+			})
+		})?;
+		drop(corrupt_sender);
+
+		let mut corrupt_chunks = corrupt_receiver.iter().collect::<Vec<_>>();
+		corrupt_chunks.sort();
+		callback(VerifyProgress { chunks_processed: total_chunks, total_chunks, corrupt_chunks: corrupt_chunks.len() as u64 });
+		Ok(corrupt_chunks)
+	}
+
+	// reads the raw data of the given chunks and sends the appropriate verification jobs to the worker threads.
+	// The progress callback will be called with the number of processed chunks in the appropriate interval.
+	fn send_chunk_verification_jobs<F: FnMut(u64)>(
+		&mut self,
+		chunk_numbers: Vec<u64>,
+		enc_info: &Option<EncryptionInformation>,
+		job_sender: crossbeam::channel::Sender<ChunkVerificationJob>,
+		mut progress: F) -> Result<()> {
+		for (chunks_processed, chunk_number) in (0..).zip(chunk_numbers) {
+			if chunks_processed > 0 && chunks_processed % DEFAULT_VERIFY_PROGRESS_INTERVAL == 0 {
+				progress(chunks_processed);
+			}
+			let mut flags = self.chunk_flags(chunk_number)?;
+			if flags.empty_file {
+				continue;
+			}
+			let xxhash = match extract_xxhash_from_preloaded_chunkmap(&self.chunk_maps, chunk_number) {
+				Some(xxhash) => xxhash,
+				None => return Err(ZffError::new(ZffErrorKind::MalformedSegment, format!("{ERROR_ZFFREADER_MISSING_CHUNK_XXHASH}{chunk_number}"))),
+			};
+			let mut data_chunk_number = chunk_number;
+			let mut raw_data = self.raw_chunk_data(chunk_number)?;
+
+			// the xxhash of a duplicated chunk is the xxhash of the original chunk data.
+			if flags.duplicate {
+				// an undecodable duplicate chunk will be sent unchanged and marked as corrupt by the worker.
+				// (the content of a duplicated chunk is the number of the original chunk).
+				if let Ok(ChunkContent::Duplicate(original_chunk_number)) = decode_chunk_content(
+					raw_data.clone(), chunk_number, enc_info, CompressionHeader::new(CompressionAlgorithm::None, 0, 0.0), &flags,
+					std::mem::size_of::<u64>() as u64) {
+					data_chunk_number = original_chunk_number;
+					flags = self.chunk_flags(data_chunk_number)?;
+					raw_data = self.raw_chunk_data(data_chunk_number)?;
+				}
+			}
+
+			let job = ChunkVerificationJob { chunk_number, data_chunk_number, raw_data, flags, xxhash };
+			if job_sender.send(job).is_err() {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/// Reconstructs the data of the given (corrupt or unreadable) chunk by using the parity data of the appropriate parity group
+	/// (see [ZffCreationParameters::parity_shards](crate::io::ZffCreationParameters::parity_shards)).  
+	/// The other chunks of the parity group will be verified by their integrity hash; corrupt or unreadable chunks of the
+	/// group will be treated as missing, too.  
+	/// Returns the decoded (decrypted and decompressed) data of the given chunk, which was verified against the stored integrity hash.
+	/// # Error
+	/// Fails with [ZffErrorKind::UnrecoverableChunk], if
+	///   - the chunk does not belong to an initialized (and decrypted) physical or logical object.
+	///   - there is no parity data available for the chunk.
+	///   - more chunks of the parity group are corrupt than parity shards are available.
+	///   - the reconstructed data does not match the stored integrity hash.
+	pub fn reconstruct_chunk(&mut self, chunk_number: u64) -> Result<Vec<u8>> {
+		let object_header = match self.object_of_chunk(chunk_number) {
+			Some(object_header) => object_header,
+			None => return Err(ZffError::new(
+				ZffErrorKind::UnrecoverableChunk(chunk_number), format!("{ERROR_ZFFREADER_CHUNK_OF_INITIALIZED_OBJECT}{chunk_number}"))),
+		};
+		let enc_info = EncryptionInformation::try_from(&object_header).ok();
+		let compression_header = &object_header.compression_header;
+		let chunk_size = object_header.chunk_size;
+		self.preload_chunk_xxhash_map_per_object(object_header.object_number)?;
+
+		let (last_chunk_number, parity_block) = match self.chunk_parity_block(chunk_number, &enc_info)? {
+			Some(parity_block) => parity_block,
+			None => return Err(ZffError::new(
+				ZffErrorKind::UnrecoverableChunk(chunk_number), format!("{ERROR_ZFFREADER_MISSING_CHUNK_PARITY}{chunk_number}"))),
+		};
+		// the values are decoded from the container, so a corrupt parity map must not lead to an overflow.
+		let first_chunk_number = match last_chunk_number.checked_add(1).and_then(|n| n.checked_sub(parity_block.number_of_chunks)) {
+			Some(first_chunk_number) if first_chunk_number <= chunk_number => first_chunk_number,
+			_ => return Err(ZffError::new(
+				ZffErrorKind::MalformedSegment, format!("{ERROR_ZFFREADER_MALFORMED_CHUNK_PARITY}{chunk_number}"))),
+		};
+
+		let mut chunks = Vec::with_capacity(parity_block.number_of_chunks as usize);
+		for group_chunk_number in first_chunk_number..=last_chunk_number {
+			if group_chunk_number == chunk_number {
+				chunks.push(None);
+			} else {
+				chunks.push(self.intact_raw_chunk_data(group_chunk_number, &enc_info, compression_header, chunk_size));
+			}
+		}
+		if chunks.iter().filter(|chunk| chunk.is_none()).count() > parity_block.parity_shards.len() {
+			return Err(ZffError::new(
+				ZffErrorKind::UnrecoverableChunk(chunk_number), format!("{ERROR_ZFFREADER_UNRECOVERABLE_CHUNK}{chunk_number}")));
+		}
+		let mut raw_data = parity_block.reconstruct(chunks)?.swap_remove((chunk_number - first_chunk_number) as usize);
+		let stored_size = match extract_size_from_preloaded_chunkmap(&self.chunk_maps, chunk_number) {
+			Some(size) => size,
+			None => self.segment_of_chunk(chunk_number)?.get_chunk_size(&chunk_number)?,
+		};
+		raw_data.truncate(stored_size as usize);
+
+		let flags = self.chunk_flags(chunk_number)?;
+		let xxhash = match extract_xxhash_from_preloaded_chunkmap(&self.chunk_maps, chunk_number) {
+			Some(xxhash) => xxhash,
+			None => return Err(ZffError::new(ZffErrorKind::MalformedSegment, format!("{ERROR_ZFFREADER_MISSING_CHUNK_XXHASH}{chunk_number}"))),
+		};
+		let data = match decode_chunk_content(raw_data, chunk_number, &enc_info, compression_header, &flags, chunk_size) {
+			Ok(ChunkContent::Raw(data)) => data,
+			Ok(ChunkContent::SameBytes(single_byte)) => vec![single_byte; chunk_size as usize],
+			Ok(ChunkContent::Duplicate(original_chunk_number)) => self.decoded_chunk_data(
+				original_chunk_number, &enc_info, compression_header, chunk_size)?,
+			Err(_) => return Err(ZffError::new(
+				ZffErrorKind::UnrecoverableChunk(chunk_number), format!("{ERROR_ZFFREADER_UNRECOVERABLE_CHUNK}{chunk_number}"))),
+		};
+		let size = data.len() as u64;
+		if (data.is_empty() && xxhash == 0) || Chunk::new(data.clone(), flags, size).check_integrity(xxhash)? {
+			Ok(data)
+		} else {
+			Err(ZffError::new(
+				ZffErrorKind::UnrecoverableChunk(chunk_number), format!("{ERROR_ZFFREADER_UNRECOVERABLE_CHUNK}{chunk_number}")))
+		}
+	}
+
+	// returns the header of the initialized (and decrypted) physical or logical object, which contains the given chunk.
+	fn object_of_chunk(&self, chunk_number: u64) -> Option<ObjectHeader> {
+		for (object_number, reader) in &self.object_reader {
+			let object_header = match reader {
+				ZffObjectReader::Physical(reader) => reader.object_header_ref(),
+				ZffObjectReader::Logical(reader) => reader.object_header_ref(),
+				_ => continue,
+			};
+			let chunk_numbers = get_chunks_of_unencrypted_object(&self.object_reader, *object_number).unwrap_or_default();
+			if chunk_numbers.contains(&chunk_number) {
+				return Some(object_header.clone());
+			}
+		}
+		None
+	}
+
+	// reads the parity block of the parity group, which contains the given chunk (if available).
+	// Returns the last chunk number of the group and the appropriate parity block.
+	fn chunk_parity_block(
+		&mut self,
+		chunk_number: u64,
+		encryption_information: &Option<EncryptionInformation>) -> Result<Option<(u64, ChunkParityBlock)>> {
+		let (map_chunk_number, segment_number) = match self.main_footer.chunk_parity_maps().range(chunk_number..).next() {
+			Some((map_chunk_number, segment_number)) => (*map_chunk_number, *segment_number),
+			None => return Ok(None),
+		};
+		let segment = match self.segments.get_mut(&segment_number) {
+			Some(segment) => segment,
+			None => return Err(ZffError::new(ZffErrorKind::MissingSegment(segment_number), ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
+		};
+		let offset = match segment.footer().chunk_parity_map_table.get(&map_chunk_number) {
+			Some(offset) => *offset,
+			None => return Ok(None),
+		};
+		segment.seek(SeekFrom::Start(offset))?;
+		let map = if let Some(ref enc_info) = encryption_information {
+			ChunkParityMap::decrypt_and_decode(&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, map_chunk_number)?
+		} else {
+			ChunkParityMap::decode_directly(segment)?
+		};
+		Ok(map.parity_block_of_chunk(chunk_number).map(|(last_chunk_number, block)| (last_chunk_number, block.clone())))
+	}
+
+	// returns the raw data of the given chunk, if the chunk is readable and intact (otherwise None).
+	fn intact_raw_chunk_data(
+		&mut self,
+		chunk_number: u64,
+		enc_info: &Option<EncryptionInformation>,
+		compression_header: &CompressionHeader,
+		chunk_size: u64) -> Option<Vec<u8>> {
+		let flags = self.chunk_flags(chunk_number).ok()?;
+		let raw_data = self.raw_chunk_data(chunk_number).ok()?;
+		if flags.empty_file {
+			return raw_data.is_empty().then_some(raw_data);
+		}
+		// the stored data of a duplicated chunk is the number of the original chunk.
+		if flags.duplicate {
+			return match decode_chunk_content(
+				raw_data.clone(), chunk_number, enc_info, CompressionHeader::new(CompressionAlgorithm::None, 0, 0.0), &flags, chunk_size) {
+				Ok(ChunkContent::Duplicate(_)) => Some(raw_data),
+				_ => None,
+			};
+		}
+		let xxhash = extract_xxhash_from_preloaded_chunkmap(&self.chunk_maps, chunk_number)?;
+		let job = ChunkVerificationJob { chunk_number, data_chunk_number: chunk_number, raw_data: raw_data.clone(), flags, xxhash };
+		job.verify(enc_info, compression_header, chunk_size).then_some(raw_data)
+	}
+
+	/// Returns the stored integrity hashes of all chunks of the container (the key is the appropriate chunk number).  
+	/// The chunk xxhash maps will be read directly from the segments (the objects do not have to be initialized).
+	/// # Error
+	/// Fails if the container contains an encrypted object, a segment is missing or a chunk xxhash map could not be decoded.
+	pub fn chunk_xxhashes(&mut self) -> Result<BTreeMap<u64, u64>> {
+		if self.list_objects()?.values().any(|object_type| *object_type == ObjectType::Encrypted) {
+			return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT));
+		}
+		read_unencrypted_chunk_maps::<ChunkXxHashMap, R>(
+			&mut self.segments,
+			self.main_footer.chunk_xxhash_maps(),
+			|footer| &footer.chunk_xxhash_map_table,
+			ERROR_ZFFREADER_MISSING_CHUNK_XXHASH)
+	}
+
+	/// Returns the offsets, sizes, flags and integrity hashes of all chunks of the container (see [ChunkMapsExport]).  
+	/// The chunk maps will be read directly from the segments (the objects do not have to be initialized).
+	/// With the serde feature, the returned [ChunkMapsExport] can be serialized (e.g. to JSON) for an external analysis of the container layout.
+	/// # Error
+	/// Fails if the container contains an encrypted object, a segment is missing or a chunk map could not be decoded.
+	pub fn export_chunk_maps(&mut self) -> Result<ChunkMapsExport> {
+		let integrity_hashes = self.chunk_xxhashes()?;
+		let offsets = read_unencrypted_chunk_maps::<ChunkOffsetMap, R>(
+			&mut self.segments,
+			self.main_footer.chunk_offset_maps(),
+			|footer| &footer.chunk_offset_map_table,
+			ERROR_ZFFREADER_MISSING_CHUNK_MAP)?;
+		let sizes = read_unencrypted_chunk_maps::<ChunkSizeMap, R>(
+			&mut self.segments,
+			self.main_footer.chunk_size_maps(),
+			|footer| &footer.chunk_size_map_table,
+			ERROR_ZFFREADER_MISSING_CHUNK_MAP)?;
+		let flags = read_unencrypted_chunk_maps::<ChunkFlagsMap, R>(
+			&mut self.segments,
+			self.main_footer.chunk_flags_maps(),
+			|footer| &footer.chunk_flags_map_table,
+			ERROR_ZFFREADER_MISSING_CHUNK_MAP)?;
+		let flags = flags.into_iter()
+			.map(|(chunk_number, flags)| (chunk_number, flags.names().into_iter().map(String::from).collect()))
+			.collect();
+		Ok(ChunkMapsExport { offsets, sizes, flags, integrity_hashes })
+	}
+
+	/// Compares the stored integrity hashes of all chunks of this container with the integrity hashes of the other container
+	/// (see [ZffReader::chunk_xxhashes]). Returns the numbers of the chunks (sorted ascending), whose integrity hashes differ
+	/// or which are only present in one of both containers. An empty Vec means that both containers contain the same chunks.  
+	/// Note: The integrity hashes are compared without reading any chunk data, so this is a fast check for
+	/// (probably) identical containers.
+	/// # Error
+	/// Fails if one of both containers contains an encrypted object or the chunk xxhash maps could not be read.
+	pub fn xxhash_diff<R2: Read + Seek>(&mut self, other: &mut ZffReader<R2>) -> Result<Vec<u64>> {
+		let xxhashes = self.chunk_xxhashes()?;
+		let other_xxhashes = other.chunk_xxhashes()?;
+		let mut differing_chunks = xxhashes.iter()
+			.filter(|(chunk_number, xxhash)| other_xxhashes.get(chunk_number) != Some(xxhash))
+			.map(|(chunk_number, _)| *chunk_number)
+			.collect::<Vec<_>>();
+		differing_chunks.extend(other_xxhashes.keys().filter(|chunk_number| !xxhashes.contains_key(chunk_number)));
+		differing_chunks.sort_unstable();
+		Ok(differing_chunks)
+	}
+
+	/// Returns the [ChunkFlags] of the given chunk (uses the preloaded chunkmap, if available).
+	/// # Error
+	/// May fail if the segment of the chunk is missing or the appropriate chunk flags map could not be read
+	/// (e.g. if the chunk belongs to an encrypted object).
+	pub fn chunk_flags(&mut self, chunk_number: u64) -> Result<ChunkFlags> {
+		if let Some(flags) = extract_flags_from_preloaded_chunkmap(&self.chunk_maps, chunk_number) {
+			return Ok(flags);
+		}
+		self.segment_of_chunk(chunk_number)?.get_chunk_flags(&chunk_number)
+	}
+
+	/// Returns the [ChunkFlags] and the stored bytes of the given chunk as-is: the data will not be decrypted or decompressed.
+	/// This can be used to copy the chunks of a container faithfully (e.g. to migrate the data to another storage).
+	/// Note: the chunk maps of encrypted objects are encrypted, too. So the appropriate object has to be decrypted first
+	/// to locate its chunks (the returned chunk data will remain encrypted).
+	/// # Error
+	/// May fail if the segment of the chunk is missing or the appropriate chunk maps could not be read.
+	pub fn raw_chunk_bytes(&mut self, chunk_number: u64) -> Result<(ChunkFlags, Vec<u8>)> {
+		let flags = self.chunk_flags(chunk_number)?;
+		let raw_data = self.raw_chunk_data(chunk_number)?;
+		Ok((flags, raw_data))
+	}
+
+	/// Returns an iterator over the decoded data of all chunks of the given object (see [ObjectChunks]).
+	/// For logical objects, the chunks of all files and for virtual objects, the chunks of all passive objects will be returned.
+	/// # Error
+	/// Fails if the object (or an appropriate passive object) is not initialized or is encrypted.
+	pub fn object_chunks(&mut self, object_number: u64) -> Result<ObjectChunks<'_, R>> {
+		let object_numbers = match self.object_reader.get(&object_number) {
+			Some(ZffObjectReader::Virtual(reader)) => reader.object_footer_ref().passive_objects.clone(),
+			Some(ZffObjectReader::Encrypted(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+			Some(_) => vec![object_number],
+			None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		};
+
+		let mut chunks = Vec::new();
+		let mut decoding_information = HashMap::new();
+		for object_number in object_numbers {
+			let object_header = match self.object_reader.get(&object_number).map(|reader| reader.object_header_ref()) {
+				Some(Some(object_header)) => object_header,
+				Some(None) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+				None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+			};
+			let compression_header = object_header.compression_header.clone();
+			let chunk_size = object_header.chunk_size;
+			let enc_info = get_enc_info_from_obj_reader(&self.object_reader[&object_number])?;
+			decoding_information.insert(object_number, (enc_info, compression_header, chunk_size));
+			let object_chunks = get_chunks_of_unencrypted_object(&self.object_reader, object_number)?;
+			chunks.extend(object_chunks.into_iter().map(|chunk_number| (chunk_number, object_number)));
+		}
+		chunks.sort_unstable();
+		chunks.dedup_by_key(|(chunk_number, _)| *chunk_number);
+
+		Ok(ObjectChunks {
+			reader: self,
+			chunks: chunks.into_iter(),
+			decoding_information,
+		})
+	}
+
+	// returns the decoded data of the given chunk (uses the preloaded chunkmaps, if available).
+	fn decoded_chunk_data(
+		&mut self,
+		chunk_number: u64,
+		enc_information: &Option<EncryptionInformation>,
+		compression_header: &CompressionHeader,
+		chunk_size: u64) -> Result<Vec<u8>> {
+		if let Some(samebyte) = self.chunk_maps.get_samebyte(chunk_number) {
+			return Ok(vec![samebyte; chunk_size as usize]);
+		}
+		let chunk_data = get_chunk_data(
+			&mut self.segments,
+			&self.global_chunkmap,
+			chunk_number,
+			enc_information,
+			compression_header,
+			chunk_size,
+			extract_offset_from_preloaded_chunkmap(&self.chunk_maps, chunk_number),
+			extract_size_from_preloaded_chunkmap(&self.chunk_maps, chunk_number),
+			extract_flags_from_preloaded_chunkmap(&self.chunk_maps, chunk_number),
+			&self.chunk_cache);
+		match chunk_data {
+			Ok(data) => Ok(data),
+			Err(e) => {
+				let details = format!("{ERROR_ZFFREADER_CHUNK_DATA}{chunk_number}: {e}");
+				Err(ZffError::new(ZffErrorKind::IoError(e), details))
+			},
+		}
+	}
+
+	// returns the raw (encrypted and/or compressed) data of the given chunk (uses the preloaded chunkmaps, if available).
+	fn raw_chunk_data(&mut self, chunk_number: u64) -> Result<Vec<u8>> {
+		let chunk_offset = extract_offset_from_preloaded_chunkmap(&self.chunk_maps, chunk_number);
+		let chunk_size = extract_size_from_preloaded_chunkmap(&self.chunk_maps, chunk_number);
+		self.segment_of_chunk(chunk_number)?.raw_chunk_data(chunk_number, chunk_offset, chunk_size)
+	}
+
+	// returns the segment which contains the given chunk.
+	fn segment_of_chunk(&mut self, chunk_number: u64) -> Result<&mut Segment<R>> {
+		let segment_number = match get_segment_of_chunk_no(chunk_number, &self.global_chunkmap) {
+			Some(segment_number) => segment_number,
+			None => return Err(ZffError::new(ZffErrorKind::MissingChunk(chunk_number), format!("{ERROR_ZFFREADER_MISSING_CHUNK}{chunk_number}"))),
+		};
+		match self.segments.get_mut(&segment_number) {
+			Some(segment) => Ok(segment),
+			None => Err(ZffError::new(ZffErrorKind::MissingSegment(segment_number), ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
+		}
+	}
+
+	/// Returns the numbers of the segments, which contain the object header, the object footer and the chunks of the given object.
+	/// The segment numbers are read from the [MainFooter] and the chunk maps, so the appropriate segments do not have to be present
+	/// (e.g. to determine which data of the object is affected by a missing segment).
+	/// # Error
+	/// Fails if the object is not initialized or is encrypted.
+	pub fn object_segments(&self, object_number: u64) -> Result<ObjectSegmentInfo> {
+		if let Some(ZffObjectReader::Encrypted(_)) = self.object_reader.get(&object_number) {
+			return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT));
+		}
+		let header_segment = self.main_footer.object_header().get(&object_number);
+		let footer_segment = self.main_footer.object_footer().get(&object_number);
+		let (header_segment, footer_segment) = match (header_segment, footer_segment) {
+			(Some(header_segment), Some(footer_segment)) => (*header_segment, *footer_segment),
+			_ => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		};
+		let chunk_segments = get_chunks_of_unencrypted_object(&self.object_reader, object_number)?
+			.into_iter()
+			// the keys of the global chunkmap are the last chunk numbers of the appropriate chunk maps.
+			.filter_map(|chunk_number| self.global_chunkmap.range(chunk_number..).next().map(|(_, segment_number)| *segment_number))
+			.collect();
+		Ok(ObjectSegmentInfo {
+			header_segment,
+			footer_segment,
+			chunk_segments,
+		})
+	}
+
+	/// Counts the set [ChunkFlags] of all chunks of all initialized physical and logical objects by using
+	/// the chunk flags maps of the segments (encrypted objects, which were not decrypted, will be skipped).
+	/// # Error
+	/// May fail if a chunk flags map could not be read or decoded (e.g. corrupted segments).
+	pub fn chunk_flag_statistics(&mut self) -> Result<ChunkFlagStats> {
+		let mut stats = ChunkFlagStats::default();
+		for object_number in self.unencrypted_object_no() {
+			if let Some(ZffObjectReader::Virtual(_)) = self.object_reader.get(&object_number) {
+				continue;
+			}
+			let mut chunk_numbers = get_chunks_of_unencrypted_object(&self.object_reader, object_number)?;
+			chunk_numbers.sort_unstable();
+			let enc_info = get_enc_info_from_obj_reader(&self.object_reader[&object_number])?;
+			for segment in self.segments.values_mut() {
+				let map_offsets = segment.footer().chunk_flags_map_table.iter()
+					.filter(|(chunk_number, _)| chunk_numbers.binary_search(chunk_number).is_ok())
+					.map(|(chunk_number, offset)| (*chunk_number, *offset))
+					.collect::<Vec<_>>();
+				for (chunk_number, offset) in map_offsets {
+					segment.seek(SeekFrom::Start(offset))?;
+					let mut map = if let Some(ref enc_info) = enc_info {
+						ChunkFlagsMap::decrypt_and_decode(
+							&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
+					} else {
+						ChunkFlagsMap::decode_directly(segment)?
+					};
+					for (chunk_number, flags) in map.flush() {
+						if chunk_numbers.binary_search(&chunk_number).is_ok() {
+							stats.add(&flags);
+						}
+					}
+				}
+			}
+		}
+		Ok(stats)
+	}
+
+	/// Adds the (blake3) deduplication hashes of all chunks of the given (baseline) object to the given [DeduplicationChunkMap].
+	/// The map can be used as [ZffCreationParameters::deduplication_chunkmap] to extend this container
+	/// (by using [ZffFilesOutput::ExtendContainer](crate::io::zffwriter::ZffFilesOutput::ExtendContainer)) with a new object
+	/// (e.g. an incremental acquisition of the same volume): chunks with the same content as a chunk of the baseline object will
+	/// then be stored as references to the appropriate chunk of the baseline object.  
+	/// The new object must not be encrypted and has to use the same compression algorithm as the baseline object.
+	/// # Error
+	/// May fail if
+	///   - the object is not initialized, is a virtual object or is encrypted.
+	///   - a chunk could not be read or decoded (e.g. corrupted or missing segments).
+	pub fn fill_deduplication_chunkmap(&mut self, object_number: u64, deduplication_map: &mut DeduplicationChunkMap) -> Result<()> {
+		let object_header = match self.object_reader.get(&object_number) {
+			Some(ZffObjectReader::Physical(reader)) => reader.object_header_ref(),
+			Some(ZffObjectReader::Logical(reader)) => reader.object_header_ref(),
+			Some(ZffObjectReader::Virtual(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			Some(ZffObjectReader::Encrypted(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+			None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		};
+		let compression_header = object_header.compression_header.clone();
+		let chunk_size = object_header.chunk_size;
+		// the chunks of (decrypted) encrypted objects can not be referenced by other objects.
+		if get_enc_info_from_obj_reader(&self.object_reader[&object_number])?.is_some() {
+			return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT));
+		}
+
+		for chunk_number in get_chunks_of_unencrypted_object(&self.object_reader, object_number)? {
+			let flags = self.chunk_flags(chunk_number)?;
+			// same byte chunks will not be deduplicated, duplicates are referring to an already added chunk.
+			if flags.same_bytes || flags.duplicate || flags.empty_file {
+				continue;
+			}
+			let raw_data = self.raw_chunk_data(chunk_number)?;
+			if let ChunkContent::Raw(data) = decode_chunk_content(
+				raw_data, chunk_number, &None::<EncryptionInformation>, &compression_header, &flags, chunk_size)? {
+				deduplication_map.append_entry(chunk_number, blake3::hash(&data))?;
+			}
+		}
 		Ok(())
 	}
 
@@ -334,6 +1581,8 @@ impl<R: Read + Seek> ZffReader<R> {
 	///   - The appropriate object number does not exist or is uninitialized.
 	///   - The appropriate object is not encrypted.
 	///   - The decryption password is incorrect.
+	///   - The authentication of an encrypted chunk map of the object fails (a [ZffErrorKind::IntegrityError] will be returned,
+	///     the object remains decrypted in this case).
 	///   - The decoding or reading of the data fails (e.g. corrupted or missing segments)
 	pub fn decrypt_object<P: AsRef<[u8]>>(&mut self, object_number: u64, decryption_password: P) -> Result<ObjectType> {
 		let object_reader = match self.object_reader.get_mut(&object_number) {
@@ -354,10 +1603,29 @@ impl<R: Read + Seek> ZffReader<R> {
 
 		// auto-preload chunkmaps for performance reasons
 		self.auto_preload_object_maps(object_number)?;
+		self.apply_read_options(object_number)?;
 
 		Ok(o_type)
 	}
 
+	/// Tries to decrypt all initialized encrypted objects with the given password (see [ZffReader::decrypt_object]).  
+	/// Returns the result for each encrypted object: the [ObjectType] of the decrypted object or the appropriate error
+	/// (e.g. if the object was encrypted with a different password). A failed decryption does not abort the decryption of
+	/// the other objects; the appropriate object remains encrypted.
+	pub fn decrypt_all_objects<P: AsRef<[u8]>>(&mut self, decryption_password: P) -> BTreeMap<u64, Result<ObjectType>> {
+		let mut encrypted_objects = self.object_reader.iter()
+			.filter(|(_, reader)| matches!(reader, ZffObjectReader::Encrypted(_)))
+			.map(|(object_number, _)| *object_number)
+			.collect::<Vec<_>>();
+		encrypted_objects.sort_unstable();
+
+		let mut results = BTreeMap::new();
+		for object_number in encrypted_objects {
+			results.insert(object_number, self.decrypt_object(object_number, decryption_password.as_ref()));
+		}
+		results
+	}
+
 	/// Defines a new preload chunkmap which will be held in memory, if none exists up to this point.
 	/// This method will (then) only "initialize" a new preload chunkmap. You have to fill this map by using  
 	/// methods like self::preloaded_chunkmap() or self::preload_chunkmap_full().  
@@ -380,6 +1648,14 @@ impl<R: Read + Seek> ZffReader<R> {
 		self.chunk_maps.set_mode_redb(db)
 	}
 
+	/// Returns the Redb of the redb-based preloaded chunkmap (see [ZffReader::set_preload_chunkmap_mode_redb]) and resets the preloaded chunkmap,
+	/// so the chunkmaps will be read from the segments again. All entries were already committed to the Redb, so the Redb can be reopened
+	/// later (e.g. by a long-lived service) and set again, instead of preloading the chunkmaps again.  
+	/// Returns None (and keeps the preloaded chunkmap), if no redb-based preloaded chunkmap is used.
+	pub fn take_preloaded_redb(&mut self) -> Option<Database> {
+		self.chunk_maps.take_redb()
+	}
+
 	/// Automatically preloads all maps of the specific object (will be used in case of encrypted maps for performance reasons).
 	fn auto_preload_object_maps(&mut self, object_number: u64) -> Result<()> {
 		self.preload_chunk_offset_map_per_object(object_number)?;
@@ -399,7 +1675,7 @@ impl<R: Read + Seek> ZffReader<R> {
 				segment.seek(SeekFrom::Start(*offset))?;
 				let mut map = if let Some(ref enc_info) = encryption_information {
 					ChunkOffsetMap::decrypt_and_decode(
-						&enc_info.encryption_key, &enc_info.algorithm, segment, chunk_number)?
+						&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
 				} else {
 					ChunkOffsetMap::decode_directly(segment)?
 				};
@@ -459,7 +1735,7 @@ impl<R: Read + Seek> ZffReader<R> {
 				segment.seek(SeekFrom::Start(*offset))?;
 				let mut map = if let Some(ref enc_info) = encryption_information {
 					ChunkSizeMap::decrypt_and_decode(
-						&enc_info.encryption_key, &enc_info.algorithm, segment, chunk_number)?
+						&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
 				} else {
 					ChunkSizeMap::decode_directly(segment)?
 				};
@@ -521,7 +1797,7 @@ impl<R: Read + Seek> ZffReader<R> {
 				segment.seek(SeekFrom::Start(*offset))?;
 				let mut map = if let Some(ref enc_info) = encryption_information {
 					ChunkFlagsMap::decrypt_and_decode(
-						&enc_info.encryption_key, &enc_info.algorithm, segment, chunk_number)?
+						&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
 				} else {
 					ChunkFlagsMap::decode_directly(segment)?
 				};
@@ -583,7 +1859,7 @@ impl<R: Read + Seek> ZffReader<R> {
 				segment.seek(SeekFrom::Start(*offset))?;
 				let mut map = if let Some(ref enc_info) = encryption_information {
 					ChunkXxHashMap::decrypt_and_decode(
-						&enc_info.encryption_key, &enc_info.algorithm, segment, chunk_number)?
+						&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
 				} else {
 					ChunkXxHashMap::decode_directly(segment)?
 				};
@@ -645,7 +1921,7 @@ impl<R: Read + Seek> ZffReader<R> {
 				segment.seek(SeekFrom::Start(*offset))?;
 				let mut map = if let Some(ref enc_info) = encryption_information {
 					ChunkSamebytesMap::decrypt_and_decode(
-						&enc_info.encryption_key, &enc_info.algorithm, segment, chunk_number)?
+						&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
 				} else {
 					ChunkSamebytesMap::decode_directly(segment)?
 				};
@@ -707,7 +1983,7 @@ impl<R: Read + Seek> ZffReader<R> {
 				segment.seek(SeekFrom::Start(*offset))?;
 				let mut map = if let Some(ref enc_info) = encryption_information {
 					ChunkDeduplicationMap::decrypt_and_decode(
-						&enc_info.encryption_key, &enc_info.algorithm, segment, chunk_number)?
+						&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm, segment, chunk_number)?
 				} else {
 					ChunkDeduplicationMap::decode_directly(segment)?
 				};
@@ -790,6 +2066,38 @@ impl<R: Read + Seek> ZffReader<R> {
 		}
 	}
 
+	/// Returns the file numbers (in ascending order) of all files of the active (logical) object.
+	/// # Error
+	/// May fail if   
+	/// - the active object is not a "logical" object.  
+	/// - no object was set as active.  
+	pub fn file_numbers(&self) -> Result<Vec<u64>> {
+		let mut file_numbers: Vec<u64> = self.active_logical_object_reader()?.files().keys().copied().collect();
+		file_numbers.sort_unstable();
+		Ok(file_numbers)
+	}
+
+	/// Returns the [FileMetadata] of all files of the active (logical) object.
+	/// # Error
+	/// May fail if   
+	/// - the active object is not a "logical" object.  
+	/// - no object was set as active.  
+	pub fn files_metadata(&self) -> Result<BTreeMap<u64, FileMetadata>> {
+		Ok(self.active_logical_object_reader()?.files().iter()
+			.map(|(file_number, metadata)| (*file_number, metadata.clone()))
+			.collect())
+	}
+
+	fn active_logical_object_reader(&self) -> Result<&ZffObjectReaderLogical> {
+		match self.object_reader.get(&self.active_object) {
+			Some(ZffObjectReader::Logical(reader)) => Ok(reader),
+			Some(ZffObjectReader::Physical(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_PHYSICAL_OBJECT)),
+			Some(ZffObjectReader::Encrypted(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+			Some(ZffObjectReader::Virtual(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			None => Err(ZffError::new(ZffErrorKind::MissingObjectNumber, self.active_object.to_string())),
+		}
+	}
+
 	/// Returns the [FileHeader] of the appropriate active file.
 	/// # Error
 	/// May fail if   
@@ -826,6 +2134,164 @@ impl<R: Read + Seek> ZffReader<R> {
 		}
 	}
 
+	/// Returns the hash values of the appropriate active file, which were stored in its [FileFooter].
+	/// # Error
+	/// May fail if   
+	/// - the active object is not a "logical" object.  
+	/// - the active file number was not set.  
+	/// - no object was set as active.  
+	pub fn current_file_hashes(&mut self) -> Result<Vec<HashValue>> {
+		Ok(self.current_filefooter()?.hash_header.hashes)
+	}
+
+	/// Returns the recommended metadata of the appropriate active file: the timestamps (atime, mtime, ctime, btime)
+	/// and - on unix systems - the uid, gid and mode (see the appropriate keys in [crate::constants]).
+	/// # Error
+	/// May fail if   
+	/// - the active object is not a "logical" object.  
+	/// - the active file number was not set.  
+	/// - no object was set as active.  
+	pub fn current_file_recommended_metadata(&mut self) -> Result<HashMap<String, MetadataExtendedValue>> {
+		Ok(extract_recommended_metadata(&self.current_fileheader()?))
+	}
+
+	/// Returns the original path of the appropriate active file, if the path was stored by the [ZffWriter](crate::io::zffwriter::ZffWriter)
+	/// (see [ZffCreationParameters::original_paths](crate::io::ZffCreationParameters::original_paths)).
+	/// # Error
+	/// May fail if   
+	/// - the active object is not a "logical" object.  
+	/// - the active file number was not set.  
+	/// - no object was set as active.  
+	pub fn current_file_original_path(&mut self) -> Result<Option<PathBuf>> {
+		Ok(self.current_fileheader()?.original_path())
+	}
+
+	/// Returns all stored metadata of the appropriate active file.
+	/// # Error
+	/// May fail if   
+	/// - the active object is not a "logical" object.  
+	/// - the active file number was not set.  
+	/// - no object was set as active.  
+	pub fn current_file_metadata(&mut self) -> Result<HashMap<String, MetadataExtendedValue>> {
+		Ok(extract_all_metadata(&self.current_fileheader()?))
+	}
+
+	/// Reads the data of the active object (or of the active file of a logical object) at the given offset into the given buffer,
+	/// without changing the current position of the reader (like [std::os::unix::fs::FileExt::read_at]).
+	/// Returns the number of read bytes (which could be less than the length of the buffer, or 0 if the offset is at or beyond the end of the data).
+	/// # Error
+	/// Fails if no object is active, the active object is encrypted or the appropriate chunks could not be read.
+	pub fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> std::io::Result<usize> {
+		let position = self.stream_position()?;
+		self.seek(SeekFrom::Start(offset))?;
+		let result = self.read(buf);
+		self.seek(SeekFrom::Start(position))?;
+		result
+	}
+
+	/// Returns a reference to the [MainFooter] of the container.
+	pub fn main_footer_ref(&self) -> &MainFooter {
+		&self.main_footer
+	}
+
+	/// Verifies the given detached signature of the container (see [Signature::sign_container_footer](crate::Signature::sign_container_footer))
+	/// with the given key bytes (signing key or verifying key). The signed bytes will be recomputed from the parsed [MainFooter].
+	/// # Error
+	/// Fails if the key or the signature has an invalid length.
+	pub fn verify_detached_signature<K: AsRef<[u8]>>(&self, key: K, signature: &[u8]) -> Result<bool> {
+		Signature::verify_container_footer(key, &self.main_footer, signature)
+	}
+
+	/// Returns true if at least one object of the container was created with hash signatures (see [ZffReader::object_is_signed]).
+	/// # Error
+	/// Fails if a segment is missing which should contain an object header or an object header could not be decoded.
+	pub fn is_signed(&mut self) -> Result<bool> {
+		let object_numbers = self.main_footer.object_header().keys().copied().collect::<Vec<_>>();
+		for object_number in object_numbers {
+			if self.object_is_signed(object_number)? {
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
+
+	/// Returns true if the sign_hash flag is set in the header of the given object (see [ObjectFlags]).  
+	/// The flags of encrypted objects are stored unencrypted, so the object does not have to be initialized or decrypted.
+	/// # Error
+	/// Fails if the object does not exist, the appropriate segment is missing or the object header could not be decoded.
+	pub fn object_is_signed(&mut self, object_number: u64) -> Result<bool> {
+		let segment_number = match self.main_footer.object_header().get(&object_number) {
+			Some(segment_number) => *segment_number,
+			None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		};
+		let segment = match self.segments.get_mut(&segment_number) {
+			Some(segment) => segment,
+			None => return Err(ZffError::new(ZffErrorKind::MissingSegment(segment_number), segment_number.to_string())),
+		};
+		match segment.read_object_header(object_number) {
+			Ok(object_header) => Ok(object_header.flags.sign_hash),
+			Err(_) => Ok(segment.read_encrypted_object_header(object_number)?.flags.sign_hash),
+		}
+	}
+
+	/// Verifies the ed25519 signatures of the stored hash values of the active object with the given key bytes (signing key or verifying key).  
+	/// Zff does not sign the stored (compressed and/or encrypted) chunk data, but the hash values, which were calculated over the
+	/// plaintext data of the object (physical objects) or of each file (logical objects). These hash values are stored in the object footer
+	/// or in the file footers, so the signatures can be verified without reading the chunk data.
+	/// Returns all hash values with a missing or invalid signature or an empty Vec, if all signatures are valid.  
+	/// Note: Whether the stored hash values match the data itself has to be checked separately (e.g. by recalculating the hash values).
+	/// # Error
+	/// May fail if
+	///   - no object was set as active.
+	///   - the active object is an encrypted object: the hash values and signatures are stored in the encrypted footers,
+	///     so the object has to be decrypted first (see [ZffReader::decrypt_object]).
+	///   - the active object is a virtual object or was created without hash signatures.
+	///   - the key has an invalid length or the file footers could not be read.
+	pub fn verify_all_stored<K: AsRef<[u8]>>(&mut self, key: K) -> Result<Vec<InvalidHashSignature>> {
+		let key = key.as_ref();
+		let (object_header, object_footer) = match self.object_reader.get(&self.active_object) {
+			Some(ZffObjectReader::Physical(reader)) => (reader.object_header_ref().clone(), reader.object_footer()),
+			Some(ZffObjectReader::Logical(reader)) => (reader.object_header_ref().clone(), reader.object_footer()),
+			Some(ZffObjectReader::Encrypted(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_SIGNATURES_OF_ENCRYPTED_OBJECT)),
+			Some(ZffObjectReader::Virtual(_)) => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, self.active_object.to_string())),
+		};
+		if !object_header.has_hash_signatures() {
+			return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_ZFFREADER_NO_HASH_SIGNATURES));
+		}
+
+		let mut invalid_signatures = Vec::new();
+		match object_footer {
+			ObjectFooter::Physical(footer) => {
+				for hash_value in footer.hash_header.hashes {
+					if !verify_hash_value_signature(key, &hash_value)? {
+						invalid_signatures.push(InvalidHashSignature { file_number: None, hash_type: hash_value.hash_type().clone() });
+					}
+				}
+			},
+			_ => {
+				let active_file = self.active_logical_object_reader()?.active_file();
+				let result = self.verify_stored_file_signatures(key, &mut invalid_signatures);
+				self.set_active_file(active_file)?;
+				result?;
+			},
+		}
+		Ok(invalid_signatures)
+	}
+
+	// verifies the signatures of the stored hash values of all files of the active (logical) object.
+	fn verify_stored_file_signatures(&mut self, key: &[u8], invalid_signatures: &mut Vec<InvalidHashSignature>) -> Result<()> {
+		for file_number in self.file_numbers()? {
+			self.set_active_file(file_number)?;
+			for hash_value in self.current_file_hashes()? {
+				if !verify_hash_value_signature(key, &hash_value)? {
+					invalid_signatures.push(InvalidHashSignature { file_number: Some(file_number), hash_type: hash_value.hash_type().clone() });
+				}
+			}
+		}
+		Ok(())
+	}
+
 	/// Returns a reference to the [ObjectHeader] of the appropriate active object.
 	/// # Error
 	/// May fail if   
@@ -854,6 +2320,131 @@ impl<R: Read + Seek> ZffReader<R> {
 		}
 	}
 
+	/// Returns the error log of the active (physical) object, which contains the read errors of the input data
+	/// (e.g. the unreadable sectors, which were filled with zeros) that occurred during the acquisition.  
+	/// The appropriate chunks are marked with the error flag (see [ChunkFlags::error](crate::header::ChunkFlags::error)).
+	/// # Error
+	/// May fail if   
+	/// - no object was set as active.  
+	/// - the active object is not a physical object (or was not decrypted).
+	pub fn active_object_error_log(&self) -> Result<Vec<AcquisitionErrorEntry>> {
+		match self.get_active_reader()? {
+			ZffObjectReader::Physical(reader) => match reader.object_footer() {
+				ObjectFooter::Physical(footer) => Ok(footer.error_log),
+				_ => unreachable!(),
+			},
+			ZffObjectReader::Logical(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_LOGICAL_OBJECT)),
+			ZffObjectReader::Virtual(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			ZffObjectReader::Encrypted(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+		}
+	}
+
+	/// Returns the hash values, which were calculated during the acquisition and stored in the [ObjectFooter] of the
+	/// appropriate (physical) object, without reading (or re-calculating) the data of the object.  
+	/// The hash values of the files of logical objects are stored separately (see [ZffReader::current_file_hashes]).
+	/// # Error
+	/// May fail if   
+	/// - the object was not initialized.  
+	/// - the object is not a physical object (or was not decrypted).
+	pub fn stored_hash_values(&self, object_number: u64) -> Result<Vec<HashValue>> {
+		match self.object_reader.get(&object_number) {
+			Some(ZffObjectReader::Physical(reader)) => Ok(reader.object_footer_unwrapped_ref().hash_header.hashes.clone()),
+			Some(ZffObjectReader::Logical(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_LOGICAL_OBJECT)),
+			Some(ZffObjectReader::Virtual(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_VIRTUAL_OBJECT)),
+			Some(ZffObjectReader::Encrypted(_)) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+			None => Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		}
+	}
+
+	/// Returns the stored hash values (see [ZffReader::stored_hash_values]) of all initialized physical objects of the container.  
+	/// Logical, virtual and encrypted (not yet decrypted) objects will be omitted.
+	pub fn stored_hash_values_all(&self) -> BTreeMap<u64, Vec<HashValue>> {
+		self.object_reader.iter()
+			.filter_map(|(object_number, object_reader)| match object_reader {
+				ZffObjectReader::Physical(reader) => Some((*object_number, reader.object_footer_unwrapped_ref().hash_header.hashes.clone())),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Re-calculates the hash values of the given (physical) object by using the hash types of the stored hash values
+	/// (see [ZffReader::stored_hash_values]) and returns them in the same order, so they can be compared with the stored hash values.  
+	/// The data of the object will be read only once for all hash types. The hashers will be distributed across the given number of
+	/// threads (see [MultiHasher]). Neither the active object nor the reader positions will be changed.
+	/// # Error
+	/// May fail if   
+	/// - the object was not initialized.  
+	/// - the object is not a physical object (or was not decrypted).  
+	/// - the data of the object could not be read or decoded.
+	pub fn calculate_hash_values(&mut self, object_number: u64, n_threads: usize) -> Result<Vec<HashValue>> {
+		let hash_types = self.stored_hash_values(object_number)?.iter()
+			.map(|hash_value| hash_value.hash_type().clone())
+			.collect();
+		let mut hasher = MultiHasher::new(hash_types, n_threads);
+		self.export_physical_object(object_number, &mut hasher, None)?;
+		Ok(hasher.finalize().into_iter()
+			.map(|(hash_type, hash)| HashValue::new(hash_type, hash, None))
+			.collect())
+	}
+
+	/// Returns the size of the (uncompressed) data of the appropriate object.  
+	/// For logical objects, the sum of the data sizes (see [FileMetadata]) of all files will be returned
+	/// (incl. the stored data of directories and links).
+	/// # Error
+	/// May fail if   
+	/// - the object was not initialized.  
+	/// - the object was not decrypted.
+	pub fn object_data_size(&self, object_number: u64) -> Result<u64> {
+		match self.object_reader.get(&object_number) {
+			Some(object_reader) => object_reader.data_size(),
+			None => Err(ZffError::new(ZffErrorKind::MissingObjectNumber, object_number.to_string())),
+		}
+	}
+
+	/// Returns the sum of the (uncompressed) data sizes of all initialized physical and logical objects (see [ZffReader::object_data_size]).  
+	/// Encrypted (not yet decrypted) objects will be omitted. Virtual objects will be omitted too, as they only refer to the data of other objects.
+	pub fn container_data_size(&self) -> u64 {
+		self.object_reader.values()
+			.filter(|object_reader| matches!(object_reader, ZffObjectReader::Physical(_) | ZffObjectReader::Logical(_)))
+			.filter_map(|object_reader| object_reader.data_size().ok())
+			.sum()
+	}
+
+	/// Returns the [DescriptionHeader]s of all initialized objects (the key is the appropriate object number).  
+	/// Uninitialized objects and encrypted (not yet decrypted) objects without an unencrypted description
+	/// (see [ObjectFlags::plaintext_description]) will be omitted.
+	pub fn object_descriptions(&self) -> BTreeMap<u64, DescriptionHeader> {
+		let mut descriptions = BTreeMap::new();
+		for (object_number, object_reader) in &self.object_reader {
+			if let Some(description_header) = object_description(object_reader) {
+				descriptions.insert(*object_number, description_header.clone());
+			}
+		}
+		descriptions
+	}
+
+	/// Returns the numbers of all initialized objects (sorted ascending), whose [DescriptionHeader] matches the given predicate.  
+	/// Uninitialized objects and encrypted (not yet decrypted) objects without an unencrypted description will be skipped.
+	pub fn find_objects<F: Fn(&DescriptionHeader) -> bool>(&self, predicate: F) -> Vec<u64> {
+		let mut object_numbers = Vec::new();
+		for (object_number, object_reader) in &self.object_reader {
+			let description_header = match object_description(object_reader) {
+				Some(description_header) => description_header,
+				None => continue,
+			};
+			if predicate(description_header) {
+				object_numbers.push(*object_number);
+			}
+		}
+		object_numbers.sort_unstable();
+		object_numbers
+	}
+
+	/// Returns the numbers of all initialized objects with the given evidence number (see [ZffReader::find_objects]).
+	pub fn find_by_evidence_number(&self, evidence_number: &str) -> Vec<u64> {
+		self.find_objects(|description_header| description_header.evidence_number() == Some(evidence_number))
+	}
+
 	fn get_active_reader(&self) -> Result<&ZffObjectReader> {
 		match self.object_reader.get(&self.active_object) {
 			Some(reader) => Ok(reader),
@@ -869,7 +2460,7 @@ impl<R: Read + Seek> ZffReader<R> {
 	pub fn segment_mut_ref(&mut self, segment_number: u64) -> Result<&mut Segment<R>> {
 		match self.segments.get_mut(&segment_number) {
 			Some(segment) => Ok(segment),
-			None => Err(ZffError::new(ZffErrorKind::MissingSegment, segment_number.to_string()))
+			None => Err(ZffError::new(ZffErrorKind::MissingSegment(segment_number), segment_number.to_string()))
 		}
 	}
 }
@@ -879,7 +2470,7 @@ impl<R: Read + Seek> Read for ZffReader<R> {
 		{
 			let object_reader = match self.object_reader.get_mut(&self.active_object) {
 				Some(object_reader) => object_reader,
-				None => return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
+				None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
 			};
 			match object_reader {
 				ZffObjectReader::Physical(reader) => return reader.read_with_segments(buffer, &mut self.segments, &self.chunk_maps),
@@ -897,7 +2488,7 @@ impl<R: Read + Seek> Read for ZffReader<R> {
 				ZffObjectReader::Virtual(reader) => reader.object_footer_ref().passive_objects.clone(),
 				_ => unreachable!(),
 			},
-			None => return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
+			None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
 		};
 		for passive_object_no in passive_objects_vec {
 			let object_header = match self.object_reader.get(&passive_object_no) {
@@ -917,7 +2508,7 @@ impl<R: Read + Seek> Read for ZffReader<R> {
 		}
 		let object_reader = match self.object_reader.get_mut(&self.active_object) {
 			Some(object_reader) => object_reader,
-			None => return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
+			None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
 		};
 		match object_reader {
 			ZffObjectReader::Virtual(reader) => { 
@@ -933,7 +2524,7 @@ impl<R: Read + Seek> Seek for ZffReader<R> {
 	fn seek(&mut self, seek_from: SeekFrom) -> std::result::Result<u64, std::io::Error> {
 		let object_reader = match self.object_reader.get_mut(&self.active_object) {
 			Some(object_reader) => object_reader,
-			None => return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
+			None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{ERROR_ZFFREADER_MISSING_OBJECT}{}", self.active_object)))
 		};
 		object_reader.seek(seek_from)
 	}
@@ -1008,48 +2599,70 @@ fn try_find_footer<R: Read + Seek>(reader: &mut R) -> Result<Footer> {
 	}
 }
 
+// returns the data of a chunk of a missing segment (zeros, if zero filling is enabled) or the appropriate error.
+fn missing_chunk_data(chunk_number: u64, chunk_length: u64, zero_fill: bool) -> Result<Vec<u8>> {
+	if zero_fill {
+		Ok(vec![0; chunk_length as usize])
+	} else {
+		Err(ZffError::new(ZffErrorKind::MissingChunk(chunk_number), format!("{ERROR_ZFFREADER_MISSING_CHUNK}{chunk_number}")))
+	}
+}
+
+// returns the (decoded) data of the given chunk. Duplicated chunks will be resolved by using the global chunkmap,
+// so the original chunk could be stored in an other segment.
+#[allow(clippy::too_many_arguments)]
 fn get_chunk_data<C, R>(
-	segment: &mut Segment<R>, 
+	segments: &mut HashMap<u64, Segment<R>>,
+	global_chunkmap: &BTreeMap<u64, u64>,
 	current_chunk_number: u64, 
 	enc_information: &Option<EncryptionInformation>,
-	compression_algorithm: C,
+	compression_header: C,
 	original_chunk_size: u64, // size of the uncompressed data
 	chunk_offset: Option<u64>,
 	chunk_size: Option<u64>, // size of the compressed chunk
 	chunk_flags: Option<ChunkFlags>,
+	chunk_cache: &ChunkCache,
 	) -> std::result::Result<Vec<u8>, std::io::Error>
 where
-	C: Borrow<CompressionAlgorithm> + std::marker::Copy,
+	C: Borrow<CompressionHeader> + std::marker::Copy,
 	R: Read + Seek
 {
-	let chunk_content = match segment.chunk_data(
+	if let Some(data) = chunk_cache.get(current_chunk_number) {
+		return Ok(data);
+	}
+	let segment = match get_segment_of_chunk_no(current_chunk_number, global_chunkmap).and_then(|segment_no| segments.get_mut(&segment_no)) {
+		Some(segment) => segment,
+		None => return Err(ZffError::new(
+			ZffErrorKind::MissingChunk(current_chunk_number), format!("{ERROR_ZFFREADER_MISSING_CHUNK}{current_chunk_number}")).into()),
+	};
+	let chunk_content = segment.chunk_data(
 		current_chunk_number, 
 		enc_information, 
-		compression_algorithm, 
+		compression_header, 
 		chunk_offset,
 		chunk_size,
-		chunk_flags.clone()) {
-		Ok(data) => data,
-		Err(e) => match e.unwrap_kind() {
-			ZffErrorKind::IoError(io_error) => return Err(io_error),
-			error => return Err(std::io::Error::new(std::io::ErrorKind::Other, error.to_string())) 
-		},
-	};
-	match chunk_content {
-		ChunkContent::Raw(data) => Ok(data),
-		ChunkContent::SameBytes(single_byte) => Ok(vec![single_byte; original_chunk_size as usize]),
+		chunk_flags.clone(),
+		original_chunk_size)?;
+	let data = match chunk_content {
+		ChunkContent::Raw(data) => data,
+		ChunkContent::SameBytes(single_byte) => vec![single_byte; original_chunk_size as usize],
+		// the chunk maps of the original chunk have to be read from the appropriate segment.
 		ChunkContent::Duplicate(dup_chunk_no) => {
 			get_chunk_data(
-				segment, 
+				segments,
+				global_chunkmap,
 				dup_chunk_no, 
 				enc_information, 
-				compression_algorithm,
+				compression_header,
 				original_chunk_size, 
-				chunk_offset,
-				chunk_size,
-				chunk_flags)
+				None,
+				None,
+				None,
+				chunk_cache)?
 		}
-	}
+	};
+	chunk_cache.insert(current_chunk_number, &data);
+	Ok(data)
 }
 
 
@@ -1087,7 +2700,7 @@ fn initialize_object_reader<R: Read + Seek>(
 	};
 
 	match segments.get_mut(segment_no_header) {
-		None => Err(ZffError::new(ZffErrorKind::MissingSegment, segment_no_header.to_string())),
+		None => Err(ZffError::new(ZffErrorKind::MissingSegment(*segment_no_header), segment_no_header.to_string())),
 		Some(segment) => if segment.read_object_header(object_number).is_ok() {
 							initialize_unencrypted_object_reader(
 								object_number,
@@ -1116,19 +2729,30 @@ fn initialize_unencrypted_object_reader<R: Read + Seek>(
 	#[cfg(feature = "log")]
 	debug!("Initialize unencrypted object reader for object {}", obj_number);
 	let header = match segments.get_mut(&header_segment_no) {
-		None => return Err(ZffError::new(ZffErrorKind::MissingSegment, header_segment_no.to_string())),
+		None => return Err(ZffError::new(ZffErrorKind::MissingSegment(header_segment_no), header_segment_no.to_string())),
 		Some(segment) => segment.read_object_header(obj_number)?,
 	};
 	
 	let footer = match segments.get_mut(&footer_segment_no) {
-		None => return Err(ZffError::new(ZffErrorKind::MissingSegment, header_segment_no.to_string())),
+		None => return Err(ZffError::new(ZffErrorKind::MissingSegment(footer_segment_no), footer_segment_no.to_string())),
 		Some(segment) => segment.read_object_footer(obj_number)?,
 	};
 
 	let obj_reader = match footer {
 		ObjectFooter::Physical(physical) => ZffObjectReader::Physical(Box::new(ZffObjectReaderPhysical::with_obj_metadata(header, physical, global_chunkmap))),
 		ObjectFooter::Logical(logical) => ZffObjectReader::Logical(Box::new(ZffObjectReaderLogical::with_obj_metadata_recommended(header, logical, segments, global_chunkmap)?)),
-		ObjectFooter::Virtual(virt) => ZffObjectReader::Virtual(Box::new(ZffObjectReaderVirtual::with_data(header, virt, global_chunkmap))),
+		ObjectFooter::Virtual(virt) => {
+			let virtual_object_map = match segments.get_mut(&virt.virtual_object_map_segment_no) {
+				None => return Err(ZffError::new(ZffErrorKind::MissingSegment(virt.virtual_object_map_segment_no), virt.virtual_object_map_segment_no.to_string())),
+				Some(segment) => {
+					segment.seek(SeekFrom::Start(virt.virtual_object_map_offset))?;
+					VirtualObjectMap::decode_directly(segment)?
+				},
+			};
+			let mut reader = ZffObjectReaderVirtual::with_data(header, virt, global_chunkmap);
+			reader.fill_object_map(virtual_object_map.offsetmaps);
+			ZffObjectReader::Virtual(Box::new(reader))
+		},
 	};
 	Ok(obj_reader)
 }
@@ -1142,11 +2766,11 @@ fn initialize_encrypted_object_reader<R: Read + Seek>(
 	) -> Result<ZffObjectReader> {
 
 	let header = match segments.get_mut(&header_segment_no) {
-		None => return Err(ZffError::new(ZffErrorKind::MissingSegment, header_segment_no.to_string())),
+		None => return Err(ZffError::new(ZffErrorKind::MissingSegment(header_segment_no), header_segment_no.to_string())),
 		Some(segment) => segment.read_encrypted_object_header(obj_number)?,
 	};
 	let footer = match segments.get_mut(&footer_segment_no) {
-		None => return Err(ZffError::new(ZffErrorKind::MissingSegment, header_segment_no.to_string())),
+		None => return Err(ZffError::new(ZffErrorKind::MissingSegment(footer_segment_no), footer_segment_no.to_string())),
 		Some(segment) => segment.read_encrypted_object_footer(obj_number)?,
 	};
 	let obj_reader = ZffObjectReader::Encrypted(
@@ -1282,6 +2906,40 @@ fn extract_samebyte_from_preloaded_chunkmap(preloaded_chunkmap: &PreloadedChunkM
 	}
 }
 
+// compares the integrity hash of the given chunk data with the appropriate preloaded xxhash value.
+// The algorithm of the integrity hash is determined by the preloaded chunk flags.
+// The chunk of an empty file contains no data and is stored with the xxhash value 0.
+fn check_chunk_integrity(chunk_number: u64, chunk_data: &[u8], preloaded_chunkmap: &PreloadedChunkMaps) -> std::result::Result<(), std::io::Error> {
+	let xxhash = match extract_xxhash_from_preloaded_chunkmap(preloaded_chunkmap, chunk_number) {
+		Some(xxhash) => xxhash,
+		None => return Err(std::io::Error::new(
+			std::io::ErrorKind::NotFound, format!("{ERROR_ZFFREADER_MISSING_CHUNK_XXHASH}{chunk_number}"))),
+	};
+	let integrity = extract_flags_from_preloaded_chunkmap(preloaded_chunkmap, chunk_number).unwrap_or_default().integrity();
+	if (chunk_data.is_empty() && xxhash == 0) || integrity.calculate(chunk_data) == xxhash {
+		Ok(())
+	} else {
+		Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{ERROR_ZFFREADER_CHUNK_INTEGRITY}{chunk_number}")))
+	}
+}
+
+// tries to extract the appropriate xxhash of the given chunk number.
+// returns a None in case of error or if the chunkmap is a [PreloadedChunkmaps::None].
+fn extract_xxhash_from_preloaded_chunkmap(preloaded_chunkmap: &PreloadedChunkMaps, chunk_number: u64) -> Option<u64> {
+	match preloaded_chunkmap {
+		PreloadedChunkMaps::None => None,
+		PreloadedChunkMaps::InMemory(preloaded_maps) => {
+			preloaded_maps.xxhashs.get(&chunk_number).copied()
+		},
+		PreloadedChunkMaps::Redb(db) => {
+			let read_txn = db.begin_read().ok()?;
+    		let table = read_txn.open_table(PRELOADED_CHUNK_XXHASH_MAP_TABLE).ok()?;
+    		let value = table.get(&chunk_number).ok()??.value();
+    		Some(value)
+		}
+	}
+}
+
 fn get_chunks_of_unencrypted_object(object_reader: &HashMap<u64, ZffObjectReader>, object_number: u64) -> Result<Vec<u64>> {
 	let obj_reader = match object_reader.get(&object_number) {
 		Some(reader) => reader,
@@ -1321,6 +2979,69 @@ fn get_chunks_of_unencrypted_object(object_reader: &HashMap<u64, ZffObjectReader
 	Ok(chunk_numbers)
 }
 
+// reads and decodes the (unencrypted) chunk maps of the given type, which are listed in the given main footer map, from the segments.
+fn read_unencrypted_chunk_maps<M, R>(
+	segments: &mut HashMap<u64, Segment<R>>,
+	maps: &BTreeMap<u64, u64>,
+	map_table: fn(&SegmentFooter) -> &BTreeMap<u64, u64>,
+	missing_map_error: &str) -> Result<BTreeMap<u64, M::Value>>
+where
+	M: ChunkMap + HeaderCoding<Item = M>,
+	R: Read + Seek,
+{
+	let mut chunkmap = BTreeMap::new();
+	for (map_chunk_number, segment_number) in maps {
+		let segment = match segments.get_mut(segment_number) {
+			Some(segment) => segment,
+			None => return Err(ZffError::new(ZffErrorKind::MissingSegment(*segment_number), ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
+		};
+		let offset = match map_table(segment.footer()).get(map_chunk_number) {
+			Some(offset) => *offset,
+			None => return Err(ZffError::new(
+				ZffErrorKind::MalformedSegment, format!("{missing_map_error}{map_chunk_number}"))),
+		};
+		segment.seek(SeekFrom::Start(offset))?;
+		chunkmap.extend(M::decode_directly(segment)?.flush());
+	}
+	Ok(chunkmap)
+}
+
+// returns the description header of the given object (of encrypted objects only, if the description is stored unencrypted).
+fn object_description(object_reader: &ZffObjectReader) -> Option<&DescriptionHeader> {
+	match object_reader {
+		ZffObjectReader::Physical(reader) => Some(&reader.object_header_ref().description_header),
+		ZffObjectReader::Logical(reader) => Some(&reader.object_header_ref().description_header),
+		ZffObjectReader::Virtual(reader) => Some(&reader.object_header_ref().description_header),
+		ZffObjectReader::Encrypted(reader) => reader.encrypted_header_ref().description_header(),
+	}
+}
+
+// verifies the signature of the given hash value (returns false, if the hash value has no signature).
+fn verify_hash_value_signature(key: &[u8], hash_value: &HashValue) -> Result<bool> {
+	match hash_value.ed25519_signature() {
+		Some(signature) => Signature::verify(key, hash_value.hash(), signature),
+		None => Ok(false),
+	}
+}
+
+// returns the number of the segment following the highest available segment.
+// If no segment contains the main footer, at least this segment has to be missing.
+fn next_segment_number<R: Read + Seek>(segments: &HashMap<u64, Segment<R>>) -> u64 {
+	segments.keys().max().map_or(1, |segment_number| segment_number + 1)
+}
+
+// returns the error for a missing main footer, which contains the expected (last) segment number and the supplied segment numbers.
+fn missing_main_footer_error<R: Read + Seek>(segments: &HashMap<u64, Segment<R>>) -> ZffError {
+	let expected_segment_number = next_segment_number(segments);
+	let mut supplied_segment_numbers = segments.keys().copied().collect::<Vec<_>>();
+	supplied_segment_numbers.sort_unstable();
+	let supplied_segment_numbers = supplied_segment_numbers.iter().map(|segment_number| segment_number.to_string()).collect::<Vec<_>>().join(", ");
+	ZffError::new(
+		ZffErrorKind::MissingSegment(expected_segment_number),
+		format!("{ERROR_MISSING_SEGMENT_MAIN_FOOTER}{ERROR_MISSING_SEGMENT_MAIN_FOOTER_EXPECTED_SEGMENT}{expected_segment_number}\
+			{ERROR_MISSING_SEGMENT_MAIN_FOOTER_SUPPLIED_SEGMENTS}[{supplied_segment_numbers}]"))
+}
+
 fn get_enc_info_from_obj_reader(object_reader: &ZffObjectReader) -> Result<Option<EncryptionInformation>> {
 		let enc_info = match object_reader {
 		ZffObjectReader::Physical(reader) => EncryptionInformation::try_from(reader.object_header_ref()),
@@ -1336,4 +3057,27 @@ fn get_enc_info_from_obj_reader(object_reader: &ZffObjectReader) -> Result<Optio
 		},
 	};
 	Ok(enc_info)
-}
\ No newline at end of file
+}
+
+// A chunk, which should be verified by a worker thread of [ZffReader::verify_all_with_threads].
+struct ChunkVerificationJob {
+	chunk_number: u64, // the number of the chunk to verify
+	data_chunk_number: u64, // the number of the chunk which contains the data (differs for duplicated chunks)
+	raw_data: Vec<u8>,
+	flags: ChunkFlags,
+	xxhash: u64, // the stored xxhash value
+}
+
+impl ChunkVerificationJob {
+	// decrypts and decompresses the data and compares the xxhash values.
+	// returns false, if the chunk is corrupt (or could not be decoded).
+	fn verify(self, enc_info: &Option<EncryptionInformation>, compression_header: &CompressionHeader, chunk_size: u64) -> bool {
+		let data = match decode_chunk_content(self.raw_data, self.data_chunk_number, enc_info, compression_header, &self.flags, chunk_size) {
+			Ok(ChunkContent::Raw(data)) => data,
+			Ok(ChunkContent::SameBytes(single_byte)) => vec![single_byte; chunk_size as usize],
+			Ok(ChunkContent::Duplicate(_)) | Err(_) => return false,
+		};
+		let size = data.len() as u64;
+		Chunk::new(data, self.flags, size).check_integrity(self.xxhash).unwrap_or(false)
+	}
+}
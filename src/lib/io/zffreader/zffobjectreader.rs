@@ -34,6 +34,60 @@ pub enum ZffObjectReader {
 	Encrypted(Box<ZffObjectReaderEncrypted>),
 }
 
+impl ZffObjectReader {
+	/// Enables or disables the integrity check (xxhash) of each chunk while reading (has no effect for encrypted objects).
+	pub(crate) fn set_verify_on_read(&mut self, verify: bool) {
+		match self {
+			ZffObjectReader::Physical(reader) => reader.verify_on_read = verify,
+			ZffObjectReader::Logical(reader) => reader.verify_on_read = verify,
+			ZffObjectReader::Virtual(reader) => reader.verify_on_read = verify,
+			ZffObjectReader::Encrypted(_) => (),
+		}
+	}
+
+	/// Returns the size of the (uncompressed) data of the object.  
+	/// For logical objects, the sum of the data sizes (see [FileMetadata]) of all files will be returned
+	/// (incl. the stored data of directories and links).
+	pub(crate) fn data_size(&self) -> Result<u64> {
+		match self {
+			ZffObjectReader::Physical(reader) => Ok(reader.object_footer.length_of_data),
+			ZffObjectReader::Logical(reader) => Ok(reader.files.values().map(|metadata| metadata.length_of_data).sum()),
+			ZffObjectReader::Virtual(reader) => Ok(reader.object_footer.length_of_data),
+			ZffObjectReader::Encrypted(_) => Err(ZffError::new(ZffErrorKind::MismatchObjectType, ERROR_ZFFREADER_OPERATION_ENCRYPTED_OBJECT)),
+		}
+	}
+
+	/// Returns a reference to the [ObjectHeader] of the object (or None, if the object is encrypted).
+	pub(crate) fn object_header_ref(&self) -> Option<&ObjectHeader> {
+		match self {
+			ZffObjectReader::Physical(reader) => Some(reader.object_header_ref()),
+			ZffObjectReader::Logical(reader) => Some(reader.object_header_ref()),
+			ZffObjectReader::Virtual(reader) => Some(reader.object_header_ref()),
+			ZffObjectReader::Encrypted(_) => None,
+		}
+	}
+
+	/// Enables or disables the zero filling of chunks of missing segments (has no effect for encrypted objects).
+	pub(crate) fn set_zero_fill_missing_chunks(&mut self, zero_fill: bool) {
+		match self {
+			ZffObjectReader::Physical(reader) => reader.zero_fill_missing_chunks = zero_fill,
+			ZffObjectReader::Logical(reader) => reader.zero_fill_missing_chunks = zero_fill,
+			ZffObjectReader::Virtual(reader) => reader.zero_fill_missing_chunks = zero_fill,
+			ZffObjectReader::Encrypted(_) => (),
+		}
+	}
+
+	/// Sets the (shared) cache for the decoded chunk data (has no effect for encrypted objects).
+	pub(crate) fn set_chunk_cache(&mut self, chunk_cache: ChunkCache) {
+		match self {
+			ZffObjectReader::Physical(reader) => reader.chunk_cache = chunk_cache,
+			ZffObjectReader::Logical(reader) => reader.chunk_cache = chunk_cache,
+			ZffObjectReader::Virtual(reader) => reader.chunk_cache = chunk_cache,
+			ZffObjectReader::Encrypted(_) => (),
+		}
+	}
+}
+
 impl Seek for ZffObjectReader {
 	fn seek(&mut self, seek_from: std::io::SeekFrom) -> std::result::Result<u64, std::io::Error> {
 		match self {
@@ -52,7 +106,10 @@ pub struct ZffObjectReaderPhysical {
 	object_header: ObjectHeader,
 	object_footer: ObjectFooterPhysical,
 	global_chunkmap: Arc<BTreeMap<u64, u64>>,
-	position: u64
+	position: u64,
+	verify_on_read: bool, // checks the integrity (xxhash) of each read chunk
+	zero_fill_missing_chunks: bool, // fills chunks of missing segments with zeros
+	chunk_cache: ChunkCache, // caches the decoded data of recently read chunks
 }
 
 impl ZffObjectReaderPhysical {
@@ -66,7 +123,10 @@ impl ZffObjectReaderPhysical {
 			object_header,
 			object_footer,
 			global_chunkmap,
-			position: 0
+			position: 0,
+			verify_on_read: false,
+			zero_fill_missing_chunks: false,
+			chunk_cache: ChunkCache::default(),
 		}
 	}
 
@@ -92,23 +152,24 @@ impl ZffObjectReaderPhysical {
 		segments: &mut HashMap<u64, Segment<R>>,
 		preloaded_chunkmaps: &PreloadedChunkMaps,
 		) -> std::result::Result<usize, std::io::Error> {
+		// nothing to read at (or beyond) the end of the data.
+		if self.position >= self.object_footer.length_of_data {
+			return Ok(0);
+		}
 		let chunk_size = self.object_header.chunk_size;
 		let first_chunk_number = self.object_footer.first_chunk_number;
 		let last_chunk_number = first_chunk_number + self.object_footer.number_of_chunks - 1;
 		let mut current_chunk_number = (first_chunk_number * chunk_size + self.position) / chunk_size;
 		let mut inner_position = (self.position % chunk_size) as usize; // the inner chunk position
 		let mut read_bytes = 0; // number of bytes which are written to buffer
-		let compression_algorithm = &self.object_header.compression_header.algorithm;
+		let compression_header = &self.object_header.compression_header;
 
 		loop {
 			if read_bytes == buffer.len() || current_chunk_number > last_chunk_number {
 				break;
 			}
-			let segment = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
-				Some(segment_no) => match segments.get_mut(&segment_no) {
-					Some(segment) => segment,
-					None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
-				},
+			let segment_available = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
+				Some(segment_no) => segments.contains_key(&segment_no),
 				None => break,
 			};
 			let enc_information = EncryptionInformation::try_from(&self.object_header).ok();
@@ -119,16 +180,28 @@ impl ZffObjectReaderPhysical {
 			
 			let chunk_data = if let Some(samebyte) = preloaded_chunkmaps.get_samebyte(current_chunk_number) {
 				vec![samebyte; chunk_size as usize]
+			} else if segment_available {
+				let chunk_data = get_chunk_data(
+					segments,
+					&self.global_chunkmap,
+					current_chunk_number, 
+					&enc_information, 
+					compression_header, 
+					chunk_size,
+					optional_chunk_offset,
+					optional_chunk_size,
+					optional_chunk_flags,
+					&self.chunk_cache)?;
+				if self.verify_on_read {
+					check_chunk_integrity(current_chunk_number, &chunk_data, preloaded_chunkmaps)?;
+				}
+				chunk_data
 			} else {
-				get_chunk_data(
-				segment, 
-				current_chunk_number, 
-				&enc_information, 
-				compression_algorithm, 
-				chunk_size,
-				optional_chunk_offset,
-				optional_chunk_size,
-				optional_chunk_flags)?
+				// the last chunk could be smaller than the chunk size.
+				let chunk_length = self.object_footer.length_of_data
+					.saturating_sub((current_chunk_number - first_chunk_number) * chunk_size)
+					.min(chunk_size);
+				missing_chunk_data(current_chunk_number, chunk_length, self.zero_fill_missing_chunks)?
 			};
 			let mut cursor = Cursor::new(&chunk_data[inner_position..]);
 			read_bytes += cursor.read(&mut buffer[read_bytes..])?;
@@ -139,6 +212,104 @@ impl ZffObjectReaderPhysical {
 		self.position += read_bytes as u64;
 		Ok(read_bytes)
 	}
+
+	/// Reads the first bytes (up to the given length) of the object. The position of the reader will not be changed.
+	pub(crate) fn first_bytes<R: Read + Seek>(
+		&mut self,
+		length: usize,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		) -> std::result::Result<Vec<u8>, std::io::Error> {
+		self.read_range(0, length, segments, preloaded_chunkmaps)
+	}
+
+	/// Writes the complete data of the object (up to the length of the data) to the given writer
+	/// and returns the number of written bytes. The position of the reader will not be changed.  
+	/// The optional progress callback will be called with the number of written bytes after each chunk.
+	pub(crate) fn export<R: Read + Seek, W: Write>(
+		&self,
+		output: &mut W,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		mut progress: Option<&mut dyn FnMut(u64)>,
+		) -> Result<u64> {
+		let chunk_size = self.object_header.chunk_size;
+		let length_of_data = self.object_footer.length_of_data;
+		let first_chunk_number = self.object_footer.first_chunk_number;
+		let last_chunk_number = first_chunk_number + self.object_footer.number_of_chunks - 1;
+		let compression_header = &self.object_header.compression_header;
+		let enc_information = EncryptionInformation::try_from(&self.object_header).ok();
+		let mut written_bytes = 0;
+
+		for current_chunk_number in first_chunk_number..=last_chunk_number {
+			if written_bytes >= length_of_data {
+				break;
+			}
+			let segment_available = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
+				Some(segment_no) => segments.contains_key(&segment_no),
+				None => return Err(ZffError::new(ZffErrorKind::MissingChunk(current_chunk_number), ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
+			};
+
+			let chunk_data = if let Some(samebyte) = preloaded_chunkmaps.get_samebyte(current_chunk_number) {
+				vec![samebyte; chunk_size as usize]
+			} else if segment_available {
+				let chunk_data = get_chunk_data(
+					segments,
+					&self.global_chunkmap,
+					current_chunk_number,
+					&enc_information,
+					compression_header,
+					chunk_size,
+					extract_offset_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number),
+					extract_size_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number),
+					extract_flags_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number),
+					&self.chunk_cache);
+				let chunk_data = match chunk_data {
+					Ok(data) => data,
+					Err(e) => {
+						let details = format!("{ERROR_ZFFREADER_CHUNK_DATA}{current_chunk_number}: {e}");
+						return Err(ZffError::new(ZffErrorKind::IoError(e), details));
+					},
+				};
+				if self.verify_on_read {
+					check_chunk_integrity(current_chunk_number, &chunk_data, preloaded_chunkmaps)?;
+				}
+				chunk_data
+			} else {
+				let chunk_length = length_of_data
+					.saturating_sub((current_chunk_number - first_chunk_number) * chunk_size)
+					.min(chunk_size);
+				missing_chunk_data(current_chunk_number, chunk_length, self.zero_fill_missing_chunks)?
+			};
+			let remaining_bytes = (length_of_data - written_bytes).min(chunk_data.len() as u64) as usize;
+			output.write_all(&chunk_data[..remaining_bytes])?;
+			written_bytes += remaining_bytes as u64;
+			if let Some(callback) = progress.as_mut() {
+				callback(written_bytes);
+			}
+		}
+		Ok(written_bytes)
+	}
+
+	/// Reads up to the given length of bytes, starting at the given offset of the object.
+	/// The position of the reader will not be changed.
+	pub(crate) fn read_range<R: Read + Seek>(
+		&mut self,
+		offset: u64,
+		length: usize,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		) -> std::result::Result<Vec<u8>, std::io::Error> {
+		// the buffer should never be larger than the remaining data of the object.
+		let length = std::cmp::min(length as u64, self.object_footer.length_of_data.saturating_sub(offset)) as usize;
+		let previous_position = self.position;
+		self.position = offset;
+		let mut buffer = vec![0u8; length];
+		let read_bytes = self.read_with_segments(&mut buffer, segments, preloaded_chunkmaps);
+		self.position = previous_position;
+		buffer.truncate(read_bytes?);
+		Ok(buffer)
+	}
 }
 
 impl Seek for ZffObjectReaderPhysical {
@@ -175,6 +346,9 @@ pub struct ZffObjectReaderLogical {
 	active_file: u64, // filenumber of active file
 	files: HashMap<u64, FileMetadata>,//<filenumber, metadata>,
 	global_chunkmap: Arc<BTreeMap<u64, u64>>,
+	verify_on_read: bool, // checks the integrity (xxhash) of each read chunk
+	zero_fill_missing_chunks: bool, // fills chunks of missing segments with zeros
+	chunk_cache: ChunkCache, // caches the decoded data of recently read chunks
 }
 
 impl ZffObjectReaderLogical {
@@ -235,12 +409,12 @@ impl ZffObjectReaderLogical {
 				Some(key) => key,
 				None => return Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, "")),
 			};
-			Some(EncryptionInformation::new(key, encryption_header.algorithm.clone()))
+			Some(EncryptionInformation::new(key, encryption_header.algorithm.clone(), encryption_header.nonce_extension))
 		} else {
 			None
 		};
 		match segments.get_mut(header_segment_number) {
-			None => Err(ZffError::new(ZffErrorKind::MissingSegment, "")),
+			None => Err(ZffError::new(ZffErrorKind::MissingSegment(*header_segment_number), header_segment_number.to_string())),
 			Some(segment) => {
 				segment.seek(SeekFrom::Start(*header_offset))?;
 				//check encryption
@@ -270,12 +444,12 @@ impl ZffObjectReaderLogical {
 				Some(key) => key,
 				None => return Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, "")),
 			};
-			Some(EncryptionInformation::new(key, encryption_header.algorithm.clone()))
+			Some(EncryptionInformation::new(key, encryption_header.algorithm.clone(), encryption_header.nonce_extension))
 		} else {
 			None
 		};
 		match segments.get_mut(footer_segment_number) {
-			None => Err(ZffError::new(ZffErrorKind::MissingSegment, "")),
+			None => Err(ZffError::new(ZffErrorKind::MissingSegment(*footer_segment_number), footer_segment_number.to_string())),
 			Some(segment) => {
 				segment.seek(SeekFrom::Start(*footer_offset))?;
 				//check encryption
@@ -303,7 +477,7 @@ impl ZffObjectReaderLogical {
 				Some(key) => key,
 				None => return Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, "")),
 			};
-			Some(EncryptionInformation::new(key, encryption_header.algorithm.clone()))
+			Some(EncryptionInformation::new(key, encryption_header.algorithm.clone(), encryption_header.nonce_extension))
 		} else {
 			None
 		};
@@ -327,7 +501,7 @@ impl ZffObjectReaderLogical {
 			};
 
 			let fileheader = match segments.get_mut(header_segment_number) {
-				None => return Err(ZffError::new(ZffErrorKind::MissingSegment, "")),
+				None => return Err(ZffError::new(ZffErrorKind::MissingSegment(*header_segment_number), header_segment_number.to_string())),
 				Some(segment) => {
 					segment.seek(SeekFrom::Start(*header_offset))?;
 					//check encryption
@@ -340,7 +514,7 @@ impl ZffObjectReaderLogical {
 			};
 
 			let filefooter = match segments.get_mut(footer_segment_number) {
-				None => return Err(ZffError::new(ZffErrorKind::MissingSegment, "")),
+				None => return Err(ZffError::new(ZffErrorKind::MissingSegment(*footer_segment_number), footer_segment_number.to_string())),
 				Some(segment) => {
 					segment.seek(SeekFrom::Start(*footer_offset))?;
 					//check encryption
@@ -362,13 +536,41 @@ impl ZffObjectReaderLogical {
 		#[cfg(feature = "log")]
 		debug!("{} files were successfully initialized for logical object {}.", files.len(), object_header.object_number);
 
-		Ok(Self {
+		let mut reader = Self {
 			object_header,
 			object_footer,
 			active_file: 1,
 			files,
 			global_chunkmap: Arc::clone(&global_chunkmap),
-		})
+			verify_on_read: false,
+			zero_fill_missing_chunks: false,
+			chunk_cache: ChunkCache::default(),
+		};
+		reader.resolve_hardlink_targets(segments);
+		Ok(reader)
+	}
+
+	// reads the file number of the hardlinked file, which is stored as the data of each hardlink, and sets the
+	// appropriate hardlink target. If the data can not be read (e.g. due to a missing segment), the target remains None.
+	fn resolve_hardlink_targets<R: Read + Seek>(&mut self, segments: &mut HashMap<u64, Segment<R>>) {
+		let hardlinks: Vec<u64> = self.files.iter()
+			.filter(|(_, metadata)| metadata.file_type == FileType::Hardlink)
+			.map(|(filenumber, _)| *filenumber)
+			.collect();
+		for filenumber in hardlinks {
+			let mut encoded_target = Vec::new();
+			let hardlink_target = match self.extract_file(filenumber, &mut encoded_target, segments, &PreloadedChunkMaps::None) {
+				Ok(_) => u64::decode_directly(&mut encoded_target.as_slice()).ok(),
+				Err(_e) => {
+					#[cfg(feature = "log")]
+					debug!("Could not read the hardlink target of file {filenumber}: {_e}");
+					None
+				}
+			};
+			if let Some(metadata) = self.files.get_mut(&filenumber) {
+				metadata.hardlink_target = hardlink_target;
+			}
+		}
 	}
 
 	/// Sets the file of the appropriate filenumber active.
@@ -413,7 +615,7 @@ impl ZffObjectReaderLogical {
 			Some(metadata) => metadata,
 			None => return Err(
 				std::io::Error::new(
-					std::io::ErrorKind::Other, 
+					std::io::ErrorKind::NotFound, 
 					format!("{ERROR_MISSING_FILE_NUMBER}{}", self.active_file))),
 		};
 
@@ -423,16 +625,13 @@ impl ZffObjectReaderLogical {
 		let mut current_chunk_number = (first_chunk_number * chunk_size + active_filemetadata.position) / chunk_size;
 		let mut inner_position = (active_filemetadata.position % chunk_size) as usize; // the inner chunk position
 		let mut read_bytes = 0; // number of bytes which are written to buffer
-		let compression_algorithm = &self.object_header.compression_header.algorithm;
+		let compression_header = &self.object_header.compression_header;
 		loop {
 			if read_bytes == buffer.len() || current_chunk_number > last_chunk_number {
 				break;
 			}
-			let segment = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
-				Some(segment_no) => match segments.get_mut(&segment_no) {
-					Some(segment) => segment,
-					None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
-				},
+			let segment_available = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
+				Some(segment_no) => segments.contains_key(&segment_no),
 				None => break,
 			};
 			let enc_information = EncryptionInformation::try_from(&self.object_header).ok();
@@ -441,15 +640,29 @@ impl ZffObjectReaderLogical {
 			let optional_chunk_size = extract_size_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number);
 			let optional_chunk_flags = extract_flags_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number);
 
-			let chunk_data = get_chunk_data(
-				segment, 
-				current_chunk_number, 
-				&enc_information, 
-				compression_algorithm, 
-				chunk_size,
-				optional_chunk_offset,
-				optional_chunk_size,
-				optional_chunk_flags)?;
+			let chunk_data = if segment_available {
+				let chunk_data = get_chunk_data(
+					segments,
+					&self.global_chunkmap,
+					current_chunk_number, 
+					&enc_information, 
+					compression_header, 
+					chunk_size,
+					optional_chunk_offset,
+					optional_chunk_size,
+					optional_chunk_flags,
+					&self.chunk_cache)?;
+				if self.verify_on_read {
+					check_chunk_integrity(current_chunk_number, &chunk_data, preloaded_chunkmaps)?;
+				}
+				chunk_data
+			} else {
+				// the last chunk of the file could be smaller than the chunk size.
+				let chunk_length = active_filemetadata.length_of_data
+					.saturating_sub((current_chunk_number - first_chunk_number) * chunk_size)
+					.min(chunk_size);
+				missing_chunk_data(current_chunk_number, chunk_length, self.zero_fill_missing_chunks)?
+			};
 			let mut cursor = Cursor::new(&chunk_data[inner_position..]);
 			read_bytes += cursor.read(&mut buffer[read_bytes..])?;
 			inner_position = 0;
@@ -459,6 +672,97 @@ impl ZffObjectReaderLogical {
 		active_filemetadata.position += read_bytes as u64;
 		Ok(read_bytes)
 	}
+
+	/// Reads the first bytes (up to the given length) of the first regular file (the file with the lowest file number) of this object.
+	/// The active file and the reader positions will not be changed.
+	pub(crate) fn first_bytes<R: Read + Seek>(
+		&mut self,
+		length: usize,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		) -> Result<Vec<u8>> {
+		let first_file = match self.files.iter()
+			.filter(|(_, metadata)| metadata.file_type == FileType::File)
+			.map(|(filenumber, _)| *filenumber)
+			.min() {
+			Some(filenumber) => filenumber,
+			None => return Err(ZffError::new(ZffErrorKind::NoFilesLeft, self.object_header.object_number.to_string())),
+		};
+		let previous_active_file = self.active_file;
+		self.active_file = first_file;
+		let read_range = self.read_range(0, length, segments, preloaded_chunkmaps);
+		self.active_file = previous_active_file;
+		read_range
+	}
+
+	/// Reads up to the given length of bytes of the active file, starting at the given offset.
+	/// The position of the reader will not be changed.
+	pub(crate) fn read_range<R: Read + Seek>(
+		&mut self,
+		offset: u64,
+		length: usize,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		) -> Result<Vec<u8>> {
+		// the buffer should never be larger than the remaining data of the active file.
+		let length = std::cmp::min(length as u64, self.filemetadata()?.length_of_data.saturating_sub(offset)) as usize;
+		let previous_position = self.stream_position()?;
+		self.seek(SeekFrom::Start(offset))?;
+
+		let mut buffer = vec![0u8; length];
+		let read_bytes = self.read_with_segments(&mut buffer, segments, preloaded_chunkmaps);
+
+		self.seek(SeekFrom::Start(previous_position))?;
+		buffer.truncate(read_bytes?);
+		Ok(buffer)
+	}
+
+	/// Writes the data of the file with the appropriate file number to the given writer and returns the number of written bytes.
+	/// Only the data up to the length of the file will be written (see [FileMetadata::length_of_data]).
+	/// The active file and the reader positions will not be changed.
+	pub(crate) fn extract_file<R: Read + Seek, W: Write>(
+		&mut self,
+		filenumber: u64,
+		output: &mut W,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		) -> Result<u64> {
+		let length_of_data = match self.files.get(&filenumber) {
+			Some(metadata) => metadata.length_of_data,
+			None => return Err(ZffError::new(ZffErrorKind::MissingFileNumber, filenumber.to_string())),
+		};
+		let previous_active_file = self.active_file;
+		self.active_file = filenumber;
+		let previous_position = self.stream_position()?;
+		self.seek(SeekFrom::Start(0))?;
+
+		let result = self.copy_active_file_to(output, length_of_data, segments, preloaded_chunkmaps);
+
+		self.seek(SeekFrom::Start(previous_position))?;
+		self.active_file = previous_active_file;
+		result
+	}
+
+	fn copy_active_file_to<R: Read + Seek, W: Write>(
+		&mut self,
+		output: &mut W,
+		length_of_data: u64,
+		segments: &mut HashMap<u64, Segment<R>>,
+		preloaded_chunkmaps: &PreloadedChunkMaps,
+		) -> Result<u64> {
+		let mut buffer = vec![0u8; self.object_header.chunk_size as usize];
+		let mut written_bytes = 0;
+		while written_bytes < length_of_data {
+			let read_bytes = self.read_with_segments(&mut buffer, segments, preloaded_chunkmaps)?;
+			if read_bytes == 0 {
+				break;
+			}
+			let bytes_to_write = std::cmp::min(read_bytes as u64, length_of_data - written_bytes) as usize;
+			output.write_all(&buffer[..bytes_to_write])?;
+			written_bytes += bytes_to_write as u64;
+		}
+		Ok(written_bytes)
+	}
 }
 
 impl Seek for ZffObjectReaderLogical {
@@ -468,7 +772,7 @@ impl Seek for ZffObjectReaderLogical {
 			Some(metadata) => metadata,
 			None => return Err(
 				std::io::Error::new(
-					std::io::ErrorKind::Other, 
+					std::io::ErrorKind::NotFound, 
 					format!("{ERROR_MISSING_FILE_NUMBER}{}", self.active_file))),
 		};
 
@@ -511,6 +815,12 @@ pub struct ZffObjectReaderVirtual {
 	position: u64,
 	/// The global chunkmap
 	global_chunkmap: Arc<BTreeMap<u64, u64>>,
+	/// checks the integrity (xxhash) of each read chunk
+	verify_on_read: bool,
+	/// fills chunks of missing segments with zeros
+	zero_fill_missing_chunks: bool,
+	/// caches the decoded data of recently read chunks
+	chunk_cache: ChunkCache,
 }
 
 impl ZffObjectReaderVirtual {
@@ -524,9 +834,12 @@ impl ZffObjectReaderVirtual {
 			object_header,
 			object_footer,
 			passive_object_header: BTreeMap::new(),
-			virtual_object_map: BTreeSet::new(), //TODO: Fill this map with the appropriate data :D !!!
+			virtual_object_map: BTreeSet::new(),
 			position: 0,
-			global_chunkmap: Arc::clone(&global_chunkmap)
+			global_chunkmap: Arc::clone(&global_chunkmap),
+			verify_on_read: false,
+			zero_fill_missing_chunks: false,
+			chunk_cache: ChunkCache::default(),
 		}
 	}
 
@@ -570,67 +883,72 @@ impl ZffObjectReaderVirtual {
 		
 		let mut read_bytes = 0; // number of bytes which are written to buffer
 		
-		'outer: loop {
+		while read_bytes < buffer.len() {
+			let position = self.position + read_bytes as u64;
+			if position >= self.object_footer.length_of_data {
+				break;
+			}
 			// find the appropriate mapping information.
-			let virtual_mapping_information = match get_vmi_info(
+			let (mapping_offset, virtual_mapping_information) = get_vmi_info(
 				&self.virtual_object_map, 
-				self.position, 
-				segments) {
-				Ok(mi) => mi,
-				Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
-			};
+				position, 
+				segments)?;
+			let offset_in_mapping = position - mapping_offset;
+			if offset_in_mapping >= virtual_mapping_information.length {
+				break;
+			}
 
 			let object_header = get_affected_object_header(&virtual_mapping_information, &self.passive_object_header)?;
 			let chunk_size = object_header.chunk_size;
-			let mut current_chunk_number = virtual_mapping_information.start_chunk_no;
-			let mut inner_position = virtual_mapping_information.chunk_offset as usize; // the inner chunk position
-			let mut remaining_offset_length = virtual_mapping_information.length as usize;
-			let compression_algorithm = &self.object_header.compression_header.algorithm;
-
-			loop {
-				if read_bytes == buffer.len() {
-					break 'outer;
-				}
-				let segment = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
-					Some(segment_no) => match segments.get_mut(&segment_no) {
-						Some(segment) => segment,
-						None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
-					},
-					None => break,
+			// the data has to be decompressed with the compression parameters of the passive object.
+			let compression_header = &object_header.compression_header;
+			let absolute_offset = virtual_mapping_information.chunk_offset + offset_in_mapping;
+			let mut current_chunk_number = virtual_mapping_information.start_chunk_no + absolute_offset / chunk_size;
+			let mut inner_position = (absolute_offset % chunk_size) as usize; // the inner chunk position
+			let mut remaining_offset_length = virtual_mapping_information.length - offset_in_mapping;
+
+			while remaining_offset_length > 0 && read_bytes < buffer.len() {
+				let segment_available = match get_segment_of_chunk_no(current_chunk_number, &self.global_chunkmap) {
+					Some(segment_no) => segments.contains_key(&segment_no),
+					None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{ERROR_ZFFREADER_CHUNK_DATA}{current_chunk_number}"))),
 				};
 				let enc_information = EncryptionInformation::try_from(object_header).ok();
 
-
 				let optional_chunk_offset = extract_offset_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number);
 				let optional_chunk_size = extract_size_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number);
 				let optional_chunk_flags = extract_flags_from_preloaded_chunkmap(preloaded_chunkmaps, current_chunk_number);
 
-				let chunk_data = get_chunk_data(
-					segment, 
-					current_chunk_number, 
-					&enc_information, 
-					compression_algorithm, 
-					chunk_size,
-					optional_chunk_offset,
-					optional_chunk_size,
-					optional_chunk_flags)?;
-				let mut should_break = false;
-				let mut cursor = if remaining_offset_length as u64 > chunk_data[inner_position..].len() as u64 {
-					Cursor::new(&chunk_data[inner_position..])
+				let chunk_data = if segment_available {
+					let chunk_data = get_chunk_data(
+						segments,
+						&self.global_chunkmap,
+						current_chunk_number, 
+						&enc_information, 
+						compression_header, 
+						chunk_size,
+						optional_chunk_offset,
+						optional_chunk_size,
+						optional_chunk_flags,
+						&self.chunk_cache)?;
+					if self.verify_on_read {
+						check_chunk_integrity(current_chunk_number, &chunk_data, preloaded_chunkmaps)?;
+					}
+					chunk_data
 				} else {
-					should_break = true;
-					Cursor::new(&chunk_data[inner_position..remaining_offset_length])
+					missing_chunk_data(current_chunk_number, chunk_size, self.zero_fill_missing_chunks)?
 				};
-				let read_bytes_at_round = cursor.read(&mut buffer[read_bytes..])?;
-				read_bytes += read_bytes_at_round;
-				remaining_offset_length -= read_bytes_at_round;
-				if should_break {
+				if inner_position >= chunk_data.len() {
 					break;
 				}
+				let bytes_to_copy = (chunk_data.len() - inner_position)
+					.min(buffer.len() - read_bytes)
+					.min(remaining_offset_length as usize);
+				buffer[read_bytes..read_bytes + bytes_to_copy].copy_from_slice(&chunk_data[inner_position..inner_position + bytes_to_copy]);
+				read_bytes += bytes_to_copy;
+				remaining_offset_length -= bytes_to_copy as u64;
 				inner_position = 0;
 				current_chunk_number += 1;
 			}
-
 		}
 
 		self.position += read_bytes as u64;
@@ -653,14 +971,17 @@ fn get_affected_object_header<'a>(
 fn get_vmi_info<R: Read + Seek>(
 	vmi_map: &BTreeSet<BTreeMap<u64, (u64, u64)>>, 
 	offset: u64,
-	segments: &mut HashMap<u64, Segment<R>>) -> Result<VirtualMappingInformation> {
-    let (segment_no, offset) = find_vmi_offset(vmi_map, offset).ok_or_else(|| ZffError::new(ZffErrorKind::ValueNotInMap, "VMI not found"))?;
-    let segment = match segments.get_mut(&segment_no) {
+	segments: &mut HashMap<u64, Segment<R>>) -> Result<(u64, VirtualMappingInformation)> {
+	let (mapping_offset, segment_no, vmi_offset) = match find_vmi_offset(vmi_map, offset) {
+		Some(values) => values,
+		None => return Err(ZffError::new(ZffErrorKind::ValueNotInMap, format!("{ERROR_ZFFREADER_VIRTUAL_OBJECT_MAP}{offset}"))),
+	};
+	let segment = match segments.get_mut(&segment_no) {
 		Some(segment) => segment,
-		None => return Err(ZffError::new(ZffErrorKind::MissingSegment, ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
+		None => return Err(ZffError::new(ZffErrorKind::MissingSegment(segment_no), ERROR_ZFFREADER_SEGMENT_NOT_FOUND)),
 	};
-	segment.seek(SeekFrom::Start(offset))?;
-	VirtualMappingInformation::decode_directly(segment)
+	segment.seek(SeekFrom::Start(vmi_offset))?;
+	Ok((mapping_offset, VirtualMappingInformation::decode_directly(segment)?))
 }
 
 impl Seek for ZffObjectReaderVirtual {
@@ -674,14 +995,14 @@ impl Seek for ZffObjectReaderVirtual {
 			} else if value >= 0 {
 					self.position += value as u64;
 			} else {
-				self.position -= value as u64;
+				self.position -= value.unsigned_abs();
 			},
-			SeekFrom::End(value) => if self.position as i64 + value < 0 {
+			SeekFrom::End(value) => if self.object_footer.length_of_data as i64 + value < 0 {
 				return Err(std::io::Error::new(std::io::ErrorKind::Other, ERROR_IO_NOT_SEEKABLE_NEGATIVE_POSITION))
 			} else if value >= 0 {
 					self.position = self.object_footer.length_of_data + value as u64;
 			} else {
-				self.position = self.object_footer.length_of_data - value as u64;
+				self.position = self.object_footer.length_of_data - value.unsigned_abs();
 			},
 		}
 		Ok(self.position)
@@ -717,6 +1038,11 @@ impl ZffObjectReaderEncrypted {
 		}
 	}
 
+	/// Returns a reference to the [EncryptedObjectHeader] of this object.
+	pub fn encrypted_header_ref(&self) -> &EncryptedObjectHeader {
+		&self.encrypted_header
+	}
+
 	/// Tries to decrypt the [ZffObjectReader] with the given parameters.
 	pub fn decrypt_with_password<P, R>(&mut self, password: P, segments: &mut HashMap<u64, Segment<R>>) -> Result<ZffObjectReader> 
 	where
@@ -727,7 +1053,7 @@ impl ZffObjectReaderEncrypted {
 
 		let enc_info = EncryptionInformation::try_from(&decrypted_object_header)?;
 
-		let decrypted_footer = self.encrypted_footer.decrypt(enc_info.encryption_key, enc_info.algorithm)?;
+		let decrypted_footer = self.encrypted_footer.decrypt(enc_info.encryption_key, enc_info.nonce_extension, enc_info.algorithm)?;
 
 		let obj_reader = match decrypted_footer {
 			ObjectFooter::Physical(physical) => ZffObjectReader::Physical(Box::new(
@@ -771,6 +1097,11 @@ pub struct FileMetadata {
 	pub acquisition_end: Option<u64>,
 	/// The appropriate hash header of the file. 
 	pub hash_header: Option<HashHeader>,
+	/// The file number of the file, which is hardlinked by this file (only available for files of the type [FileType::Hardlink]).
+	pub hardlink_target: Option<u64>,
+	/// The original path of the file, if it was stored by the [ZffWriter](crate::io::zffwriter::ZffWriter)
+	/// (see [ZffCreationParameters::original_paths](crate::io::ZffCreationParameters::original_paths)).
+	pub original_path: Option<PathBuf>,
 }
 
 impl FileMetadata {
@@ -784,6 +1115,7 @@ impl FileMetadata {
 	/// - the number of chunks
 	/// - the internally used reader position
 	/// - the filetype
+	/// - the hardlink target (will be set by the [ZffObjectReaderLogical], if the file is a hardlink)
 	pub fn with_header_minimal(fileheader: &FileHeader, filefooter: &FileFooter) -> Self {
 		Self {
 			parent_file_number: fileheader.parent_file_number,
@@ -797,6 +1129,8 @@ impl FileMetadata {
 			acquisition_start: None,
 			acquisition_end: None,
 			hash_header: None,
+			hardlink_target: None,
+			original_path: None,
 		}
 	}
 
@@ -812,6 +1146,8 @@ impl FileMetadata {
 	/// - the filetype
 	/// - the filename
 	/// - the metadata of the file
+	/// - the hardlink target (will be set by the [ZffObjectReaderLogical], if the file is a hardlink)
+	/// - the original path (if stored)
 	pub fn with_header_recommended(fileheader: &FileHeader, filefooter: &FileFooter) -> Self {
 		Self {
 			parent_file_number: fileheader.parent_file_number,
@@ -825,6 +1161,8 @@ impl FileMetadata {
 			acquisition_start: None,
 			acquisition_end: None,
 			hash_header: None,
+			hardlink_target: None,
+			original_path: fileheader.original_path(),
 		}
 	}
 
@@ -842,6 +1180,8 @@ impl FileMetadata {
 	/// - the metadata of the file
 	/// - the timestamps of start and end of the acquisition
 	/// - the appropriate hash header
+	/// - the hardlink target (will be set by the [ZffObjectReaderLogical], if the file is a hardlink)
+	/// - the original path (if stored)
 	pub fn with_header_all(fileheader: &FileHeader, filefooter: &FileFooter) -> Self {
 		Self {
 			parent_file_number: fileheader.parent_file_number,
@@ -855,6 +1195,8 @@ impl FileMetadata {
 			acquisition_start: Some(filefooter.acquisition_start),
 			acquisition_end: Some(filefooter.acquisition_end),
 			hash_header: Some(filefooter.hash_header.clone()),
+			hardlink_target: None,
+			original_path: fileheader.original_path(),
 		}
 	}
 }
\ No newline at end of file
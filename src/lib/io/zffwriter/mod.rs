@@ -1,14 +1,25 @@
+// - modules
+mod transcode;
+mod segmented_stream;
+
 // - STD
+use std::collections::BTreeSet;
 use std::fs::OpenOptions;
 use std::io::{Seek, SeekFrom, Write};
 use std::ops::{Add, AddAssign};
+use std::time::SystemTime;
 
 // Parent
 use super::*;
 
+// - re-exports
+pub use transcode::*;
+pub use segmented_stream::*;
+
 // - internal
 use crate::{
-    footer::SegmentFooter, header::{ChunkMapType, ChunkMaps, SegmentHeader, ChunkMap},
+    footer::{SegmentFooter, ObjectFooter}, header::{ChunkMapType, ChunkMaps, SegmentHeader, ChunkMap, ChunkParityBlock},
+    io::zffreader::ZffReader,
     Segment,
     HeaderCoding,
     ValueDecoder,
@@ -35,12 +46,14 @@ enum ReadState {
     ChunkXxHashMap,
     ChunkSamebytesMap,
     ChunkDeduplicationMap,
+    ChunkParityMap,
     LastChunkOffsetMapOfObject,
     LastChunkSizeMapOfObject,
     LastChunkFlagsMapOfObject,
     LastChunkXxHashMapOfObject,
     LastChunkSamebytesMapOfObject,
     LastChunkDeduplicationMapOfObject,
+    LastChunkParityMapOfObject,
     ObjectFooter,
     SegmentFooter,
     MainFooter,
@@ -74,6 +87,7 @@ impl Default for SegmentationState {
 }
 
 // - external
+use time::OffsetDateTime;
 #[cfg(feature = "log")]
 use log::trace;
 
@@ -130,6 +144,10 @@ struct ZffWriterInProgressData {
     current_encoded_chunk_samebytes_map_read_bytes: ReadBytes, // the number of bytes read from the current encoded chunk samebytes map,
     current_encoded_chunk_deduplication_map: Vec<u8>, // the current encoded chunk deduplication map,
     current_encoded_chunk_deduplication_map_read_bytes: ReadBytes, // the number of bytes read from the current encoded chunk deduplication map,
+    current_encoded_chunk_parity_map: Vec<u8>, // the current encoded chunk parity map,
+    current_encoded_chunk_parity_map_read_bytes: ReadBytes, // the number of bytes read from the current encoded chunk parity map,
+    current_parity_group: Vec<Vec<u8>>, // the stored data of the chunks of the current parity group,
+    current_parity_group_last_chunk_number: u64, // the number of the last chunk of the current parity group,
     current_prepared_data_queue: Option<PreparedData>, // the current prepared data in queue,
     current_prepared_data_queue_state: PreparedDataQueueState, // the current state of the prepared data queue,
     current_encoded_chunked_data: Vec<u8>, // the current encoded chunked data,
@@ -139,6 +157,8 @@ struct ZffWriterInProgressData {
     main_footer: MainFooter, // the main footer,
     encoded_main_footer: Vec<u8>, // the encoded main footer,
     encoded_main_footer_read_bytes: ReadBytes, // the number of bytes read from the encoded main footer,
+    deduplication_stats: DeduplicationStats, // the statistics of the written chunks,
+    input_bytes_read_finished_objects: u64, // the number of input bytes read by the already finished object encoders,
 }
 
 impl ZffWriterInProgressData {
@@ -158,6 +178,7 @@ impl ZffWriterInProgressData {
 #[derive(Debug)]
 pub enum ZffFilesOutput {
     /// To stream the data via implemented Read.
+    /// A segmented container can be streamed segment by segment by using a [SegmentedStreamReader].
     Stream,
 	/// Build a new container by using the appropriate Path-prefix
 	/// (e.g. if "/home/user/zff_container" is given, "/home/user/zff_container.z??" will be used).
@@ -167,6 +188,48 @@ pub enum ZffFilesOutput {
 }
 
 
+/// The progress of a [ZffWriter], e.g. reported by [ZffWriter::generate_files_with_progress].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZffWriteProgress {
+    /// The total number of bytes written (read from the [ZffWriter]) so far.
+    pub bytes_read_total: u64,
+    /// The number of the current segment.
+    pub current_segment: u64,
+    /// The number of files left in all logical objects.
+    pub files_left_total: u64,
+    /// The number of bytes read from the input data of all physical objects so far.
+    pub input_bytes_read: u64,
+    /// The total size of the input data of all physical objects, if known
+    /// (see [ZffCreationParameters::total_input_size]).
+    pub total_input_size: Option<u64>,
+}
+
+impl ZffWriteProgress {
+    /// Returns the percentage (0.0 - 100.0) of the read input data of all physical objects.
+    /// Returns None, if the total size of the input data is unknown (see [ZffCreationParameters::total_input_size]).
+    pub fn percentage(&self) -> Option<f64> {
+        match self.total_input_size {
+            Some(0) => Some(100.0),
+            Some(total_input_size) => Some((self.input_bytes_read as f64 / total_input_size as f64 * 100.0).min(100.0)),
+            None => None,
+        }
+    }
+}
+
+/// The deduplication statistics of the chunks written by a [ZffWriter] (see [ZffWriter::deduplication_stats]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeduplicationStats {
+    /// The total number of chunks written so far.
+    pub total_chunks: u64,
+    /// The number of chunks, which were stored as reference to an identical chunk.
+    pub deduplicated_chunks: u64,
+    /// The number of chunks, which contain only the same byte (and were stored as this single byte).
+    pub samebytes_chunks: u64,
+    /// The estimated number of bytes saved by the deduplication (the number of deduplicated chunks multiplied by the chunk size
+    /// of the appropriate object; the last chunk of an object could be smaller).
+    pub estimated_bytes_saved: u64,
+}
+
 /// ZffWriter is a struct that is used to create a new zff container while using the appropriate Read implementation of this struct.
 /// 
 /// ZffWriter only supports to create a new zff container in a single segment.
@@ -180,10 +243,14 @@ pub struct ZffWriter<R: Read> {
     read_state: ReadState,
     segmentation_state: SegmentationState,
     output: ZffFilesOutput,
+    passive_object_information: HashMap<u64, PassiveObjectInformation>, // the information of all objects, which can be referred by virtual objects.
+    read_throttle: Option<ReadThrottle>, // limits the rate of the read input data.
+    preserve_acquisition_timestamps: bool, // the container-wide acquisition timestamps were taken over from an existing container.
 }
 
 impl<R: Read> ZffWriter<R> {
     /// Returns a new ZffWriter with the given values.
+    /// The chunks of each object will be compressed by using the [CompressionHeader] of the appropriate [ObjectHeader].
     pub fn with_data(
         physical_objects: HashMap<ObjectHeader, R>, // <ObjectHeader, input_data stream>
 		logical_objects: HashMap<ObjectHeader, Vec<PathBuf>>, //<ObjectHeader, input_files>
@@ -191,7 +258,46 @@ impl<R: Read> ZffWriter<R> {
         params: ZffCreationParameters,
         output: ZffFilesOutput,
     ) -> Result<Self> {
-        setup_container(physical_objects, logical_objects, hash_types, params, output)
+        setup_container(physical_objects, logical_objects, HashMap::new(), hash_types, params, output)
+    }
+
+    /// Returns a new ZffWriter with the given values, which additionally creates the given virtual objects.  
+    /// A virtual object links the given ranges of physical objects in the given order. The referred physical objects
+    /// have to exist in the container, which will be extended, or have to be created by this writer.
+    /// The object numbers will be assigned in the order physical objects, logical objects, virtual objects (each ordered by the
+    /// given object numbers). The ranges refer to the given object numbers of the new physical objects, which take precedence
+    /// over the object numbers of the extended container.
+    /// Encrypted passive objects of an extended container are not supported.
+    pub fn with_virtual_objects(
+        physical_objects: HashMap<ObjectHeader, R>, // <ObjectHeader, input_data stream>
+		logical_objects: HashMap<ObjectHeader, Vec<PathBuf>>, //<ObjectHeader, input_files>
+        virtual_objects: HashMap<ObjectHeader, Vec<VirtualObjectRange>>, //<ObjectHeader, ranges of the passive objects>
+		hash_types: Vec<HashType>,
+        params: ZffCreationParameters,
+        output: ZffFilesOutput,
+    ) -> Result<Self> {
+        setup_container(physical_objects, logical_objects, virtual_objects, hash_types, params, output)
+    }
+
+    /// Returns a new ZffWriter, which creates a new container by using the given (already set up) object encoders.
+    /// In contrast to [ZffWriter::with_data], the object numbers of the given object encoders will be kept.
+    pub(crate) fn with_object_encoders(
+        object_encoder: Vec<ObjectEncoder<R>>,
+        params: ZffCreationParameters,
+        output: ZffFilesOutput,
+    ) -> Result<Self> {
+        if let ZffFilesOutput::ExtendContainer(_) = output {
+            return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_WRITER_EXTEND_OBJECT_ENCODERS));
+        }
+        setup_writer(object_encoder, None, HashMap::new(), 0, params, output)
+    }
+
+    // takes over the container-wide acquisition timestamps of an existing container (e.g. to transcode this container),
+    // so the timestamps of the main footer will not be set while writing.
+    pub(crate) fn preserve_acquisition_timestamps(&mut self, acquisition_start: Option<u64>, acquisition_end: Option<u64>) {
+        self.in_progress_data.main_footer.acquisition_start = acquisition_start;
+        self.in_progress_data.main_footer.acquisition_end = acquisition_end;
+        self.preserve_acquisition_timestamps = true;
     }
 
     /// Returns the current chunk number.
@@ -226,6 +332,22 @@ impl<R: Read> ZffWriter<R> {
         total_files
     }
 
+    /// Returns the current [ZffWriteProgress].
+    pub fn progress(&self) -> ZffWriteProgress {
+        ZffWriteProgress {
+            bytes_read_total: self.in_progress_data.bytes_read.total,
+            current_segment: self.current_segment_no(),
+            files_left_total: self.files_left_total(),
+            input_bytes_read: self.in_progress_data.input_bytes_read_finished_objects + self.current_object_encoder.read_bytes_underlying_data(),
+            total_input_size: self.optional_parameters.total_input_size,
+        }
+    }
+
+    /// Returns the [DeduplicationStats] of all chunks written so far.
+    pub fn deduplication_stats(&self) -> DeduplicationStats {
+        self.in_progress_data.deduplication_stats.clone()
+    }
+
     /// sets the next segment.
     pub fn next_segment(&mut self) -> Result<()> {
         // check if the current segment is already finished
@@ -253,24 +375,42 @@ impl<R: Read> ZffWriter<R> {
 
     /// Generates the files for the current state of the ZFF container.
     pub fn generate_files(&mut self) -> Result<()> {
-		
-        let mut file_extension = String::from(FILE_EXTENSION_INITIALIZER);
+        self.generate_files_with_progress(|_| {})
+    }
 
+    /// Works like [ZffWriter::generate_files], but calls the given callback with the current [ZffWriteProgress]
+    /// after each finished segment and every [DEFAULT_WRITER_PROGRESS_INTERVAL] bytes in between.  
+    /// The callback is called synchronously by the write loop: the generation of the files
+    /// will not continue until the callback returns (so the callback should not block).
+    pub fn generate_files_with_progress<F>(&mut self, mut callback: F) -> Result<()>
+    where
+        F: FnMut(ZffWriteProgress),
+    {
         let mut initial_extend =  match &self.output {
             ZffFilesOutput::Stream => return Err(ZffError::new(ZffErrorKind::InvalidOption, "")), //TODO: Define other kind of error here
             ZffFilesOutput::NewContainer(_) => false,
             ZffFilesOutput::ExtendContainer(_) => true,
         };
 
+        // an extended container will be continued with the existing (last) segment, new segments will follow after this segment.
+        let mut file_extension = match &self.output {
+            ZffFilesOutput::ExtendContainer(ref path_vec) => match path_vec[0].extension() { // should never get out of bound when fn setup_container was used before.
+                Some(extension) => extension.to_string_lossy().to_string(),
+                None => return Err(ZffError::new(ZffErrorKind::FileExtensionParserError, path_vec[0].to_string_lossy())),
+            },
+            _ => String::from(FILE_EXTENSION_INITIALIZER),
+        };
+
         loop {
-            file_extension = file_extension_next_value(&file_extension)?;
+            if !initial_extend {
+                file_extension = file_extension_next_value(&file_extension)?;
+            }
             let mut segment_filename = match &self.output {
                 ZffFilesOutput::Stream => unreachable!(),
                 ZffFilesOutput::NewContainer(ref path) => path.clone(),
-                ZffFilesOutput::ExtendContainer(ref path_vec) => path_vec[0].clone(), // should never get out of bound when fn setup_container was used before.
+                ZffFilesOutput::ExtendContainer(ref path_vec) => path_vec[0].clone(),
             };
 
-            // set_extension should not affect the ExtendContainer paths.
 	    	segment_filename.set_extension(&file_extension);
 
 	    	let mut output_file = match initial_extend {
@@ -297,6 +437,7 @@ impl<R: Read> ZffWriter<R> {
             };
 
             let mut buffer = vec![0u8; DEFAULT_BUFFER_SIZE as usize];
+            let mut bytes_since_last_progress = 0;
             
             loop {
                 match self.read(&mut buffer) {
@@ -304,11 +445,17 @@ impl<R: Read> ZffWriter<R> {
                         break;
                     },
                     Ok(n) => {
-                        output_file.write_all(&buffer[..n])?
+                        output_file.write_all(&buffer[..n])?;
+                        bytes_since_last_progress += n as u64;
+                        if bytes_since_last_progress >= DEFAULT_WRITER_PROGRESS_INTERVAL {
+                            bytes_since_last_progress = 0;
+                            callback(self.progress());
+                        }
                     },
                     Err(e) => return Err(e.into()),
                 }
             }
+            callback(self.progress());
 
             match self.next_segment() {
                 Ok(_) => {},
@@ -321,6 +468,30 @@ impl<R: Read> ZffWriter<R> {
 
     }
 
+    /// Writes the whole (non-segmented) container into the given [Write]r and returns the number of written bytes.
+    /// In contrast to [ZffWriter::generate_files], the appropriate [ZffFilesOutput] will not be used to determine the output.
+    /// # Error
+    /// Fails if a target segment size was set or if the [ZffWriter] should extend an existing container.
+    pub fn write_to<W: Write>(&mut self, mut output: W) -> Result<u64> {
+        if self.optional_parameters.target_segment_size.is_some() || matches!(self.output, ZffFilesOutput::ExtendContainer(_)) {
+            return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_WRITER_WRITE_TO_UNSUPPORTED_OUTPUT));
+        }
+        let mut buffer = vec![0u8; DEFAULT_BUFFER_SIZE];
+        let mut bytes_written = 0;
+        loop {
+            match self.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => {
+                    output.write_all(&buffer[..n])?;
+                    bytes_written += n as u64;
+                },
+                Err(e) => return Err(e.into()),
+            }
+        }
+        output.flush()?;
+        Ok(bytes_written)
+    }
+
     /// Returns true if the chunkmap was full and flushed.
     fn check_chunkmap_is_full_and_flush(&mut self, chunk_map_type: ChunkMapType) -> Result<bool> {
         match chunk_map_type {
@@ -372,9 +543,41 @@ impl<R: Read> ZffWriter<R> {
                     Ok(false)
                 }
             },
+            ChunkMapType::ParityMap => {
+                if self.in_progress_data.chunkmaps.parity_map.is_full() {
+                    self.flush_chunkmap(chunk_map_type)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            },
         }
     }
 
+    /// calculates the parity block of the current parity group (if any) and adds it to the chunk parity map.
+    /// Returns true if the chunk parity map was full and flushed (before the parity block was added).
+    fn finalize_parity_group(&mut self) -> Result<bool> {
+        let parity_shards = match self.optional_parameters.parity_shards {
+            Some((_, parity_shards)) => parity_shards,
+            None => return Ok(false),
+        };
+        if self.in_progress_data.current_parity_group.is_empty() {
+            return Ok(false);
+        }
+        let chunks = std::mem::take(&mut self.in_progress_data.current_parity_group);
+        let last_chunk_number = self.in_progress_data.current_parity_group_last_chunk_number;
+        let parity_block = match ChunkParityBlock::calculate(&chunks, parity_shards)? {
+            Some(parity_block) => parity_block,
+            None => return Ok(false),
+        };
+        if self.in_progress_data.chunkmaps.parity_map.add_chunk_entry(last_chunk_number, &parity_block) {
+            return Ok(false);
+        }
+        self.flush_chunkmap(ChunkMapType::ParityMap)?;
+        self.in_progress_data.chunkmaps.parity_map.add_chunk_entry(last_chunk_number, parity_block);
+        Ok(true)
+    }
+
     /// checks if the appropriate object has an encryption header and encrypts the chunkmap if necessary.
     fn encode_chunkmap<C>(&self, chunkmap: &C, last_chunk_no: u64) -> Result<Vec<u8>> 
     where
@@ -383,7 +586,7 @@ impl<R: Read> ZffWriter<R> {
         if let Some(encryption_header) = &self.current_object_encoder.get_obj_header().encryption_header {
             let key = encryption_header.get_encryption_key_ref().unwrap(); //unwrap should be safe here - I don't know how we would encrypt all the other stuff, without knowing the key. :D
             let algorithm = &encryption_header.algorithm;
-            Ok(chunkmap.encrypt_encoded_map(key, algorithm, last_chunk_no)?)
+            Ok(chunkmap.encrypt_encoded_map(key, encryption_header.nonce_extension, algorithm, last_chunk_no)?)
         } else {
              Ok(chunkmap.encode_directly())
         }
@@ -458,6 +661,16 @@ impl<R: Read> ZffWriter<R> {
                     self.in_progress_data.chunkmaps.duplicate_chunks.flush();
                 }
             },
+            ChunkMapType::ParityMap => {
+                if let Some(chunk_no) = self.in_progress_data.chunkmaps.parity_map.chunkmap().keys().max() {
+                    self.in_progress_data.main_footer.chunk_parity_maps.insert(*chunk_no, segment_number);
+                    self.in_progress_data.segment_footer.chunk_parity_map_table.insert(*chunk_no, self.in_progress_data.bytes_read.current_segment);
+                    self.in_progress_data.current_encoded_chunk_parity_map = self.encode_chunkmap(
+                        &self.in_progress_data.chunkmaps.parity_map, *chunk_no)?;
+                    self.in_progress_data.current_encoded_chunk_parity_map_read_bytes = ReadBytes::NotRead;
+                    self.in_progress_data.chunkmaps.parity_map.flush();
+                }
+            },
         }
         Ok(())
     }
@@ -481,6 +694,11 @@ impl<R: Read> Read for ZffWriter<R> {
         // may improve performance in different cases.
         let buf_len = buf.len();
 
+        // the container-wide acquisition starts with the first read (or was already set by the extended or the transcoded container).
+        if !self.preserve_acquisition_timestamps && self.in_progress_data.main_footer.acquisition_start().is_none() {
+            self.in_progress_data.main_footer.set_acquisition_start(OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64);
+        }
+
         'read_loop: loop {
             match self.segmentation_state {
                 SegmentationState::Finished(_) => {
@@ -690,6 +908,33 @@ impl<R: Read> Read for ZffWriter<R> {
                         return Ok(bytes_written_to_buffer);
                     };
 
+                    // switch to the next state
+                    match self.segmentation_state {
+                        SegmentationState::Partial(_) => self.read_state = ReadState::Chunking,
+                        SegmentationState::Full(_) => {
+                            self.read_state = ReadState::ChunkParityMap;
+                            self.flush_chunkmap(ChunkMapType::ParityMap)?;
+                        },
+                        SegmentationState::Finished(_) => unreachable!(),
+                        SegmentationState::FullLastSegment(_) => unreachable!(),
+                        SegmentationState::FinishedLastSegment(_) => unreachable!(),
+                    };
+                },
+
+                ReadState::ChunkParityMap => {
+                    #[cfg(feature = "log")]
+                    trace!("ReadState::ChunkParityMap");
+                    // reads the chunkmap if not already read
+                    let read_bytes = fill_buffer(
+                        &self.in_progress_data.current_encoded_chunk_parity_map, 
+                        &mut self.in_progress_data.current_encoded_chunk_parity_map_read_bytes, 
+                        buf, 
+                        &mut bytes_written_to_buffer)?;
+                    self.in_progress_data.bytes_read += read_bytes as u64;
+                    if bytes_written_to_buffer >= buf_len {
+                        return Ok(bytes_written_to_buffer);
+                    };
+
                     // switch to the next state
                     match self.segmentation_state {
                         SegmentationState::Partial(_) => self.read_state = ReadState::Chunking,
@@ -831,6 +1076,30 @@ impl<R: Read> Read for ZffWriter<R> {
                         return Ok(bytes_written_to_buffer);
                     };
 
+                    // prepare and switch to the next state
+                    // the parity group of the last chunks of the object will be completed. The chunk parity map cannot be full here,
+                    // because it was checked before the last chunk was read.
+                    self.finalize_parity_group()?;
+                    // write the chunk parity map even there is some space left in map to ensure
+                    // that this map will be written if there is no next object.
+                    self.flush_chunkmap(ChunkMapType::ParityMap)?;
+                    self.read_state = ReadState::LastChunkParityMapOfObject;
+                },
+
+                ReadState::LastChunkParityMapOfObject => {
+                    #[cfg(feature = "log")]
+                    trace!("ReadState::LastChunkParityMapOfObject");
+                    // reads the chunkmap if not already read
+                    let read_bytes = fill_buffer(
+                        &self.in_progress_data.current_encoded_chunk_parity_map, 
+                        &mut self.in_progress_data.current_encoded_chunk_parity_map_read_bytes, 
+                        buf, 
+                        &mut bytes_written_to_buffer)?;
+                    self.in_progress_data.bytes_read += read_bytes as u64;
+                    if bytes_written_to_buffer >= buf_len {
+                        return Ok(bytes_written_to_buffer);
+                    };
+
                     // prepare and switch to the next state
                     // this will only fail in case of encryption errrors - which should be happend before
                     let object_footer = match self.current_object_encoder.get_encoded_footer() {
@@ -907,6 +1176,19 @@ impl<R: Read> Read for ZffWriter<R> {
                         self.read_state = ReadState::ChunkDeduplicationMap;
                         continue;
                     };
+
+                    // the parity block of a complete parity group will be added before the next chunk is read.
+                    if let Some((data_chunks, _)) = self.optional_parameters.parity_shards {
+                        if self.in_progress_data.current_parity_group.len() >= data_chunks && self.finalize_parity_group()? {
+                            self.read_state = ReadState::ChunkParityMap;
+                            continue;
+                        }
+                    }
+
+                    if self.check_chunkmap_is_full_and_flush(ChunkMapType::ParityMap)? {
+                        self.read_state = ReadState::ChunkParityMap;
+                        continue;
+                    };
                     // handles the prepared data queue
                     loop {
                         match self.in_progress_data.current_prepared_data_queue_state {
@@ -931,12 +1213,18 @@ impl<R: Read> Read for ZffWriter<R> {
                                         }
                                 };
 
-                                if !self.in_progress_data.chunkmaps.offset_map.add_chunk_entry(
-                                    current_chunk_number, self.in_progress_data.bytes_read.current_segment) {
-                                    self.flush_chunkmap(ChunkMapType::OffsetMap)?;
-                                    self.read_state = ReadState::ChunkOffsetMap;
-                                    self.in_progress_data.chunkmaps.offset_map.add_chunk_entry(
-                                        current_chunk_number, self.in_progress_data.bytes_read.current_segment);
+                                // file headers and file footers are not chunks and must not be added to the chunk offset map.
+                                if let PreparedData::PreparedChunk(_) = data {
+                                    if let Some(read_throttle) = &mut self.read_throttle {
+                                        read_throttle.consume(self.current_object_encoder.get_obj_header().chunk_size);
+                                    }
+                                    if !self.in_progress_data.chunkmaps.offset_map.add_chunk_entry(
+                                        current_chunk_number, self.in_progress_data.bytes_read.current_segment) {
+                                        self.flush_chunkmap(ChunkMapType::OffsetMap)?;
+                                        self.read_state = ReadState::ChunkOffsetMap;
+                                        self.in_progress_data.chunkmaps.offset_map.add_chunk_entry(
+                                            current_chunk_number, self.in_progress_data.bytes_read.current_segment);
+                                    }
                                 }
             
                                 self.in_progress_data.current_prepared_data_queue = Some(data);
@@ -1044,10 +1332,29 @@ impl<R: Read> Read for ZffWriter<R> {
                                         PreparedData::PreparedChunk(prepared_chunk) => prepared_chunk.data(),
                                         PreparedData::PreparedFileHeader(ref prepared_file_header) => prepared_file_header,
                                         PreparedData::PreparedFileFooter(ref prepared_file_footer) => prepared_file_footer,
+                                        PreparedData::PreparedVirtualMappingInformation(ref prepared_mapping_information) => prepared_mapping_information,
+                                        PreparedData::PreparedVirtualObjectMap(ref prepared_object_map) => prepared_object_map,
                                     },
                                     None => unreachable!(),
                                 };
                                 self.in_progress_data.current_encoded_chunked_data = data.to_vec();
+                                if let Some(PreparedData::PreparedChunk(prepared_chunk)) = &self.in_progress_data.current_prepared_data_queue {
+                                    let deduplication_stats = &mut self.in_progress_data.deduplication_stats;
+                                    deduplication_stats.total_chunks += 1;
+                                    if prepared_chunk.samebytes().is_some() {
+                                        deduplication_stats.samebytes_chunks += 1;
+                                    }
+                                    if prepared_chunk.duplicated().is_some() {
+                                        deduplication_stats.deduplicated_chunks += 1;
+                                        deduplication_stats.estimated_bytes_saved += self.current_object_encoder.get_obj_header().chunk_size;
+                                    }
+                                }
+                                // the stored data of the chunk will be used to calculate the parity data of the current parity group.
+                                if let (Some(PreparedData::PreparedChunk(prepared_chunk)), Some(_)) = (
+                                    &self.in_progress_data.current_prepared_data_queue, self.optional_parameters.parity_shards) {
+                                    self.in_progress_data.current_parity_group.push(prepared_chunk.data().clone());
+                                    self.in_progress_data.current_parity_group_last_chunk_number = current_chunk_number;
+                                }
                                 self.in_progress_data.current_encoded_chunked_data_read_bytes = ReadBytes::NotRead;
                                 self.in_progress_data.current_prepared_data_queue_state = PreparedDataQueueState::None;
                                 self.in_progress_data.current_prepared_data_queue = None;
@@ -1071,9 +1378,20 @@ impl<R: Read> Read for ZffWriter<R> {
                     };
 
                     // switch to the next state
+                    if let Some(passive_object_information) = self.current_object_encoder.passive_object_information() {
+                        self.passive_object_information.insert(self.current_object_encoder.obj_number(), passive_object_information);
+                    }
+                    let next_chunk_number = self.current_object_encoder.current_chunk_number();
                     self.current_object_encoder = match self.object_encoder.pop() {
-                        Some(creator_obj_encoder) => creator_obj_encoder,
+                        Some(creator_obj_encoder) => {
+                            self.in_progress_data.input_bytes_read_finished_objects += self.current_object_encoder.read_bytes_underlying_data();
+                            creator_obj_encoder
+                        },
                         None => {
+                            // the end of the acquisition has to be set before the length of the main footer is used below.
+                            if !self.preserve_acquisition_timestamps {
+                                self.in_progress_data.main_footer.set_acquisition_end(OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64);
+                            }
                             self.in_progress_data.segment_footer.set_footer_offset(self.in_progress_data.bytes_read.current_segment);
                             self.in_progress_data.segment_footer.set_length_of_segment(
                                 self.in_progress_data.bytes_read.current_segment + 
@@ -1086,9 +1404,16 @@ impl<R: Read> Read for ZffWriter<R> {
                             continue;
                         }
                     };
+                    self.current_object_encoder.set_initial_chunk_number(next_chunk_number);
+                    if let ObjectEncoder::Virtual(virtual_object_encoder) = &mut self.current_object_encoder {
+                        if let Err(e) = virtual_object_encoder.resolve_passive_objects(&self.passive_object_information) {
+                            return Err(std::io::Error::new(std::io::ErrorKind::Other, e));
+                        }
+                    }
                     self.in_progress_data.main_footer.object_header.insert(self.current_object_encoder.obj_number(), self.current_segment_no());
                     self.read_state = ReadState::ObjectHeader;
                     self.in_progress_data.current_encoded_object_header = self.current_object_encoder.get_encoded_header();
+                    self.in_progress_data.current_encoded_object_header_read_bytes = ReadBytes::NotRead;
                     self.in_progress_data.segment_footer.add_object_header_offset(
                         self.current_object_encoder.obj_number(),
                         self.in_progress_data.bytes_read.current_segment);
@@ -1144,11 +1469,13 @@ impl<R: Read> Read for ZffWriter<R> {
 fn setup_container<R: Read>(
     physical_objects: HashMap<ObjectHeader, R>,
     logical_objects: HashMap<ObjectHeader, Vec<PathBuf>>,
+    virtual_objects: HashMap<ObjectHeader, Vec<VirtualObjectRange>>,
     hash_types: Vec<HashType>,
     params: ZffCreationParameters,
     output: ZffFilesOutput) -> Result<ZffWriter<R>> {
     let mut physical_objects = physical_objects;
     let mut logical_objects = logical_objects;
+    let mut virtual_objects = virtual_objects;
 
     let mut total_bytes_read = 0;
 
@@ -1185,6 +1512,7 @@ fn setup_container<R: Read>(
                     total_bytes_read += raw_segment.seek(SeekFrom::End(0))?;
                 }
                 // try to decode the segment header to check if the file is a valid segment.
+                raw_segment.rewind()?;
                 let _ = Segment::new_from_reader(raw_segment)?;
             }
             extension_parameter
@@ -1194,7 +1522,20 @@ fn setup_container<R: Read>(
 
     //initially check if all EncryptionHeader are contain a decrypted encryption key for physical and logical objects.
     // uses check_encryption_key_in_header for all ObjectHeader in physical_objects and logical_objects:
-    prepare_object_header(&mut physical_objects, &mut logical_objects, &extender_parameter)?;
+    prepare_object_header(&mut physical_objects, &mut logical_objects, &mut virtual_objects, &extender_parameter, &params.zstd_dictionary)?;
+
+    // the virtual objects may refer to objects of the container, which will be extended.
+    let passive_object_information = match (&output, &extender_parameter) {
+        (ZffFilesOutput::ExtendContainer(files_to_extend), Some(extender_parameter)) => {
+            let existing_passive_objects = virtual_objects.values()
+                .flatten()
+                .map(|range| range.object_number)
+                .filter(|object_number| *object_number < extender_parameter.next_object_no)
+                .collect::<BTreeSet<_>>();
+            read_passive_object_information(files_to_extend, existing_passive_objects)?
+        },
+        _ => HashMap::new(),
+    };
 
     let signature_key_bytes = &params.signature_key.as_ref().map(|signing_key| signing_key.to_bytes().to_vec());
     let mut object_encoder = Vec::with_capacity(physical_objects.len()+logical_objects.len());
@@ -1210,20 +1551,59 @@ fn setup_container<R: Read>(
         &hash_types,
         signature_key_bytes,
         initial_chunk_number,
+        params.encoding_threads,
+        params.chunk_integrity,
+        &params.bad_block_strategy,
+        IoInterruptRetries::with_parameters(&params),
         &mut object_encoder)?;
 
+    let per_file_hash_types = if params.per_file_hashes.is_empty() {
+        &hash_types
+    } else {
+        &params.per_file_hashes
+    };
+
     setup_logical_object_encoder(
         logical_objects,
-        &hash_types,
+        per_file_hash_types,
         signature_key_bytes,
         initial_chunk_number,
+        params.chunk_integrity,
+        IoInterruptRetries::with_parameters(&params),
+        params.original_paths,
+        &mut object_encoder)?;
+
+    setup_virtual_object_encoder(
+        virtual_objects,
+        initial_chunk_number,
         &mut object_encoder)?;
 
+    setup_writer(object_encoder, extender_parameter, passive_object_information, total_bytes_read, params, output)
+}
+
+// builds the [ZffWriter] for the given object encoders (the object encoders will be processed in the given order).
+fn setup_writer<R: Read>(
+    object_encoder: Vec<ObjectEncoder<R>>,
+    extender_parameter: Option<ZffExtenderParameter>,
+    passive_object_information: HashMap<u64, PassiveObjectInformation>,
+    total_bytes_read: u64,
+    params: ZffCreationParameters,
+    output: ZffFilesOutput) -> Result<ZffWriter<R>> {
+    if let Some((data_chunks, parity_shards)) = params.parity_shards {
+        if data_chunks == 0 || parity_shards == 0 || data_chunks + parity_shards > 256 {
+            return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_INVALID_PARITY_SHARDS));
+        }
+    }
+
+    let mut object_encoder = object_encoder;
     object_encoder.reverse();
-    let current_object_encoder = match object_encoder.pop() {
+    let mut current_object_encoder = match object_encoder.pop() {
         Some(creator_obj_encoder) => creator_obj_encoder,
         None => return Err(ZffError::new(ZffErrorKind::NoObjectsLeft, "")),
     };
+    if let ObjectEncoder::Virtual(virtual_object_encoder) = &mut current_object_encoder {
+        virtual_object_encoder.resolve_passive_objects(&passive_object_information)?;
+    }
 
     let mut segmentation_state = SegmentationState::default();
 
@@ -1246,6 +1626,8 @@ fn setup_container<R: Read>(
         in_progress_data.main_footer.object_header.insert(current_object_encoder.obj_number(), extender_parameter.segment_number);
     }
 
+    let read_throttle = params.max_read_bytes_per_sec.and_then(ReadThrottle::new);
+
     Ok(ZffWriter {
         read_state,
         object_encoder,
@@ -1253,10 +1635,43 @@ fn setup_container<R: Read>(
         in_progress_data,
         segmentation_state,
         optional_parameters: params,
-        output
+        output,
+        passive_object_information,
+        read_throttle,
+        preserve_acquisition_timestamps: false,
     })
 }
 
+// reads the information of the given (physical) objects of an existing container, which are needed to refer to these objects.
+fn read_passive_object_information(
+    files: &[PathBuf],
+    object_numbers: BTreeSet<u64>) -> Result<HashMap<u64, PassiveObjectInformation>> {
+    let mut passive_object_information = HashMap::new();
+    if object_numbers.is_empty() {
+        return Ok(passive_object_information);
+    }
+    let mut segments = Vec::with_capacity(files.len());
+    for file in files {
+        segments.push(File::open(file)?);
+    }
+    let mut reader = ZffReader::with_reader(segments)?;
+    for object_number in object_numbers {
+        reader.initialize_object(object_number)?;
+        reader.set_active_object(object_number)?;
+        let chunk_size = reader.active_object_header_ref()?.chunk_size;
+        let object_footer = match reader.active_object_footer()? {
+            ObjectFooter::Physical(object_footer) => object_footer,
+            _ => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, object_number.to_string())),
+        };
+        passive_object_information.insert(object_number, PassiveObjectInformation {
+            first_chunk_number: object_footer.first_chunk_number,
+            chunk_size,
+            length_of_data: object_footer.length_of_data,
+        });
+    }
+    Ok(passive_object_information)
+}
+
 fn build_in_progress_data(params: &ZffCreationParameters) -> ZffWriterInProgressData {
     let mut in_progress_data = ZffWriterInProgressData::new();
     in_progress_data.main_footer.description_notes = params.description_notes.clone();
@@ -1276,6 +1691,7 @@ fn build_in_progress_data(params: &ZffCreationParameters) -> ZffWriterInProgress
     in_progress_data.chunkmaps.xxhash_map.set_target_size(chunkmap_size as usize);
     in_progress_data.chunkmaps.same_bytes_map.set_target_size(chunkmap_size as usize);
     in_progress_data.chunkmaps.duplicate_chunks.set_target_size(chunkmap_size as usize);
+    in_progress_data.chunkmaps.parity_map.set_target_size(chunkmap_size as usize);
 
     in_progress_data
 }
@@ -0,0 +1,94 @@
+// - STD
+use std::io::{Read, Write};
+
+// - internal
+use crate::{
+	Result,
+	ZffError,
+	ZffErrorKind,
+	constants::ERROR_WRITER_SEGMENTED_STREAM_EXTEND,
+};
+
+use super::{ZffWriter, ZffFilesOutput, ZffWriteProgress};
+
+/// A reader, which splits the output of a [ZffWriter] into the single segments of the container
+/// (e.g. to upload each segment as a separate part to an object store with a limited object size).
+/// The [ZffWriter] has to be created with a target segment size (see
+/// [ZffCreationParameters::target_segment_size](crate::io::ZffCreationParameters::target_segment_size)), otherwise the container
+/// will consist of a single segment. Each segment contains its own segment footer and the last segment contains the main footer,
+/// so the segments are identical to the segments created by [ZffWriter::generate_files] and can be read by a
+/// [ZffReader](crate::io::zffreader::ZffReader).
+///
+/// The [Read] implementation returns the data of the current segment and reports the end of the segment by reading 0 bytes.
+/// Use [SegmentedStreamReader::next_segment] to continue with the next segment.
+pub struct SegmentedStreamReader<R: Read> {
+	writer: ZffWriter<R>,
+}
+
+impl<R: Read> SegmentedStreamReader<R> {
+	/// Returns a new [SegmentedStreamReader] for the given [ZffWriter].
+	/// # Error
+	/// Fails if the [ZffWriter] should extend an existing container.
+	pub fn new(writer: ZffWriter<R>) -> Result<Self> {
+		if let ZffFilesOutput::ExtendContainer(_) = writer.output {
+			return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_WRITER_SEGMENTED_STREAM_EXTEND));
+		}
+		Ok(Self { writer })
+	}
+
+	/// Returns the number of the current segment (starting with 1).
+	pub fn current_segment_number(&self) -> u64 {
+		self.writer.current_segment_no()
+	}
+
+	/// Returns the current [ZffWriteProgress] of the underlying [ZffWriter].
+	pub fn progress(&self) -> ZffWriteProgress {
+		self.writer.progress()
+	}
+
+	/// Continues with the next segment, after the current segment was read completely.
+	/// Returns false, if the current segment was the last segment of the container.
+	/// # Error
+	/// Fails if the current segment was not read completely.
+	pub fn next_segment(&mut self) -> Result<bool> {
+		match self.writer.next_segment() {
+			Ok(_) => Ok(true),
+			Err(e) => match e.get_kind() {
+				ZffErrorKind::NoObjectsLeft => Ok(false),
+				_ => Err(e),
+			}
+		}
+	}
+
+	/// Writes each segment of the container into the writer, which is returned by the given function for the appropriate
+	/// segment number, and returns the number of written segments.
+	/// # Error
+	/// Fails if the given function fails or if the encoding or writing of the segments fails.
+	pub fn write_segments<W, F>(&mut self, mut open_segment: F) -> Result<u64>
+	where
+		W: Write,
+		F: FnMut(u64) -> Result<W>,
+	{
+		let mut number_of_segments = 0;
+		loop {
+			let mut output = open_segment(self.current_segment_number())?;
+			std::io::copy(self, &mut output)?;
+			output.flush()?;
+			number_of_segments += 1;
+			if !self.next_segment()? {
+				return Ok(number_of_segments);
+			}
+		}
+	}
+
+	/// Returns the underlying [ZffWriter].
+	pub fn into_inner(self) -> ZffWriter<R> {
+		self.writer
+	}
+}
+
+impl<R: Read> Read for SegmentedStreamReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		self.writer.read(buf)
+	}
+}
@@ -0,0 +1,230 @@
+// - STD
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+// - internal
+use crate::{
+	Result,
+	ZffError,
+	ZffErrorKind,
+	ObjectEncoder,
+	PhysicalObjectEncoder,
+	LogicalObjectEncoder,
+	HashType,
+	header::{CompressionHeader, FileHeader, HashHeader, ObjectHeader},
+	footer::ObjectFooter,
+	io::{ZffCreationParameters, zffreader::{ZffReader, ObjectType}},
+	constants::*,
+};
+
+use super::{ZffWriter, ZffFilesOutput};
+
+/// Re-compresses all objects of the container, which can be read by the given [ZffReader], with the given compression settings
+/// and writes them to a new container at the given output (e.g. to shrink an Lz4 compressed container by using Zstd).
+/// The object numbers, the object headers (incl. the descriptions) and the file headers (incl. the file metadata) will be preserved.
+/// The hash values of each object (or file) will be recalculated by using the same hash types, so the integrity
+/// checks of the new container will still pass. The description notes of the container will be preserved, too.
+/// The acquisition information of the original acquisition will be preserved, too: the container-wide acquisition timestamps,
+/// the acquisition timestamps of each object and the bad chunks and the error log of each physical object.
+/// The acquisition timestamps of the file footers of logical objects will be set to the time of the transcoding.
+/// # Error
+/// Fails if
+///   - the container contains encrypted objects.
+///   - the container contains signed hash values (the signatures cannot be recreated without the private key).
+///   - the container contains virtual objects (not supported yet).
+///   - the given output refers to an existing container ([ZffFilesOutput::ExtendContainer]).
+///   - there is an error while reading the source container or writing the new container.
+pub fn transcode_container<R: Read + Seek + 'static>(
+	reader: ZffReader<R>,
+	new_compression: CompressionHeader,
+	output: ZffFilesOutput) -> Result<()> {
+	rewrite_container(reader, Some(new_compression), None, output)
+}
+
+/// Splits the container, which can be read by the given [ZffReader], into segments of the given target size
+/// (e.g. to store a large single-segment container on a FAT32 formatted medium) and writes them to the given output prefix
+/// (the segments will be named like ```<output_prefix>.z01```, ```<output_prefix>.z02```, ...).
+/// This is a transcoding (see [transcode_container]) which only changes the segmentation: the compression settings, the object numbers
+/// and the chunk numbers will be preserved, so the hash values of the new container will still match.
+/// # Error
+/// Fails for the same reasons as [transcode_container] or if the given target segment size is too small.
+pub fn resegment<R: Read + Seek + 'static>(
+	reader: ZffReader<R>,
+	target_segment_size: u64,
+	output_prefix: PathBuf) -> Result<()> {
+	rewrite_container(reader, None, Some(target_segment_size), ZffFilesOutput::NewContainer(output_prefix))
+}
+
+// rewrites the container with the given new compression settings (or with the original settings, if None) and the given target segment size.
+fn rewrite_container<R: Read + Seek + 'static>(
+	reader: ZffReader<R>,
+	new_compression: Option<CompressionHeader>,
+	target_segment_size: Option<u64>,
+	output: ZffFilesOutput) -> Result<()> {
+	let mut reader = reader;
+
+	// the object types will be checked before initializing the objects to detect encrypted objects.
+	let object_types = reader.list_objects()?;
+	for (object_number, object_type) in &object_types {
+		match object_type {
+			ObjectType::Encrypted => return Err(ZffError::new(
+				ZffErrorKind::UnsupportedTranscoding, format!("{ERROR_TRANSCODE_ENCRYPTED_OBJECT}{object_number}"))),
+			ObjectType::Virtual => return Err(ZffError::new(
+				ZffErrorKind::UnsupportedTranscoding, format!("{ERROR_TRANSCODE_VIRTUAL_OBJECT}{object_number}"))),
+			ObjectType::Physical | ObjectType::Logical => (),
+		}
+	}
+	reader.initialize_objects_all()?;
+
+	let description_notes = reader.main_footer_ref().description_notes.clone();
+	let acquisition_start = reader.main_footer_ref().acquisition_start();
+	let acquisition_end = reader.main_footer_ref().acquisition_end();
+	let reader = Rc::new(RefCell::new(reader));
+	let mut object_encoder = Vec::with_capacity(object_types.len());
+	for (object_number, object_type) in object_types {
+		let encoder = match object_type {
+			ObjectType::Physical => setup_physical_object_encoder(&reader, object_number, &new_compression)?,
+			_ => setup_logical_object_encoder(&reader, object_number, &new_compression)?,
+		};
+		object_encoder.push(encoder);
+	}
+
+	let params = ZffCreationParameters {
+		description_notes,
+		target_segment_size,
+		unique_identifier: rand::random(),
+		..Default::default()
+	};
+	let mut writer = ZffWriter::with_object_encoders(object_encoder, params, output)?;
+	writer.preserve_acquisition_timestamps(acquisition_start, acquisition_end);
+	writer.generate_files()
+}
+
+// returns the header of the active object with the new compression settings, if given (the header will be checked for transcodability).
+fn transcoded_object_header<R: Read + Seek>(reader: &ZffReader<R>, new_compression: &Option<CompressionHeader>) -> Result<ObjectHeader> {
+	let mut object_header = reader.active_object_header_ref()?.clone();
+	if object_header.encryption_header.is_some() {
+		return Err(ZffError::new(
+			ZffErrorKind::UnsupportedTranscoding, format!("{ERROR_TRANSCODE_ENCRYPTED_OBJECT}{}", object_header.object_number)));
+	}
+	if let Some(new_compression) = new_compression {
+		object_header.compression_header = new_compression.clone();
+	}
+	Ok(object_header)
+}
+
+// returns the hash types of the given hash header (fails, if the hash values are signed).
+fn hash_types_of(hash_header: &HashHeader, object_number: u64) -> Result<Vec<HashType>> {
+	let mut hash_types = Vec::with_capacity(hash_header.hashes.len());
+	for hash_value in &hash_header.hashes {
+		if hash_value.ed25519_signature.is_some() {
+			return Err(ZffError::new(
+				ZffErrorKind::UnsupportedTranscoding, format!("{ERROR_TRANSCODE_SIGNED_OBJECT}{object_number}")));
+		}
+		hash_types.push(hash_value.hash_type.clone());
+	}
+	Ok(hash_types)
+}
+
+fn setup_physical_object_encoder<R: Read + Seek + 'static>(
+	reader: &Rc<RefCell<ZffReader<R>>>,
+	object_number: u64,
+	new_compression: &Option<CompressionHeader>) -> Result<ObjectEncoder<TranscodingReader<R>>> {
+	let (object_header, object_footer) = {
+		let mut inner_reader = reader.borrow_mut();
+		inner_reader.set_active_object(object_number)?;
+		let object_header = transcoded_object_header(&inner_reader, new_compression)?;
+		let object_footer = match inner_reader.active_object_footer()? {
+			ObjectFooter::Physical(object_footer) => object_footer,
+			_ => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, object_number.to_string())),
+		};
+		(object_header, object_footer)
+	};
+	let hash_types = hash_types_of(&object_footer.hash_header, object_number)?;
+	let mut encoder = PhysicalObjectEncoder::new(
+		object_header,
+		TranscodingReader::new(Rc::clone(reader), object_number, None),
+		hash_types,
+		None,
+		INITIAL_CHUNK_NUMBER,
+		None,
+		Default::default(),
+		None,
+		Default::default())?;
+	encoder.preserve_acquisition_information(&object_footer);
+	Ok(ObjectEncoder::Physical(Box::new(encoder)))
+}
+
+fn setup_logical_object_encoder<R: Read + Seek + 'static>(
+	reader: &Rc<RefCell<ZffReader<R>>>,
+	object_number: u64,
+	new_compression: &Option<CompressionHeader>) -> Result<ObjectEncoder<TranscodingReader<R>>> {
+	let mut inner_reader = reader.borrow_mut();
+	inner_reader.set_active_object(object_number)?;
+	let object_header = transcoded_object_header(&inner_reader, new_compression)?;
+	let object_footer = match inner_reader.active_object_footer()? {
+		ObjectFooter::Logical(object_footer) => object_footer,
+		_ => return Err(ZffError::new(ZffErrorKind::MismatchObjectType, object_number.to_string())),
+	};
+
+	let mut hash_types = Vec::new();
+	let mut files: Vec<(Box<dyn Read>, FileHeader)> = Vec::new();
+	// the files will be encoded in ascending order of their file numbers (the encoder pops the last file first).
+	for file_number in inner_reader.file_numbers()?.into_iter().rev() {
+		inner_reader.set_active_file(file_number)?;
+		let file_header = inner_reader.current_fileheader()?;
+		for hash_type in hash_types_of(&inner_reader.current_filefooter()?.hash_header, object_number)? {
+			if !hash_types.contains(&hash_type) {
+				hash_types.push(hash_type);
+			}
+		}
+		let file_reader = TranscodingReader::new(Rc::clone(reader), object_number, Some(file_number));
+		files.push((Box::new(file_reader), file_header));
+	}
+
+	let mut encoder = LogicalObjectEncoder::with_file_readers(
+		object_header,
+		files,
+		object_footer.root_dir_filenumbers,
+		hash_types,
+		INITIAL_CHUNK_NUMBER,
+		Default::default())?;
+	encoder.preserve_acquisition_timestamps(object_footer.acquisition_start, object_footer.acquisition_end);
+	Ok(ObjectEncoder::Logical(Box::new(encoder)))
+}
+
+/// Reads the (decompressed) data of an object (or of a file of a logical object) of a shared [ZffReader].
+/// Each reader keeps its own position, so multiple readers can share the same [ZffReader].
+struct TranscodingReader<R: Read + Seek> {
+	reader: Rc<RefCell<ZffReader<R>>>,
+	object_number: u64,
+	file_number: Option<u64>,
+	position: u64,
+}
+
+impl<R: Read + Seek> TranscodingReader<R> {
+	fn new(reader: Rc<RefCell<ZffReader<R>>>, object_number: u64, file_number: Option<u64>) -> Self {
+		Self {
+			reader,
+			object_number,
+			file_number,
+			position: 0,
+		}
+	}
+}
+
+impl<R: Read + Seek> Read for TranscodingReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let mut reader = self.reader.borrow_mut();
+		reader.set_active_object(self.object_number)?;
+		if let Some(file_number) = self.file_number {
+			reader.set_active_file(file_number)?;
+		}
+		reader.seek(SeekFrom::Start(self.position))?;
+		let read_bytes = reader.read(buf)?;
+		self.position += read_bytes as u64;
+		Ok(read_bytes)
+	}
+}
@@ -1,4 +1,6 @@
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+// the memory mapping of segment files (see ZffReader::with_mmap) needs a single (audited) unsafe call.
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 #![deny(missing_docs)]
 //#![deny(warnings)]
 //! This crate provides the reference implementation of the forensic file format Zff.
@@ -2,7 +2,7 @@
 use std::io::{Read, Cursor};
 use std::path::PathBuf;
 use std::fs::File;
-use std::collections::HashMap;
+use std::collections::{HashMap, BTreeMap, BTreeSet};
 use std::time::SystemTime;
 use std::rc::Rc;
 use std::cell::RefCell;
@@ -13,19 +13,23 @@ use std::os::unix::fs::MetadataExt;
 use std::os::unix::fs::FileTypeExt;
 
 use crate::PreparedChunk;
+use crate::ChunkIntegrity;
 #[cfg(target_family = "unix")]
 use crate::SpecialFileEncodingInformation;
 // - internal
 use crate::{
 	Result,
-	io::buffer_chunk,
+	io::{buffer_chunk_with_strategy, BadBlockStrategy, IoInterruptRetries},
 	HeaderCoding,
 	HashType,
 	EncodingThreadPoolManager,
 	Signature,
 	ZffError,
 	ZffErrorKind,
-	FileTypeEncodingInformation
+	FileTypeEncodingInformation,
+	ERROR_ENCRYPTED_VIRTUAL_OBJECT,
+	ERROR_EMPTY_VIRTUAL_OBJECT_RANGE,
+	ERROR_VIRTUAL_OBJECT_RANGE_OUT_OF_BOUNDS,
 };
 
 #[cfg(feature = "log")]
@@ -40,11 +44,14 @@ use crate::{
 		FileType,
 		EncryptionInformation,
 		DeduplicationChunkMap,
+		ObjectType,
+		VirtualMappingInformation,
+		VirtualObjectMap,
 	},
-	footer::{ObjectFooterPhysical, ObjectFooterLogical},
+	footer::{ObjectFooterPhysical, ObjectFooterLogical, ObjectFooterVirtual, AcquisitionErrorEntry},
 	FileEncoder,
 };
-use super::chunking;
+use super::{chunking, same_bytes_chunking, finalize_encoded_chunk, ChunkEncodingWorkerPool};
 
 // - external
 use ed25519_dalek::SigningKey;
@@ -59,14 +66,20 @@ pub enum PreparedData {
 	PreparedFileHeader(Vec<u8>),
 	/// A prepared file footer.
 	PreparedFileFooter(Vec<u8>),
+	/// A prepared [VirtualMappingInformation].
+	PreparedVirtualMappingInformation(Vec<u8>),
+	/// A prepared [VirtualObjectMap].
+	PreparedVirtualObjectMap(Vec<u8>),
 }
 
-/// An encoder for each object. This is a wrapper Enum for [PhysicalObjectEncoder] and [LogicalObjectEncoder].
+/// An encoder for each object. This is a wrapper Enum for [PhysicalObjectEncoder], [LogicalObjectEncoder] and [VirtualObjectEncoder].
 pub enum ObjectEncoder<R: Read> {
 	/// Wrapper for [PhysicalObjectEncoder].
 	Physical(Box<PhysicalObjectEncoder<R>>),
 	/// Wrapper for [LogicalObjectEncoder].
 	Logical(Box<LogicalObjectEncoder>),
+	/// Wrapper for [VirtualObjectEncoder].
+	Virtual(Box<VirtualObjectEncoder>),
 }
 
 impl<R: Read> ObjectEncoder<R> {
@@ -75,6 +88,7 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => obj.obj_number(),
 			ObjectEncoder::Logical(obj) => obj.obj_number(),
+			ObjectEncoder::Virtual(obj) => obj.obj_number(),
 		}
 	}
 
@@ -83,6 +97,7 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => obj.current_chunk_number,
 			ObjectEncoder::Logical(obj) => obj.current_chunk_number,
+			ObjectEncoder::Virtual(obj) => obj.current_chunk_number,
 		}
 	}
 
@@ -91,6 +106,7 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => &obj.obj_header,
 			ObjectEncoder::Logical(obj) => &obj.obj_header,
+			ObjectEncoder::Virtual(obj) => &obj.obj_header,
 		}
 	}
 
@@ -99,6 +115,7 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => obj.get_encoded_header(),
 			ObjectEncoder::Logical(obj) => obj.get_encoded_header(),
+			ObjectEncoder::Virtual(obj) => obj.get_encoded_header(),
 		}
 	}
 
@@ -107,6 +124,7 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => obj.encryption_key.clone(),
 			ObjectEncoder::Logical(obj) => obj.encryption_key.clone(),
+			ObjectEncoder::Virtual(_) => None,
 		}
 	}
 
@@ -115,6 +133,7 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => obj.get_encoded_footer(),
 			ObjectEncoder::Logical(obj) => obj.get_encoded_footer(),
+			ObjectEncoder::Virtual(obj) => obj.get_encoded_footer(),
 		}
 	}
 
@@ -128,6 +147,17 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(obj) => obj.get_next_chunk(deduplication_map),
 			ObjectEncoder::Logical(obj) => obj.get_next_data(current_offset, current_segment_no, deduplication_map),
+			ObjectEncoder::Virtual(obj) => obj.get_next_data(current_offset, current_segment_no),
+		}
+	}
+
+	/// Returns the number of bytes read from the underlying input data so far.
+	/// Will return 0 if the object encoder is not a physical object encoder.
+	pub fn read_bytes_underlying_data(&self) -> u64 {
+		match self {
+			ObjectEncoder::Physical(obj) => obj.read_bytes_underlying_data,
+			ObjectEncoder::Logical(_) => 0,
+			ObjectEncoder::Virtual(_) => 0,
 		}
 	}
 
@@ -137,6 +167,45 @@ impl<R: Read> ObjectEncoder<R> {
 		match self {
 			ObjectEncoder::Physical(_) => None,
 			ObjectEncoder::Logical(obj) => Some(obj.files.len() as u64),
+			ObjectEncoder::Virtual(_) => None,
+		}
+	}
+
+	/// Sets the first chunk number of the object (the chunk numbers of consecutive objects have to be continuous).
+	/// This has to be called before the first data of the object will be read.
+	pub(crate) fn set_initial_chunk_number(&mut self, chunk_number: u64) {
+		match self {
+			ObjectEncoder::Physical(obj) => {
+				// preserved bad chunks and read errors (see PhysicalObjectEncoder::preserve_acquisition_information) have to be moved, too.
+				let previous_chunk_number = obj.initial_chunk_number;
+				obj.bad_chunks = obj.bad_chunks.iter().map(|bad_chunk| bad_chunk - previous_chunk_number + chunk_number).collect();
+				for entry in &mut obj.error_log {
+					entry.chunk_number = entry.chunk_number - previous_chunk_number + chunk_number;
+				}
+				obj.current_chunk_number = chunk_number;
+				obj.initial_chunk_number = chunk_number;
+				obj.read_ahead_chunk_number = chunk_number;
+			},
+			ObjectEncoder::Logical(obj) => {
+				obj.current_chunk_number = chunk_number;
+				if let Some(file_encoder) = &mut obj.current_file_encoder {
+					file_encoder.set_initial_chunk_number(chunk_number);
+				}
+			},
+			ObjectEncoder::Virtual(obj) => obj.current_chunk_number = chunk_number,
+		}
+	}
+
+	/// Returns the information, which are needed to refer to this object by a virtual object
+	/// (only available for physical objects).
+	pub(crate) fn passive_object_information(&self) -> Option<PassiveObjectInformation> {
+		match self {
+			ObjectEncoder::Physical(obj) => Some(PassiveObjectInformation {
+				first_chunk_number: obj.initial_chunk_number,
+				chunk_size: obj.obj_header.chunk_size,
+				length_of_data: obj.read_bytes_underlying_data,
+			}),
+			_ => None,
 		}
 	}
 }
@@ -150,20 +219,40 @@ pub struct PhysicalObjectEncoder<R: Read> {
 	current_chunk_number: u64,
 	initial_chunk_number: u64,
 	encoding_thread_pool_manager: EncodingThreadPoolManager,
+	/// the optional worker pool to encode multiple chunks in parallel.
+	chunk_encoding_workers: Option<ChunkEncodingWorkerPool>,
+	/// the number of the next chunk, which will be read from the underlying data (by using the worker pool).
+	read_ahead_chunk_number: u64,
+	read_ahead_eof: bool,
 	signing_key: Option<SigningKey>,
 	encryption_key: Option<Vec<u8>>,
 	acquisition_start: u64,
 	acquisition_end: u64,
+	bad_block_strategy: Option<BadBlockStrategy>,
+	io_interrupt_retries: IoInterruptRetries,
+	/// the numbers of the chunks, which contain unreadable sectors.
+	bad_chunks: BTreeSet<u64>,
+	error_log: Vec<AcquisitionErrorEntry>, // the read errors of the input data
+	preserve_acquisition_information: bool, // the acquisition information were taken over from an existing object (e.g. by transcoding).
 }
 
 impl<R: Read> PhysicalObjectEncoder<R> {
-	/// Returns a new [PhysicalObjectEncoder] by the given values.
+	/// Returns a new [PhysicalObjectEncoder] by the given values.  
+	/// If more than one encoding thread is given, the chunks will be compressed, hashed (xxhash) and encrypted
+	/// by the appropriate number of worker threads in parallel (the data will be read ahead in sequential order).  
+	/// Read errors of the underlying data will be handled by the given [BadBlockStrategy] (if set),
+	/// interrupted read operations will be retried like defined by the given [IoInterruptRetries].
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		obj_header: ObjectHeader,
 		reader: R,
 		hash_types: Vec<HashType>,
 		signing_key_bytes: Option<Vec<u8>>,
-		current_chunk_number: u64) -> Result<PhysicalObjectEncoder<R>> {
+		current_chunk_number: u64,
+		encoding_threads: Option<usize>,
+		chunk_integrity: ChunkIntegrity,
+		bad_block_strategy: Option<BadBlockStrategy>,
+		io_interrupt_retries: IoInterruptRetries) -> Result<PhysicalObjectEncoder<R>> {
 		
 		let signing_key = match &signing_key_bytes {
 	    	Some(bytes) => Some(Signature::bytes_to_signingkey(bytes)?),
@@ -179,12 +268,24 @@ impl<R: Read> PhysicalObjectEncoder<R> {
 	    	(obj_header.encode_directly(), None)
 	    };
 
-		let mut encoding_thread_pool_manager = EncodingThreadPoolManager::new(
-			obj_header.compression_header.clone(), obj_header.chunk_size as usize);
+		let mut encoding_thread_pool_manager = EncodingThreadPoolManager::new(obj_header.compression_header.clone(), chunk_integrity);
 
 	    for h_type in hash_types {
 			encoding_thread_pool_manager.add_hashing_thread(h_type.clone());
 	    };
+
+		let chunk_encoding_workers = match encoding_threads {
+			Some(threads) if threads > 1 => {
+				let encryption = match (&encryption_key, &obj_header.encryption_header) {
+					(Some(key), Some(encryption_header)) => Some(EncryptionInformation::new(
+						key.clone(), encryption_header.algorithm.clone(), encryption_header.nonce_extension)),
+					_ => None,
+				};
+				Some(ChunkEncodingWorkerPool::new(
+					threads, obj_header.compression_header.clone(), obj_header.chunk_size as usize, encryption, chunk_integrity))
+			},
+			_ => None,
+		};
 		
 		Ok(Self {
 			obj_header,
@@ -193,13 +294,34 @@ impl<R: Read> PhysicalObjectEncoder<R> {
 			current_chunk_number,
 			initial_chunk_number: current_chunk_number,
 			encoding_thread_pool_manager,
+			chunk_encoding_workers,
+			read_ahead_chunk_number: current_chunk_number,
+			read_ahead_eof: false,
 			encryption_key,
 			signing_key,
 			acquisition_start: 0,
 			acquisition_end: 0,
+			bad_block_strategy,
+			io_interrupt_retries,
+			bad_chunks: BTreeSet::new(),
+			error_log: Vec::new(),
+			preserve_acquisition_information: false,
 		})
 	}
 
+	/// Takes over the acquisition information of the given footer of an already acquired object (e.g. to transcode this object):
+	/// the acquisition timestamps will be preserved instead of set while encoding and the bad chunks and the read errors
+	/// will be applied to the appropriate chunks of this encoder (the chunks will be flagged as erroneous, too).
+	pub(crate) fn preserve_acquisition_information(&mut self, object_footer: &ObjectFooterPhysical) {
+		self.acquisition_start = object_footer.acquisition_start;
+		self.acquisition_end = object_footer.acquisition_end;
+		let initial_chunk_number = self.initial_chunk_number;
+		let rebase = |chunk_number: u64| chunk_number.saturating_sub(object_footer.first_chunk_number) + initial_chunk_number;
+		self.bad_chunks = object_footer.bad_chunks.iter().map(|chunk_number| rebase(*chunk_number)).collect();
+		self.error_log = object_footer.error_log.iter().map(|entry| AcquisitionErrorEntry::new(rebase(entry.chunk_number), entry.message.as_str())).collect();
+		self.preserve_acquisition_information = true;
+	}
+
 	/// Returns the current chunk number.
 	pub fn object_header(&self) -> &ObjectHeader {
 		&self.obj_header
@@ -230,6 +352,9 @@ impl<R: Read> PhysicalObjectEncoder<R> {
 		&mut self,
 		deduplication_map: Option<&mut DeduplicationChunkMap>,
 		) -> Result<PreparedData> {
+		if self.chunk_encoding_workers.is_some() {
+			return self.get_next_chunk_parallel(deduplication_map);
+		}
 			
 		// checks and adds a deduplication thread to the internal thread manager (check is included in the add_deduplication_thread method)
 		if deduplication_map.is_some() {
@@ -238,44 +363,119 @@ impl<R: Read> PhysicalObjectEncoder<R> {
 
 		// prepare chunked data:
 	    let chunk_size = self.obj_header.chunk_size as usize;
-	    let buffered_chunk = buffer_chunk(&mut self.underlying_data, chunk_size)?;
+	    let buffered_chunk = buffer_chunk_with_strategy(&mut self.underlying_data, chunk_size, self.bad_block_strategy.as_ref(), &self.io_interrupt_retries)?;
 	    self.read_bytes_underlying_data += buffered_chunk.bytes_read;
 	    if buffered_chunk.buffer.is_empty() {
 	    	return Err(ZffError::new(ZffErrorKind::ReadEOF, ""));
 	    };
+	    if buffered_chunk.error_flag {
+	    	self.bad_chunks.insert(self.current_chunk_number);
+	    	let chunk_number = self.current_chunk_number;
+	    	self.error_log.extend(buffered_chunk.error_messages.iter().map(|message| AcquisitionErrorEntry::new(chunk_number, message.as_str())));
+	    }
 
-		self.encoding_thread_pool_manager.update(buffered_chunk.buffer);
+		let samebyte = self.encoding_thread_pool_manager.update_same_bytes_checked(
+			buffered_chunk.buffer, buffered_chunk.bytes_read, chunk_size as u64);
 
-		let encryption_algorithm = self.obj_header.encryption_header.as_ref().map(|encryption_header| &encryption_header.algorithm);
-		let encryption_key = if let Some(encryption_header) = &self.obj_header.encryption_header {
-			match encryption_header.get_encryption_key_ref() {
-				Some(key) => Some(key),
+		let encryption_information = if let Some(encryption_header) = &self.obj_header.encryption_header {
+			match encryption_header.get_encryption_key() {
+				Some(key) => Some(EncryptionInformation::new(key, encryption_header.algorithm.clone(), encryption_header.nonce_extension)),
 				None => return Err(ZffError::new(ZffErrorKind::MissingEncryptionKey, self.obj_header.object_number.to_string()))
 			}
 	    } else {
 	    	None
 	    };
 
-		let chunk = chunking(
-			&mut self.encoding_thread_pool_manager,
+		let chunk = match samebyte {
+			Some(samebyte) => same_bytes_chunking(
+				&self.encoding_thread_pool_manager,
+				self.current_chunk_number,
+				samebyte,
+				encryption_information.as_ref(),
+			)?,
+			None => chunking(
+				&mut self.encoding_thread_pool_manager,
+				self.current_chunk_number,
+				buffered_chunk.bytes_read,
+				chunk_size as u64,
+				deduplication_map,
+				encryption_information.as_ref(),
+				false, // there is no empty file flag for a physical object
+			)?,
+		};
+		let chunk = self.set_error_flag(chunk);
+	    
+		self.current_chunk_number += 1;
+	    Ok(PreparedData::PreparedChunk(chunk))
+	}
+
+	// reads the underlying data ahead to keep the worker pool busy and returns the next encoded chunk in sequential order.
+	// The deduplication map will be updated here (and not by the workers) to ensure the sequential order.
+	fn get_next_chunk_parallel(
+		&mut self,
+		deduplication_map: Option<&mut DeduplicationChunkMap>,
+		) -> Result<PreparedData> {
+		let chunk_size = self.obj_header.chunk_size as usize;
+		// unwrap is safe here, because this method is only called if the worker pool exists.
+		let chunk_encoding_workers = self.chunk_encoding_workers.as_mut().unwrap();
+		while !self.read_ahead_eof && !chunk_encoding_workers.is_full() {
+			let buffered_chunk = buffer_chunk_with_strategy(&mut self.underlying_data, chunk_size, self.bad_block_strategy.as_ref(), &self.io_interrupt_retries)?;
+			self.read_bytes_underlying_data += buffered_chunk.bytes_read;
+			if buffered_chunk.buffer.is_empty() {
+				self.read_ahead_eof = true;
+				break;
+			}
+			if buffered_chunk.error_flag {
+				self.bad_chunks.insert(self.read_ahead_chunk_number);
+				let chunk_number = self.read_ahead_chunk_number;
+				self.error_log.extend(buffered_chunk.error_messages.iter().map(|message| AcquisitionErrorEntry::new(chunk_number, message.as_str())));
+			}
+			// the hash values of the whole object have to be calculated in sequential order.
+			self.encoding_thread_pool_manager.update_hashing_threads(buffered_chunk.buffer.clone());
+			chunk_encoding_workers.send(
+				self.read_ahead_chunk_number,
+				buffered_chunk.buffer,
+				buffered_chunk.bytes_read,
+				deduplication_map.is_some())?;
+			self.read_ahead_chunk_number += 1;
+		}
+		if chunk_encoding_workers.is_empty() {
+			return Err(ZffError::new(ZffErrorKind::ReadEOF, ""));
+		}
+
+		let encoded_chunk = chunk_encoding_workers.receive(self.current_chunk_number)?;
+		let encryption_information = match (&self.encryption_key, &self.obj_header.encryption_header) {
+			(Some(key), Some(encryption_header)) => Some(EncryptionInformation::new(
+				key.clone(), encryption_header.algorithm.clone(), encryption_header.nonce_extension)),
+			_ => None,
+		};
+		let chunk = finalize_encoded_chunk(
+			encoded_chunk,
 			self.current_chunk_number,
-			buffered_chunk.bytes_read,
-			chunk_size as u64,
 			deduplication_map,
-			encryption_key,
-			encryption_algorithm,
-			false, // there is no empty file flag for a physical object
+			encryption_information.as_ref(),
 		)?;
-	    
+		let chunk = self.set_error_flag(chunk);
+
 		self.current_chunk_number += 1;
-	    Ok(PreparedData::PreparedChunk(chunk))
+		Ok(PreparedData::PreparedChunk(chunk))
+	}
+
+	// sets the error flag of the given chunk, if the current chunk contains unreadable sectors.
+	fn set_error_flag(&self, mut chunk: PreparedChunk) -> PreparedChunk {
+		if self.bad_chunks.contains(&self.current_chunk_number) {
+			chunk.set_error_flag();
+		}
+		chunk
 	}
 
 	/// Generates a appropriate footer. Attention: A call of this method ...
-	/// - sets the acquisition end time to the current time
+	/// - sets the acquisition end time to the current time (if the acquisition information were not preserved)
 	/// - finalizes the underlying hashing threads
 	pub fn get_encoded_footer(&mut self) -> Result<Vec<u8>> {
-		self.acquisition_end = OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64;
+		if !self.preserve_acquisition_information {
+			self.acquisition_end = OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64;
+		}
 		let mut hash_values = Vec::new();
 	    for (hash_type, hash) in self.encoding_thread_pool_manager.hashing_threads.finalize_all() {
 	        let mut hash_value = HashValue::new_empty(hash_type.clone());
@@ -291,7 +491,7 @@ impl<R: Read> PhysicalObjectEncoder<R> {
 		hashes_to_log(self.obj_header.object_number, None, &hash_values);
 
 	    let hash_header = HashHeader::new(hash_values);
-		let footer = ObjectFooterPhysical::new(
+		let mut footer = ObjectFooterPhysical::new(
 			self.obj_number(),
 			self.acquisition_start,
 			self.acquisition_end,
@@ -299,12 +499,17 @@ impl<R: Read> PhysicalObjectEncoder<R> {
 			self.initial_chunk_number,
 			self.current_chunk_number - self.initial_chunk_number,
 			hash_header);
+		if self.preserve_acquisition_information || self.bad_block_strategy.as_ref().map_or(false, |strategy| strategy.record_bad_chunks) {
+			footer.bad_chunks = self.bad_chunks.iter().copied().collect();
+		}
+		footer.error_log = self.error_log.clone();
 
 		if let Some(encryption_key) = &self.encryption_key {
 			let encryption_information = EncryptionInformation {
 				encryption_key: encryption_key.to_vec(),
 				// unwrap should be safe here: there should not an encryption key exists without an encryption header.
-				algorithm: self.obj_header.encryption_header.clone().unwrap().algorithm.clone()
+				algorithm: self.obj_header.encryption_header.clone().unwrap().algorithm.clone(),
+				nonce_extension: self.obj_header.encryption_header.as_ref().unwrap().nonce_extension,
 			};
 	    	footer.encrypt_directly(encryption_information)
 	    } else {
@@ -328,7 +533,7 @@ pub struct LogicalObjectEncoder {
 	/// The appropriate original object header
 	obj_header: ObjectHeader,
 	//encoded_header_remaining_bytes: usize,
-	files: Vec<(PathBuf, FileHeader)>,
+	files: Vec<(FileSource, FileHeader)>,
 	current_file_encoder: Option<FileEncoder>,
 	current_file_header_read: bool,
 	current_file_number: u64,
@@ -341,19 +546,21 @@ pub struct LogicalObjectEncoder {
 	directory_children: HashMap<u64, Vec<u64>>, //<directory file number, Vec<child filenumber>>
 	object_footer: ObjectFooterLogical,
 	empty_file_eof: bool,
+	io_interrupt_retries: IoInterruptRetries,
+	preserve_acquisition_timestamps: bool, // the acquisition timestamps were taken over from an existing object (e.g. by transcoding).
 }
 
 impl LogicalObjectEncoder {
 	/// Returns the encoded footer for this object.
-	/// Sets the acquisition end timestamp of the object footer to current system time.
+	/// Sets the acquisition end timestamp of the object footer to current system time (if the timestamps were not preserved).
 	pub fn get_encoded_footer(&mut self) -> Result<Vec<u8>> {
-		let systemtime = OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64;
-		self.object_footer.set_acquisition_end(systemtime);
+		self.finish_acquisition();
 		if let Some(encryption_key) = &self.encryption_key {
 			let encryption_information = EncryptionInformation {
 				encryption_key: encryption_key.to_vec(),
 				// unwrap should be safe here: there should not an encryption key exists without an encryption header.
-				algorithm: self.obj_header.encryption_header.clone().unwrap().algorithm.clone()
+				algorithm: self.obj_header.encryption_header.clone().unwrap().algorithm.clone(),
+				nonce_extension: self.obj_header.encryption_header.as_ref().unwrap().nonce_extension,
 			};
 	    	self.object_footer.encrypt_directly(encryption_information)
 	    } else {
@@ -367,6 +574,7 @@ impl LogicalObjectEncoder {
 	}
 
 	/// Returns a new [LogicalObjectEncoder] by the given values.
+	/// Interrupted read operations of the files will be retried like defined by the given [IoInterruptRetries].
 	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		obj_header: ObjectHeader,
@@ -377,7 +585,62 @@ impl LogicalObjectEncoder {
 		symlink_real_paths: HashMap<u64, PathBuf>, //File number <-> Symlink real path
 		hardlink_map: HashMap<u64, u64>, // <filenumber, filenumber of hardlink>
 		directory_children: HashMap<u64, Vec<u64>>,
-		current_chunk_number: u64) -> Result<LogicalObjectEncoder> {		
+		current_chunk_number: u64,
+		chunk_integrity: ChunkIntegrity,
+		io_interrupt_retries: IoInterruptRetries) -> Result<LogicalObjectEncoder> {
+		let files = files.into_iter().map(|(path, file_header)| (FileSource::Path(path), file_header)).collect();
+		Self::with_file_sources(
+			obj_header,
+			files,
+			root_dir_filenumbers,
+			hash_types,
+			signing_key_bytes,
+			symlink_real_paths,
+			hardlink_map,
+			directory_children,
+			current_chunk_number,
+			chunk_integrity,
+			io_interrupt_retries)
+	}
+
+	/// Returns a new [LogicalObjectEncoder], which reads the content of each file from the given reader.
+	/// The readers have to provide the already encoded content of the appropriate file type
+	/// (e.g. the encoded real path of a symlink), like it is returned by a [ZffReader](crate::io::zffreader::ZffReader).
+	pub(crate) fn with_file_readers(
+		obj_header: ObjectHeader,
+		files: Vec<(Box<dyn Read>, FileHeader)>,
+		root_dir_filenumbers: Vec<u64>,
+		hash_types: Vec<HashType>,
+		current_chunk_number: u64,
+		chunk_integrity: ChunkIntegrity) -> Result<LogicalObjectEncoder> {
+		let files = files.into_iter().map(|(reader, file_header)| (FileSource::Reader(reader), file_header)).collect();
+		Self::with_file_sources(
+			obj_header,
+			files,
+			root_dir_filenumbers,
+			hash_types,
+			None,
+			HashMap::new(),
+			HashMap::new(),
+			HashMap::new(),
+			current_chunk_number,
+			chunk_integrity,
+			IoInterruptRetries::default())
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn with_file_sources(
+		obj_header: ObjectHeader,
+		files: Vec<(FileSource, FileHeader)>,
+		root_dir_filenumbers: Vec<u64>,
+		hash_types: Vec<HashType>,
+		signing_key_bytes: Option<Vec<u8>>,
+		symlink_real_paths: HashMap<u64, PathBuf>, //File number <-> Symlink real path
+		hardlink_map: HashMap<u64, u64>, // <filenumber, filenumber of hardlink>
+		directory_children: HashMap<u64, Vec<u64>>,
+		current_chunk_number: u64,
+		chunk_integrity: ChunkIntegrity,
+		io_interrupt_retries: IoInterruptRetries) -> Result<LogicalObjectEncoder> {		
 
 		// ensures that the encryption key is available in decrypted form.
 		let (_encoded_header, encryption_key) = if let Some(encryption_header) = &obj_header.encryption_header {
@@ -394,8 +657,7 @@ impl LogicalObjectEncoder {
 	    	None => None
 	    };
 
-		let mut encoding_thread_pool_manager = EncodingThreadPoolManager::new(
-			obj_header.compression_header.clone(), obj_header.chunk_size as usize);
+		let mut encoding_thread_pool_manager = EncodingThreadPoolManager::new(obj_header.compression_header.clone(), chunk_integrity);
 
 	    for h_type in hash_types {
 			encoding_thread_pool_manager.add_hashing_thread(h_type.clone());
@@ -404,72 +666,35 @@ impl LogicalObjectEncoder {
 		let encoding_thread_pool_manager = Rc::new(RefCell::new(encoding_thread_pool_manager));
 
 		let mut files = files;
-		let (path, current_file_header) = match files.pop() {
-			Some((path, header)) => (path, header),
+		let (source, current_file_header) = match files.pop() {
+			Some((source, header)) => (source, header),
 			None => return Err(ZffError::new(ZffErrorKind::NoFilesLeft, "There is no input file"))
 		};
-		//open first file path - if the path is not accessable, create an empty reader.
-		#[cfg_attr(target_os = "windows", allow(clippy::needless_borrows_for_generic_args))]
-		let reader = match File::open(&path) {
-			Ok(reader) => Box::new(reader),
-			Err(_) => create_empty_reader()
-		};
-
 		let current_file_number = current_file_header.file_number;
 
 		let encryption_information = if let Some(encryption_key) = &encryption_key {
-			obj_header.encryption_header.clone().map(|enc_header| EncryptionInformation::new(encryption_key.to_vec(), enc_header.algorithm.clone()))
+			obj_header.encryption_header.clone().map(|enc_header| EncryptionInformation::new(encryption_key.to_vec(), enc_header.algorithm.clone(), enc_header.nonce_extension))
 		} else {
 			None
 		};
 
-		let filetype_encoding_information = match current_file_header.file_type {
-			FileType::File => FileTypeEncodingInformation::File,
-			FileType::Directory => {
-				let mut children = Vec::new();
-				for child in directory_children.get(&current_file_number).unwrap_or(&Vec::new()) {
-					children.push(*child);
-				};
-				FileTypeEncodingInformation::Directory(children)
-			},
-			FileType::Symlink => {
-				let real_path = symlink_real_paths.get(&current_file_number).unwrap_or(&PathBuf::new()).clone();
-				FileTypeEncodingInformation::Symlink(real_path)
-			},
-			FileType::Hardlink => {
-				let hardlink_filenumber = hardlink_map.get(&current_file_number).unwrap_or(&0);
-				FileTypeEncodingInformation::Hardlink(*hardlink_filenumber)
-			},
-			#[cfg(target_family = "windows")]
-			FileType::SpecialFile => unreachable!("Special files are not supported on Windows"),
-			#[cfg(target_family = "unix")]
-			FileType::SpecialFile => {
-				let metadata = std::fs::metadata(&path)?;
-
-				let specialfile_info = if metadata.file_type().is_char_device() {
-					SpecialFileEncodingInformation::Char(metadata.rdev())
-				} else if metadata.file_type().is_block_device() {
-					SpecialFileEncodingInformation::Block(metadata.rdev())
-				} else if metadata.file_type().is_fifo() {
-					SpecialFileEncodingInformation::Fifo(metadata.rdev())
-				} else if metadata.file_type().is_socket() {
-					SpecialFileEncodingInformation::Socket(metadata.rdev())
-				} else {
-					return Err(ZffError::new(ZffErrorKind::UnknownFileType, "Unknown special file type"));
-				};
-				FileTypeEncodingInformation::SpecialFile(specialfile_info)
-			},
-		};
+		let (reader, filetype_encoding_information) = open_file_source(
+			source,
+			&current_file_header,
+			&symlink_real_paths,
+			&hardlink_map,
+			&directory_children)?;
 
 		let first_file_encoder = Some(FileEncoder::new(
 			current_file_header,
 			obj_header.clone(),
-			Box::new(reader), 
+			reader,
 			Rc::clone(&encoding_thread_pool_manager),
 			signing_key.clone(),
 			encryption_information, 
 			current_chunk_number, 
-			filetype_encoding_information)?);
+			filetype_encoding_information,
+			io_interrupt_retries)?);
 		
 		let mut object_footer = ObjectFooterLogical::new_empty(obj_header.object_number);
 		for filenumber in root_dir_filenumbers {
@@ -491,6 +716,8 @@ impl LogicalObjectEncoder {
 			directory_children,
 			object_footer,
 			empty_file_eof: false,
+			io_interrupt_retries,
+			preserve_acquisition_timestamps: false,
 		})
 	}
 
@@ -544,6 +771,10 @@ impl LogicalObjectEncoder {
 					match file_encoder.get_next_chunk(deduplication_map) {
 						Ok(data) => {
 							self.current_chunk_number += 1;
+							// an empty file consists of a single empty chunk, the file footer has to follow directly.
+							if data.flags().empty_file {
+								self.empty_file_eof = true;
+							}
 							return Ok(PreparedData::PreparedChunk(data));
 						},
 						Err(e) => match e.get_kind() {
@@ -563,69 +794,32 @@ impl LogicalObjectEncoder {
 				self.object_footer.add_file_footer_segment_number(self.current_file_number, current_segment_no);
 				self.object_footer.add_file_footer_offset(self.current_file_number, current_offset);
 				
-				let (path, current_file_header) = match self.files.pop() {
-					Some((path, header)) => (path, header),
+				let (source, current_file_header) = match self.files.pop() {
+					Some((source, header)) => (source, header),
 					None => {
 						// if no files left, the acquisition ends and the date will be written to the object footer.
 						// The appropriate file footer will be returned.
-						self.object_footer.set_acquisition_end(OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64);
+						self.finish_acquisition();
 						self.current_file_encoder = None;
 						return Ok(prepared_file_footer);
 					}
 				};
-
-				#[cfg_attr(target_os = "windows", allow(clippy::needless_borrows_for_generic_args))]
-				let reader = match File::open(&path) {
-					Ok(reader) => Box::new(reader),
-					Err(_) => create_empty_reader()
-				};
 		     	
 				self.current_file_number = current_file_header.file_number;
 
 				let encryption_information = if let Some(encryption_key) = &self.encryption_key {
 					self.obj_header.encryption_header.as_ref().map(|enc_header| EncryptionInformation::new(
-						encryption_key.to_vec(), enc_header.algorithm.clone()))
+						encryption_key.to_vec(), enc_header.algorithm.clone(), enc_header.nonce_extension))
 				} else {
 					None
 				};
 
-				let filetype_encoding_information = match current_file_header.file_type {
-					FileType::File => FileTypeEncodingInformation::File,
-					FileType::Directory => {
-						let mut children = Vec::new();
-						for child in self.directory_children.get(&self.current_file_number).unwrap_or(&Vec::new()) {
-							children.push(*child);
-						};
-						FileTypeEncodingInformation::Directory(children)
-					},
-					FileType::Symlink => {
-						let real_path = self.symlink_real_paths.get(&self.current_file_number).unwrap_or(&PathBuf::new()).clone();
-						FileTypeEncodingInformation::Symlink(real_path)
-					},
-					FileType::Hardlink => {
-						let hardlink_filenumber = self.hardlink_map.get(&self.current_file_number).unwrap_or(&0);
-						FileTypeEncodingInformation::Hardlink(*hardlink_filenumber)
-					},
-					#[cfg(target_family = "windows")]
-					FileType::SpecialFile => unreachable!("Special files are not supported on Windows"),
-					#[cfg(target_family = "unix")]
-					FileType::SpecialFile => {
-						let metadata = std::fs::metadata(&path)?;
-		
-						let specialfile_info = if metadata.file_type().is_char_device() {
-							SpecialFileEncodingInformation::Char(metadata.rdev())
-						} else if metadata.file_type().is_block_device() {
-							SpecialFileEncodingInformation::Block(metadata.rdev())
-						} else if metadata.file_type().is_fifo() {
-							SpecialFileEncodingInformation::Fifo(metadata.rdev())
-						} else if metadata.file_type().is_socket() {
-							SpecialFileEncodingInformation::Socket(metadata.rdev())
-						} else {
-							return Err(ZffError::new(ZffErrorKind::UnknownFileType, "Unknown special file type"));
-						};
-						FileTypeEncodingInformation::SpecialFile(specialfile_info)
-					},
-				};
+				let (reader, filetype_encoding_information) = open_file_source(
+					source,
+					&current_file_header,
+					&self.symlink_real_paths,
+					&self.hardlink_map,
+					&self.directory_children)?;
        			
 			    self.current_file_header_read = false;
 				self.current_file_encoder = Some(FileEncoder::new(
@@ -636,16 +830,33 @@ impl LogicalObjectEncoder {
 					self.signing_key.clone(),
 					encryption_information, 
 					self.current_chunk_number, 
-					filetype_encoding_information)?);
+					filetype_encoding_information,
+					self.io_interrupt_retries)?);
 				Ok(prepared_file_footer)
 			},
 			None => {
-				self.object_footer.set_acquisition_end(OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64);
+				self.finish_acquisition();
 				Err(ZffError::new(ZffErrorKind::ReadEOF, ""))
 			},
 		}	
 	}
 
+	/// Takes over the acquisition timestamps of an already acquired object (e.g. to transcode this object),
+	/// so the timestamps of the object footer will not be set while encoding.
+	/// The acquisition timestamps of the file footers will be set while encoding, anyway.
+	pub(crate) fn preserve_acquisition_timestamps(&mut self, acquisition_start: u64, acquisition_end: u64) {
+		self.object_footer.set_acquisition_start(acquisition_start);
+		self.object_footer.set_acquisition_end(acquisition_end);
+		self.preserve_acquisition_timestamps = true;
+	}
+
+	// sets the acquisition end timestamp of the object footer to the current time (if the timestamps were not preserved).
+	fn finish_acquisition(&mut self) {
+		if !self.preserve_acquisition_timestamps {
+			self.object_footer.set_acquisition_end(OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64);
+		}
+	}
+
 	/// Returns the underlying encryption key (if available).
 	pub fn encryption_key(&self) -> Option<Vec<u8>> {
 		self.encryption_key.clone()
@@ -653,6 +864,223 @@ impl LogicalObjectEncoder {
 
 }
 
+/// A range of the data of a (physical) passive object, which is referred by a virtual object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VirtualObjectRange {
+	/// The object number of the passive object.
+	pub object_number: u64,
+	/// The offset of the range in the data of the passive object.
+	pub offset: u64,
+	/// The length of the range.
+	pub length: u64,
+}
+
+impl VirtualObjectRange {
+	/// Returns a new [VirtualObjectRange] with the given values.
+	pub fn new(object_number: u64, offset: u64, length: u64) -> Self {
+		Self {
+			object_number,
+			offset,
+			length,
+		}
+	}
+}
+
+/// Contains the information of a physical object, which are needed to refer to the object by a virtual object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PassiveObjectInformation {
+	pub first_chunk_number: u64,
+	pub chunk_size: u64,
+	pub length_of_data: u64,
+}
+
+/// The [VirtualObjectEncoder] can be used to encode a virtual object.  
+/// A virtual object contains no chunks, but links the given ranges of other (physical) objects in the given order.
+pub struct VirtualObjectEncoder {
+	/// The appropriate object header
+	obj_header: ObjectHeader,
+	ranges: Vec<VirtualObjectRange>,
+	/// The mapping information of the ranges (will be set by resolving the passive objects).
+	mapping_information: Vec<VirtualMappingInformation>,
+	current_mapping: usize,
+	current_virtual_offset: u64,
+	offset_map: BTreeMap<u64, (u64, u64)>, // <virtual offset, (segment number, offset of the VMI)>
+	object_map_written: bool,
+	current_chunk_number: u64,
+	object_footer: ObjectFooterVirtual,
+}
+
+impl VirtualObjectEncoder {
+	/// Returns a new [VirtualObjectEncoder] by the given values.
+	/// # Error
+	/// Fails if the object header is not a header of a virtual object, if the object header contains an encryption header
+	/// (encrypted virtual objects are not supported yet) or if no (or an empty) range is given.
+	pub fn new(obj_header: ObjectHeader, ranges: Vec<VirtualObjectRange>, current_chunk_number: u64) -> Result<VirtualObjectEncoder> {
+		if obj_header.object_type != ObjectType::Virtual {
+			return Err(ZffError::new(ZffErrorKind::MismatchObjectType, obj_header.object_number.to_string()));
+		}
+		if obj_header.encryption_header.is_some() {
+			return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_ENCRYPTED_VIRTUAL_OBJECT));
+		}
+		if ranges.is_empty() || ranges.iter().any(|range| range.length == 0) {
+			return Err(ZffError::new(ZffErrorKind::InvalidOption, ERROR_EMPTY_VIRTUAL_OBJECT_RANGE));
+		}
+		let passive_objects = ranges.iter().map(|range| range.object_number).collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>();
+		let length_of_data = ranges.iter().map(|range| range.length).sum();
+		let object_footer = ObjectFooterVirtual::with_data(obj_header.object_number, 0, passive_objects, length_of_data, 0, 0);
+		Ok(Self {
+			obj_header,
+			ranges,
+			mapping_information: Vec::new(),
+			current_mapping: 0,
+			current_virtual_offset: 0,
+			offset_map: BTreeMap::new(),
+			object_map_written: false,
+			current_chunk_number,
+			object_footer,
+		})
+	}
+
+	/// Returns the appropriate object number.
+	pub fn obj_number(&self) -> u64 {
+		self.obj_header.object_number
+	}
+
+	/// Returns the object header.
+	pub fn object_header(&self) -> &ObjectHeader {
+		&self.obj_header
+	}
+
+	/// Returns the ranges of the passive objects.
+	pub fn ranges(&self) -> &Vec<VirtualObjectRange> {
+		&self.ranges
+	}
+
+	/// Creates the [VirtualMappingInformation] for all ranges by using the given information of the passive objects.
+	pub(crate) fn resolve_passive_objects(&mut self, passive_objects: &HashMap<u64, PassiveObjectInformation>) -> Result<()> {
+		let mut mapping_information = Vec::with_capacity(self.ranges.len());
+		for range in &self.ranges {
+			let passive_object = match passive_objects.get(&range.object_number) {
+				Some(passive_object) => passive_object,
+				None => return Err(ZffError::new(ZffErrorKind::MissingObjectNumber, range.object_number.to_string())),
+			};
+			if range.offset.checked_add(range.length).map_or(true, |end| end > passive_object.length_of_data) {
+				return Err(ZffError::new(
+					ZffErrorKind::InvalidOption,
+					format!("{ERROR_VIRTUAL_OBJECT_RANGE_OUT_OF_BOUNDS}{}", range.object_number)));
+			}
+			mapping_information.push(VirtualMappingInformation::with_data(
+				range.object_number,
+				passive_object.first_chunk_number + range.offset / passive_object.chunk_size,
+				range.offset % passive_object.chunk_size,
+				range.length));
+		}
+		self.mapping_information = mapping_information;
+		Ok(())
+	}
+
+	/// Returns the encoded object header.
+	/// Note: **A call of this method sets the creation timestamp to the current time**.
+	pub fn get_encoded_header(&mut self) -> Vec<u8> {
+		if self.object_footer.creation_timestamp == 0 {
+			self.object_footer.creation_timestamp = OffsetDateTime::from(SystemTime::now()).unix_timestamp() as u64;
+		}
+		self.obj_header.encode_directly()
+	}
+
+	/// Returns the next encoded data - an encoded [VirtualMappingInformation] for each range, followed by the [VirtualObjectMap].
+	pub fn get_next_data(&mut self, current_offset: u64, current_segment_no: u64) -> Result<PreparedData> {
+		if let Some(mapping_information) = self.mapping_information.get(self.current_mapping) {
+			self.offset_map.insert(self.current_virtual_offset, (current_segment_no, current_offset));
+			self.current_virtual_offset += mapping_information.length;
+			self.current_mapping += 1;
+			return Ok(PreparedData::PreparedVirtualMappingInformation(mapping_information.encode_directly()));
+		}
+		if !self.object_map_written {
+			self.object_map_written = true;
+			self.object_footer.virtual_object_map_offset = current_offset;
+			self.object_footer.virtual_object_map_segment_no = current_segment_no;
+			let mut offsetmaps = BTreeSet::new();
+			offsetmaps.insert(std::mem::take(&mut self.offset_map));
+			return Ok(PreparedData::PreparedVirtualObjectMap(VirtualObjectMap::with_data(offsetmaps).encode_directly()));
+		}
+		Err(ZffError::new(ZffErrorKind::ReadEOF, ""))
+	}
+
+	/// Returns the encoded footer for this object.
+	pub fn get_encoded_footer(&mut self) -> Result<Vec<u8>> {
+		Ok(self.object_footer.encode_directly())
+	}
+}
+
+// The source of the content of a file, which should be encoded by a [LogicalObjectEncoder].
+enum FileSource {
+	// the file will be read from the given path.
+	Path(PathBuf),
+	// the (already encoded) file content will be read from the given reader.
+	Reader(Box<dyn Read>),
+}
+
+// opens the given file source and returns the appropriate reader and the file type specific encoding information.
+fn open_file_source(
+	source: FileSource,
+	file_header: &FileHeader,
+	symlink_real_paths: &HashMap<u64, PathBuf>,
+	hardlink_map: &HashMap<u64, u64>,
+	directory_children: &HashMap<u64, Vec<u64>>) -> Result<(Box<dyn Read>, FileTypeEncodingInformation)> {
+	let path = match source {
+		// the content is already encoded, so it could be treated like a regular file.
+		FileSource::Reader(reader) => return Ok((reader, FileTypeEncodingInformation::File)),
+		FileSource::Path(path) => path,
+	};
+	//open the file path - if the path is not accessable, create an empty reader.
+	#[cfg_attr(target_os = "windows", allow(clippy::needless_borrows_for_generic_args))]
+	let reader: Box<dyn Read> = match File::open(&path) {
+		Ok(reader) => Box::new(reader),
+		Err(_) => create_empty_reader()
+	};
+
+	let file_number = file_header.file_number;
+	let filetype_encoding_information = match file_header.file_type {
+		FileType::File => FileTypeEncodingInformation::File,
+		FileType::Directory => {
+			let mut children = Vec::new();
+			for child in directory_children.get(&file_number).unwrap_or(&Vec::new()) {
+				children.push(*child);
+			};
+			FileTypeEncodingInformation::Directory(children)
+		},
+		FileType::Symlink => {
+			let real_path = symlink_real_paths.get(&file_number).unwrap_or(&PathBuf::new()).clone();
+			FileTypeEncodingInformation::Symlink(real_path)
+		},
+		FileType::Hardlink => {
+			let hardlink_filenumber = hardlink_map.get(&file_number).unwrap_or(&0);
+			FileTypeEncodingInformation::Hardlink(*hardlink_filenumber)
+		},
+		#[cfg(target_family = "windows")]
+		FileType::SpecialFile => unreachable!("Special files are not supported on Windows"),
+		#[cfg(target_family = "unix")]
+		FileType::SpecialFile => {
+			let metadata = std::fs::metadata(&path)?;
+
+			let specialfile_info = if metadata.file_type().is_char_device() {
+				SpecialFileEncodingInformation::Char(metadata.rdev())
+			} else if metadata.file_type().is_block_device() {
+				SpecialFileEncodingInformation::Block(metadata.rdev())
+			} else if metadata.file_type().is_fifo() {
+				SpecialFileEncodingInformation::Fifo(metadata.rdev())
+			} else if metadata.file_type().is_socket() {
+				SpecialFileEncodingInformation::Socket(metadata.rdev())
+			} else {
+				return Err(ZffError::new(ZffErrorKind::UnknownFileType, "Unknown special file type"));
+			};
+			FileTypeEncodingInformation::SpecialFile(specialfile_info)
+		},
+	};
+	Ok((reader, filetype_encoding_information))
+}
+
 fn create_empty_reader() -> Box<dyn Read> {
 	let buffer = Vec::<u8>::new();
 	let cursor = Cursor::new(buffer);
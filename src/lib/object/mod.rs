@@ -3,7 +3,7 @@ use std::sync::{
     Arc,
     RwLock, RwLockReadGuard,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, BTreeMap};
 use std::thread::{self};
 use std::io::copy as io_copy;
 
@@ -13,7 +13,6 @@ mod encoder;
 // - re-exports
 pub use encoder::*;
 
-use crate::io::calculate_xxhash;
 // - internal
 use crate::{
     Result,
@@ -21,12 +20,15 @@ use crate::{
     HashType,
     Hash,
     CompressionAlgorithm,
+    compression::{compress_buffer_brotli, compress_buffer_xz, compress_buffer_gzip},
 	PreparedChunk,
+	ChunkIntegrity,
     io::{buffer_chunk, check_same_byte},
-	header::{ChunkFlags, DeduplicationChunkMap},
+	header::{ChunkFlags, DeduplicationChunkMap, EncryptionInformation},
 	error::{ZffError, ZffErrorKind},
-	encryption::{Encryption, EncryptionAlgorithm},
+	encryption::Encryption,
 	ChunkContent,
+	constants::ERROR_CHUNK_ENCODING_WORKERS_STOPPED,
 };
 
 /// Indicates if the data are compressed or not.
@@ -47,7 +49,7 @@ pub(crate) enum CompressedData {
 /// The EncodingThreadPoolManager contains the following threads:
 /// - HashingThreadManager: The HashingThreadManager is used to manage the hashing threads.
 /// - CompressionThread: The CompressionThread is used to compress the data.
-/// - XxHashThread: The XxHashThread is used to calculate the xxh3 64bit of the data.
+/// - XxHashThread: The XxHashThread is used to calculate the integrity hash (xxh3 64bit by default) of the data.
 /// - SameBytesThread: The SameBytesThread is used to check if the data are same bytes or not.
 #[derive(Debug)]
 pub struct EncodingThreadPoolManager {
@@ -59,20 +61,26 @@ pub struct EncodingThreadPoolManager {
     xxhash_thread: XxHashThread,
 	/// the same bytes thread.
 	same_bytes_thread: SameBytesThread,
+	/// the algorithm of the integrity hash, which is calculated by the xxhash thread.
+	chunk_integrity: ChunkIntegrity,
+	/// the integrity hashes of full same bytes chunks (the chunk size is fixed for each manager).
+	same_bytes_integrity_hashes: HashMap<u8, u64>,
     /// the data, which will be used by the appropriate threads.
     pub data: Arc<RwLock<Vec<u8>>>, 
 }
 
 impl EncodingThreadPoolManager {
     /// creates a new ThreadPoolManager with the given number of hashing threads.
-    pub fn new(compression_header: CompressionHeader, chunk_size: usize) -> Self {
+    pub fn new(compression_header: CompressionHeader, chunk_integrity: ChunkIntegrity) -> Self {
         let data = Arc::new(RwLock::new(Vec::new()));
         let hashing_thread_manager = HashingThreadManager::new(Arc::clone(&data));
         Self {
             hashing_threads: hashing_thread_manager,
-            compression_thread: CompressionThread::new(compression_header, chunk_size, Arc::clone(&data)),
+            compression_thread: CompressionThread::new(compression_header, Arc::clone(&data)),
 			same_bytes_thread: SameBytesThread::new(Arc::clone(&data)),
-            xxhash_thread: XxHashThread::new(Arc::clone(&data)),
+            xxhash_thread: XxHashThread::new(Arc::clone(&data), chunk_integrity),
+			chunk_integrity,
+			same_bytes_integrity_hashes: HashMap::new(),
             data,
         }
     }
@@ -91,6 +99,32 @@ impl EncodingThreadPoolManager {
 		self.trigger();
     }
 
+	/// updates the data and triggers only the hashing threads (the other encoding steps will be done
+	/// by a [ChunkEncodingWorkerPool] in this case).
+	pub(crate) fn update_hashing_threads(&mut self, data: Vec<u8>) {
+		{
+			let mut w = self.data.write().unwrap();
+			*w = data;
+		}
+		self.hashing_threads.trigger();
+	}
+
+	/// updates the data and checks, if the data of a full chunk are same bytes (e.g. a hole of a sparse file).
+	/// In this case, only the hashing threads will be triggered (the compression and the integrity hash calculation
+	/// can be skipped, see [same_bytes_chunking]) and the appropriate byte will be returned.
+	/// Otherwise, all threads will be triggered (like [EncodingThreadPoolManager::update]).
+	pub(crate) fn update_same_bytes_checked(&mut self, data: Vec<u8>, samebyte_checklen_value: u64, chunk_size: u64) -> Option<u8> {
+		// if the length of the buffer is not equal the target chunk size, the same byte flag can not be set.
+		if samebyte_checklen_value != chunk_size || !check_same_byte(&data) {
+			self.update(data);
+			return None;
+		}
+		let samebyte = data[0];
+		self.same_bytes_integrity_hashes.entry(samebyte).or_insert_with(|| self.chunk_integrity.calculate(&data));
+		self.update_hashing_threads(data);
+		Some(samebyte)
+	}
+
 	/// finalizes all hashing threads and returns a `HashMap<HashType, Vec<u8>>` with the appropriate hash values.
 	pub fn finalize_all_hashing_threads(&mut self) -> HashMap<HashType, Vec<u8>> {
 		self.hashing_threads.finalize_all()
@@ -290,8 +324,8 @@ pub(crate) struct XxHashThread {
 }
 
 impl XxHashThread {
-	/// creates a new xxhash thread.
-	pub fn new(data: Arc<RwLock<Vec<u8>>>) -> Self {
+	/// creates a new xxhash thread, which calculates the integrity hash by using the given algorithm.
+	pub fn new(data: Arc<RwLock<Vec<u8>>>, chunk_integrity: ChunkIntegrity) -> Self {
 		let (trigger, trigger_receiver) = crossbeam::channel::unbounded::<crossbeam::sync::WaitGroup>();
 		let result = Arc::new(RwLock::new(0));
 		let c_result = Arc::clone(&result);
@@ -300,7 +334,7 @@ impl XxHashThread {
 			while let Ok(wg) = trigger_receiver.recv() {
 				let mut w_result = c_result.write().unwrap();
 				let r_data = c_data.read().unwrap();
-				*w_result = calculate_xxhash(&r_data);
+				*w_result = chunk_integrity.calculate(&r_data);
 				drop(wg);
 			}
 		});
@@ -340,7 +374,7 @@ pub(crate) struct CompressionThread {
 
 impl CompressionThread {
 	/// creates a new compression thread.
-	pub fn new(compression_header: CompressionHeader, chunk_size: usize, data: Arc<RwLock<Vec<u8>>>) -> Self {
+	pub fn new(compression_header: CompressionHeader, data: Arc<RwLock<Vec<u8>>>) -> Self {
 		let (trigger, trigger_receiver) = crossbeam::channel::unbounded::<crossbeam::sync::WaitGroup>();
 		let result = Arc::new(RwLock::new(CompressedData::Raw));
 		let c_result = Arc::clone(&result);
@@ -349,7 +383,7 @@ impl CompressionThread {
 			while let Ok(wg) = trigger_receiver.recv() {
 				let mut w_result = c_result.write().unwrap();
 				let r_data = c_data.read().unwrap();
-				*w_result = Self::compress_buffer(&r_data, chunk_size, &compression_header);
+				*w_result = Self::compress_buffer(&r_data, &compression_header);
 				drop(wg);
 			}
 		});
@@ -360,19 +394,24 @@ impl CompressionThread {
 		}
 	}
 
-	fn compress_buffer(buf: &std::sync::RwLockReadGuard<'_, Vec<u8>>, chunk_size: usize,compression_header: &CompressionHeader) -> CompressedData {
+	fn compress_buffer(buf: &[u8], compression_header: &CompressionHeader) -> CompressedData {
 		let compression_threshold = compression_header.threshold;
+		if compression_header.never_compress() {
+			return CompressedData::Raw;
+		}
 	
 		match compression_header.algorithm {
 			CompressionAlgorithm::None => CompressedData::Raw,
 			CompressionAlgorithm::Zstd => {
 				let compression_level = compression_header.level as i32;
-				let mut stream = match zstd::stream::read::Encoder::new(buf.as_slice(), compression_level) {
+				let dictionary = compression_header.dictionary.as_deref().unwrap_or_default();
+				let mut stream = match zstd::stream::read::Encoder::with_dictionary(buf, compression_level, dictionary) {
 					Ok(stream) => stream,
 					Err(e) => return CompressedData::Err(ZffError::from(e)),
 				};
 				// unwrap is safe here, because the read will not fail on a Vec<u8>.
-				let buffered_chunk = buffer_chunk(&mut stream, chunk_size * compression_header.level as usize).unwrap();
+				// the compressed data will never be larger than the zstd compress bound of the input data.
+				let buffered_chunk = buffer_chunk(&mut stream, zstd::zstd_safe::compress_bound(buf.len())).unwrap();
 				if (buf.len() as f32 / buffered_chunk.buffer.len() as f32) < compression_threshold {
 					CompressedData::Raw
 				} else {
@@ -382,7 +421,7 @@ impl CompressionThread {
 			CompressionAlgorithm::Lz4 => {
 				let buffer = Vec::new();
 				let mut compressor = lz4_flex::frame::FrameEncoder::new(buffer);
-				if let Err(e) = io_copy(&mut buf.as_slice(), &mut compressor) {
+				if let Err(e) = io_copy(&mut &buf[..], &mut compressor) {
 					return CompressedData::Err(ZffError::from(e));
 				};
 				let compressed_data = match compressor.finish() {
@@ -395,7 +434,40 @@ impl CompressionThread {
 				} else {
 					CompressedData::Compressed(compressed_data)
 				}
-			}
+			},
+			CompressionAlgorithm::Brotli => {
+				let compressed_data = match compress_buffer_brotli(buf, compression_header.level) {
+					Ok(data) => data,
+					Err(e) => return CompressedData::Err(e),
+				};
+				if (buf.len() as f32 / compressed_data.len() as f32) < compression_threshold {
+					CompressedData::Raw
+				} else {
+					CompressedData::Compressed(compressed_data)
+				}
+			},
+			CompressionAlgorithm::Xz => {
+				let compressed_data = match compress_buffer_xz(buf, compression_header.level) {
+					Ok(data) => data,
+					Err(e) => return CompressedData::Err(e),
+				};
+				if (buf.len() as f32 / compressed_data.len() as f32) < compression_threshold {
+					CompressedData::Raw
+				} else {
+					CompressedData::Compressed(compressed_data)
+				}
+			},
+			CompressionAlgorithm::Gzip => {
+				let compressed_data = match compress_buffer_gzip(buf, compression_header.level) {
+					Ok(data) => data,
+					Err(e) => return CompressedData::Err(e),
+				};
+				if (buf.len() as f32 / compressed_data.len() as f32) < compression_threshold {
+					CompressedData::Raw
+				} else {
+					CompressedData::Compressed(compressed_data)
+				}
+			},
 		}
 	}
 
@@ -471,6 +543,245 @@ impl SameBytesThread {
 	}
 }
 
+/// The intermediate result of a chunk, which was encoded by a [ChunkEncodingWorkerPool].
+#[derive(Debug)]
+pub(crate) struct EncodedChunk {
+	/// the compressed (if the compression flag is set) and encrypted (if an encryption key is given) data.
+	data: Vec<u8>,
+	/// indicates if the data are compressed.
+	compressed: bool,
+	/// the xxhash (integrity hash) of the original data.
+	xxhash: u64,
+	/// the algorithm, which was used to calculate the integrity hash.
+	chunk_integrity: ChunkIntegrity,
+	/// the appropriate byte, if the chunk consists of same bytes.
+	samebyte: Option<u8>,
+	/// the blake3 hash of the original data, if the deduplication was requested.
+	deduplication_hash: Option<blake3::Hash>,
+}
+
+/// A job for the [ChunkEncodingWorkerPool].
+#[derive(Debug)]
+struct ChunkEncodingJob {
+	chunk_number: u64,
+	data: Vec<u8>,
+	bytes_read: u64,
+	deduplication: bool,
+}
+
+/// A pool of worker threads to compress, hash (xxhash) and encrypt multiple chunks in parallel.
+/// The chunks have to be sent in sequential order and can be received in the same order,
+/// regardless of the order in which the workers finish their jobs.
+#[derive(Debug)]
+pub(crate) struct ChunkEncodingWorkerPool {
+	/// the sender to distribute the jobs to the workers (will be dropped first to stop the workers).
+	job_sender: Option<crossbeam::channel::Sender<ChunkEncodingJob>>,
+	/// the handles of the worker threads, which will be joined, when the pool is dropped.
+	workers: Vec<thread::JoinHandle<()>>,
+	/// the receiver to collect the (unordered) results of the workers.
+	result_receiver: crossbeam::channel::Receiver<(u64, Result<EncodedChunk>)>,
+	/// already received results, which are waiting for their turn.
+	pending_results: BTreeMap<u64, Result<EncodedChunk>>,
+	/// the number of jobs, which were sent but not received yet.
+	jobs_in_progress: usize,
+	/// the maximum number of jobs in progress.
+	capacity: usize,
+}
+
+impl ChunkEncodingWorkerPool {
+	/// creates a new pool with the given number of worker threads.
+	pub fn new(
+		number_of_threads: usize,
+		compression_header: CompressionHeader,
+		chunk_size: usize,
+		encryption: Option<EncryptionInformation>,
+		chunk_integrity: ChunkIntegrity) -> Self {
+		let (job_sender, job_receiver) = crossbeam::channel::unbounded::<ChunkEncodingJob>();
+		let (result_sender, result_receiver) = crossbeam::channel::unbounded::<(u64, Result<EncodedChunk>)>();
+		let mut workers = Vec::with_capacity(number_of_threads);
+		for _ in 0..number_of_threads {
+			let job_receiver = job_receiver.clone();
+			let result_sender = result_sender.clone();
+			let compression_header = compression_header.clone();
+			let encryption = encryption.clone();
+			workers.push(thread::spawn(move || {
+				while let Ok(job) = job_receiver.recv() {
+					let result = Self::encode_chunk(job.chunk_number, job.data, job.bytes_read, job.deduplication, chunk_size, &compression_header, &encryption, chunk_integrity);
+					// the pool was dropped, if the result could not be sent.
+					if result_sender.send((job.chunk_number, result)).is_err() {
+						break;
+					}
+				}
+			}));
+		}
+		Self {
+			job_sender: Some(job_sender),
+			workers,
+			result_receiver,
+			pending_results: BTreeMap::new(),
+			jobs_in_progress: 0,
+			capacity: number_of_threads * 2,
+		}
+	}
+
+	/// returns true, if the pool can accept further jobs without growing the internal queue.
+	pub fn is_full(&self) -> bool {
+		self.jobs_in_progress >= self.capacity
+	}
+
+	/// returns true, if there are no jobs in progress.
+	pub fn is_empty(&self) -> bool {
+		self.jobs_in_progress == 0
+	}
+
+	/// sends the data of the given chunk to the workers.
+	/// # Error
+	/// Fails if all worker threads have stopped (e.g. due to a panic).
+	pub fn send(&mut self, chunk_number: u64, data: Vec<u8>, bytes_read: u64, deduplication: bool) -> Result<()> {
+		let job = ChunkEncodingJob { chunk_number, data, bytes_read, deduplication };
+		// the job sender is only taken while the pool is dropped, so the sending fails only if all workers have stopped.
+		let sent = match &self.job_sender {
+			Some(job_sender) => job_sender.send(job).is_ok(),
+			None => false,
+		};
+		if !sent {
+			return Err(ZffError::new(ZffErrorKind::Custom, format!("{ERROR_CHUNK_ENCODING_WORKERS_STOPPED}{chunk_number}")));
+		}
+		self.jobs_in_progress += 1;
+		Ok(())
+	}
+
+	/// blocks until the encoded chunk with the given chunk number is available.
+	/// # Error
+	/// Fails if the encoding of the chunk failed or all worker threads have stopped (e.g. due to a panic).
+	pub fn receive(&mut self, chunk_number: u64) -> Result<EncodedChunk> {
+		loop {
+			if let Some(result) = self.pending_results.remove(&chunk_number) {
+				self.jobs_in_progress -= 1;
+				return result;
+			}
+			// the channel is only disconnected, if all workers have stopped before sending the appropriate result.
+			let (received_chunk_number, result) = self.result_receiver.recv().map_err(|_| ZffError::new(
+				ZffErrorKind::Custom, format!("{ERROR_CHUNK_ENCODING_WORKERS_STOPPED}{chunk_number}")))?;
+			self.pending_results.insert(received_chunk_number, result);
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn encode_chunk(
+		chunk_number: u64,
+		data: Vec<u8>,
+		bytes_read: u64,
+		deduplication: bool,
+		chunk_size: usize,
+		compression_header: &CompressionHeader,
+		encryption: &Option<EncryptionInformation>,
+		chunk_integrity: ChunkIntegrity) -> Result<EncodedChunk> {
+		let xxhash = chunk_integrity.calculate(&data);
+		// the same byte flag can only be set for a full chunk.
+		let samebyte = if bytes_read == chunk_size as u64 && check_same_byte(&data) {
+			Some(data[0])
+		} else {
+			None
+		};
+		let deduplication_hash = if deduplication && samebyte.is_none() {
+			Some(blake3::hash(&data))
+		} else {
+			None
+		};
+		let (data, compressed) = match samebyte {
+			Some(samebyte) => (vec![samebyte], false),
+			None => match CompressionThread::compress_buffer(&data, compression_header) {
+				CompressedData::Compressed(compressed_data) => (compressed_data, true),
+				CompressedData::Raw => (data, false),
+				CompressedData::Err(e) => return Err(e),
+			},
+		};
+		let data = match encryption {
+			Some(encryption) => Vec::<u8>::encrypt(&encryption.encryption_key, &data, chunk_number, encryption.nonce_extension, &encryption.algorithm)?,
+			None => data,
+		};
+		Ok(EncodedChunk {
+			data,
+			compressed,
+			xxhash,
+			chunk_integrity,
+			samebyte,
+			deduplication_hash,
+		})
+	}
+}
+
+impl Drop for ChunkEncodingWorkerPool {
+	fn drop(&mut self) {
+		// the workers stop, when the job channel is disconnected (after the already sent jobs are processed).
+		drop(self.job_sender.take());
+		for worker in self.workers.drain(..) {
+			// a panic of a worker was already reported by the appropriate receive() call.
+			let _ = worker.join();
+		}
+	}
+}
+
+/// creates a chunk by using the given [EncodedChunk]. This function has to be called in sequential order of the chunk numbers,
+/// because the deduplication map will be updated here.
+pub(crate) fn finalize_encoded_chunk(
+	encoded_chunk: EncodedChunk,
+	current_chunk_number: u64,
+	deduplication_map: Option<&mut DeduplicationChunkMap>,
+	encryption_information: Option<&EncryptionInformation>,
+) -> Result<PreparedChunk> {
+	let mut flags = ChunkFlags::default();
+	flags.set_integrity(encoded_chunk.chunk_integrity);
+	if encoded_chunk.samebyte.is_some() {
+		flags.same_bytes = true;
+	} else if let (Some(deduplication_map), Some(b3h)) = (deduplication_map, encoded_chunk.deduplication_hash) {
+		if let Ok(chunk_no) = deduplication_map.get_chunk_number(b3h) {
+			flags.duplicate = true;
+			let chunked_data = chunk_no.to_le_bytes().to_vec();
+			let chunked_data = match encryption_information {
+				Some(enc_info) => Vec::<u8>::encrypt(
+					&enc_info.encryption_key, &chunked_data, current_chunk_number, enc_info.nonce_extension, &enc_info.algorithm)?,
+				None => chunked_data,
+			};
+			let size = chunked_data.len() as u64;
+			return Ok(PreparedChunk::new(chunked_data, flags, size, encoded_chunk.xxhash, None, Some(chunk_no)));
+		}
+		deduplication_map.append_entry(current_chunk_number, b3h)?;
+	}
+	flags.compression = encoded_chunk.compressed;
+	let size = encoded_chunk.data.len() as u64;
+	Ok(PreparedChunk::new(
+		encoded_chunk.data,
+		flags,
+		size,
+		encoded_chunk.xxhash,
+		encoded_chunk.samebyte,
+		None,
+	))
+}
+
+/// creates a same bytes chunk of the given byte, which was detected by [EncodingThreadPoolManager::update_same_bytes_checked].
+pub(crate) fn same_bytes_chunking(
+	encoding_thread_pool_manager: &EncodingThreadPoolManager,
+	current_chunk_number: u64,
+	samebyte: u8,
+	encryption_information: Option<&EncryptionInformation>,
+) -> Result<PreparedChunk> {
+	let mut flags = ChunkFlags::default();
+	flags.set_integrity(encoding_thread_pool_manager.chunk_integrity);
+	flags.same_bytes = true;
+	// the integrity hash was calculated by the update_same_bytes_checked method.
+	let xxhash = encoding_thread_pool_manager.same_bytes_integrity_hashes.get(&samebyte).copied().unwrap_or_default();
+	let chunked_data = match encryption_information {
+		Some(enc_info) => Vec::<u8>::encrypt(
+			&enc_info.encryption_key, [samebyte], current_chunk_number, enc_info.nonce_extension, &enc_info.algorithm)?,
+		None => vec![samebyte],
+	};
+	let size = chunked_data.len() as u64;
+	Ok(PreparedChunk::new(chunked_data, flags, size, xxhash, Some(samebyte), None))
+}
+
 /// creates a chunk by using the given data and the given chunk size.
 pub(crate) fn chunking(
 	encoding_thread_pool_manager: &mut EncodingThreadPoolManager,
@@ -478,12 +789,12 @@ pub(crate) fn chunking(
 	samebyte_checklen_value: u64,
 	chunk_size: u64, // target chunk size,
 	deduplication_map: Option<&mut DeduplicationChunkMap>,
-	encryption_key: Option<&Vec<u8>>,
-	encryption_algorithm: Option<&EncryptionAlgorithm>,
+	encryption_information: Option<&EncryptionInformation>,
 	empty_file_flag: bool,
 ) -> Result<PreparedChunk> {
 	let mut flags = ChunkFlags::default();
 	flags.empty_file = empty_file_flag;
+	flags.set_integrity(encoding_thread_pool_manager.chunk_integrity);
 	if empty_file_flag {
 		return Ok(PreparedChunk::new(Vec::new(), flags, 0, 0, None, None));
 	}
@@ -536,18 +847,15 @@ pub(crate) fn chunking(
 	if compression_flag {
 		flags.compression = true;
 	}
-	let chunked_data = match &encryption_key {
-		Some(encryption_key) => {
-			let encryption_algorithm = match encryption_algorithm {
-				Some(algorithm) => algorithm,
-				None => return Err(ZffError::new(ZffErrorKind::MissingEncryptionHeader, "")),
-			};
+	let chunked_data = match encryption_information {
+		Some(enc_info) => {
 			//TODO: check to encrypt the content if the chunked data also in the "compression_thread"?.
 			Vec::<u8>::encrypt(
-				encryption_key,
+				&enc_info.encryption_key,
 				&chunked_data,
 				current_chunk_number,
-				encryption_algorithm)?
+				enc_info.nonce_extension,
+				&enc_info.algorithm)?
 		},
 		None => chunked_data,
 	};
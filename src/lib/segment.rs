@@ -1,7 +1,8 @@
 // - STD
 use core::borrow::Borrow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{Read, Seek, SeekFrom};
+use std::ops::RangeInclusive;
 
 // - internal
 use crate::{
@@ -11,10 +12,9 @@ use crate::{
 	ZffError,
 	ZffErrorKind,
 	Encryption,
-	CompressionAlgorithm,
 	ChunkContent,
-	decompress_buffer,
-	header::{SegmentHeader, ObjectHeader, EncryptionInformation, EncryptedObjectHeader, ChunkFlags},
+	decompress_buffer_with_limit,
+	header::{SegmentHeader, ObjectHeader, EncryptionInformation, EncryptedObjectHeader, ChunkFlags, CompressionHeader},
 	footer::{SegmentFooter, ObjectFooter, EncryptedObjectFooter},
 	ERROR_MISSING_OBJECT_HEADER_IN_SEGMENT,
 	ERROR_MISSING_OBJECT_FOOTER_IN_SEGMENT,
@@ -70,6 +70,14 @@ impl<R: Read + Seek> Segment<R> {
 		Ok(Self::with_header_and_data(segment_header, data, segment_footer))
 	}
 
+	/// Opens the segment behind the given [Read](std::io::Read)er by decoding its [crate::header::SegmentHeader]
+	/// and [crate::footer::SegmentFooter].
+	/// This is the entry point for tools which want to inspect a single segment without building a whole
+	/// [ZffReader](crate::io::zffreader::ZffReader).
+	pub fn open(data: R) -> Result<Segment<R>> {
+		Self::new_from_reader(data)
+	}
+
 	/// Returns a reference to the underlying [crate::header::SegmentHeader].
 	pub fn header(&self) -> &SegmentHeader {
 		&self.header
@@ -80,6 +88,22 @@ impl<R: Read + Seek> Segment<R> {
 		&self.footer
 	}
 
+	/// Returns the (sorted) numbers of all objects which have an object header or an object footer in this segment.
+	pub fn object_numbers(&self) -> Vec<u64> {
+		self.footer.object_header_offsets.keys()
+			.chain(self.footer.object_footer_offsets.keys())
+			.copied()
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect()
+	}
+
+	/// Returns the range of the chunk numbers stored in this segment or None, if this segment contains no chunks.
+	pub fn chunk_number_range(&self) -> Option<RangeInclusive<u64>> {
+		let (last_chunk_number, _) = self.footer.chunk_offset_map_table.last_key_value()?;
+		Some(self.footer.first_chunk_number..=*last_chunk_number)
+	}
+
 	/// Returns the offset of the appropriate chunk (number).
 	pub fn get_chunk_offset(&mut self, chunk_number: &u64) -> Result<u64> {
 		let chunkmap_offset = get_chunkmap_offset(&self.footer.chunk_offset_map_table, *chunk_number)?;
@@ -151,17 +175,30 @@ impl<R: Read + Seek> Segment<R> {
 	
 	/// Returns the chunked data, uncompressed and unencrypted.
 	/// Chunk metadata could be optionally attached, e.g. from a precached chunk map.
+	#[allow(clippy::too_many_arguments)]
 	pub(crate) fn chunk_data<E, C>(&mut self, 
 		chunk_number: u64, 
 		encryption_information: &Option<E>, 
-		compression_algorithm: C,
+		compression_header: C,
 		chunk_offset: Option<u64>,
 		chunk_size: Option<u64>,
-		flags: Option<ChunkFlags>,) -> Result<ChunkContent>
+		flags: Option<ChunkFlags>,
+		original_chunk_size: u64,) -> Result<ChunkContent>
 	where
 		E: Borrow<EncryptionInformation>,
-		C: Borrow<CompressionAlgorithm>,
+		C: Borrow<CompressionHeader>,
 	{
+		let flags = match flags {
+			None => self.get_chunk_flags(&chunk_number)?,
+			Some(flags) => flags
+		};
+		let raw_data_buffer = self.raw_chunk_data(chunk_number, chunk_offset, chunk_size)?;
+		decode_chunk_content(raw_data_buffer, chunk_number, encryption_information, compression_header, &flags, original_chunk_size)
+	}
+
+	/// Returns the raw (maybe encrypted and/or compressed) data of the appropriate chunk.
+	/// Chunk metadata could be optionally attached, e.g. from a precached chunk map.
+	pub(crate) fn raw_chunk_data(&mut self, chunk_number: u64, chunk_offset: Option<u64>, chunk_size: Option<u64>) -> Result<Vec<u8>> {
 		let chunk_offset = match chunk_offset {
 			None => self.get_chunk_offset(&chunk_number)?,
 			Some(offset) => offset
@@ -170,44 +207,12 @@ impl<R: Read + Seek> Segment<R> {
 			None => self.get_chunk_size(&chunk_number)?,
 			Some(size) => size
 		};
-		let flags = match flags {
-			None => self.get_chunk_flags(&chunk_number)?,
-			Some(flags) => flags
-		};
-
 
 		self.data.seek(SeekFrom::Start(chunk_offset))?;
 
 		let mut raw_data_buffer = vec![0u8; chunk_size as usize];
 		self.data.read_exact(&mut raw_data_buffer)?;
-
-		if let Some(enc_info) = encryption_information {
-			let enc_info = enc_info.borrow();
-			raw_data_buffer = Vec::<u8>::decrypt(
-				&enc_info.encryption_key, 
-				raw_data_buffer, 
-				chunk_number, 
-				&enc_info.algorithm)?;
-		}
-		let chunk_content = if flags.compression {
-			decompress_buffer(&raw_data_buffer, compression_algorithm.borrow())?
-		} else {
-			raw_data_buffer
-		};
-
-		if flags.same_bytes {
-			let single_byte = match chunk_content.first() {
-				Some(data) => data,
-				None => return Err(ZffError::new(ZffErrorKind::MalformedSegment, "Could not find expected samebyte in chunk content.")),
-			};
-			Ok(ChunkContent::SameBytes(*single_byte))
-		} else if flags.duplicate {
-			let mut arr: [u8; 8] = Default::default();
-			arr.copy_from_slice(&chunk_content);
-			Ok(ChunkContent::Duplicate(u64::from_le_bytes(arr)))
-		} else {
-			Ok(ChunkContent::Raw(chunk_content))
-		}
+		Ok(raw_data_buffer)
 	}
 
 	/// Returns the [crate::header::ObjectHeader] of the given object number, if available in this [Segment]. Otherwise, returns an error.
@@ -291,7 +296,7 @@ impl<R: Read + Seek> Segment<R> {
 		self.data.seek(SeekFrom::Start(*offset))?;
 		let encrypted_object_footer = EncryptedObjectFooter::decode_directly(&mut self.data)?;
 		let enc_info = encryption_information.borrow();
-		encrypted_object_footer.decrypt_and_consume(&enc_info.encryption_key, &enc_info.algorithm)
+		encrypted_object_footer.decrypt_and_consume(&enc_info.encryption_key, enc_info.nonce_extension, &enc_info.algorithm)
 	}
 }
 
@@ -333,4 +338,53 @@ fn get_first_chunknumber(map: &BTreeMap<u64, u64>, chunk_number: u64, first_segm
         	Err(ZffError::new(ZffErrorKind::ValueNotInMap, chunk_number.to_string()))
         },
     }
-}
\ No newline at end of file
+}
+
+/// Decrypts (if necessary) and decompresses (if necessary) the given raw chunk data.
+/// The decompressed data must not be larger than the given (uncompressed) chunk size of the appropriate object.
+pub(crate) fn decode_chunk_content<E, C>(
+	mut raw_data_buffer: Vec<u8>,
+	chunk_number: u64,
+	encryption_information: &Option<E>,
+	compression_header: C,
+	flags: &ChunkFlags,
+	chunk_size: u64) -> Result<ChunkContent>
+where
+	E: Borrow<EncryptionInformation>,
+	C: Borrow<CompressionHeader>,
+{
+	if let Some(enc_info) = encryption_information {
+		let enc_info = enc_info.borrow();
+		raw_data_buffer = Vec::<u8>::decrypt(
+			&enc_info.encryption_key, 
+			raw_data_buffer, 
+			chunk_number, 
+			enc_info.nonce_extension,
+			&enc_info.algorithm)?;
+	}
+	let chunk_content = if flags.compression {
+		let compression_header = compression_header.borrow();
+		decompress_buffer_with_limit(
+			&raw_data_buffer, &compression_header.algorithm, compression_header.dictionary.as_deref(), chunk_size as usize)?
+	} else {
+		raw_data_buffer
+	};
+
+	if flags.same_bytes {
+		let single_byte = match chunk_content.first() {
+			Some(data) => data,
+			None => return Err(ZffError::new(ZffErrorKind::MalformedSegment, "Could not find expected samebyte in chunk content.")),
+		};
+		Ok(ChunkContent::SameBytes(*single_byte))
+	} else if flags.duplicate {
+		// the content of a duplicated chunk is the number of the original chunk (a corrupt flag or size could lead to any other length).
+		let original_chunk_number = match <[u8; 8]>::try_from(chunk_content.as_slice()) {
+			Ok(bytes) => u64::from_le_bytes(bytes),
+			Err(_) => return Err(ZffError::new(
+				ZffErrorKind::MalformedSegment, format!("Could not find the original chunk number in the content of duplicated chunk {chunk_number}."))),
+		};
+		Ok(ChunkContent::Duplicate(original_chunk_number))
+	} else {
+		Ok(ChunkContent::Raw(chunk_content))
+	}
+}
@@ -18,7 +18,10 @@ use crate::{
 	Result,
 	ZffError,
 	ZffErrorKind,
+	HeaderCoding,
 	ED25519_DALEK_SIGNATURE_LEN,
+	ERROR_INVALID_SIGNATURE_LENGTH,
+	footer::MainFooter,
 };
 
 
@@ -76,6 +79,30 @@ impl Signature {
 		signature.to_bytes()
 	}
 
+	/// Creates a detached signature over the given [MainFooter], which can be distributed separately from the container.  
+	/// The signed message is the encoded [MainFooter] (see [HeaderCoding::encode_directly]), which is exactly the byte sequence
+	/// of the main footer as stored at the end of the last segment (from the footer offset to the end of the segment).  
+	/// Note that the main footer describes the structure of the container (the location of objects and chunk maps);
+	/// the integrity of the data itself is ensured by the stored hash values and per-chunk signatures.
+	pub fn sign_container_footer(main_footer: &MainFooter, signing_key: &SigningKey) -> Vec<u8> {
+		Signature::sign(signing_key, &main_footer.encode_directly()).to_vec()
+	}
+
+	/// Verifies a detached signature (see [Signature::sign_container_footer]) of the given [MainFooter]
+	/// with the given key bytes (signing key or verifying keys are possible to use here).
+	/// # Error
+	/// Fails if the key or the signature has an invalid length.
+	pub fn verify_container_footer<K>(key: K, main_footer: &MainFooter, signature: &[u8]) -> Result<bool>
+	where
+		K: AsRef<[u8]>
+	{
+		let signature: [u8; ED25519_DALEK_SIGNATURE_LEN] = match signature.try_into() {
+			Ok(signature) => signature,
+			Err(_) => return Err(ZffError::new(ZffErrorKind::InvalidOption, format!("{ERROR_INVALID_SIGNATURE_LENGTH}{}", signature.len()))),
+		};
+		Signature::verify(key, &main_footer.encode_directly(), signature)
+	}
+
 	/// verify the data with the given base64 encoded key (signing key or verifying keys are possible to use here).
 	pub fn verify_with_base64_key<K: Into<String>>(key: K, message: &[u8], signature: [u8; ED25519_DALEK_SIGNATURE_LEN]) -> Result<bool> {
 		let key = base64engine.decode(key.into())?;
@@ -120,11 +120,25 @@ pub trait HeaderCoding {
 			return Err(ZffError::new(ZffErrorKind::HeaderDecodeMismatchIdentifier, ERROR_HEADER_DECODER_MISMATCH_IDENTIFIER));
 		}
 		let header_length = Self::decode_header_length(data)? as usize;
-		let mut header_content = vec![0u8; header_length-DEFAULT_LENGTH_HEADER_IDENTIFIER-DEFAULT_LENGTH_VALUE_HEADER_LENGTH];
+		let content_length = match header_length.checked_sub(DEFAULT_LENGTH_HEADER_IDENTIFIER+DEFAULT_LENGTH_VALUE_HEADER_LENGTH) {
+			Some(content_length) => content_length,
+			None => return Err(ZffError::new_header_decode_error(ERROR_HEADER_DECODER_HEADER_LENGTH)),
+		};
+		let mut header_content = vec![0u8; content_length];
 		data.read_exact(&mut header_content)?;
 		Self::decode_content(header_content)
 	}
 
+	/// decodes the header from the given byte slice (e.g. from a buffer, which was filled without a [Read] implementation
+	/// like [std::fs::File]).
+	/// Returns the decoded header and the number of consumed bytes, so that following headers (or chunk maps)
+	/// can be decoded from the remaining bytes of the slice.
+	fn decode_from_slice(data: &[u8]) -> Result<(Self::Item, usize)> {
+		let mut remaining = data;
+		let item = Self::decode_directly(&mut remaining)?;
+		Ok((item, data.len() - remaining.len()))
+	}
+
 	/// Method to show the "name" of the appropriate struct (e.g. to use this with fmt::Display).
 	/// This method is a necassary helper method for fmt::Display and serde::ser::SerializeStruct (and for some debugging purposes).
 	fn struct_name() -> &'static str;
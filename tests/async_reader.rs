@@ -0,0 +1,63 @@
+#![cfg(feature = "async")]
+// - STD
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Cursor, SeekFrom};
+
+// - internal
+use zff::{
+	HashType,
+	header::ObjectType as HeaderObjectType,
+	io::{
+		ZffCreationParameters,
+		zffwriter::ZffFilesOutput,
+		zffreader::{AsyncZffReader, ObjectType},
+	},
+};
+
+// - external
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+mod common;
+use common::{CHUNK_SIZE, input_data, object_header, test_directory, write_physical_object_with};
+
+#[tokio::test]
+async fn async_reader_reads_and_seeks_active_object() {
+	let test_dir = test_directory("async_reader");
+
+	let output = test_dir.join("container");
+	write_physical_object_with(
+		object_header(1, HeaderObjectType::Physical),
+		Cursor::new(input_data()),
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone()));
+
+	let segments = vec![File::open(output.with_extension("z01")).unwrap()];
+	let mut reader = AsyncZffReader::with_reader(segments).await.unwrap();
+	assert_eq!(reader.list_objects().await.unwrap(), BTreeMap::from([(1, ObjectType::Physical)]));
+	reader.initialize_objects_all().await.unwrap();
+	reader.set_active_object(1).await.unwrap();
+
+	let mut data = Vec::new();
+	reader.read_to_end(&mut data).await.unwrap();
+	assert_eq!(data, input_data());
+
+	let offset = CHUNK_SIZE + 10;
+	assert_eq!(reader.seek(SeekFrom::Start(offset)).await.unwrap(), offset);
+	let mut buffer = vec![0; 100];
+	reader.read_exact(&mut buffer).await.unwrap();
+	assert_eq!(buffer, input_data()[offset as usize..offset as usize + 100]);
+	assert_eq!(reader.stream_position().await.unwrap(), offset + 100);
+
+	// read_range does not change the position of the reader.
+	let range = reader.read_range(1, 10, 20).await.unwrap();
+	assert_eq!(range, input_data()[10..30]);
+	assert_eq!(reader.stream_position().await.unwrap(), offset + 100);
+	assert!(reader.set_active_object(2).await.is_err());
+
+	let inner = reader.into_inner().unwrap();
+	assert_eq!(inner.container_data_size(), input_data().len() as u64);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
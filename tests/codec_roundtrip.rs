@@ -0,0 +1,939 @@
+// - STD
+use std::collections::{HashMap, BTreeMap};
+use std::io::{Cursor, Write};
+
+// - internal
+use zff::{
+	Hash,
+	MultiHasher,
+	HeaderCoding,
+	ValueEncoder,
+	HashType,
+	ChunkIntegrity,
+	CompressionAlgorithm,
+	EncryptionAlgorithm,
+	KDFScheme,
+	PBEScheme,
+	encryption::{Encryption, encrypt_pbkdf2sha256_aes256cbc, encrypt_argon2_aes256cbc, encrypt_scrypt_aes128cbc, encrypt_scrypt_aes256cbc, gen_random_key, gen_random_iv, gen_random_salt, gen_random_nonce_extension},
+	ZffErrorKind,
+	io::compress_chunk,
+	decompress_buffer,
+	decompress_buffer_with_dictionary,
+	decompress_buffer_with_limit,
+	train_zstd_dictionary,
+	constants::{
+		COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS,
+		COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS,
+		DEFAULT_HEADER_VERSION_OBJECT_HEADER,
+		HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION,
+		DEFAULT_FOOTER_VERSION_SEGMENT_FOOTER,
+		FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY,
+		DEFAULT_FOOTER_VERSION_MAIN_FOOTER,
+		FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY,
+		FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS,
+		DEFAULT_HEADER_VERSION_CHUNK_FLAG_MAP,
+		HEADER_VERSION_CHUNK_FLAG_MAP_CRC64,
+		DEFAULT_HEADER_VERSION_COMPRESSION_HEADER,
+		HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY,
+		DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL,
+		FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS,
+		DEFAULT_HEADER_VERSION_ENCRYPTION_HEADER,
+		HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION,
+	},
+	header::*,
+	footer::*,
+};
+
+// - external
+use ordered_float::OrderedFloat;
+
+const PASSWORD: &str = "zff_test_password";
+const PBKDF2_ITERATIONS: u32 = 1000;
+// small argon2id parameters to keep the tests fast.
+const ARGON2_MEM_COST: u32 = 1024;
+const ARGON2_LANES: u32 = 1;
+const ARGON2_ITERATIONS: u32 = 1;
+// small scrypt parameters (with r != p to detect swapped parameters).
+const SCRYPT_LOGN: u8 = 10;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+fn description_header() -> DescriptionHeader {
+	let mut description_header = DescriptionHeader::new_empty();
+	description_header.set_case_number("1");
+	description_header.set_evidence_number("AB1234");
+	description_header.set_examiner_name("ph0llux");
+	description_header.set_notes("Some notes");
+	description_header.custom_identifier_value("location", "lab 2");
+	description_header
+}
+
+fn hash_header() -> HashHeader {
+	HashHeader::new(vec![
+		HashValue::new(HashType::Blake3, vec![1u8; 32], None),
+		HashValue::new(HashType::SHA256, vec![2u8; 32], Some([3u8; 64])),
+	])
+}
+
+fn compression_header() -> CompressionHeader {
+	CompressionHeader::new(CompressionAlgorithm::Zstd, 3, 1.05)
+}
+
+// returns the encryption header (with the decrypted key) and the plain encryption key.
+fn encryption_header() -> (EncryptionHeader, Vec<u8>) {
+	let encryption_key = gen_random_key(256);
+	let salt = gen_random_salt();
+	let nonce = gen_random_iv();
+	let encrypted_encryption_key = encrypt_pbkdf2sha256_aes256cbc(PBKDF2_ITERATIONS, &salt, &nonce, PASSWORD, &encryption_key).unwrap();
+	let kdf_parameters = KDFParameters::PBKDF2SHA256Parameters(PBKDF2SHA256Parameters::new(PBKDF2_ITERATIONS, salt));
+	let pbe_header = PBEHeader::new(KDFScheme::PBKDF2SHA256, PBEScheme::AES256CBC, kdf_parameters, nonce);
+	let encryption_header = EncryptionHeader::new(pbe_header, EncryptionAlgorithm::AES256GCM, encrypted_encryption_key);
+	(encryption_header, encryption_key)
+}
+
+fn argon2id_encryption_header() -> (EncryptionHeader, Vec<u8>) {
+	let encryption_key = gen_random_key(256);
+	let salt = gen_random_salt();
+	let nonce = gen_random_iv();
+	let encrypted_encryption_key = encrypt_argon2_aes256cbc(
+		ARGON2_MEM_COST, ARGON2_LANES, ARGON2_ITERATIONS, &salt, &nonce, PASSWORD, &encryption_key).unwrap();
+	let kdf_parameters = KDFParameters::Argon2idParameters(Argon2idParameters::new(ARGON2_MEM_COST, ARGON2_LANES, ARGON2_ITERATIONS, salt));
+	let pbe_header = PBEHeader::new(KDFScheme::Argon2id, PBEScheme::AES256CBC, kdf_parameters, nonce);
+	let encryption_header = EncryptionHeader::new(pbe_header, EncryptionAlgorithm::AES256GCM, encrypted_encryption_key);
+	(encryption_header, encryption_key)
+}
+
+fn scrypt_encryption_header(pbe_scheme: PBEScheme) -> (EncryptionHeader, Vec<u8>) {
+	let encryption_key = gen_random_key(256);
+	let salt = gen_random_salt();
+	let nonce = gen_random_iv();
+	let encrypted_encryption_key = match pbe_scheme {
+		PBEScheme::AES128CBC => encrypt_scrypt_aes128cbc(SCRYPT_LOGN, SCRYPT_R, SCRYPT_P, &salt, &nonce, PASSWORD, &encryption_key),
+		PBEScheme::AES256CBC => encrypt_scrypt_aes256cbc(SCRYPT_LOGN, SCRYPT_R, SCRYPT_P, &salt, &nonce, PASSWORD, &encryption_key),
+		_ => unreachable!(),
+	}.unwrap();
+	let kdf_parameters = KDFParameters::ScryptParameters(ScryptParameters::new(SCRYPT_LOGN, SCRYPT_R, SCRYPT_P, salt));
+	let pbe_header = PBEHeader::new(KDFScheme::Scrypt, pbe_scheme, kdf_parameters, nonce);
+	let encryption_header = EncryptionHeader::new(pbe_header, EncryptionAlgorithm::AES256GCM, encrypted_encryption_key);
+	(encryption_header, encryption_key)
+}
+
+fn object_header(encryption_header: Option<EncryptionHeader>) -> ObjectHeader {
+	let flags = ObjectFlags {
+		encryption: encryption_header.is_some(),
+		sign_hash: true,
+		plaintext_description: false,
+	};
+	ObjectHeader::new(
+		3,
+		encryption_header,
+		262144,
+		compression_header(),
+		description_header(),
+		ObjectType::Physical,
+		flags)
+}
+
+fn assert_object_header_eq(expected: &ObjectHeader, decoded: &ObjectHeader) {
+	// ObjectHeader::eq() only compares the object numbers.
+	assert_eq!(expected.object_number, decoded.object_number);
+	assert_eq!(expected.flags, decoded.flags);
+	assert_eq!(expected.chunk_size, decoded.chunk_size);
+	assert_eq!(expected.compression_header, decoded.compression_header);
+	assert_eq!(expected.description_header, decoded.description_header);
+	assert_eq!(expected.object_type, decoded.object_type);
+}
+
+fn file_header() -> FileHeader {
+	let mut metadata_ext = HashMap::new();
+	metadata_ext.insert("mt".to_string(), MetadataExtendedValue::U64(1_700_000_000));
+	metadata_ext.insert("user".to_string(), MetadataExtendedValue::String("root".to_string()));
+	metadata_ext.insert("hidden".to_string(), MetadataExtendedValue::Bool(false));
+	metadata_ext.insert("ratio".to_string(), MetadataExtendedValue::F64(OrderedFloat(0.5)));
+	metadata_ext.insert("xattr".to_string(), MetadataExtendedValue::ByteArray(vec![0, 1, 2, 3]));
+	FileHeader::new(5, FileType::File, "textfile.txt", 2, metadata_ext)
+}
+
+fn file_footer() -> FileFooter {
+	FileFooter::new(5, 1_700_000_000, 1_700_000_100, hash_header(), 12, 4, 1_000_000)
+}
+
+fn map(entries: &[(u64, u64)]) -> BTreeMap<u64, u64> {
+	entries.iter().copied().collect()
+}
+
+fn hashmap(entries: &[(u64, u64)]) -> HashMap<u64, u64> {
+	entries.iter().copied().collect()
+}
+
+#[test]
+fn segment_header_roundtrip() {
+	let header = SegmentHeader::new(0xDEADBEEF, 2, 32768);
+	let decoded = SegmentHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	// SegmentHeader::eq() only compares the segment numbers.
+	assert_eq!(header.unique_identifier, decoded.unique_identifier);
+	assert_eq!(header.segment_number, decoded.segment_number);
+	assert_eq!(header.chunkmap_size, decoded.chunkmap_size);
+}
+
+#[test]
+fn headers_are_decoded_from_slices() {
+	let header = SegmentHeader::new(0xDEADBEEF, 1, 32768);
+	let chunk_offset_map = ChunkOffsetMap::with_data(BTreeMap::from([(1, 45), (2, 4141)]));
+	let mut data = header.encode_directly();
+	data.append(&mut chunk_offset_map.encode_directly());
+
+	let (decoded_header, consumed) = SegmentHeader::decode_from_slice(&data).unwrap();
+	assert_eq!(consumed, header.encode_directly().len());
+	assert_eq!(header.unique_identifier, decoded_header.unique_identifier);
+	let (decoded_map, map_length) = ChunkOffsetMap::decode_from_slice(&data[consumed..]).unwrap();
+	assert_eq!(consumed + map_length, data.len());
+	assert_eq!(chunk_offset_map, decoded_map);
+
+	// a header length which is smaller than the identifier and the length value itself is malformed.
+	let mut malformed = header.encode_directly();
+	malformed[4..12].copy_from_slice(&4u64.to_le_bytes());
+	let error = SegmentHeader::decode_from_slice(&malformed).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::HeaderDecodeError));
+	assert!(SegmentHeader::decode_from_slice(&data[..consumed - 1]).is_err());
+}
+
+#[test]
+fn encrypted_chunk_maps_are_authenticated() {
+	let key = gen_random_key(256);
+	let nonce_extension = gen_random_nonce_extension();
+	let chunk_offset_map = ChunkOffsetMap::with_data(BTreeMap::from([(1, 45), (2, 4141)]));
+	let encrypted = chunk_offset_map.encrypt_encoded_map(&key, nonce_extension, EncryptionAlgorithm::AES256GCM, 1).unwrap();
+	let decrypted = ChunkOffsetMap::decrypt_and_decode(&key, nonce_extension, EncryptionAlgorithm::AES256GCM, &mut Cursor::new(&encrypted), 1).unwrap();
+	assert_eq!(chunk_offset_map, decrypted);
+
+	// a manipulated byte of the encrypted map is detected by the authentication tag (instead of a decoding error or a wrong map).
+	let mut corrupt = encrypted.clone();
+	let last = corrupt.len() - 1;
+	corrupt[last] ^= 0x01;
+	let error = ChunkOffsetMap::decrypt_and_decode(&key, nonce_extension, EncryptionAlgorithm::AES256GCM, &mut Cursor::new(&corrupt), 1).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::IntegrityError));
+
+	let chunk_flags_map = ChunkFlagsMap::with_data(BTreeMap::from([(1, ChunkFlags::default())]));
+	let mut corrupt = chunk_flags_map.encrypt_encoded_map(&key, nonce_extension, EncryptionAlgorithm::CHACHA20POLY1305, 1).unwrap();
+	let last = corrupt.len() - 1;
+	corrupt[last] ^= 0x80;
+	let error = ChunkFlagsMap::decrypt_and_decode(&key, nonce_extension, EncryptionAlgorithm::CHACHA20POLY1305, &mut Cursor::new(&corrupt), 1).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::IntegrityError));
+}
+
+#[test]
+fn chunk_flags_maps_with_crc64_chunks_use_a_separate_version() {
+	// identifier (4 bytes), header length (8 bytes), version.
+	let mut flags = ChunkFlags::default();
+	let chunk_flags_map = ChunkFlagsMap::with_data(BTreeMap::from([(1, flags.clone())]));
+	assert_eq!(chunk_flags_map.encode_directly()[12], DEFAULT_HEADER_VERSION_CHUNK_FLAG_MAP);
+
+	flags.set_integrity(ChunkIntegrity::Crc64);
+	let chunk_flags_map = ChunkFlagsMap::with_data(BTreeMap::from([(1, ChunkFlags::default()), (2, flags)]));
+	let encoded = chunk_flags_map.encode_directly();
+	assert_eq!(encoded[12], HEADER_VERSION_CHUNK_FLAG_MAP_CRC64);
+	assert_eq!(chunk_flags_map, ChunkFlagsMap::decode_directly(&mut Cursor::new(&encoded)).unwrap());
+	let mut unknown_version = encoded;
+	unknown_version[12] = HEADER_VERSION_CHUNK_FLAG_MAP_CRC64 + 1;
+	let error = ChunkFlagsMap::decode_directly(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+
+	let key = gen_random_key(256);
+	let nonce_extension = gen_random_nonce_extension();
+	let encrypted = chunk_flags_map.encrypt_encoded_map(&key, nonce_extension, EncryptionAlgorithm::AES256GCM, 1).unwrap();
+	assert_eq!(encrypted[12], HEADER_VERSION_CHUNK_FLAG_MAP_CRC64);
+	let decrypted = ChunkFlagsMap::decrypt_and_decode(&key, nonce_extension, EncryptionAlgorithm::AES256GCM, &mut Cursor::new(&encrypted), 1).unwrap();
+	assert_eq!(chunk_flags_map, decrypted);
+}
+
+#[test]
+fn compression_header_roundtrip() {
+	let header = compression_header();
+	let decoded = CompressionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+}
+
+#[test]
+fn brotli_compression_roundtrip() {
+	let header = CompressionHeader::new(CompressionAlgorithm::Brotli, 9, 1.05);
+	let decoded = CompressionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+
+	let data = b"zff brotli compression test. ".repeat(1024);
+	let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(compressed);
+	assert_eq!(data, decompress_buffer(&compressed_data, CompressionAlgorithm::Brotli).unwrap());
+}
+
+// some small, similar records (e.g. like the content of small log or config files).
+fn dictionary_samples() -> Vec<Vec<u8>> {
+	(0..500u64).map(|i| format!(
+		"{{\"id\": {i}, \"host\": \"workstation-{}\", \"user\": \"examiner{}\", \"event\": \"logon\", \"status\": \"success\", \"duration\": {}}}\n",
+		i % 17, i % 5, i * 31 % 1000).repeat(1 + (i % 3) as usize).into_bytes()).collect()
+}
+
+#[test]
+fn zstd_dictionary_compression_roundtrip() {
+	let samples = dictionary_samples();
+	let dictionary = train_zstd_dictionary(&samples, 4096).unwrap();
+	let header = CompressionHeader::new_with_dictionary(CompressionAlgorithm::Zstd, 3, 1.05, dictionary.clone());
+	let decoded = CompressionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+	// headers without a dictionary are encoded as before, headers with a dictionary use a separate version.
+	assert_eq!(compression_header().encode_directly().len() + dictionary.encode_directly().len(), header.encode_directly().len());
+	assert_eq!(compression_header().encode_directly()[12], DEFAULT_HEADER_VERSION_COMPRESSION_HEADER);
+	let encoded = header.encode_directly();
+	assert_eq!(encoded[12], HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY);
+	let mut unknown_version = encoded;
+	unknown_version[12] = HEADER_VERSION_COMPRESSION_HEADER_DICTIONARY + 1;
+	let error = CompressionHeader::decode_directly(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+
+	let data = samples[42].clone();
+	let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(compressed);
+	let (compressed_data_without_dictionary, _) = compress_chunk(data.clone(), &compression_header()).unwrap();
+	assert!(compressed_data.len() < compressed_data_without_dictionary.len());
+
+	assert_eq!(data, decompress_buffer_with_dictionary(&compressed_data, CompressionAlgorithm::Zstd, Some(&dictionary)).unwrap());
+	let error = decompress_buffer(&compressed_data, CompressionAlgorithm::Zstd).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::MissingCompressionDictionary));
+	assert_eq!(data, decompress_buffer(&compressed_data_without_dictionary, CompressionAlgorithm::Zstd).unwrap());
+}
+
+#[test]
+fn xz_compression_roundtrip() {
+	let header = CompressionHeader::new(CompressionAlgorithm::Xz, 6, 1.05);
+	let decoded = CompressionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+	assert_eq!(CompressionAlgorithm::from("lzma"), CompressionAlgorithm::Xz);
+
+	let data = b"zff xz compression test. ".repeat(1024);
+	let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(compressed);
+	assert_eq!(data, decompress_buffer(&compressed_data, CompressionAlgorithm::Xz).unwrap());
+
+	// incompressible data is stored raw.
+	let mut state = 0x2545F4914F6CDD1Du64;
+	let data: Vec<u8> = (0..4096).map(|_| { state ^= state << 13; state ^= state >> 7; state ^= state << 17; state as u8 }).collect();
+	let (stored_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(!compressed);
+	assert_eq!(data, stored_data);
+}
+
+#[test]
+fn gzip_compression_roundtrip() {
+	let header = CompressionHeader::new(CompressionAlgorithm::Gzip, 6, 1.05);
+	let decoded = CompressionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+	assert_eq!(CompressionAlgorithm::from("gzip"), CompressionAlgorithm::Gzip);
+	assert_eq!(CompressionAlgorithm::from("deflate"), CompressionAlgorithm::Gzip);
+
+	let data = b"zff gzip compression test. ".repeat(1024);
+	let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(compressed);
+	// each compressed chunk is a complete gzip stream.
+	assert_eq!(compressed_data[..2], [0x1f, 0x8b]);
+	assert_eq!(data, decompress_buffer(&compressed_data, CompressionAlgorithm::Gzip).unwrap());
+
+	// levels greater than 9 are clamped.
+	let header = CompressionHeader::new(CompressionAlgorithm::Gzip, 22, 1.05);
+	let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(compressed);
+	assert_eq!(data, decompress_buffer(&compressed_data, CompressionAlgorithm::Gzip).unwrap());
+
+	// incompressible data is stored raw.
+	let mut state = 0x2545F4914F6CDD1Du64;
+	let data: Vec<u8> = (0..4096).map(|_| { state ^= state << 13; state ^= state >> 7; state ^= state << 17; state as u8 }).collect();
+	let (stored_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(!compressed);
+	assert_eq!(data, stored_data);
+}
+
+#[test]
+fn description_header_roundtrip() {
+	let header = description_header();
+	let decoded = DescriptionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+}
+
+#[test]
+fn hash_header_roundtrip() {
+	let header = hash_header();
+	let decoded = HashHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+}
+
+#[test]
+fn encryption_header_roundtrip() {
+	let (header, encryption_key) = encryption_header();
+	let mut decoded = EncryptionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+	assert_eq!(encryption_key, decoded.decrypt_encryption_key(PASSWORD).unwrap());
+}
+
+#[test]
+fn encryption_headers_with_a_nonce_extension_use_a_separate_version() {
+	let (header, encryption_key) = encryption_header();
+	assert_ne!(header.nonce_extension, encryption_header().0.nonce_extension);
+	// identifier (4 bytes), header length (8 bytes), version.
+	let encoded = header.encode_directly();
+	assert_eq!(encoded[12], HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION);
+
+	// older encryption headers have no nonce extension, so a zeroed nonce extension is used.
+	let mut legacy = encoded[..encoded.len() - 16].to_vec();
+	let legacy_length = legacy.len() as u64;
+	legacy[4..12].copy_from_slice(&legacy_length.to_le_bytes());
+	legacy[12] = DEFAULT_HEADER_VERSION_ENCRYPTION_HEADER;
+	let mut decoded = EncryptionHeader::decode_directly(&mut Cursor::new(legacy)).unwrap();
+	assert_eq!(decoded.nonce_extension, [0u8; 16]);
+	assert_eq!(encryption_key, decoded.decrypt_encryption_key(PASSWORD).unwrap());
+
+	let mut unknown_version = encoded;
+	unknown_version[12] = HEADER_VERSION_ENCRYPTION_HEADER_NONCE_EXTENSION + 1;
+	let error = EncryptionHeader::decode_directly(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+}
+
+#[test]
+fn xchacha20_nonce_contains_the_nonce_extension() {
+	let key = gen_random_key(256);
+	let nonce_extension = gen_random_nonce_extension();
+	let message = b"zff".to_vec();
+	let encrypted = Vec::<u8>::encrypt(&key, &message, 1, nonce_extension, EncryptionAlgorithm::XCHACHA20POLY1305).unwrap();
+	assert_ne!(encrypted, Vec::<u8>::encrypt(&key, &message, 1, [0u8; 16], EncryptionAlgorithm::XCHACHA20POLY1305).unwrap());
+	assert_eq!(message, Vec::<u8>::decrypt(&key, &encrypted, 1, nonce_extension, EncryptionAlgorithm::XCHACHA20POLY1305).unwrap());
+	assert!(Vec::<u8>::decrypt(&key, &encrypted, 1, [0u8; 16], EncryptionAlgorithm::XCHACHA20POLY1305).is_err());
+}
+
+#[test]
+fn pbkdf2_iterations_beyond_u16_roundtrip() {
+	let iterations = u16::MAX as u32 + 1;
+	let encryption_key = gen_random_key(256);
+	let salt = gen_random_salt();
+	let nonce = gen_random_iv();
+	let encrypted_encryption_key = encrypt_pbkdf2sha256_aes256cbc(iterations, &salt, &nonce, PASSWORD, &encryption_key).unwrap();
+	let kdf_parameters = KDFParameters::PBKDF2SHA256Parameters(PBKDF2SHA256Parameters::new(iterations, salt));
+	let pbe_header = PBEHeader::new(KDFScheme::PBKDF2SHA256, PBEScheme::AES256CBC, kdf_parameters, nonce);
+	let header = EncryptionHeader::new(pbe_header, EncryptionAlgorithm::AES256GCM, encrypted_encryption_key);
+
+	let mut decoded = EncryptionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	match &decoded.pbe_header.kdf_parameters {
+		KDFParameters::PBKDF2SHA256Parameters(parameters) => assert_eq!(parameters.iterations, iterations),
+		_ => panic!("unexpected kdf parameters"),
+	}
+	assert_eq!(encryption_key, decoded.decrypt_encryption_key(PASSWORD).unwrap());
+}
+
+#[test]
+fn argon2id_encryption_header_roundtrip() {
+	let (header, encryption_key) = argon2id_encryption_header();
+	let mut decoded = EncryptionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+	assert_eq!(encryption_key, decoded.decrypt_encryption_key(PASSWORD).unwrap());
+}
+
+#[test]
+fn scrypt_encryption_header_roundtrip() {
+	for pbe_scheme in [PBEScheme::AES128CBC, PBEScheme::AES256CBC] {
+		let (header, encryption_key) = scrypt_encryption_header(pbe_scheme);
+		let mut decoded = EncryptionHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+		assert_eq!(header, decoded);
+		assert_eq!(encryption_key, decoded.decrypt_encryption_key(PASSWORD).unwrap());
+	}
+}
+
+#[test]
+fn unknown_encryption_algorithm_is_rejected() {
+	let (mut header, _) = encryption_header();
+	header.algorithm = EncryptionAlgorithm::XCHACHA20POLY1305;
+	let mut encoded = header.encode_directly();
+	let decoded = EncryptionHeader::decode_directly(&mut Cursor::new(encoded.clone())).unwrap();
+	assert_eq!(decoded.algorithm, EncryptionAlgorithm::XCHACHA20POLY1305);
+
+	// the algorithm follows the encryption header identifier (4), length (8), version (1) and the pbe header.
+	let algorithm_position = 13 + header.pbe_header.encode_directly().len();
+	assert_eq!(encoded[algorithm_position], EncryptionAlgorithm::XCHACHA20POLY1305 as u8);
+	encoded[algorithm_position] = 0xFF;
+	let error = EncryptionHeader::decode_directly(&mut Cursor::new(encoded)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::HeaderDecodeError));
+}
+
+#[test]
+fn unknown_kdf_parameters_are_rejected() {
+	let (header, _) = encryption_header();
+	let mut encoded = header.pbe_header.encode_directly();
+	// the kdf parameters start after the pbe header identifier (4), length (8), version (1), kdf scheme (1) and pbe scheme (1).
+	encoded[15..19].copy_from_slice(&0xDEADBEEFu32.to_be_bytes());
+	assert!(PBEHeader::decode_directly(&mut Cursor::new(encoded)).is_err());
+}
+
+#[test]
+fn object_header_roundtrip() {
+	let header = object_header(None);
+	let decoded = ObjectHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_object_header_eq(&header, &decoded);
+	assert!(decoded.encryption_header.is_none());
+}
+
+#[test]
+fn encrypted_object_header_roundtrip() {
+	let (encryption_header, encryption_key) = encryption_header();
+	let header = object_header(Some(encryption_header.clone()));
+	let encoded = header.encode_encrypted_header_directly(&encryption_key).unwrap();
+	let decoded = ObjectHeader::decode_encrypted_header_with_password(&mut Cursor::new(encoded), PASSWORD).unwrap();
+	assert_object_header_eq(&header, &decoded);
+	let decoded_encryption_header = decoded.encryption_header.unwrap();
+	assert_eq!(encryption_header.pbe_header, decoded_encryption_header.pbe_header);
+	assert_eq!(encryption_header.algorithm, decoded_encryption_header.algorithm);
+	assert_eq!(encryption_header.encrypted_encryption_key, decoded_encryption_header.encrypted_encryption_key);
+	assert_eq!(Some(encryption_key), decoded_encryption_header.get_encryption_key());
+}
+
+#[test]
+fn encrypted_object_header_with_plaintext_description_roundtrip() {
+	let (encryption_header, encryption_key) = encryption_header();
+	let mut header = object_header(Some(encryption_header));
+	header.flags.plaintext_description = true;
+	let encoded = header.encode_encrypted_header_directly(&encryption_key).unwrap();
+	// identifier (4 bytes), header length (8 bytes), version.
+	assert_eq!(encoded[12], HEADER_VERSION_OBJECT_HEADER_PLAINTEXT_DESCRIPTION);
+
+	// the description can be read without the password.
+	let encrypted_header = EncryptedObjectHeader::decode_directly(&mut Cursor::new(&encoded)).unwrap();
+	assert!(encrypted_header.flags.plaintext_description);
+	assert_eq!(encrypted_header.description_header(), Some(&description_header()));
+	assert_eq!(encrypted_header.encode_directly(), encoded);
+	assert_object_header_eq(&header, &encrypted_header.decrypt_and_consume_with_password(PASSWORD).unwrap());
+	let decoded = ObjectHeader::decode_encrypted_header_with_password(&mut Cursor::new(&encoded), PASSWORD).unwrap();
+	assert_object_header_eq(&header, &decoded);
+
+	// a plaintext description flag without the appropriate header version is rejected.
+	let mut invalid_version = encoded.clone();
+	invalid_version[12] = DEFAULT_HEADER_VERSION_OBJECT_HEADER;
+	let error = EncryptedObjectHeader::decode_directly(&mut Cursor::new(invalid_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+
+	// without the flag, the description is only stored encrypted.
+	header.flags.plaintext_description = false;
+	let encoded = header.encode_encrypted_header_directly(&encryption_key).unwrap();
+	assert_eq!(encoded[12], DEFAULT_HEADER_VERSION_OBJECT_HEADER);
+	assert_eq!(EncryptedObjectHeader::decode_directly(&mut Cursor::new(encoded)).unwrap().description_header(), None);
+}
+
+#[test]
+fn file_header_roundtrip() {
+	let header = file_header();
+	let decoded = FileHeader::decode_directly(&mut Cursor::new(header.encode_directly())).unwrap();
+	assert_eq!(header, decoded);
+}
+
+#[test]
+fn encrypted_file_header_roundtrip() {
+	let header = file_header();
+	for algorithm in [EncryptionAlgorithm::CHACHA20POLY1305, EncryptionAlgorithm::XCHACHA20POLY1305] {
+		let encryption_information = EncryptionInformation::new(gen_random_key(256), algorithm, gen_random_nonce_extension());
+		let encoded = header.encode_encrypted_header_directly(&encryption_information).unwrap();
+		let decoded = FileHeader::decode_encrypted_header_with_key(&mut Cursor::new(encoded), &encryption_information).unwrap();
+		assert_eq!(header, decoded);
+	}
+}
+
+#[test]
+fn file_footer_roundtrip() {
+	let footer = file_footer();
+	let decoded = FileFooter::decode_directly(&mut Cursor::new(footer.encode_directly())).unwrap();
+	assert_eq!(footer, decoded);
+}
+
+#[test]
+fn encrypted_file_footer_roundtrip() {
+	let footer = file_footer();
+	let encryption_information = EncryptionInformation::new(gen_random_key(128), EncryptionAlgorithm::AES128GCM, gen_random_nonce_extension());
+	let encoded = footer.encode_encrypted_header_directly(&encryption_information).unwrap();
+	let decoded = FileFooter::decode_encrypted_footer_with_key(&mut Cursor::new(encoded), &encryption_information).unwrap();
+	assert_eq!(footer, decoded);
+}
+
+#[test]
+fn segment_footer_roundtrip() {
+	let footer = SegmentFooter::new(
+		4096,
+		hashmap(&[(1, 64), (2, 1024)]),
+		hashmap(&[(1, 2048)]),
+		map(&[(10, 100)]),
+		map(&[(10, 200)]),
+		map(&[(10, 300)]),
+		map(&[(10, 400)]),
+		map(&[(10, 500)]),
+		map(&[(10, 600)]),
+		1,
+		3000);
+	let decoded = SegmentFooter::decode_directly(&mut Cursor::new(footer.encode_directly())).unwrap();
+	assert_eq!(footer, decoded);
+}
+
+#[test]
+fn main_footer_roundtrip() {
+	let footer = MainFooter::new(
+		2,
+		map(&[(1, 1), (2, 2)]),
+		map(&[(1, 1), (2, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		Some("global notes".to_string()),
+		8192);
+	let decoded = MainFooter::decode_directly(&mut Cursor::new(footer.encode_directly())).unwrap();
+	assert_eq!(footer, decoded);
+
+	let mut footer_with_timestamps = footer.clone();
+	footer_with_timestamps.set_acquisition_start(1_700_000_000);
+	footer_with_timestamps.set_acquisition_end(1_700_000_100);
+	let decoded = MainFooter::decode_directly(&mut Cursor::new(footer_with_timestamps.encode_directly())).unwrap();
+	assert_eq!(footer_with_timestamps, decoded);
+	assert_eq!(decoded.acquisition_duration(), Some(100));
+
+	let footer_without_notes = MainFooter { description_notes: None, ..footer };
+	let decoded = MainFooter::decode_directly(&mut Cursor::new(footer_without_notes.encode_directly())).unwrap();
+	assert_eq!(footer_without_notes, decoded);
+}
+
+#[test]
+fn footers_with_chunk_parity_maps_use_a_separate_version() {
+	// identifier (4 bytes), footer length (8 bytes), version.
+	let mut segment_footer = SegmentFooter::new_empty();
+	assert_eq!(segment_footer.encode_directly()[12], DEFAULT_FOOTER_VERSION_SEGMENT_FOOTER);
+	segment_footer.chunk_parity_map_table = map(&[(10, 700)]);
+	let encoded = segment_footer.encode_directly();
+	assert_eq!(encoded[12], FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY);
+	assert_eq!(segment_footer, SegmentFooter::decode_directly(&mut Cursor::new(&encoded)).unwrap());
+	let mut unknown_version = encoded;
+	unknown_version[12] = FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY + 1;
+	let error = SegmentFooter::decode_directly(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+	// the table is decoded by the version of the footer (so a footer of this version without the table is malformed).
+	let mut missing_table = SegmentFooter::new_empty().encode_directly();
+	missing_table[12] = FOOTER_VERSION_SEGMENT_FOOTER_CHUNK_PARITY;
+	assert!(SegmentFooter::decode_directly(&mut Cursor::new(missing_table)).is_err());
+
+	let mut main_footer = MainFooter::default();
+	assert_eq!(main_footer.encode_directly()[12], DEFAULT_FOOTER_VERSION_MAIN_FOOTER);
+	main_footer.chunk_parity_maps = map(&[(10, 1)]);
+	let encoded = main_footer.encode_directly();
+	assert_eq!(encoded[12], FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY);
+	assert_eq!(main_footer, MainFooter::decode_directly(&mut Cursor::new(&encoded)).unwrap());
+	let mut unknown_version = encoded;
+	unknown_version[12] = FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS + 1;
+	let error = MainFooter::decode_directly(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+	let mut missing_table = MainFooter::default().encode_directly();
+	missing_table[12] = FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY;
+	assert!(MainFooter::decode_directly(&mut Cursor::new(missing_table)).is_err());
+}
+
+#[test]
+fn main_footer_with_acquisition_timestamps_uses_a_separate_version() {
+	let mut main_footer = MainFooter::default();
+	main_footer.set_acquisition_start(1_700_000_000);
+	let encoded = main_footer.encode_directly();
+	assert_eq!(encoded[12], FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS);
+	assert_eq!(main_footer, MainFooter::decode_directly(&mut Cursor::new(&encoded)).unwrap());
+
+	main_footer.set_acquisition_end(1_700_000_100);
+	main_footer.chunk_parity_maps = map(&[(10, 1)]);
+	let encoded = main_footer.encode_directly();
+	assert_eq!(encoded[12], FOOTER_VERSION_MAIN_FOOTER_ACQUISITION_TIMESTAMPS);
+	assert_eq!(main_footer, MainFooter::decode_directly(&mut Cursor::new(&encoded)).unwrap());
+
+	// footers of older versions are still readable.
+	let main_footer = MainFooter { acquisition_start: None, acquisition_end: None, ..main_footer };
+	let encoded = main_footer.encode_directly();
+	assert_eq!(encoded[12], FOOTER_VERSION_MAIN_FOOTER_CHUNK_PARITY);
+	assert_eq!(main_footer, MainFooter::decode_directly(&mut Cursor::new(&encoded)).unwrap());
+}
+
+#[test]
+fn object_footer_physical_roundtrip() {
+	let footer = ObjectFooterPhysical::new(1, 1_700_000_000, 1_700_000_100, 1_000_000, 1, 4, hash_header());
+	let decoded = ObjectFooter::decode_directly(&mut Cursor::new(footer.encode_directly())).unwrap();
+	assert_eq!(ObjectFooter::Physical(footer.clone()), decoded);
+
+	let mut footer_with_bad_chunks = footer.clone();
+	footer_with_bad_chunks.bad_chunks = vec![2, 3];
+	let decoded = ObjectFooter::decode_directly(&mut Cursor::new(footer_with_bad_chunks.encode_directly())).unwrap();
+	assert_eq!(ObjectFooter::Physical(footer_with_bad_chunks.clone()), decoded);
+
+	footer_with_bad_chunks.error_log = vec![AcquisitionErrorEntry::new(2, "unreadable sector"), AcquisitionErrorEntry::new(3, "interrupted")];
+	let decoded = ObjectFooter::decode_directly(&mut Cursor::new(footer_with_bad_chunks.encode_directly())).unwrap();
+	assert_eq!(ObjectFooter::Physical(footer_with_bad_chunks), decoded);
+
+	// the error log could also be stored without recorded bad chunks.
+	let mut footer_with_error_log = footer;
+	footer_with_error_log.error_log = vec![AcquisitionErrorEntry::new(2, "unreadable sector")];
+	let decoded = ObjectFooter::decode_directly(&mut Cursor::new(footer_with_error_log.encode_directly())).unwrap();
+	assert_eq!(ObjectFooter::Physical(footer_with_error_log), decoded);
+}
+
+#[test]
+fn object_footer_physical_with_acquisition_errors_uses_a_separate_version() {
+	// identifier (4 bytes), footer length (8 bytes), version.
+	let mut footer = ObjectFooterPhysical::new(1, 1_700_000_000, 1_700_000_100, 1_000_000, 1, 4, hash_header());
+	assert_eq!(footer.encode_directly()[12], DEFAULT_FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL);
+	footer.bad_chunks = vec![2, 3];
+	let encoded = footer.encode_directly();
+	assert_eq!(encoded[12], FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS);
+	let mut unknown_version = encoded;
+	unknown_version[12] = FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS + 1;
+	let error = ObjectFooter::decode_directly(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+
+	let mut footer_with_error_log = ObjectFooterPhysical::new(1, 1_700_000_000, 1_700_000_100, 1_000_000, 1, 4, hash_header());
+	footer_with_error_log.error_log = vec![AcquisitionErrorEntry::new(2, "unreadable sector")];
+	assert_eq!(footer_with_error_log.encode_directly()[12], FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS);
+
+	// the version of encrypted footers is stored in plaintext, too.
+	let encryption_information = EncryptionInformation::new(gen_random_key(256), EncryptionAlgorithm::AES256GCM, gen_random_nonce_extension());
+	let encrypted = footer.encrypt_directly(&encryption_information).unwrap();
+	assert_eq!(encrypted[12], FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS);
+	let encrypted_footer = EncryptedObjectFooter::decode_directly(&mut Cursor::new(encrypted)).unwrap();
+	assert_eq!(encrypted_footer.version(), FOOTER_VERSION_OBJECT_FOOTER_PHYSICAL_ACQUISITION_ERRORS);
+	let decrypted = encrypted_footer.decrypt(&encryption_information.encryption_key, encryption_information.nonce_extension, &encryption_information.algorithm).unwrap();
+	assert_eq!(ObjectFooter::Physical(footer), decrypted);
+}
+
+#[test]
+fn object_footer_logical_roundtrip() {
+	let footer = ObjectFooterLogical::new(
+		2,
+		1_700_000_000,
+		1_700_000_100,
+		vec![1, 2],
+		hashmap(&[(1, 1), (2, 1), (3, 2)]),
+		hashmap(&[(1, 100), (2, 200), (3, 300)]),
+		hashmap(&[(1, 1), (2, 2), (3, 2)]),
+		hashmap(&[(1, 150), (2, 250), (3, 350)]));
+	let decoded = ObjectFooter::decode_directly(&mut Cursor::new(footer.encode_directly())).unwrap();
+	assert_eq!(ObjectFooter::Logical(footer), decoded);
+}
+
+#[test]
+fn decompression_is_limited_to_the_given_size() {
+	let data = b"zff bounded decompression test. ".repeat(1024);
+	for algorithm in [CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4, CompressionAlgorithm::Brotli, CompressionAlgorithm::Xz, CompressionAlgorithm::Gzip] {
+		let header = CompressionHeader::new(algorithm.clone(), 3, 1.05);
+		let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+		assert!(compressed);
+		assert_eq!(data, decompress_buffer_with_limit(&compressed_data, &algorithm, None, data.len()).unwrap());
+		let error = decompress_buffer_with_limit(&compressed_data, &algorithm, None, data.len() - 1).unwrap_err();
+		assert!(matches!(error.get_kind(), ZffErrorKind::MalformedSegment));
+	}
+}
+
+// the compressed data of incompressible input could be larger than the input (e.g. with a low compression level).
+#[test]
+#[allow(deprecated)]
+fn zstd_compression_of_incompressible_data() {
+	let data = gen_random_key(4096 * 8);
+	let header = CompressionHeader::new(CompressionAlgorithm::Zstd, 1, 0.5);
+	let (compressed_data, compressed) = compress_chunk(data.clone(), &header).unwrap();
+	assert!(compressed);
+	assert!(compressed_data.len() > data.len());
+	assert_eq!(data, decompress_buffer_with_limit(&compressed_data, CompressionAlgorithm::Zstd, None, data.len()).unwrap());
+
+	// the deprecated compress_buffer() ignores the given chunk size (which truncated the data at low compression levels).
+	let (legacy_compressed_data, _) = zff::io::compress_buffer(data.clone(), 4096, &header).unwrap();
+	assert_eq!(compressed_data, legacy_compressed_data);
+}
+
+#[test]
+fn compression_threshold_sentinels() {
+	let compressible_data = vec![0x41; 4096 * 8];
+	for algorithm in [CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4] {
+		let header = CompressionHeader::new(algorithm, 1, COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS);
+		assert!(header.never_compress());
+		assert!(!header.always_compress());
+		let (data, compressed) = compress_chunk(compressible_data.clone(), &header).unwrap();
+		assert!(!compressed);
+		assert_eq!(data, compressible_data);
+	}
+
+	let incompressible_data = gen_random_key(4096 * 8);
+	let header = CompressionHeader::new(CompressionAlgorithm::Zstd, 1, COMPRESSION_RATIO_THRESHOLD_ALWAYS_COMPRESS);
+	assert!(header.always_compress());
+	assert!(!header.never_compress());
+	let (_, compressed) = compress_chunk(incompressible_data, &header).unwrap();
+	assert!(compressed);
+
+	let header = CompressionHeader::new(CompressionAlgorithm::Lz4, 1, COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS);
+	let mut cursor = Cursor::new(header.encode_directly());
+	let decoded_header = CompressionHeader::decode_directly(&mut cursor).unwrap();
+	assert!(decoded_header.never_compress());
+}
+
+#[test]
+fn hash_bytes_matches_hash_reader() {
+	// larger than the internal buffer of Hash::hash_reader.
+	let data = (0..1024 * 1024 + 17).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+	for hash_type in HashType::all() {
+		let hash = Hash::hash_bytes(hash_type, &data);
+		let mut hasher = Hash::new_hasher(hash_type);
+		hasher.update(&data);
+		assert_eq!(hash, hasher.finalize().to_vec(), "{hash_type} hash mismatch");
+		assert_eq!(Hash::hash_reader(hash_type, Cursor::new(&data)).unwrap(), hash, "{hash_type} hash mismatch");
+	}
+	assert_eq!(
+		hex::encode(Hash::hash_bytes(&HashType::SHA256, b"")),
+		"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+}
+
+#[test]
+fn multi_hasher_matches_hash_bytes() {
+	let data = (0..1024 * 1024 + 17).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+	for n_threads in [0, 1, 2, 16] {
+		let mut hasher = MultiHasher::new(HashType::all().to_vec(), n_threads);
+		for part in data.chunks(100_000) {
+			hasher.write_all(part).unwrap();
+		}
+		let hashes = hasher.finalize();
+		assert_eq!(hashes.len(), HashType::all().len());
+		for ((hash_type, hash), expected_hash_type) in hashes.into_iter().zip(HashType::all()) {
+			assert_eq!(&hash_type, expected_hash_type);
+			assert_eq!(hash, Hash::hash_bytes(&hash_type, &data), "{hash_type} hash mismatch (threads: {n_threads})");
+		}
+	}
+}
+
+#[test]
+fn hash_type_string_roundtrip() {
+	for hash_type in HashType::all() {
+		let parsed: HashType = hash_type.to_string().parse().unwrap();
+		assert_eq!(&parsed, hash_type);
+	}
+	assert_eq!("blake2b-512".parse::<HashType>().unwrap(), HashType::Blake2b512);
+	assert_eq!("SHA3-256".parse::<HashType>().unwrap(), HashType::SHA3_256);
+	assert_eq!("sha256".parse::<HashType>().unwrap(), HashType::SHA256);
+	let error = "md5".parse::<HashType>().unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::ParseError));
+}
+
+#[cfg(feature = "serde")]
+fn serde_json_roundtrip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+	let json = serde_json::to_string(value).unwrap();
+	serde_json::from_str(&json).unwrap_or_else(|e| panic!("unable to deserialize {json}: {e}"))
+}
+
+// the serialized hash values are keyed by their hash type, so the deserialized hash values are ordered by the hash type.
+#[cfg(feature = "serde")]
+fn sorted_hash_header() -> HashHeader {
+	let mut header = hash_header();
+	header.hashes.sort_by_key(|hashvalue| hashvalue.hash_type.clone() as u8);
+	header
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn headers_serde_roundtrip() {
+	let header = SegmentHeader::new(0xDEADBEEF, 2, 32768);
+	let decoded = serde_json_roundtrip(&header);
+	assert_eq!(header.unique_identifier, decoded.unique_identifier);
+	assert_eq!(header.segment_number, decoded.segment_number);
+	assert_eq!(header.chunkmap_size, decoded.chunkmap_size);
+
+	let header = compression_header();
+	assert_eq!(header, serde_json_roundtrip(&header));
+	let header = CompressionHeader::new_with_dictionary(CompressionAlgorithm::Zstd, 3, 1.05, vec![1, 2, 3]);
+	assert_eq!(header, serde_json_roundtrip(&header));
+
+	let header = description_header();
+	assert_eq!(header, serde_json_roundtrip(&header));
+
+	let header = sorted_hash_header();
+	assert_eq!(header, serde_json_roundtrip(&header));
+	assert_eq!(header, serde_json_roundtrip(&hash_header()));
+
+	for (header, _) in [encryption_header(), argon2id_encryption_header(), scrypt_encryption_header(PBEScheme::AES128CBC)] {
+		assert_eq!(header, serde_json_roundtrip(&header));
+		assert_eq!(header.pbe_header, serde_json_roundtrip(&header.pbe_header));
+	}
+	// the decrypted encryption key is serialized, too.
+	let (mut header, encryption_key) = encryption_header();
+	header.decrypt_encryption_key(PASSWORD).unwrap();
+	let decoded = serde_json_roundtrip(&header);
+	assert_eq!(header, decoded);
+	assert_eq!(decoded.get_encryption_key(), Some(encryption_key));
+
+	let header = object_header(None);
+	let decoded = serde_json_roundtrip(&header);
+	assert_object_header_eq(&header, &decoded);
+	assert!(decoded.encryption_header.is_none());
+	let (encryption_header, _) = encryption_header();
+	let header = object_header(Some(encryption_header.clone()));
+	let decoded = serde_json_roundtrip(&header);
+	assert_object_header_eq(&header, &decoded);
+	assert_eq!(decoded.encryption_header, Some(encryption_header));
+
+	let header = file_header();
+	assert_eq!(header, serde_json_roundtrip(&header));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn footers_serde_roundtrip() {
+	let footer = FileFooter::new(5, 1_700_000_000, 1_700_000_100, sorted_hash_header(), 12, 4, 1_000_000);
+	assert_eq!(footer, serde_json_roundtrip(&footer));
+
+	let footer = SegmentFooter::new(
+		4096,
+		hashmap(&[(1, 64), (2, 1024)]),
+		hashmap(&[(1, 2048)]),
+		map(&[(10, 100)]),
+		map(&[(10, 200)]),
+		map(&[(10, 300)]),
+		map(&[(10, 400)]),
+		map(&[(10, 500)]),
+		map(&[(10, 600)]),
+		1,
+		3000);
+	assert_eq!(footer, serde_json_roundtrip(&footer));
+
+	let mut footer = MainFooter::new(
+		2,
+		map(&[(1, 1), (2, 2)]),
+		map(&[(1, 1), (2, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		map(&[(10, 1), (20, 2)]),
+		Some("global notes".to_string()),
+		8192);
+	footer.set_acquisition_start(1_700_000_000);
+	footer.set_acquisition_end(1_700_000_100);
+	assert_eq!(footer, serde_json_roundtrip(&footer));
+
+	let mut footer = ObjectFooterPhysical::new(1, 1_700_000_000, 1_700_000_100, 1_000_000, 1, 4, sorted_hash_header());
+	footer.bad_chunks = vec![2, 3];
+	footer.error_log = vec![AcquisitionErrorEntry::new(2, "unreadable sector")];
+	let footer = ObjectFooter::Physical(footer);
+	assert_eq!(footer, serde_json_roundtrip(&footer));
+
+	let footer = ObjectFooter::Logical(ObjectFooterLogical::new(
+		2,
+		1_700_000_000,
+		1_700_000_100,
+		vec![1, 2],
+		hashmap(&[(1, 1), (2, 1), (3, 2)]),
+		hashmap(&[(1, 100), (2, 200), (3, 300)]),
+		hashmap(&[(1, 1), (2, 2), (3, 2)]),
+		hashmap(&[(1, 150), (2, 250), (3, 350)])));
+	assert_eq!(footer, serde_json_roundtrip(&footer));
+
+	let footer = ObjectFooter::Virtual(ObjectFooterVirtual::with_data(3, 1_700_000_000, vec![1, 2], 4096, 512, 2));
+	assert_eq!(footer, serde_json_roundtrip(&footer));
+}
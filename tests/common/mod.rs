@@ -0,0 +1,89 @@
+// The helpers are shared by several integration tests, but not every test uses each of them.
+#![allow(dead_code)]
+
+// - STD
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+// - internal
+use zff::{
+	HashType,
+	CompressionAlgorithm,
+	header::{ObjectHeader, ObjectType, ObjectFlags, CompressionHeader, DescriptionHeader},
+	io::{
+		ZffCreationParameters,
+		zffwriter::{ZffWriter, ZffFilesOutput},
+	},
+};
+
+pub const CHUNK_SIZE: u64 = 4096;
+
+// creates a fresh (empty) test directory with the given name in the temporary directory and returns its path.
+pub fn test_directory(name: &str) -> PathBuf {
+	let test_dir = std::env::temp_dir().join(format!("zff_test_{name}_{}", std::process::id()));
+	let _ = fs::remove_dir_all(&test_dir);
+	fs::create_dir_all(&test_dir).unwrap();
+	test_dir
+}
+
+// returns the sorted paths of all segments of the container in the given directory.
+pub fn segment_paths(directory: &Path) -> Vec<PathBuf> {
+	let mut paths: Vec<PathBuf> = fs::read_dir(directory).unwrap().map(|entry| entry.unwrap().path()).collect();
+	paths.sort();
+	paths
+}
+
+// some compressible data, followed by a block of same bytes and a partial chunk.
+pub fn input_data() -> Vec<u8> {
+	let mut data: Vec<u8> = (0..CHUNK_SIZE * 3).map(|i| (i % 13) as u8).collect();
+	data.extend(vec![0xAB; CHUNK_SIZE as usize]);
+	data.extend((0..CHUNK_SIZE / 2).map(|i| (i % 7) as u8));
+	data
+}
+
+// (probably) incompressible data of the given number of chunks.
+pub fn incompressible_data(number_of_chunks: u64, seed: u64) -> Vec<u8> {
+	let mut state = seed;
+	(0..CHUNK_SIZE * number_of_chunks).map(|_| {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		state as u8
+	}).collect()
+}
+
+// the header of an unencrypted and unsigned object, which will be compressed by using zstd.
+pub fn object_header(object_number: u64, object_type: ObjectType) -> ObjectHeader {
+	ObjectHeader::new(
+		object_number,
+		None,
+		CHUNK_SIZE,
+		CompressionHeader::new(CompressionAlgorithm::Zstd, 3, 1.05),
+		DescriptionHeader::new_empty(),
+		object_type,
+		ObjectFlags { encryption: false, sign_hash: false, plaintext_description: false })
+}
+
+// writes the given data as a single physical object (see object_header) to the given output.
+pub fn write_physical_object(data: Vec<u8>, hash_types: Vec<HashType>, target_segment_size: Option<u64>, output: ZffFilesOutput) {
+	let params = ZffCreationParameters {
+		target_segment_size,
+		..Default::default()
+	};
+	write_physical_object_with(object_header(1, ObjectType::Physical), Cursor::new(data), hash_types, params, output);
+}
+
+// writes the given input as a single physical object with the given header and parameters to the given output.
+pub fn write_physical_object_with<R: Read>(
+	object_header: ObjectHeader,
+	input: R,
+	hash_types: Vec<HashType>,
+	params: ZffCreationParameters,
+	output: ZffFilesOutput) {
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, input);
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), hash_types, params, output).unwrap();
+	writer.generate_files().unwrap();
+}
@@ -0,0 +1,293 @@
+// - STD
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+// - internal
+use zff::{
+	HashType,
+	Signature,
+	CompressionAlgorithm,
+	EncryptionAlgorithm,
+	KDFScheme,
+	PBEScheme,
+	ZffErrorKind,
+	constants::{DEFAULT_CHUNK_SIZE, DEFAULT_COMPRESSION_LEVEL},
+	encryption::{encrypt_pbkdf2sha256_aes256cbc, gen_random_key, gen_random_iv, gen_random_salt},
+	header::*,
+	io::{
+		ZffCreationParameters,
+		zffwriter::{ZffWriter, ZffFilesOutput},
+		zffreader::{ZffReader, ObjectType as ReaderObjectType, peek_container_info},
+	},
+};
+
+const PASSWORD: &str = "zff_test_password";
+const PBKDF2_ITERATIONS: u32 = 1000;
+
+mod common;
+use common::{CHUNK_SIZE, input_data, incompressible_data, object_header, test_directory, write_physical_object_with};
+
+fn encryption_header(algorithm: EncryptionAlgorithm, key_length: usize) -> EncryptionHeader {
+	encryption_header_with_password(algorithm, key_length, PASSWORD)
+}
+
+fn encryption_header_with_password(algorithm: EncryptionAlgorithm, key_length: usize, password: &str) -> EncryptionHeader {
+	let encryption_key = gen_random_key(key_length);
+	let salt = gen_random_salt();
+	let nonce = gen_random_iv();
+	let encrypted_encryption_key = encrypt_pbkdf2sha256_aes256cbc(PBKDF2_ITERATIONS, &salt, &nonce, password, &encryption_key).unwrap();
+	let kdf_parameters = KDFParameters::PBKDF2SHA256Parameters(PBKDF2SHA256Parameters::new(PBKDF2_ITERATIONS, salt));
+	let pbe_header = PBEHeader::new(KDFScheme::PBKDF2SHA256, PBEScheme::AES256CBC, kdf_parameters, nonce);
+	let mut encryption_header = EncryptionHeader::new(pbe_header, algorithm, encrypted_encryption_key);
+	encryption_header.decrypted_encryption_key = Some(encryption_key);
+	encryption_header
+}
+
+fn build_encrypted_container(name: &str, data: Vec<u8>, encryption_header: EncryptionHeader) -> (PathBuf, ZffReader<File>) {
+	let test_dir = test_directory(name);
+
+	let mut object_header = object_header(1, ObjectType::Physical);
+	object_header.encryption_header = Some(encryption_header);
+	object_header.flags.encryption = true;
+	object_header.description_header.set_case_number(name);
+	let output = test_dir.join("container");
+	write_physical_object_with(
+		object_header,
+		Cursor::new(data),
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone()));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	(test_dir, reader)
+}
+
+#[test]
+fn encrypted_physical_object_roundtrip() {
+	let algorithms = [
+		(EncryptionAlgorithm::AES128GCM, 128),
+		(EncryptionAlgorithm::AES256GCM, 256),
+		(EncryptionAlgorithm::CHACHA20POLY1305, 256),
+		(EncryptionAlgorithm::XCHACHA20POLY1305, 256),
+	];
+	for (algorithm, key_length) in algorithms {
+		let data = input_data();
+		let (test_dir, mut reader) = build_encrypted_container(
+			&algorithm.to_string(), data.clone(), encryption_header(algorithm, key_length));
+
+		assert_eq!(reader.list_objects().unwrap().get(&1), Some(&ReaderObjectType::Encrypted));
+		assert!(reader.decrypt_object(1, "wrong password").is_err());
+		assert_eq!(reader.decrypt_object(1, PASSWORD).unwrap(), ReaderObjectType::Physical);
+
+		reader.set_active_object(1).unwrap();
+		let mut decrypted_data = Vec::new();
+		reader.read_to_end(&mut decrypted_data).unwrap();
+		assert!(decrypted_data == data);
+
+		fs::remove_dir_all(test_dir).unwrap();
+	}
+}
+
+#[test]
+fn object_descriptions_omit_encrypted_objects() {
+	let (test_dir, mut reader) = build_encrypted_container(
+		"object_descriptions", input_data(), encryption_header(EncryptionAlgorithm::AES256GCM, 256));
+
+	assert!(reader.object_descriptions().is_empty());
+	// the integrity hashes of encrypted objects cannot be compared.
+	assert!(matches!(reader.chunk_xxhashes().unwrap_err().get_kind(), ZffErrorKind::MismatchObjectType));
+	assert!(matches!(reader.export_chunk_maps().unwrap_err().get_kind(), ZffErrorKind::MismatchObjectType));
+	reader.decrypt_object(1, PASSWORD).unwrap();
+	let descriptions = reader.object_descriptions();
+	assert_eq!(descriptions.len(), 1);
+	assert_eq!(descriptions[&1].case_number(), Some("object_descriptions"));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_all_stored_requires_decryption_of_encrypted_objects() {
+	let (test_dir, mut reader) = build_encrypted_container(
+		"verify_all_stored_encrypted", input_data(), encryption_header(EncryptionAlgorithm::AES256GCM, 256));
+	let key = Signature::new_signing_key().verifying_key().to_bytes();
+	// the flags of encrypted objects can be read without the decryption password.
+	assert!(!reader.object_is_signed(1).unwrap());
+	assert!(!reader.is_signed().unwrap());
+
+	reader.set_active_object(1).unwrap();
+	let error = reader.verify_all_stored(key).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::MismatchObjectType));
+	assert!(error.to_string().contains("decryption password is required"));
+	reader.decrypt_object(1, PASSWORD).unwrap();
+	// the object was created without hash signatures.
+	let error = reader.verify_all_stored(key).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::InvalidOption));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn plaintext_description_of_encrypted_object_is_readable_without_password() {
+	let test_dir = test_directory("plaintext_description");
+
+	let data = input_data();
+	let mut description_header = DescriptionHeader::new_empty();
+	description_header.set_case_number("plaintext_description");
+	description_header.set_evidence_number("E-01");
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical)
+		.chunk_size(CHUNK_SIZE)
+		.encryption(encryption_header(EncryptionAlgorithm::AES256GCM, 256))
+		.description(description_header.clone())
+		.plaintext_description(true)
+		.build()
+		.unwrap();
+	assert!(object_header.flags.plaintext_description);
+	let output = test_dir.join("container");
+	write_physical_object_with(
+		object_header,
+		Cursor::new(data.clone()),
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone()));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	assert_eq!(reader.list_objects().unwrap()[&1], ReaderObjectType::Encrypted);
+	assert_eq!(reader.object_descriptions().get(&1), Some(&description_header));
+	assert_eq!(reader.find_by_evidence_number("E-01"), vec![1]);
+
+	// the chunk data stays encrypted.
+	reader.decrypt_object(1, PASSWORD).unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut decrypted_data = Vec::new();
+	reader.read_to_end(&mut decrypted_data).unwrap();
+	assert!(decrypted_data == data);
+	assert_eq!(reader.active_object_header_ref().unwrap().description_header, description_header);
+
+	// the flag will only be set for encrypted objects.
+	let object_header = ObjectHeader::builder(2, ObjectType::Physical).plaintext_description(true).build().unwrap();
+	assert!(!object_header.flags.plaintext_description);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn object_header_builder_requires_encryption_key() {
+	let mut header_without_key = encryption_header(EncryptionAlgorithm::AES256GCM, 256);
+	header_without_key.decrypted_encryption_key = None;
+	let result = ObjectHeader::builder(1, ObjectType::Physical).encryption(header_without_key).build();
+	assert!(matches!(result.unwrap_err().get_kind(), ZffErrorKind::MissingEncryptionKey));
+	let result = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(0).build();
+	assert!(matches!(result.unwrap_err().get_kind(), ZffErrorKind::InvalidOption));
+
+	let object_header = ObjectHeader::builder(2, ObjectType::Logical)
+		.encryption(encryption_header(EncryptionAlgorithm::AES256GCM, 256))
+		.sign_hash(true)
+		.build()
+		.unwrap();
+	assert_eq!(object_header.object_number, 2);
+	assert_eq!(object_header.object_type, ObjectType::Logical);
+	assert_eq!(object_header.chunk_size, DEFAULT_CHUNK_SIZE);
+	assert_eq!(object_header.compression_header, CompressionHeader::new(CompressionAlgorithm::Zstd, DEFAULT_COMPRESSION_LEVEL, 1.05));
+	assert_eq!(object_header.flags, ObjectFlags { encryption: true, sign_hash: true, plaintext_description: false });
+}
+
+#[test]
+fn decrypt_all_objects_reports_per_object_results() {
+	let test_dir = test_directory("decrypt_all");
+
+	// two objects are encrypted with the same password, one object with an other password and one object is unencrypted.
+	// The writer assigns the object numbers on its own, so the objects are identified by their data.
+	let mut physical_objects = HashMap::new();
+	let mut data = Vec::new();
+	for (object_number, password) in [(1, Some(PASSWORD)), (2, Some(PASSWORD)), (3, Some("other_password")), (4, None)] {
+		let mut builder = ObjectHeader::builder(object_number, ObjectType::Physical).chunk_size(CHUNK_SIZE);
+		if let Some(password) = password {
+			builder = builder.encryption(encryption_header_with_password(EncryptionAlgorithm::AES256GCM, 256, password));
+		}
+		let mut object_data = input_data();
+		object_data.extend(incompressible_data(2, object_number));
+		physical_objects.insert(builder.build().unwrap(), Cursor::new(object_data.clone()));
+		data.push(object_data);
+	}
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(
+		physical_objects,
+		HashMap::new(),
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	let unencrypted_objects = reader.list_decrypted_objects();
+	assert_eq!(unencrypted_objects.len(), 1);
+	assert_eq!(reader.list_initialized_objects(), reader.list_objects().unwrap());
+	assert_eq!(reader.list_initialized_objects().values().filter(|object_type| **object_type == ReaderObjectType::Encrypted).count(), 3);
+
+	let results = reader.decrypt_all_objects(PASSWORD);
+	assert_eq!(results.len(), 3);
+	assert!(results.keys().all(|object_number| !unencrypted_objects.contains_key(object_number)));
+	let failed_objects = results.iter().filter(|(_, result)| result.is_err()).map(|(object_number, _)| *object_number).collect::<Vec<_>>();
+	assert_eq!(failed_objects.len(), 1);
+	assert!(results.values().flatten().all(|object_type| object_type == &ReaderObjectType::Physical));
+	assert!(!reader.list_decrypted_objects().contains_key(&failed_objects[0]));
+	assert_eq!(reader.list_objects().unwrap().get(&failed_objects[0]), Some(&ReaderObjectType::Encrypted));
+	let initialized_objects = reader.list_initialized_objects();
+	assert_eq!(initialized_objects.len(), 4);
+	assert_eq!(initialized_objects.get(&failed_objects[0]), Some(&ReaderObjectType::Encrypted));
+	assert_eq!(initialized_objects.values().filter(|object_type| **object_type == ReaderObjectType::Physical).count(), 3);
+
+	let mut read_objects = Vec::new();
+	for object_number in reader.list_decrypted_objects().into_keys() {
+		reader.set_active_object(object_number).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		read_objects.push(data.iter().position(|object_data| object_data == &read_data).expect("unexpected object data"));
+	}
+	read_objects.sort_unstable();
+	// the object with the other password remains encrypted.
+	assert_eq!(read_objects, vec![0, 1, 3]);
+
+	// the same result in a single step.
+	let mut reader = ZffReader::with_reader_and_password(vec![File::open(output.with_extension("z01")).unwrap()], PASSWORD).unwrap();
+	assert_eq!(reader.list_decrypted_objects().len(), 3);
+	assert_eq!(reader.list_initialized_objects().get(&failed_objects[0]), Some(&ReaderObjectType::Encrypted));
+	reader.decrypt_object(failed_objects[0], "other_password").unwrap();
+	assert_eq!(reader.list_decrypted_objects().len(), 4);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn raw_chunk_bytes_are_not_decrypted() {
+	// the (probably) incompressible data starts in the middle of the fifth chunk.
+	let mut data = input_data();
+	data.extend(incompressible_data(2, 0x2545F4914F6CDD1D));
+	let (test_dir, mut reader) = build_encrypted_container(
+		"raw_chunk_bytes", data.clone(), encryption_header(EncryptionAlgorithm::AES256GCM, 256));
+
+	reader.decrypt_object(1, PASSWORD).unwrap();
+	let (flags, raw_data) = reader.raw_chunk_bytes(6).unwrap();
+	// the incompressible chunk is stored uncompressed, but encrypted (with the appropriate AEAD tag).
+	assert!(!flags.compression);
+	assert_eq!(raw_data.len() as u64, CHUNK_SIZE + 16);
+	assert!(raw_data[..CHUNK_SIZE as usize] != data[(CHUNK_SIZE * 5) as usize..(CHUNK_SIZE * 6) as usize]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn peek_container_info_detects_encrypted_objects() {
+	let encryption_header = encryption_header(EncryptionAlgorithm::AES256GCM, 256);
+	let (test_dir, _) = build_encrypted_container("peek_encrypted", input_data(), encryption_header);
+	let mut segment = File::open(test_dir.join("container").with_extension("z01")).unwrap();
+	let container_info = peek_container_info(&mut segment).unwrap();
+	assert_eq!(container_info.first_object_encrypted, Some(true));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
@@ -0,0 +1,639 @@
+// - STD
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// - internal
+use zff::{
+	Hash,
+	HashType,
+	Signature,
+	ZffErrorKind,
+	ValueDecoder,
+	CompressionAlgorithm,
+	constants::{METADATA_ATIME, METADATA_MTIME, METADATA_CTIME, METADATA_BTIME},
+	header::{ObjectHeader, ObjectType, CompressionHeader, FileType, MetadataExtendedValue},
+	io::{
+		ZffCreationParameters,
+		OriginalPathFormat,
+		zffwriter::{ZffWriter, ZffFilesOutput, transcode_container},
+		zffreader::ZffReader,
+	},
+	footer::ObjectFooter,
+};
+
+mod common;
+use common::{CHUNK_SIZE, object_header, test_directory};
+
+// creates a small directory tree in a fresh temporary directory and returns its path.
+fn prepare_input_directory(name: &str) -> PathBuf {
+	let test_dir = test_directory(name);
+	let input_dir = test_dir.join("input");
+	fs::create_dir_all(input_dir.join("subdir")).unwrap();
+	fs::write(input_dir.join("data.bin"), file_content()).unwrap();
+	fs::write(input_dir.join("subdir").join("text.txt"), b"zff logical object test").unwrap();
+	test_dir
+}
+
+// 2.5 chunks of compressible data.
+fn file_content() -> Vec<u8> {
+	(0..CHUNK_SIZE * 5 / 2).map(|i| (i % 251) as u8).collect()
+}
+
+fn build_logical_container(test_dir: &Path, compression_algorithm: CompressionAlgorithm) -> ZffReader<File> {
+	let mut object_header = object_header(1, ObjectType::Logical);
+	object_header.compression_header = CompressionHeader::new(compression_algorithm, 3, 1.05);
+	let mut logical_objects = HashMap::new();
+	logical_objects.insert(object_header, vec![test_dir.join("input")]);
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::<Cursor<Vec<u8>>>::with_data(
+		HashMap::new(),
+		logical_objects,
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	reader
+}
+
+// returns the file number of the regular file with the given name.
+fn find_file(reader: &mut ZffReader<File>, filename: &str) -> u64 {
+	let (file_number, _) = reader.files_metadata().unwrap().into_iter()
+		.find(|(_, metadata)| metadata.file_type == FileType::File && metadata.filename.as_deref() == Some(filename))
+		.unwrap();
+	reader.set_active_file(file_number).unwrap();
+	file_number
+}
+
+#[test]
+fn file_numbers_lists_all_files() {
+	let test_dir = prepare_input_directory("file_numbers");
+	let reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+
+	// input, data.bin, subdir and text.txt
+	let file_numbers = reader.file_numbers().unwrap();
+	assert_eq!(file_numbers, vec![1, 2, 3, 4]);
+	let files_metadata = reader.files_metadata().unwrap();
+	assert_eq!(files_metadata.keys().copied().collect::<Vec<_>>(), file_numbers);
+	let text_file = files_metadata.values().find(|metadata| metadata.filename.as_deref() == Some("text.txt")).unwrap();
+	let subdir = &files_metadata[&text_file.parent_file_number];
+	assert_eq!(subdir.filename.as_deref(), Some("subdir"));
+	assert_eq!(subdir.file_type, FileType::Directory);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn object_data_size_sums_file_sizes() {
+	let test_dir = prepare_input_directory("object_data_size");
+	let reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+
+	// the size includes the stored data of the directories (the lists of their children).
+	let files_metadata = reader.files_metadata().unwrap();
+	let regular_file_size: u64 = files_metadata.values()
+		.filter(|metadata| metadata.file_type == FileType::File)
+		.map(|metadata| metadata.length_of_data)
+		.sum();
+	assert_eq!(regular_file_size, (file_content().len() + b"zff logical object test".len()) as u64);
+	let expected_size: u64 = files_metadata.values().map(|metadata| metadata.length_of_data).sum();
+	assert_eq!(reader.object_data_size(1).unwrap(), expected_size);
+	assert_eq!(reader.container_data_size(), expected_size);
+	assert!(reader.object_data_size(2).is_err());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn extract_file_matches_input() {
+	for compression_algorithm in [CompressionAlgorithm::None, CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4, CompressionAlgorithm::Gzip] {
+		let test_dir = prepare_input_directory(&compression_algorithm.to_string());
+		let mut reader = build_logical_container(&test_dir, compression_algorithm);
+		assert!(reader.verify_all().unwrap().is_empty());
+
+		let file_number = find_file(&mut reader, "data.bin");
+		let mut extracted = Vec::new();
+		let written_bytes = reader.extract_file(file_number, &mut extracted).unwrap();
+		assert_eq!(written_bytes, file_content().len() as u64);
+		assert_eq!(extracted, file_content());
+
+		// extract_file must not change the position of the active file.
+		let file_number = find_file(&mut reader, "text.txt");
+		let mut buffer = [0u8; 3];
+		reader.read_exact(&mut buffer).unwrap();
+		let mut extracted = Vec::new();
+		reader.extract_file(file_number, &mut extracted).unwrap();
+		assert_eq!(extracted, b"zff logical object test");
+		let mut rest = Vec::new();
+		reader.read_to_end(&mut rest).unwrap();
+		assert_eq!(rest, b" logical object test");
+
+		fs::remove_dir_all(test_dir).unwrap();
+	}
+}
+
+#[test]
+fn chunk_offsets_point_to_the_chunk_data_of_files() {
+	let test_dir = prepare_input_directory("chunk_offsets");
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::None);
+
+	// every chunk of a file follows its file header, so a wrong offset would return the encoded file header.
+	for (_, metadata) in reader.files_metadata().unwrap() {
+		if metadata.file_type != FileType::File {
+			continue;
+		}
+		let content = match metadata.filename.as_deref() {
+			Some("data.bin") => file_content(),
+			_ => b"zff logical object test".to_vec(),
+		};
+		for (index, chunk_number) in (metadata.first_chunk_number..metadata.first_chunk_number + metadata.number_of_chunks).enumerate() {
+			let (_, raw_data) = reader.raw_chunk_bytes(chunk_number).unwrap();
+			let start = index * CHUNK_SIZE as usize;
+			let end = std::cmp::min(start + CHUNK_SIZE as usize, content.len());
+			assert_eq!(raw_data, &content[start..end], "wrong offset of chunk {chunk_number}");
+		}
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn read_at_reads_the_active_file() {
+	let test_dir = prepare_input_directory("read_at_logical");
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+
+	find_file(&mut reader, "data.bin");
+	let mut buffer = vec![0u8; 100];
+	assert_eq!(reader.read_at(CHUNK_SIZE - 50, &mut buffer).unwrap(), buffer.len());
+	assert_eq!(buffer[..], file_content()[CHUNK_SIZE as usize - 50..CHUNK_SIZE as usize + 50]);
+
+	find_file(&mut reader, "text.txt");
+	reader.seek(SeekFrom::Start(4)).unwrap();
+	let mut buffer = vec![0u8; 6];
+	reader.read_at(12, &mut buffer).unwrap();
+	assert_eq!(&buffer, b"object");
+	let mut rest = Vec::new();
+	reader.read_to_end(&mut rest).unwrap();
+	assert_eq!(rest, b"logical object test");
+
+	// ranges past the end of the active file are limited to the remaining data.
+	assert_eq!(reader.read_range(1, 18, usize::MAX).unwrap(), b" test");
+	assert!(reader.read_range(1, 1000, usize::MAX).unwrap().is_empty());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn directory_tree_preserves_empty_directories_and_symlinks() {
+	let test_dir = prepare_input_directory("directory_tree");
+	let input_dir = test_dir.join("input");
+	fs::create_dir(input_dir.join("empty")).unwrap();
+	std::os::unix::fs::symlink("subdir/text.txt", input_dir.join("link")).unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+
+	let files_metadata = reader.files_metadata().unwrap();
+	let root = files_metadata.values().find(|metadata| metadata.filename.as_deref() == Some("input")).unwrap();
+	assert_eq!(root.file_type, FileType::Directory);
+	let (empty_dir_number, empty_dir) = files_metadata.iter()
+		.find(|(_, metadata)| metadata.filename.as_deref() == Some("empty")).unwrap();
+	assert_eq!(empty_dir.file_type, FileType::Directory);
+	assert!(files_metadata.values().all(|metadata| metadata.parent_file_number != *empty_dir_number));
+
+	let (link_number, link) = files_metadata.iter()
+		.find(|(_, metadata)| metadata.filename.as_deref() == Some("link")).unwrap();
+	assert_eq!(link.file_type, FileType::Symlink);
+	assert_eq!(files_metadata[&link.parent_file_number].filename.as_deref(), Some("input"));
+	reader.set_active_file(*link_number).unwrap();
+	// the target path of a symlink is stored as encoded string.
+	let target = String::decode_directly(&mut reader).unwrap();
+	assert!(target.ends_with("subdir/text.txt"));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn hardlinks_record_the_file_number_of_the_linked_file() {
+	let test_dir = prepare_input_directory("hardlinks");
+	let input_dir = test_dir.join("input");
+	fs::hard_link(input_dir.join("data.bin"), input_dir.join("subdir").join("hardlink.bin")).unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+
+	// the first read of the hardlinked files contains the data, the other one refers to it.
+	let files_metadata = reader.files_metadata().unwrap();
+	let linked_files: Vec<(u64, &_)> = files_metadata.iter()
+		.filter(|(_, metadata)| matches!(metadata.filename.as_deref(), Some("data.bin") | Some("hardlink.bin")))
+		.map(|(file_number, metadata)| (*file_number, metadata))
+		.collect();
+	assert_eq!(linked_files.len(), 2);
+	let (regular_number, regular) = *linked_files.iter().find(|(_, metadata)| metadata.file_type == FileType::File).unwrap();
+	let (hardlink_number, hardlink) = *linked_files.iter().find(|(_, metadata)| metadata.file_type == FileType::Hardlink).unwrap();
+	assert_eq!(regular.hardlink_target, None);
+	assert_eq!(regular.length_of_data, file_content().len() as u64);
+	assert_eq!(hardlink.hardlink_target, Some(regular_number));
+	assert!(files_metadata.values()
+		.filter(|metadata| metadata.file_type != FileType::Hardlink)
+		.all(|metadata| metadata.hardlink_target.is_none()));
+
+	reader.set_active_file(hardlink_number).unwrap();
+	assert_eq!(reader.current_filemetadata().unwrap().hardlink_target, Some(regular_number));
+	let mut extracted = Vec::new();
+	reader.extract_file(regular_number, &mut extracted).unwrap();
+	assert_eq!(extracted, file_content());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn current_file_recommended_metadata_contains_timestamps_and_permissions() {
+	let test_dir = prepare_input_directory("recommended_metadata");
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+	find_file(&mut reader, "data.bin");
+
+	let recommended_metadata = reader.current_file_recommended_metadata().unwrap();
+	let file_metadata = fs::symlink_metadata(test_dir.join("input").join("data.bin")).unwrap();
+	let mtime = file_metadata.modified().unwrap().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+	assert_eq!(recommended_metadata[METADATA_MTIME].as_u64(), Some(mtime));
+	for key in [METADATA_ATIME, METADATA_CTIME, METADATA_BTIME] {
+		assert!(recommended_metadata[key].as_u64().is_some());
+	}
+	#[cfg(target_family = "unix")]
+	{
+		use std::os::unix::fs::MetadataExt;
+		use zff::constants::{METADATA_EXT_KEY_MODE, METADATA_EXT_KEY_UID, METADATA_EXT_KEY_GID};
+		assert_eq!(recommended_metadata[METADATA_EXT_KEY_MODE].as_u64(), Some(file_metadata.mode() as u64));
+		assert_eq!(recommended_metadata[METADATA_EXT_KEY_UID].as_u64(), Some(file_metadata.uid() as u64));
+		assert_eq!(recommended_metadata[METADATA_EXT_KEY_GID].as_i64(), Some(file_metadata.gid() as i64));
+		assert_eq!(recommended_metadata.len(), 7);
+	}
+
+	// the recommended metadata are a subset of all stored metadata.
+	let all_metadata = reader.current_file_metadata().unwrap();
+	assert!(recommended_metadata.iter().all(|(key, value)| all_metadata.get(key) == Some(value)));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn transcode_container_preserves_files_and_metadata() {
+	let test_dir = prepare_input_directory("transcode_logical");
+	#[cfg(target_family = "unix")]
+	std::os::unix::fs::symlink("subdir/text.txt", test_dir.join("input").join("link")).unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Lz4);
+
+	let output = test_dir.join("transcoded");
+	let source = ZffReader::with_reader(vec![File::open(test_dir.join("container").with_extension("z01")).unwrap()]).unwrap();
+	transcode_container(source, CompressionHeader::new(CompressionAlgorithm::Zstd, 3, 1.05), ZffFilesOutput::NewContainer(output.clone())).unwrap();
+
+	let mut transcoded = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	transcoded.initialize_objects_all().unwrap();
+	transcoded.set_active_object(1).unwrap();
+	assert_eq!(transcoded.active_object_header_ref().unwrap().compression_header.algorithm, CompressionAlgorithm::Zstd);
+	assert!(transcoded.verify_all().unwrap().is_empty());
+	let object_footer = |reader: &ZffReader<File>| match reader.active_object_footer().unwrap() {
+		ObjectFooter::Logical(footer) => footer,
+		_ => panic!("unexpected object footer type"),
+	};
+	let (source_footer, transcoded_footer) = (object_footer(&reader), object_footer(&transcoded));
+	assert_eq!(transcoded_footer.root_dir_filenumbers, source_footer.root_dir_filenumbers);
+	// the acquisition timestamps of the original acquisition are preserved.
+	assert_eq!(transcoded_footer.acquisition_start, source_footer.acquisition_start);
+	assert_eq!(transcoded_footer.acquisition_end, source_footer.acquisition_end);
+	assert_eq!(transcoded.main_footer_ref().acquisition_start(), reader.main_footer_ref().acquisition_start());
+	assert_eq!(transcoded.main_footer_ref().acquisition_end(), reader.main_footer_ref().acquisition_end());
+
+	let file_numbers = reader.file_numbers().unwrap();
+	assert_eq!(transcoded.file_numbers().unwrap(), file_numbers);
+	for file_number in file_numbers {
+		reader.set_active_file(file_number).unwrap();
+		transcoded.set_active_file(file_number).unwrap();
+		assert_eq!(transcoded.current_fileheader().unwrap(), reader.current_fileheader().unwrap());
+		assert_eq!(transcoded.current_filefooter().unwrap().hash_header.hashes, reader.current_filefooter().unwrap().hash_header.hashes);
+		let mut original_data = Vec::new();
+		reader.read_to_end(&mut original_data).unwrap();
+		let mut transcoded_data = Vec::new();
+		transcoded.read_to_end(&mut transcoded_data).unwrap();
+		assert_eq!(transcoded_data, original_data);
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn metadata_extended_value_accessors() {
+	assert_eq!(MetadataExtendedValue::U32(42).as_u64(), Some(42));
+	assert_eq!(MetadataExtendedValue::I16(-1).as_u64(), None);
+	assert_eq!(MetadataExtendedValue::I16(-1).as_i64(), Some(-1));
+	assert_eq!(MetadataExtendedValue::U64(u64::MAX).as_i64(), None);
+	assert_eq!(MetadataExtendedValue::String("zff".to_string()).as_string(), Some("zff"));
+	assert_eq!(MetadataExtendedValue::String("42".to_string()).as_u64(), None);
+	assert_eq!(MetadataExtendedValue::Bool(true).as_bool(), Some(true));
+	assert_eq!(MetadataExtendedValue::U8(1).as_bool(), None);
+}
+
+#[test]
+fn metadata_extended_value_conversions() {
+	assert_eq!(MetadataExtendedValue::from(42u64), MetadataExtendedValue::U64(42));
+	assert_eq!(u64::try_from(MetadataExtendedValue::U32(42)).unwrap(), 42);
+	assert!(matches!(u64::try_from(MetadataExtendedValue::I8(-1)).unwrap_err().get_kind(), ZffErrorKind::MissmatchIdentifier));
+	assert_eq!(i64::try_from(MetadataExtendedValue::from(-7i64)).unwrap(), -7);
+	assert!(i64::try_from(MetadataExtendedValue::from("zff")).is_err());
+	let string: String = MetadataExtendedValue::from("zff".to_string()).try_into().unwrap();
+	assert_eq!(string, "zff");
+	assert!(String::try_from(MetadataExtendedValue::U8(1)).is_err());
+	let bytes: Vec<u8> = MetadataExtendedValue::from(vec![1u8, 2, 3]).try_into().unwrap();
+	assert_eq!(bytes, vec![1, 2, 3]);
+	assert!(Vec::<u8>::try_from(MetadataExtendedValue::from("zff")).is_err());
+}
+
+#[test]
+fn sparse_file_is_stored_as_same_bytes_chunks() {
+	let test_dir = prepare_input_directory("sparse_file");
+	let mut content = vec![0u8; CHUNK_SIZE as usize * 2];
+	content.extend(file_content());
+	// fills the partial chunk of the file content and one more full chunk.
+	content.extend(vec![0u8; CHUNK_SIZE as usize * 3 / 2]);
+	fs::write(test_dir.join("input").join("sparse.bin"), &content).unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	let file_number = find_file(&mut reader, "sparse.bin");
+	let mut extracted = Vec::new();
+	reader.extract_file(file_number, &mut extracted).unwrap();
+	assert!(extracted == content);
+	let first_chunk_number = reader.files_metadata().unwrap()[&file_number].first_chunk_number;
+	let same_bytes_chunks = (first_chunk_number..first_chunk_number + 6)
+		.filter(|chunk_number| reader.chunk_flags(*chunk_number).unwrap().same_bytes)
+		.map(|chunk_number| chunk_number - first_chunk_number)
+		.collect::<Vec<_>>();
+	assert_eq!(same_bytes_chunks, vec![0, 1, 5]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn logical_object_of_empty_files_and_directories() {
+	let test_dir = test_directory("empty_files");
+	let input_dir = test_dir.join("input");
+	fs::create_dir_all(input_dir.join("empty_dir")).unwrap();
+	fs::create_dir_all(input_dir.join("subdir").join("nested_empty_dir")).unwrap();
+	for filename in ["a.txt", "b.txt", "c.txt"] {
+		fs::write(input_dir.join(filename), b"").unwrap();
+	}
+	fs::write(input_dir.join("subdir").join("d.txt"), b"").unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	let files_metadata = reader.files_metadata().unwrap();
+	// input, empty_dir, subdir, nested_empty_dir and the four files.
+	assert_eq!(files_metadata.len(), 8);
+	let mut regular_files = Vec::new();
+	for (file_number, metadata) in &files_metadata {
+		match metadata.file_type {
+			FileType::File => {
+				assert_eq!(metadata.length_of_data, 0);
+				regular_files.push(metadata.filename.clone().unwrap());
+				let mut extracted = Vec::new();
+				assert_eq!(reader.extract_file(*file_number, &mut extracted).unwrap(), 0);
+				assert!(extracted.is_empty());
+			},
+			FileType::Directory => (),
+			_ => panic!("unexpected file type: {}", metadata.file_type),
+		}
+	}
+	regular_files.sort();
+	assert_eq!(regular_files, vec!["a.txt", "b.txt", "c.txt", "d.txt"]);
+
+	// each file (and directory) has its own chunks, the chunk numbers have to be consecutive.
+	let mut chunk_ranges = files_metadata.values()
+		.map(|metadata| (metadata.first_chunk_number, metadata.number_of_chunks))
+		.collect::<Vec<_>>();
+	chunk_ranges.sort_unstable();
+	for pair in chunk_ranges.windows(2) {
+		assert_eq!(pair[0].0 + pair[0].1, pair[1].0);
+	}
+	for file_number in reader.file_numbers().unwrap() {
+		reader.set_active_file(file_number).unwrap();
+		let mut data = Vec::new();
+		reader.read_to_end(&mut data).unwrap();
+		assert_eq!(data.len() as u64, files_metadata[&file_number].length_of_data);
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn per_file_hashes_are_stored_in_the_file_footers() {
+	let test_dir = prepare_input_directory("per_file_hashes");
+	let object_header = ObjectHeader::builder(1, ObjectType::Logical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut logical_objects = HashMap::new();
+	logical_objects.insert(object_header, vec![test_dir.join("input")]);
+	let params = ZffCreationParameters {
+		per_file_hashes: vec![HashType::SHA256, HashType::Blake3],
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::<Cursor<Vec<u8>>>::with_data(
+		HashMap::new(),
+		logical_objects,
+		vec![HashType::Blake3],
+		params,
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	find_file(&mut reader, "data.bin");
+	let mut hashes = reader.current_file_hashes().unwrap();
+	hashes.sort_by_key(|hash_value| hash_value.hash_type().to_string());
+	let mut expected = Vec::new();
+	for hash_type in [HashType::SHA256, HashType::Blake3] {
+		let hash = Hash::hash_bytes(&hash_type, &file_content());
+		expected.push((hash_type, hash));
+	}
+	expected.sort_by_key(|(hash_type, _)| hash_type.to_string());
+	assert_eq!(hashes.len(), expected.len());
+	for (hash_value, (hash_type, hash)) in hashes.iter().zip(expected) {
+		assert_eq!(*hash_value.hash_type(), hash_type);
+		assert!(*hash_value.hash() == hash);
+	}
+
+	// the files of the container without explicit per file hashes are hashed by the hash types of the container.
+	fs::remove_file(output.with_extension("z01")).unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+	find_file(&mut reader, "data.bin");
+	let hashes = reader.current_file_hashes().unwrap();
+	assert_eq!(hashes.len(), 1);
+	assert_eq!(*hashes[0].hash_type(), HashType::Blake3);
+	// logical objects have no object wide hash values.
+	assert!(matches!(reader.stored_hash_values(1).unwrap_err().get_kind(), ZffErrorKind::MismatchObjectType));
+	assert!(reader.stored_hash_values_all().is_empty());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn original_paths_are_stored_as_raw_bytes() {
+	use std::ffi::OsStr;
+	use std::os::unix::ffi::OsStrExt;
+
+	let test_dir = prepare_input_directory("original_paths");
+	let input_dir = test_dir.join("input");
+	// a filename, which is not valid UTF-8.
+	let odd_path = input_dir.join("subdir").join(OsStr::from_bytes(b"odd\xff.bin"));
+	fs::write(&odd_path, b"odd").unwrap();
+	let object_header = ObjectHeader::builder(1, ObjectType::Logical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut logical_objects = HashMap::new();
+	logical_objects.insert(object_header, vec![input_dir.clone()]);
+	let params = ZffCreationParameters {
+		original_paths: Some(OriginalPathFormat::Absolute),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::<Cursor<Vec<u8>>>::with_data(
+		HashMap::new(),
+		logical_objects,
+		vec![HashType::Blake3],
+		params,
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	find_file(&mut reader, "data.bin");
+	assert_eq!(reader.current_file_original_path().unwrap(), Some(input_dir.join("data.bin")));
+	let files_metadata = reader.files_metadata().unwrap();
+	let (odd_number, odd) = files_metadata.iter()
+		.find(|(_, metadata)| metadata.original_path.as_deref() == Some(odd_path.as_path()))
+		.unwrap();
+	assert_eq!(odd.original_path.as_ref().unwrap().as_os_str().as_bytes(), odd_path.as_os_str().as_bytes());
+	assert!(files_metadata.values().all(|metadata| metadata.original_path.is_some()));
+	reader.set_active_file(*odd_number).unwrap();
+	let mut content = Vec::new();
+	reader.read_to_end(&mut content).unwrap();
+	assert_eq!(content, b"odd");
+
+	// the original paths are not stored by default.
+	fs::remove_file(output.with_extension("z01")).unwrap();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+	find_file(&mut reader, "data.bin");
+	assert_eq!(reader.current_file_original_path().unwrap(), None);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn restore_file_metadata_of_extracted_file() {
+	use std::os::unix::fs::{MetadataExt, PermissionsExt};
+	use filetime::{FileTime, set_file_times};
+	use zff::io::restore::restore_file_metadata;
+
+	let test_dir = prepare_input_directory("restore_metadata");
+	let input_file = test_dir.join("input").join("data.bin");
+	fs::set_permissions(&input_file, fs::Permissions::from_mode(0o640)).unwrap();
+	set_file_times(&input_file, FileTime::from_unix_time(1_000_000, 0), FileTime::from_unix_time(1_500_000, 0)).unwrap();
+	// extended attributes are not supported by all filesystems.
+	let xattr_supported = xattr::set(&input_file, "user.zff_test", b"restore").is_ok();
+	// the same applies to POSIX ACLs.
+	let mut acl = posix_acl::PosixACL::new(0o640);
+	acl.set(posix_acl::Qualifier::User(4242), posix_acl::ACL_READ);
+	let acl_supported = acl.write_acl(&input_file).is_ok();
+	let mut reader = build_logical_container(&test_dir, CompressionAlgorithm::Zstd);
+
+	let file_number = find_file(&mut reader, "data.bin");
+	let output_file = test_dir.join("restored.bin");
+	reader.extract_file(file_number, File::create(&output_file).unwrap()).unwrap();
+	restore_file_metadata(&output_file, &reader.current_file_metadata().unwrap()).unwrap();
+
+	let metadata = fs::metadata(&output_file).unwrap();
+	assert_eq!(metadata.mode() & 0o7777, 0o640);
+	assert_eq!(metadata.atime(), 1_000_000);
+	assert_eq!(metadata.mtime(), 1_500_000);
+	assert_eq!(metadata.uid(), fs::metadata(&input_file).unwrap().uid());
+	if xattr_supported {
+		assert_eq!(xattr::get(&output_file, "user.zff_test").unwrap(), Some(b"restore".to_vec()));
+	}
+	if acl_supported {
+		let acl = posix_acl::PosixACL::read_acl(&output_file).unwrap();
+		assert_eq!(acl.get(posix_acl::Qualifier::User(4242)), Some(posix_acl::ACL_READ));
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_all_stored_checks_file_signatures() {
+	let test_dir = prepare_input_directory("verify_all_stored_logical");
+	let mut object_header = object_header(1, ObjectType::Logical);
+	object_header.flags.sign_hash = true;
+	let mut logical_objects = HashMap::new();
+	logical_objects.insert(object_header, vec![test_dir.join("input")]);
+	let signing_key = Signature::new_signing_key();
+	let params = ZffCreationParameters {
+		signature_key: Some(signing_key.clone()),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::<Cursor<Vec<u8>>>::with_data(
+		HashMap::new(),
+		logical_objects,
+		vec![HashType::Blake3],
+		params,
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let file_number = find_file(&mut reader, "text.txt");
+	assert!(reader.verify_all_stored(signing_key.verifying_key().to_bytes()).unwrap().is_empty());
+
+	let other_key = Signature::new_signing_key().verifying_key().to_bytes();
+	let invalid_signatures = reader.verify_all_stored(other_key).unwrap();
+	let mut file_numbers = invalid_signatures.iter().filter_map(|invalid| invalid.file_number).collect::<Vec<_>>();
+	file_numbers.dedup();
+	assert!(file_numbers.contains(&find_file(&mut reader, "data.bin")));
+	assert!(file_numbers.contains(&file_number));
+	// the active file is not changed by the verification.
+	reader.set_active_file(file_number).unwrap();
+	reader.verify_all_stored(other_key).unwrap();
+	let mut content = Vec::new();
+	reader.read_to_end(&mut content).unwrap();
+	assert_eq!(content, b"zff logical object test");
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn restore_file_metadata_with_legacy_acl_keys() {
+	use zff::{constants::METADATA_EXT_KEY_MODE, io::restore::restore_file_metadata};
+
+	let test_dir = test_directory("restore_legacy_acls");
+	let output_file = test_dir.join("restored.bin");
+	fs::write(&output_file, file_content()).unwrap();
+	// POSIX ACLs are not supported by all filesystems.
+	let acl_supported = posix_acl::PosixACL::new(0o640).write_acl(&output_file).is_ok();
+
+	// older containers store the access ACL entries of regular files with the default prefix.
+	let mut metadata_ext = HashMap::new();
+	metadata_ext.insert(METADATA_EXT_KEY_MODE.to_string(), MetadataExtendedValue::from(0o100640u32));
+	metadata_ext.insert("acl::d:user:4242".to_string(), MetadataExtendedValue::from(posix_acl::ACL_READ.to_string()));
+	metadata_ext.insert("acl::d:mask".to_string(), MetadataExtendedValue::from(posix_acl::ACL_READ.to_string()));
+	if acl_supported {
+		restore_file_metadata(&output_file, &metadata_ext).unwrap();
+		let acl = posix_acl::PosixACL::read_acl(&output_file).unwrap();
+		assert_eq!(acl.get(posix_acl::Qualifier::User(4242)), Some(posix_acl::ACL_READ));
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "mmap")]
+// - STD
+use std::fs;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+// - internal
+use zff::{
+	HashType,
+	header::ObjectType,
+	io::{
+		ZffCreationParameters,
+		zffwriter::ZffFilesOutput,
+		zffreader::ZffReader,
+	},
+};
+
+mod common;
+use common::{CHUNK_SIZE, incompressible_data, object_header, test_directory, segment_paths, write_physical_object_with};
+
+#[test]
+fn mmap_reader_reads_multi_segment_container() {
+	let test_dir = test_directory("mmap_reader");
+
+	let data = incompressible_data(8, 0x2545F4914F6CDD1D);
+	let params = ZffCreationParameters {
+		target_segment_size: Some(CHUNK_SIZE * 3),
+		..Default::default()
+	};
+	write_physical_object_with(
+		object_header(1, ObjectType::Physical),
+		Cursor::new(data.clone()),
+		vec![HashType::Blake3],
+		params,
+		ZffFilesOutput::NewContainer(test_dir.join("container")));
+
+	// the order of the paths is not relevant.
+	let mut paths = segment_paths(&test_dir);
+	paths.reverse();
+	assert!(paths.len() > 1);
+
+	let mut reader = ZffReader::with_mmap(&paths).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+
+	let offset = CHUNK_SIZE * 5 + 123;
+	reader.seek(SeekFrom::Start(offset)).unwrap();
+	let mut buffer = vec![0; CHUNK_SIZE as usize];
+	reader.read_exact(&mut buffer).unwrap();
+	assert_eq!(buffer, data[offset as usize..(offset + CHUNK_SIZE) as usize]);
+	assert_eq!(reader.read_range(1, 10, 5000).unwrap(), data[10..5010]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
@@ -0,0 +1,1933 @@
+// - STD
+use std::collections::{HashMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// - internal
+use zff::{
+	Hash,
+	HashType,
+	Segment,
+	Signature,
+	ChunkIntegrity,
+	ZffError,
+	ZffErrorKind,
+	CompressionAlgorithm,
+	train_zstd_dictionary,
+	constants::{COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS, DEFAULT_VERIFY_PROGRESS_INTERVAL},
+	footer::ObjectFooter,
+	header::{ObjectHeader, ObjectType, CompressionHeader, DescriptionHeader, DeduplicationChunkMap, ChunkFlags},
+	io::{
+		ZffCreationParameters,
+		BadBlockReader,
+		ChainedReader,
+		BadBlockStrategy,
+		zffwriter::{ZffWriter, ZffFilesOutput, ZffWriteProgress, DeduplicationStats, SegmentedStreamReader, transcode_container, resegment},
+		zffreader::{ZffReader, SegmentWarning, ChunkFlagStats, VerifyProgress, open_segments_from_paths, peek_container_info},
+	},
+};
+
+mod common;
+use common::{CHUNK_SIZE, input_data, incompressible_data, object_header, test_directory, segment_paths, write_physical_object, write_physical_object_with};
+
+fn build_physical_container(name: &str, data: Vec<u8>, hash_types: Vec<HashType>) -> (PathBuf, ZffReader<File>) {
+	let test_dir = test_directory(name);
+
+	let output = test_dir.join("container");
+	write_physical_object(data, hash_types, None, ZffFilesOutput::NewContainer(output.clone()));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	(test_dir, reader)
+}
+
+#[test]
+fn stored_hashes_match_read_data() {
+	let hash_types = vec![HashType::Blake3, HashType::SHA256, HashType::Blake2b512];
+	let (test_dir, mut reader) = build_physical_container("stored_hashes", input_data(), hash_types.clone());
+
+	let mut data = Vec::new();
+	reader.read_to_end(&mut data).unwrap();
+	assert!(data == input_data());
+
+	let hash_header = match reader.active_object_footer().unwrap() {
+		ObjectFooter::Physical(footer) => footer.hash_header,
+		_ => panic!("unexpected object footer type"),
+	};
+	assert_eq!(hash_header.hashes.len(), hash_types.len());
+	assert_eq!(reader.stored_hash_values(1).unwrap(), hash_header.hashes);
+	assert_eq!(reader.stored_hash_values_all().into_iter().collect::<Vec<_>>(), vec![(1, hash_header.hashes.clone())]);
+	assert!(matches!(reader.stored_hash_values(2).unwrap_err().get_kind(), ZffErrorKind::MissingObjectNumber));
+	for hash_value in hash_header.hashes {
+		assert_eq!(Hash::hash_bytes(hash_value.hash_type(), &data), *hash_value.hash(), "{} hash mismatch", hash_value.hash_type());
+	}
+	// the hash values will be calculated in a single pass over the data.
+	for n_threads in [1, 3] {
+		let calculated = reader.calculate_hash_values(1, n_threads).unwrap();
+		assert_eq!(calculated.len(), hash_types.len());
+		for (calculated, stored) in calculated.iter().zip(reader.stored_hash_values(1).unwrap()) {
+			assert_eq!(calculated.hash_type(), stored.hash_type());
+			assert_eq!(calculated.hash(), stored.hash());
+		}
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn object_data_size_includes_partial_last_chunk() {
+	let data = input_data();
+	let (test_dir, reader) = build_physical_container("object_data_size", data.clone(), vec![HashType::Blake3]);
+	assert_eq!(reader.object_data_size(1).unwrap(), data.len() as u64);
+	assert_eq!(reader.container_data_size(), data.len() as u64);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_all_reports_corrupt_chunks() {
+	let (test_dir, mut reader) = build_physical_container("verify_all", input_data(), vec![HashType::Blake3]);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	// corrupt a compressed chunk and the same bytes chunk.
+	let mut corrupt_offsets = Vec::new();
+	for chunk_number in [4, 2] {
+		corrupt_offsets.push(reader.segment_mut_ref(1).unwrap().get_chunk_offset(&chunk_number).unwrap());
+	}
+	drop(reader);
+	let segment_path = test_dir.join("container.z01");
+	let mut segment = fs::OpenOptions::new().read(true).write(true).open(&segment_path).unwrap();
+	for offset in corrupt_offsets {
+		let mut byte = [0u8; 1];
+		segment.seek(SeekFrom::Start(offset)).unwrap();
+		segment.read_exact(&mut byte).unwrap();
+		segment.seek(SeekFrom::Start(offset)).unwrap();
+		segment.write_all(&[!byte[0]]).unwrap();
+	}
+	drop(segment);
+
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	assert_eq!(reader.verify_all().unwrap(), vec![2, 4]);
+	assert_eq!(reader.verify_all_with_threads(1).unwrap(), vec![2, 4]);
+	assert_eq!(reader.verify_all_with_threads(0).unwrap(), vec![2, 4]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_all_reports_chunks_with_a_corrupt_duplicate_flag() {
+	let (test_dir, mut reader) = build_physical_container("verify_all_duplicate_flag", input_data(), vec![HashType::Blake3]);
+	let flags = reader.chunk_flags(3).unwrap();
+	assert!(!flags.duplicate && !flags.same_bytes);
+	let flags_map_offset = *reader.segment_mut_ref(1).unwrap().footer().chunk_flags_map_table.values().next().unwrap() as usize;
+	drop(reader);
+
+	// the chunk flags map contains the chunk numbers, each followed by the flags of the appropriate chunk.
+	// The content of the (compressed) chunk is not a valid chunk number of an original chunk.
+	let segment_path = test_dir.join("container.z01");
+	let mut segment_data = fs::read(&segment_path).unwrap();
+	let mut entry = 3u64.to_le_bytes().to_vec();
+	entry.push(flags.as_bytes());
+	let position = segment_data[flags_map_offset..].windows(entry.len()).position(|window| window == entry).unwrap();
+	segment_data[flags_map_offset + position + 8] = ChunkFlags { duplicate: true, ..flags }.as_bytes();
+	fs::write(&segment_path, segment_data).unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	assert!(reader.chunk_flags(3).unwrap().duplicate);
+	assert_eq!(reader.verify_all().unwrap(), vec![3]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn progress_callback_reports_every_segment() {
+	let test_dir = test_directory("write_progress");
+
+	// (probably) incompressible data to enforce multiple segments.
+	let data = incompressible_data(16, 0x2545F4914F6CDD1D);
+	let data_len = data.len() as u64;
+
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header(1, ObjectType::Physical), Cursor::new(data));
+	let params = ZffCreationParameters {
+		target_segment_size: Some(CHUNK_SIZE * 5),
+		total_input_size: Some(data_len),
+		..Default::default()
+	};
+	let mut writer = ZffWriter::with_data(
+		physical_objects,
+		HashMap::new(),
+		vec![HashType::Blake3],
+		params,
+		ZffFilesOutput::NewContainer(test_dir.join("container"))).unwrap();
+	assert_eq!(writer.progress().percentage(), Some(0.0));
+
+	let mut progress: Vec<ZffWriteProgress> = Vec::new();
+	writer.generate_files_with_progress(|current| progress.push(current)).unwrap();
+
+	let mut segment_sizes: Vec<u64> = fs::read_dir(&test_dir).unwrap()
+		.map(|entry| entry.unwrap().metadata().unwrap().len())
+		.collect();
+	segment_sizes.sort();
+	assert!(segment_sizes.len() > 1);
+	assert_eq!(progress.len(), segment_sizes.len());
+	let segments = progress.iter().map(|current| current.current_segment).collect::<Vec<_>>();
+	assert_eq!(segments, (1..=segment_sizes.len() as u64).collect::<Vec<_>>());
+	assert!(progress.windows(2).all(|pair| pair[0].bytes_read_total < pair[1].bytes_read_total));
+	assert_eq!(progress.last().unwrap().bytes_read_total, segment_sizes.iter().sum::<u64>());
+	assert_eq!(progress.last().unwrap().files_left_total, 0);
+	assert!(progress.windows(2).all(|pair| pair[0].input_bytes_read <= pair[1].input_bytes_read));
+	assert_eq!(progress.last().unwrap().input_bytes_read, data_len);
+	assert_eq!(progress.last().unwrap().total_input_size, Some(data_len));
+	assert_eq!(progress.last().unwrap().percentage(), Some(100.0));
+	// the percentage is not available without the total input size.
+	assert_eq!(ZffWriteProgress { total_input_size: None, ..progress.last().unwrap().clone() }.percentage(), None);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn segmented_stream_matches_generated_segments() {
+	let test_dir = test_directory("segmented_stream");
+
+	// (probably) incompressible data to enforce multiple segments.
+	let data = incompressible_data(16, 0x2545F4914F6CDD1D);
+	let writer = |output| {
+		let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+		let mut physical_objects = HashMap::new();
+		physical_objects.insert(object_header, Cursor::new(data.clone()));
+		let params = ZffCreationParameters {
+			target_segment_size: Some(CHUNK_SIZE * 5),
+			..Default::default()
+		};
+		ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, output).unwrap()
+	};
+
+	let output = test_dir.join("container");
+	writer(ZffFilesOutput::NewContainer(output.clone())).generate_files().unwrap();
+	let segment_paths = (1..).map(|segment_number| output.with_extension(format!("z{segment_number:02}")))
+		.take_while(|path| path.exists())
+		.collect::<Vec<_>>();
+	assert!(segment_paths.len() > 1);
+
+	let mut stream = SegmentedStreamReader::new(writer(ZffFilesOutput::Stream)).unwrap();
+	// the first segment has to be read completely, before the next segment can be started.
+	let mut buffer = [0u8; 16];
+	stream.read_exact(&mut buffer).unwrap();
+	assert!(matches!(stream.next_segment().unwrap_err().get_kind(), ZffErrorKind::SegmentNotFinished));
+	let stream_output = test_dir.join("stream");
+	let stream_segment_path = |segment_number: u64| stream_output.with_extension(format!("z{segment_number:02}"));
+	let number_of_segments = stream.write_segments(|segment_number| {
+		let mut file = File::create(stream_segment_path(segment_number))?;
+		if segment_number == 1 {
+			file.write_all(&buffer)?;
+		}
+		Ok(file)
+	}).unwrap();
+	let segments = (1..=number_of_segments).map(|segment_number| fs::read(stream_segment_path(segment_number)).unwrap()).collect::<Vec<_>>();
+	assert_eq!(number_of_segments as usize, segment_paths.len());
+	assert_eq!(stream.current_segment_number(), number_of_segments);
+	assert!(!stream.next_segment().unwrap());
+
+	// the segments only differ in the acquisition timestamps.
+	let segment_sizes = segment_paths.iter().map(|path| fs::metadata(path).unwrap().len()).collect::<Vec<_>>();
+	assert_eq!(segments.iter().map(|segment| segment.len() as u64).collect::<Vec<_>>(), segment_sizes);
+
+	let mut reader = ZffReader::with_reader(segments.into_iter().map(Cursor::new).collect()).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn read_range_does_not_change_reader_state() {
+	let data = input_data();
+	let (test_dir, mut reader) = build_physical_container("read_range", data.clone(), vec![HashType::Blake3]);
+
+	let mut buffer = [0u8; 10];
+	reader.read_exact(&mut buffer).unwrap();
+
+	// over the chunk boundary into the same bytes chunk.
+	let offset = CHUNK_SIZE * 3 - 100;
+	assert_eq!(reader.read_range(1, offset, 200).unwrap(), data[offset as usize..offset as usize + 200]);
+	// fewer bytes at the end of the data.
+	let offset = data.len() as u64 - 50;
+	assert_eq!(reader.read_range(1, offset, 200).unwrap(), data[offset as usize..]);
+	assert!(reader.read_range(1, data.len() as u64 + 1, 200).unwrap().is_empty());
+	// the length is limited to the remaining data before the buffer is allocated.
+	assert_eq!(reader.read_range(1, offset, usize::MAX).unwrap(), data[offset as usize..]);
+	assert!(reader.read_range(1, u64::MAX, usize::MAX).unwrap().is_empty());
+	assert!(reader.read_range(2, 0, 200).is_err());
+
+	reader.read_exact(&mut buffer).unwrap();
+	assert_eq!(buffer, data[10..20]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn export_physical_object_writes_original_data() {
+	let data = input_data();
+	let (test_dir, mut reader) = build_physical_container("export", data.clone(), vec![HashType::Blake3]);
+
+	let mut progress = Vec::new();
+	let mut callback = |written_bytes| progress.push(written_bytes);
+	let mut exported = Vec::new();
+	let written_bytes = reader.export_physical_object(1, &mut exported, Some(&mut callback)).unwrap();
+	assert_eq!(written_bytes, data.len() as u64);
+	assert!(exported == data);
+	// input_data() contains 4.5 chunks.
+	assert_eq!(progress.len(), 5);
+	assert_eq!(progress.last(), Some(&(data.len() as u64)));
+
+	assert!(reader.export_physical_object(2, Vec::new(), None).is_err());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn extend_container_rolls_over_into_new_segments() {
+	for initial_segment_size in [None, Some(CHUNK_SIZE * 5)] {
+		let test_dir = test_directory(&format!("extend_{}", initial_segment_size.is_some()));
+
+		let first_data = incompressible_data(8, 0x2545F4914F6CDD1D);
+		let second_data = incompressible_data(16, 0x9E3779B97F4A7C15);
+		write_physical_object(first_data.clone(), vec![HashType::Blake3], initial_segment_size, ZffFilesOutput::NewContainer(test_dir.join("container")));
+		let initial_segments = segment_paths(&test_dir);
+
+		write_physical_object(second_data.clone(), vec![HashType::Blake3], Some(CHUNK_SIZE * 5), ZffFilesOutput::ExtendContainer(initial_segments.clone()));
+		let segments = segment_paths(&test_dir);
+		assert!(segments.len() > initial_segments.len() + 1);
+		let extensions = segments.iter().map(|path| path.extension().unwrap().to_string_lossy().to_string()).collect::<Vec<_>>();
+		let expected = (1..=segments.len()).map(|number| format!("z{number:02}")).collect::<Vec<_>>();
+		assert_eq!(extensions, expected);
+
+		let files = segments.iter().map(|path| File::open(path).unwrap()).collect();
+		let mut reader = ZffReader::with_reader(files).unwrap();
+		reader.initialize_objects_all().unwrap();
+		for (object_number, data) in [(1, first_data), (2, second_data)] {
+			reader.set_active_object(object_number).unwrap();
+			let mut read_data = Vec::new();
+			reader.read_to_end(&mut read_data).unwrap();
+			assert!(read_data == data, "data mismatch in object {object_number}");
+		}
+
+		fs::remove_dir_all(test_dir).unwrap();
+	}
+}
+
+#[test]
+fn open_segments_sorted_by_segment_number() {
+	let test_dir = test_directory("open_segments");
+
+	let data = incompressible_data(16, 0x9E3779B97F4A7C15);
+	write_physical_object(data.clone(), vec![HashType::Blake3], Some(CHUNK_SIZE / 2), ZffFilesOutput::NewContainer(test_dir.join("container")));
+	// the lexicographical order of the file names (z01, z02, ..., z09, z10, ...) differs from the segment order.
+	let mut paths = segment_paths(&test_dir).into_iter().map(|path| {
+		let segment_number: u64 = path.extension().unwrap().to_string_lossy()[1..].parse().unwrap();
+		let renamed_path = test_dir.join(format!("segment.z{segment_number}"));
+		fs::rename(&path, &renamed_path).unwrap();
+		renamed_path
+	}).collect::<Vec<_>>();
+	assert!(paths.len() > 10);
+	paths.sort();
+
+	let files = open_segments_from_paths(&paths).unwrap();
+	let mut reader = ZffReader::with_reader(files).unwrap();
+	let segment_numbers = (1..=paths.len() as u64).map(|segment_number| reader.segment_mut_ref(segment_number).unwrap().header().segment_number).collect::<Vec<_>>();
+	assert_eq!(segment_numbers, (1..=paths.len() as u64).collect::<Vec<_>>());
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn missing_last_segment_is_reported() {
+	let test_dir = test_directory("missing_last_segment");
+
+	let data = incompressible_data(12, 0x2545F4914F6CDD1D);
+	write_physical_object(data, vec![HashType::Blake3], Some(CHUNK_SIZE * 3), ZffFilesOutput::NewContainer(test_dir.join("container")));
+	let mut paths = segment_paths(&test_dir);
+	assert!(paths.len() > 3);
+	let last_segment_number = paths.len() as u64;
+	paths.pop();
+
+	let files = open_segments_from_paths(&paths).unwrap();
+	let error = ZffReader::with_reader(files).err().unwrap();
+	match error.get_kind() {
+		ZffErrorKind::MissingSegment(segment_number) => assert_eq!(*segment_number, last_segment_number),
+		kind => panic!("unexpected error kind: {kind}"),
+	}
+	let supplied_segments = (1..last_segment_number).map(|segment_number| segment_number.to_string()).collect::<Vec<_>>().join(", ");
+	assert!(error.to_string().contains(&format!("segment number {last_segment_number} or higher")));
+	assert!(error.to_string().contains(&format!("[{supplied_segments}]")));
+
+	// only the middle segments are supplied.
+	let files = open_segments_from_paths(&paths[1..3]).unwrap();
+	let error = ZffReader::with_reader(files).err().unwrap();
+	assert!(matches!(error.get_kind(), ZffErrorKind::MissingSegment(4)));
+	assert!(error.to_string().contains("[2, 3]"));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn lossy_reader_skips_missing_and_unreadable_segments() {
+	let test_dir = test_directory("lossy_reader");
+
+	let data = incompressible_data(12, 0x2545F4914F6CDD1D);
+	write_physical_object(data.clone(), vec![HashType::Blake3], Some(CHUNK_SIZE * 3), ZffFilesOutput::NewContainer(test_dir.join("container")));
+	let paths = segment_paths(&test_dir);
+	assert!(paths.len() > 3);
+
+	// segment 2 is missing and segment 3 is truncated.
+	let mut segments = Vec::new();
+	for (index, path) in paths.iter().enumerate() {
+		let mut segment = fs::read(path).unwrap();
+		match index {
+			1 => continue,
+			2 => segment.truncate(segment.len() / 2),
+			_ => (),
+		}
+		segments.push(Cursor::new(segment));
+	}
+	assert!(ZffReader::with_reader(segments.clone()).is_err());
+
+	let (mut reader, warnings) = ZffReader::with_reader_lossy(segments).unwrap();
+	assert!(matches!(warnings[0], SegmentWarning::Unreadable { reader_index: 1, segment_number: Some(3), .. }));
+	let mut missing_chunks = Vec::new();
+	for (warning, expected_segment_number) in warnings[1..].iter().zip([2, 3]) {
+		match warning {
+			SegmentWarning::Missing { segment_number, chunk_ranges } => {
+				assert_eq!(*segment_number, expected_segment_number);
+				assert!(!chunk_ranges.is_empty());
+				for (first, last) in chunk_ranges {
+					missing_chunks.extend(*first..=*last);
+				}
+			},
+			warning => panic!("unexpected warning: {warning}"),
+		}
+	}
+	assert_eq!(warnings.len(), 3);
+
+	reader.initialize_object(1).unwrap();
+	reader.set_active_object(1).unwrap();
+	let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+	assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+	let zff_error = error.get_ref().and_then(|e| e.downcast_ref::<ZffError>()).unwrap();
+	match zff_error.get_kind() {
+		ZffErrorKind::MissingChunk(chunk_number) => assert_eq!(*chunk_number, missing_chunks[0]),
+		kind => panic!("unexpected error kind: {kind}"),
+	}
+
+	// the available chunks are readable; the missing chunks are filled with zeros.
+	reader.set_zero_fill_missing_chunks(true);
+	reader.rewind().unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	let mut expected_data = data;
+	for chunk_number in missing_chunks {
+		let start = ((chunk_number - 1) * CHUNK_SIZE) as usize;
+		expected_data[start..start + CHUNK_SIZE as usize].fill(0);
+	}
+	assert!(read_data == expected_data);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn missing_object_footer_segment_is_reported() {
+	let test_dir = test_directory("missing_footer_segment");
+
+	let first_data = incompressible_data(8, 0x2545F4914F6CDD1D);
+	let second_data = incompressible_data(8, 0x9E3779B97F4A7C15);
+	write_physical_object(first_data, vec![HashType::Blake3], Some(CHUNK_SIZE * 3), ZffFilesOutput::NewContainer(test_dir.join("container")));
+	let initial_segments = segment_paths(&test_dir);
+	assert!(initial_segments.len() > 1);
+	write_physical_object(second_data, vec![HashType::Blake3], Some(CHUNK_SIZE * 3), ZffFilesOutput::ExtendContainer(initial_segments.clone()));
+
+	// the footer of object 1 is stored in the last segment of the initial container, the header in the first one.
+	let footer_segment_number = initial_segments.len() as u64;
+	let segments = segment_paths(&test_dir).iter()
+		.enumerate()
+		.filter(|(index, _)| *index as u64 + 1 != footer_segment_number)
+		.map(|(_, path)| File::open(path).unwrap())
+		.collect();
+	let (mut reader, _) = ZffReader::with_reader_lossy(segments).unwrap();
+	let error = reader.initialize_object(1).unwrap_err();
+	match error.get_kind() {
+		ZffErrorKind::MissingSegment(segment_number) => assert_eq!(*segment_number, footer_segment_number),
+		kind => panic!("unexpected error kind: {kind}"),
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn read_from_non_seekable_stream() {
+	let data = input_data();
+	let (test_dir, _) = build_physical_container("buffered_stream", data.clone(), vec![HashType::Blake3]);
+	let segment = fs::read(test_dir.join("container.z01")).unwrap();
+
+	// a chained reader does not implement Seek.
+	let stream = segment.as_slice().chain(std::io::empty());
+	let mut reader = ZffReader::with_buffered_stream(stream).unwrap();
+	assert_eq!(reader.list_objects().unwrap().len(), 1);
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn detached_signature_verifies_container_footer() {
+	let (test_dir, reader) = build_physical_container("detached_signature", input_data(), vec![HashType::Blake3]);
+	let signing_key = Signature::new_signing_key();
+	let signature = Signature::sign_container_footer(reader.main_footer_ref(), &signing_key);
+
+	let verifying_key = signing_key.verifying_key().to_bytes();
+	assert!(reader.verify_detached_signature(verifying_key, &signature).unwrap());
+	assert!(reader.verify_detached_signature(signing_key.to_keypair_bytes(), &signature).unwrap());
+
+	let other_key = Signature::new_signing_key().verifying_key().to_bytes();
+	assert!(!reader.verify_detached_signature(other_key, &signature).unwrap());
+
+	let mut tampered_signature = signature.clone();
+	tampered_signature[0] ^= 0xFF;
+	assert!(!reader.verify_detached_signature(verifying_key, &tampered_signature).unwrap());
+
+	assert!(reader.verify_detached_signature(verifying_key, &signature[1..]).is_err());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn each_object_uses_its_own_compression_header() {
+	let test_dir = test_directory("per_object_compression");
+
+	let compressed_data = input_data();
+	let uncompressed_data = input_data().into_iter().rev().collect::<Vec<_>>();
+	let mut physical_objects = HashMap::new();
+	for (object_number, compression_algorithm, data) in [
+		(1, CompressionAlgorithm::Zstd, compressed_data.clone()),
+		(2, CompressionAlgorithm::None, uncompressed_data.clone())] {
+		let mut object_header = object_header(object_number, ObjectType::Physical);
+		object_header.compression_header = CompressionHeader::new(compression_algorithm, 3, 1.05);
+		physical_objects.insert(object_header, Cursor::new(data));
+	}
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], ZffCreationParameters::default(), ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	// the object numbers are reassigned by the writer, so the objects will be identified by their data.
+	let mut algorithms = Vec::new();
+	for object_number in [1, 2] {
+		reader.set_active_object(object_number).unwrap();
+		let compression_algorithm = reader.active_object_header_ref().unwrap().compression_header.algorithm.clone();
+		let mut data = Vec::new();
+		reader.read_to_end(&mut data).unwrap();
+		match compression_algorithm {
+			CompressionAlgorithm::Zstd => assert!(data == compressed_data),
+			CompressionAlgorithm::None => assert!(data == uncompressed_data),
+			algorithm => panic!("unexpected compression algorithm: {algorithm}"),
+		}
+
+		let (first_chunk_number, number_of_chunks) = match reader.active_object_footer().unwrap() {
+			ObjectFooter::Physical(footer) => (footer.first_chunk_number, footer.number_of_chunks),
+			_ => panic!("unexpected object footer type"),
+		};
+		let compressed_chunks = (first_chunk_number..first_chunk_number + number_of_chunks)
+			.filter(|chunk_number| reader.chunk_flags(*chunk_number).unwrap().compression)
+			.count();
+		match compression_algorithm {
+			CompressionAlgorithm::Zstd => assert!(compressed_chunks > 0),
+			_ => assert_eq!(compressed_chunks, 0),
+		}
+		algorithms.push(compression_algorithm);
+	}
+	assert!(algorithms.contains(&CompressionAlgorithm::Zstd) && algorithms.contains(&CompressionAlgorithm::None));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn transcode_container_recompresses_physical_object() {
+	let hash_types = vec![HashType::Blake3, HashType::SHA256];
+	let (test_dir, reader) = build_physical_container("transcode_physical", input_data(), hash_types);
+	let source = ZffReader::with_reader(vec![File::open(test_dir.join("container").with_extension("z01")).unwrap()]).unwrap();
+	let output = test_dir.join("transcoded");
+	transcode_container(source, CompressionHeader::new(CompressionAlgorithm::Lz4, 3, 1.05), ZffFilesOutput::NewContainer(output.clone())).unwrap();
+
+	let mut transcoded = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	transcoded.initialize_objects_all().unwrap();
+	transcoded.set_active_object(1).unwrap();
+	let object_header = transcoded.active_object_header_ref().unwrap();
+	assert_eq!(object_header.compression_header.algorithm, CompressionAlgorithm::Lz4);
+	assert_eq!(object_header.chunk_size, CHUNK_SIZE);
+	assert!(transcoded.verify_all().unwrap().is_empty());
+
+	let mut data = Vec::new();
+	transcoded.read_to_end(&mut data).unwrap();
+	assert!(data == input_data());
+	// the order of the hash values is not specified.
+	let hash_values = |reader: &ZffReader<File>| match reader.active_object_footer().unwrap() {
+		ObjectFooter::Physical(footer) => {
+			let mut hashes = footer.hash_header.hashes;
+			hashes.sort_by_key(|hash_value| hash_value.hash_type().to_string());
+			hashes
+		},
+		_ => panic!("unexpected object footer type"),
+	};
+	assert_eq!(hash_values(&transcoded), hash_values(&reader));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn resegment_splits_single_segment_container() {
+	let data = incompressible_data(16, 0xD1B54A32D192ED03);
+	let (test_dir, reader) = build_physical_container("resegment", data.clone(), vec![HashType::Blake3]);
+	let source = ZffReader::with_reader(vec![File::open(test_dir.join("container").with_extension("z01")).unwrap()]).unwrap();
+	let output_dir = test_dir.join("resegmented");
+	fs::create_dir_all(&output_dir).unwrap();
+	resegment(source, CHUNK_SIZE * 5, output_dir.join("container")).unwrap();
+
+	let paths = segment_paths(&output_dir);
+	assert!(paths.len() > 1);
+	for (index, path) in paths.iter().enumerate() {
+		assert_eq!(path.extension().unwrap().to_string_lossy(), format!("z{:02}", index + 1));
+		let segment = Segment::open(File::open(path).unwrap()).unwrap();
+		assert_eq!(segment.header().segment_number, index as u64 + 1);
+		assert_eq!(segment.footer().length_of_segment, fs::metadata(path).unwrap().len());
+	}
+
+	let segments = paths.iter().map(|path| File::open(path).unwrap()).collect::<Vec<_>>();
+	let mut resegmented = ZffReader::with_reader(segments).unwrap();
+	assert_eq!(resegmented.main_footer_ref().number_of_segments(), paths.len() as u64);
+	resegmented.initialize_objects_all().unwrap();
+	resegmented.set_active_object(1).unwrap();
+	assert_eq!(resegmented.active_object_header_ref().unwrap().compression_header, reader.active_object_header_ref().unwrap().compression_header);
+	assert_eq!(resegmented.number_of_chunks(), reader.number_of_chunks());
+	assert!(resegmented.verify_all().unwrap().is_empty());
+
+	let mut resegmented_data = Vec::new();
+	resegmented.read_to_end(&mut resegmented_data).unwrap();
+	assert!(resegmented_data == data);
+	let hash_header = |reader: &ZffReader<File>| match reader.active_object_footer().unwrap() {
+		ObjectFooter::Physical(footer) => footer.hash_header,
+		_ => panic!("unexpected object footer type"),
+	};
+	assert_eq!(hash_header(&resegmented), hash_header(&reader));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn transcode_container_rejects_signed_objects() {
+	let test_dir = test_directory("transcode_signed");
+
+	let mut object_header = object_header(1, ObjectType::Physical);
+	object_header.flags.sign_hash = true;
+	let params = ZffCreationParameters {
+		signature_key: Some(Signature::new_signing_key()),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	write_physical_object_with(object_header, Cursor::new(input_data()), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output.clone()));
+
+	let source = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	let error = transcode_container(source, CompressionHeader::new(CompressionAlgorithm::Lz4, 3, 1.05), ZffFilesOutput::NewContainer(test_dir.join("transcoded"))).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedTranscoding));
+	assert!(!test_dir.join("transcoded").with_extension("z01").exists());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_all_stored_checks_hash_signatures() {
+	let test_dir = test_directory("verify_all_stored");
+
+	let mut object_header = object_header(1, ObjectType::Physical);
+	object_header.flags.sign_hash = true;
+	let signing_key = Signature::new_signing_key();
+	let params = ZffCreationParameters {
+		signature_key: Some(signing_key.clone()),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	write_physical_object_with(
+		object_header, Cursor::new(input_data()), vec![HashType::Blake3, HashType::SHA256], params, ZffFilesOutput::NewContainer(output.clone()));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	assert!(reader.is_signed().unwrap());
+	assert!(reader.object_is_signed(1).unwrap());
+	assert!(matches!(reader.object_is_signed(2).unwrap_err().get_kind(), ZffErrorKind::MissingObjectNumber));
+	assert!(reader.verify_all_stored(signing_key.verifying_key().to_bytes()).unwrap().is_empty());
+	let other_key = Signature::new_signing_key().verifying_key().to_bytes();
+	let invalid_signatures = reader.verify_all_stored(other_key).unwrap();
+	let mut invalid_hash_types = invalid_signatures.iter().map(|invalid| invalid.hash_type.to_string()).collect::<Vec<_>>();
+	invalid_hash_types.sort_unstable();
+	let mut expected_hash_types = vec![HashType::Blake3.to_string(), HashType::SHA256.to_string()];
+	expected_hash_types.sort_unstable();
+	assert_eq!(invalid_hash_types, expected_hash_types);
+	assert!(invalid_signatures.iter().all(|invalid| invalid.file_number.is_none()));
+	assert!(matches!(reader.verify_all_stored([0u8; 3]).unwrap_err().get_kind(), ZffErrorKind::WrongSignatureKeyLength));
+
+	// objects without hash signatures cannot be verified.
+	let (unsigned_test_dir, mut unsigned_reader) = build_physical_container("verify_all_stored_unsigned", input_data(), vec![HashType::Blake3]);
+	assert!(!unsigned_reader.is_signed().unwrap());
+	let error = unsigned_reader.verify_all_stored(other_key).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::InvalidOption));
+
+	fs::remove_dir_all(test_dir).unwrap();
+	fs::remove_dir_all(unsigned_test_dir).unwrap();
+}
+
+#[test]
+fn chained_input_files_are_stored_as_one_physical_object() {
+	let test_dir = test_directory("chained_input");
+
+	// the parts are not aligned to the chunk size (and contain an empty part).
+	let data = input_data();
+	let part_sizes = [CHUNK_SIZE as usize + 100, 0, CHUNK_SIZE as usize * 2 - 7];
+	let mut paths = Vec::new();
+	let mut offset = 0;
+	for (index, part_size) in part_sizes.iter().enumerate() {
+		let path = test_dir.join(format!("disk.{:03}", index + 1));
+		fs::write(&path, &data[offset..offset + part_size]).unwrap();
+		paths.push(path);
+		offset += part_size;
+	}
+	let last_part = test_dir.join("disk.004");
+	fs::write(&last_part, &data[offset..]).unwrap();
+	paths.push(last_part);
+
+	let output = test_dir.join("container");
+	write_physical_object_with(
+		object_header(1, ObjectType::Physical),
+		ChainedReader::open(&paths).unwrap(),
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone()));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	assert_eq!(reader.number_of_chunks(), (data.len() as u64 + CHUNK_SIZE - 1) / CHUNK_SIZE);
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	let hash_value = match reader.active_object_footer().unwrap() {
+		ObjectFooter::Physical(footer) => footer.hash_header.hashes[0].clone(),
+		_ => panic!("unexpected object footer type"),
+	};
+	assert_eq!(Hash::hash_bytes(&HashType::Blake3, &data), *hash_value.hash());
+
+	assert!(ChainedReader::open(&[test_dir.join("missing.001")]).is_err());
+	let mut chained_reader = ChainedReader::new(vec![Cursor::new(vec![1u8, 2]), Cursor::new(Vec::new()), Cursor::new(vec![3u8])]);
+	let mut chained_data = Vec::new();
+	chained_reader.read_to_end(&mut chained_data).unwrap();
+	assert_eq!(chained_data, vec![1, 2, 3]);
+	assert_eq!(chained_reader.remaining_readers(), 0);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_all_with_progress_reports_processed_chunks() {
+	let test_dir = test_directory("verify_progress");
+
+	// small chunks to exceed the progress interval.
+	let chunk_size = 64;
+	let total_chunks = DEFAULT_VERIFY_PROGRESS_INTERVAL + 100;
+	let data = (0..chunk_size * total_chunks).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(chunk_size).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(data));
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(
+		physical_objects, HashMap::new(), Vec::new(), ZffCreationParameters::default(), ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut reports = Vec::new();
+	let corrupt_chunks = reader.verify_all_with_threads_and_progress(2, |progress| reports.push(progress)).unwrap();
+	assert!(corrupt_chunks.is_empty());
+	assert_eq!(reports.len(), 2);
+	assert_eq!(reports[0].chunks_processed, DEFAULT_VERIFY_PROGRESS_INTERVAL);
+	assert_eq!(reports[0].total_chunks, total_chunks);
+	assert_eq!(reports[1], VerifyProgress { chunks_processed: total_chunks, total_chunks, corrupt_chunks: 0 });
+
+	let mut last_report = None;
+	reader.verify_all_with_progress(|progress| last_report = Some(progress)).unwrap();
+	assert_eq!(last_report, Some(reports[1].clone()));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn verify_on_read_detects_corrupt_chunks() {
+	let data = input_data();
+	let (test_dir, mut reader) = build_physical_container("verify_on_read", data.clone(), vec![HashType::Blake3]);
+
+	// corrupt the same bytes chunk (the data of the chunk is still decodable).
+	let offset = reader.segment_mut_ref(1).unwrap().get_chunk_offset(&4).unwrap();
+	drop(reader);
+	let segment_path = test_dir.join("container.z01");
+	let mut segment = fs::OpenOptions::new().read(true).write(true).open(&segment_path).unwrap();
+	let mut byte = [0u8; 1];
+	segment.seek(SeekFrom::Start(offset)).unwrap();
+	segment.read_exact(&mut byte).unwrap();
+	segment.seek(SeekFrom::Start(offset)).unwrap();
+	segment.write_all(&[!byte[0]]).unwrap();
+	drop(segment);
+
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+
+	// disabled by default.
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert_eq!(read_data.len(), data.len());
+	assert!(read_data != data);
+
+	reader.set_verify_on_read(true).unwrap();
+	reader.rewind().unwrap();
+	let mut buffer = vec![0u8; (CHUNK_SIZE * 3) as usize];
+	reader.read_exact(&mut buffer).unwrap();
+	assert!(buffer == data[..buffer.len()]);
+	let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+	assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+	assert!(error.to_string().ends_with(": 4"), "unexpected error: {error}");
+
+	reader.set_verify_on_read(false).unwrap();
+	reader.rewind().unwrap();
+	assert!(reader.read_to_end(&mut Vec::new()).is_ok());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn parallel_encoding_matches_sequential_encoding() {
+	// contains same bytes, duplicated and partial chunks.
+	let mut data = input_data();
+	data.extend(incompressible_data(3, 0x2545F4914F6CDD1D));
+	data.extend(incompressible_data(3, 0x2545F4914F6CDD1D));
+	data.extend(input_data());
+
+	let mut results = Vec::new();
+	for encoding_threads in [None, Some(4)] {
+		let test_dir = test_directory(&format!("encoding_threads_{}", encoding_threads.is_some()));
+
+		let params = ZffCreationParameters {
+			deduplication_chunkmap: Some(DeduplicationChunkMap::new_in_memory_map()),
+			encoding_threads,
+			..Default::default()
+		};
+		let output = test_dir.join("container");
+		write_physical_object_with(
+			object_header(1, ObjectType::Physical), Cursor::new(data.clone()), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output.clone()));
+		let segment_size = fs::metadata(output.with_extension("z01")).unwrap().len();
+
+		let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+		reader.initialize_objects_all().unwrap();
+		reader.set_active_object(1).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert!(read_data == data, "data mismatch (encoding threads: {encoding_threads:?})");
+		assert!(reader.verify_all().unwrap().is_empty());
+		let hash_header = match reader.active_object_footer().unwrap() {
+			ObjectFooter::Physical(footer) => footer.hash_header,
+			_ => panic!("unexpected object footer type"),
+		};
+		results.push((segment_size, hash_header.hashes[0].hash().clone()));
+
+		fs::remove_dir_all(test_dir).unwrap();
+	}
+	assert_eq!(results[0], results[1]);
+}
+
+#[test]
+fn zstd_dictionary_is_stored_and_used_for_reading() {
+	let test_dir = test_directory("zstd_dictionary");
+
+	let records = (0..2000u64).map(|i| format!("{{\"id\": {i}, \"host\": \"workstation-{}\", \"event\": \"logon\", \"duration\": {}}}\n", i % 17, i * 31 % 1000).into_bytes()).collect::<Vec<_>>();
+	let dictionary = train_zstd_dictionary(&records, 2048).unwrap();
+	let data = records.concat();
+
+	let params = ZffCreationParameters {
+		zstd_dictionary: Some(dictionary.clone()),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	write_physical_object_with(
+		object_header(1, ObjectType::Physical), Cursor::new(data.clone()), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output.clone()));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	assert_eq!(reader.active_object_header_ref().unwrap().compression_header.dictionary, Some(dictionary));
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn chunk_integrity_algorithm_is_recorded_and_verified() {
+	let data = input_data();
+	for chunk_integrity in [ChunkIntegrity::Xxh3, ChunkIntegrity::Crc64] {
+		for encoding_threads in [None, Some(4)] {
+			let test_dir = test_directory(&format!("chunk_integrity_{chunk_integrity}_{}", encoding_threads.is_some()));
+
+			let params = ZffCreationParameters {
+				chunk_integrity,
+				encoding_threads,
+				..Default::default()
+			};
+			let output = test_dir.join("container");
+			write_physical_object_with(
+				object_header(1, ObjectType::Physical), Cursor::new(data.clone()), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output.clone()));
+
+			let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+			reader.initialize_objects_all().unwrap();
+			reader.set_active_object(1).unwrap();
+			for chunk_number in 1..=5 {
+				let flags = reader.segment_mut_ref(1).unwrap().get_chunk_flags(&chunk_number).unwrap();
+				assert_eq!(flags.integrity(), chunk_integrity);
+			}
+			assert!(reader.verify_all().unwrap().is_empty());
+			reader.set_verify_on_read(true).unwrap();
+			let mut read_data = Vec::new();
+			reader.read_to_end(&mut read_data).unwrap();
+			assert!(read_data == data);
+
+			fs::remove_dir_all(test_dir).unwrap();
+		}
+	}
+}
+
+#[test]
+fn read_bandwidth_is_throttled() {
+	let test_dir = test_directory("throttled");
+
+	// 8 chunks with 64 KiB/s should take at least 0.5 seconds.
+	let data = incompressible_data(8, 13);
+	let params = ZffCreationParameters {
+		max_read_bytes_per_sec: Some(CHUNK_SIZE * 16),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let start = Instant::now();
+	write_physical_object_with(
+		object_header(1, ObjectType::Physical), Cursor::new(data.clone()), Vec::new(), params, ZffFilesOutput::NewContainer(output.clone()));
+	assert!(start.elapsed() >= Duration::from_millis(450));
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn chunk_flag_statistics_counts_flags_of_all_chunks() {
+	let (test_dir, mut reader) = build_physical_container("chunk_flag_statistics", input_data(), Vec::new());
+
+	// the fourth chunk contains only the same bytes.
+	let flags = reader.chunk_flags(4).unwrap();
+	assert!(flags.same_bytes);
+	assert!(!flags.error);
+
+	let mut expected = ChunkFlagStats::default();
+	for chunk_number in 1..=5 {
+		let flags = reader.chunk_flags(chunk_number).unwrap();
+		expected.chunks += 1;
+		expected.compression += flags.compression as u64;
+		expected.same_bytes += flags.same_bytes as u64;
+	}
+	let stats = reader.chunk_flag_statistics().unwrap();
+	assert_eq!(stats, expected);
+	assert_eq!(stats.chunks, 5);
+	assert_eq!(stats.same_bytes, 1);
+	assert_eq!(stats.error, 0);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn never_compress_threshold_stores_all_chunks_uncompressed() {
+	let data = input_data();
+	for encoding_threads in [None, Some(2)] {
+		let test_dir = test_directory(&format!("never_compress_{}", encoding_threads.is_some()));
+
+		let mut object_header = object_header(1, ObjectType::Physical);
+		object_header.compression_header = CompressionHeader::new(CompressionAlgorithm::Zstd, 3, COMPRESSION_RATIO_THRESHOLD_NEVER_COMPRESS);
+		let params = ZffCreationParameters {
+			encoding_threads,
+			..Default::default()
+		};
+		let output = test_dir.join("container");
+		write_physical_object_with(object_header, Cursor::new(data.clone()), Vec::new(), params, ZffFilesOutput::NewContainer(output.clone()));
+
+		let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+		reader.initialize_objects_all().unwrap();
+		reader.set_active_object(1).unwrap();
+		let stats = reader.chunk_flag_statistics().unwrap();
+		assert_eq!(stats.compression, 0, "compressed chunks found (encoding threads: {encoding_threads:?})");
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert!(read_data == data, "data mismatch (encoding threads: {encoding_threads:?})");
+
+		fs::remove_dir_all(test_dir).unwrap();
+	}
+}
+
+#[test]
+fn incremental_object_references_unchanged_chunks_of_baseline_object() {
+	let test_dir = test_directory("incremental");
+
+	// the baseline object is spread over multiple segments, the chunks 2 and 6 will be changed.
+	let baseline_data = incompressible_data(8, 0x2545F4914F6CDD1D);
+	write_physical_object(baseline_data.clone(), vec![HashType::Blake3], Some(CHUNK_SIZE * 3), ZffFilesOutput::NewContainer(test_dir.join("container")));
+	let mut changed_data = baseline_data.clone();
+	for chunk in [1, 5] {
+		let offset = (chunk * CHUNK_SIZE) as usize;
+		changed_data[offset..offset + CHUNK_SIZE as usize].copy_from_slice(&incompressible_data(1, chunk + 7));
+	}
+	let initial_segments = segment_paths(&test_dir);
+
+	let files = initial_segments.iter().map(|path| File::open(path).unwrap()).collect();
+	let mut reader = ZffReader::with_reader(files).unwrap();
+	reader.initialize_objects_all().unwrap();
+	let mut deduplication_map = DeduplicationChunkMap::new_in_memory_map();
+	reader.fill_deduplication_chunkmap(1, &mut deduplication_map).unwrap();
+	drop(reader);
+
+	let params = ZffCreationParameters {
+		target_segment_size: Some(CHUNK_SIZE * 3),
+		deduplication_chunkmap: Some(deduplication_map),
+		..Default::default()
+	};
+	write_physical_object_with(
+		object_header(2, ObjectType::Physical), Cursor::new(changed_data.clone()), vec![HashType::Blake3], params, ZffFilesOutput::ExtendContainer(initial_segments));
+
+	let files = segment_paths(&test_dir).iter().map(|path| File::open(path).unwrap()).collect();
+	let mut reader = ZffReader::with_reader(files).unwrap();
+	reader.initialize_objects_all().unwrap();
+	let duplicates = (9..=16).filter(|chunk_number| reader.chunk_flags(*chunk_number).unwrap().duplicate).count();
+	assert_eq!(duplicates, 6);
+	for verify_on_read in [false, true] {
+		for (object_number, data) in [(1, &baseline_data), (2, &changed_data)] {
+			reader.set_active_object(object_number).unwrap();
+			assert!(reader.verify_all().unwrap().is_empty());
+			reader.set_verify_on_read(verify_on_read).unwrap();
+			reader.rewind().unwrap();
+			let mut read_data = Vec::new();
+			reader.read_to_end(&mut read_data).unwrap();
+			assert!(&read_data == data, "data mismatch in object {object_number}");
+		}
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn object_chunks_yields_decoded_chunk_data() {
+	let test_dir = test_directory("object_chunks");
+
+	// contains same bytes, duplicated and partial chunks.
+	let mut data = input_data();
+	data.extend(incompressible_data(3, 0x2545F4914F6CDD1D));
+	data.extend(incompressible_data(3, 0x2545F4914F6CDD1D));
+	data.extend(incompressible_data(4, 0x9E3779B97F4A7C15));
+	let params = ZffCreationParameters {
+		target_segment_size: Some(CHUNK_SIZE * 2),
+		deduplication_chunkmap: Some(DeduplicationChunkMap::new_in_memory_map()),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	write_physical_object_with(object_header(1, ObjectType::Physical), Cursor::new(data.clone()), Vec::new(), params, ZffFilesOutput::NewContainer(output));
+
+	let mut segments = segment_paths(&test_dir);
+	assert!(segments.len() > 2);
+	let files = segments.iter().map(|path| File::open(path).unwrap()).collect();
+	let mut reader = ZffReader::with_reader(files).unwrap();
+	reader.initialize_objects_all().unwrap();
+	let mut chunk_numbers = Vec::new();
+	let mut read_data = Vec::new();
+	for chunk in reader.object_chunks(1).unwrap() {
+		let (chunk_number, chunk_data) = chunk.unwrap();
+		chunk_numbers.push(chunk_number);
+		read_data.extend(chunk_data);
+	}
+	assert_eq!(chunk_numbers, (1..=15).collect::<Vec<_>>());
+	assert!(read_data == data);
+	drop(reader);
+
+	// the chunks of a missing segment are yielded as errors, the iteration continues with the next chunk.
+	segments.remove(1);
+	let files = segments.iter().map(|path| File::open(path).unwrap()).collect();
+	let (mut reader, warnings) = ZffReader::with_reader_lossy(files).unwrap();
+	let missing_chunks = match &warnings[0] {
+		SegmentWarning::Missing { chunk_ranges, .. } => chunk_ranges.iter().flat_map(|(first, last)| *first..=*last).collect::<Vec<_>>(),
+		warning => panic!("unexpected warning: {warning}"),
+	};
+	reader.initialize_object(1).unwrap();
+	let chunks = reader.object_chunks(1).unwrap().collect::<Vec<_>>();
+	assert_eq!(chunks.len(), 15);
+	assert!(!missing_chunks.is_empty());
+	for (chunk_number, chunk) in (1..=15).zip(chunks) {
+		assert_eq!(chunk.is_err(), missing_chunks.contains(&chunk_number), "unexpected result for chunk {chunk_number}");
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+// simulates a failing drive: each read operation, which touches the given range, fails.
+struct FailingDevice {
+	data: Cursor<Vec<u8>>,
+	bad_range: std::ops::Range<u64>,
+}
+
+impl Read for FailingDevice {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let position = self.data.position();
+		if position < self.bad_range.end && position + buf.len() as u64 > self.bad_range.start {
+			return Err(std::io::Error::new(std::io::ErrorKind::Other, "input/output error"));
+		}
+		self.data.read(buf)
+	}
+}
+
+impl Seek for FailingDevice {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		self.data.seek(pos)
+	}
+}
+
+#[test]
+fn bad_sectors_are_skipped_and_recorded() {
+	let test_dir = test_directory("bad_sectors");
+
+	// the second sector of the second chunk is unreadable.
+	let data = incompressible_data(4, 0x9E3779B97F4A7C15);
+	let bad_range = CHUNK_SIZE + 512..CHUNK_SIZE + 1024;
+	let mut expected = data.clone();
+	expected[bad_range.start as usize..bad_range.end as usize].fill(0);
+
+	for encoding_threads in [None, Some(2)] {
+		let bad_block_strategy = BadBlockStrategy { retries: 1, sector_size: 512, record_bad_chunks: true };
+		let device = BadBlockReader::new(FailingDevice { data: Cursor::new(data.clone()), bad_range: bad_range.clone() }, &bad_block_strategy);
+
+		let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+		let mut physical_objects = HashMap::new();
+		physical_objects.insert(object_header, device);
+		let params = ZffCreationParameters {
+			encoding_threads,
+			bad_block_strategy: Some(bad_block_strategy),
+			..Default::default()
+		};
+		let output = test_dir.join(format!("container_{encoding_threads:?}"));
+		let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output.clone())).unwrap();
+		writer.generate_files().unwrap();
+
+		let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+		reader.initialize_objects_all().unwrap();
+		reader.set_active_object(1).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert!(read_data == expected);
+
+		let first_chunk_number = match reader.active_object_footer().unwrap() {
+			ObjectFooter::Physical(footer) => {
+				assert_eq!(footer.bad_chunks, vec![footer.first_chunk_number + 1]);
+				footer.first_chunk_number
+			},
+			_ => panic!("unexpected object footer type"),
+		};
+		assert!(reader.chunk_flags(first_chunk_number + 1).unwrap().error);
+		assert!(!reader.chunk_flags(first_chunk_number).unwrap().error);
+		assert_eq!(reader.chunk_flag_statistics().unwrap().error, 1);
+		let error_log = reader.active_object_error_log().unwrap();
+		assert_eq!(error_log.len(), 1);
+		assert_eq!(error_log[0].chunk_number, first_chunk_number + 1);
+		assert!(error_log[0].message.contains(&bad_range.start.to_string()), "unexpected message: {}", error_log[0]);
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn transcode_container_preserves_acquisition_information() {
+	let test_dir = test_directory("transcode_acquisition");
+
+	// the second sector of the second chunk is unreadable.
+	let data = incompressible_data(4, 0x9E3779B97F4A7C15);
+	let bad_block_strategy = BadBlockStrategy { retries: 1, sector_size: 512, record_bad_chunks: true };
+	let device = BadBlockReader::new(FailingDevice { data: Cursor::new(data), bad_range: CHUNK_SIZE + 512..CHUNK_SIZE + 1024 }, &bad_block_strategy);
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, device);
+	let params = ZffCreationParameters {
+		bad_block_strategy: Some(bad_block_strategy),
+		..Default::default()
+	};
+	let source_output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(source_output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	// the timestamps have a resolution of one second, so the transcoding has to take place in a later second.
+	std::thread::sleep(Duration::from_millis(1100));
+	let source = ZffReader::with_reader(vec![File::open(source_output.with_extension("z01")).unwrap()]).unwrap();
+	let output = test_dir.join("transcoded");
+	transcode_container(source, CompressionHeader::new(CompressionAlgorithm::Lz4, 3, 1.05), ZffFilesOutput::NewContainer(output.clone())).unwrap();
+
+	let open = |path: &Path| {
+		let mut reader = ZffReader::with_reader(vec![File::open(path.with_extension("z01")).unwrap()]).unwrap();
+		reader.initialize_objects_all().unwrap();
+		reader.set_active_object(1).unwrap();
+		reader
+	};
+	let source = open(&source_output);
+	let mut transcoded = open(&output);
+	assert!(transcoded.main_footer_ref().acquisition_start().is_some());
+	assert_eq!(transcoded.main_footer_ref().acquisition_start(), source.main_footer_ref().acquisition_start());
+	assert_eq!(transcoded.main_footer_ref().acquisition_end(), source.main_footer_ref().acquisition_end());
+
+	let object_footer = |reader: &ZffReader<File>| match reader.active_object_footer().unwrap() {
+		ObjectFooter::Physical(footer) => footer,
+		_ => panic!("unexpected object footer type"),
+	};
+	let (source_footer, transcoded_footer) = (object_footer(&source), object_footer(&transcoded));
+	assert_eq!(transcoded_footer.acquisition_start, source_footer.acquisition_start);
+	assert_eq!(transcoded_footer.acquisition_end, source_footer.acquisition_end);
+	assert_eq!(transcoded_footer.bad_chunks, vec![transcoded_footer.first_chunk_number + 1]);
+	assert_eq!(transcoded_footer.error_log.len(), 1);
+	assert_eq!(transcoded_footer.error_log[0].chunk_number, transcoded_footer.first_chunk_number + 1);
+	assert_eq!(transcoded_footer.error_log[0].message, source_footer.error_log[0].message);
+	assert!(transcoded.chunk_flags(transcoded_footer.first_chunk_number + 1).unwrap().error);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn read_errors_abort_without_bad_block_strategy() {
+	let data = incompressible_data(2, 0x9E3779B97F4A7C15);
+	let mut device = FailingDevice { data: Cursor::new(data), bad_range: 512..1024 };
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, &mut device);
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), Vec::new(), ZffCreationParameters::default(), ZffFilesOutput::Stream).unwrap();
+	let mut buffer = Vec::new();
+	assert!(writer.read_to_end(&mut buffer).is_err());
+}
+
+// simulates a flaky input device: the first read operations at or after the given position will be interrupted.
+struct InterruptingDevice {
+	data: Cursor<Vec<u8>>,
+	interrupt_at: u64,
+	remaining_interrupts: u32,
+}
+
+impl Read for InterruptingDevice {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if self.data.position() >= self.interrupt_at && self.remaining_interrupts > 0 {
+			self.remaining_interrupts -= 1;
+			return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "interrupted"));
+		}
+		self.data.read(buf)
+	}
+}
+
+#[test]
+fn io_interrupt_retries_are_configurable() {
+	let data = incompressible_data(3, 0x9E3779B97F4A7C15);
+	// (number of retries, expect an error flag for the interrupted chunk)
+	for (io_interrupt_retries, interrupted) in [(3, false), (1, true)] {
+		let device = InterruptingDevice { data: Cursor::new(data.clone()), interrupt_at: CHUNK_SIZE, remaining_interrupts: 3 };
+		let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+		let mut physical_objects = HashMap::new();
+		physical_objects.insert(object_header, device);
+		let params = ZffCreationParameters {
+			io_interrupt_retries: Some(io_interrupt_retries),
+			io_interrupt_wait_ms: Some(0),
+			..Default::default()
+		};
+		let start = Instant::now();
+		let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), Vec::new(), params, ZffFilesOutput::Stream).unwrap();
+		let mut container = Vec::new();
+		writer.read_to_end(&mut container).unwrap();
+		// the default wait time would delay each retry by several seconds.
+		assert!(start.elapsed() < Duration::from_secs(5));
+
+		let mut reader = ZffReader::with_reader(vec![Cursor::new(container)]).unwrap();
+		reader.initialize_objects_all().unwrap();
+		reader.set_active_object(1).unwrap();
+		let first_chunk_number = match reader.active_object_footer().unwrap() {
+			ObjectFooter::Physical(footer) => footer.first_chunk_number,
+			_ => panic!("unexpected object footer type"),
+		};
+		assert_eq!(reader.chunk_flags(first_chunk_number + 1).unwrap().error, interrupted);
+		let error_log = reader.active_object_error_log().unwrap();
+		if interrupted {
+			assert_eq!(error_log.len(), 1);
+			assert_eq!(error_log[0].chunk_number, first_chunk_number + 1);
+		} else {
+			assert!(error_log.is_empty());
+			let mut read_data = Vec::new();
+			reader.read_to_end(&mut read_data).unwrap();
+			assert!(read_data == data);
+		}
+	}
+}
+
+// writes the given data as physical object with parity data and returns the path of the first segment.
+fn build_parity_container(test_dir: &Path, data: Vec<u8>, parity_shards: (usize, usize), target_segment_size: Option<u64>) -> PathBuf {
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(data));
+	let params = ZffCreationParameters {
+		target_segment_size,
+		parity_shards: Some(parity_shards),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+	output.with_extension("z01")
+}
+
+#[test]
+fn reconstruct_chunks_by_parity_data() {
+	let test_dir = test_directory("chunk_parity");
+
+	let mut data = incompressible_data(9, 0x853C49E6748FEA9B);
+	data.extend(vec![0xCD; CHUNK_SIZE as usize]);
+	let segment_path = build_parity_container(&test_dir, data.clone(), (4, 2), None);
+
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	// corrupt two chunks of the first parity group and one chunk of the last (partial) group.
+	let mut corrupt_offsets = Vec::new();
+	for chunk_number in [2, 3, 9] {
+		corrupt_offsets.push(reader.segment_mut_ref(1).unwrap().get_chunk_offset(&chunk_number).unwrap());
+	}
+	drop(reader);
+	let mut segment = fs::OpenOptions::new().read(true).write(true).open(&segment_path).unwrap();
+	for offset in corrupt_offsets {
+		let mut bytes = [0u8; 16];
+		segment.seek(SeekFrom::Start(offset)).unwrap();
+		segment.read_exact(&mut bytes).unwrap();
+		segment.seek(SeekFrom::Start(offset)).unwrap();
+		segment.write_all(&bytes.map(|byte| !byte)).unwrap();
+	}
+	drop(segment);
+
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	assert_eq!(reader.verify_all().unwrap(), vec![2, 3, 9]);
+	for chunk_number in [2, 3, 5, 9, 10] {
+		let offset = ((chunk_number - 1) * CHUNK_SIZE) as usize;
+		assert!(reader.reconstruct_chunk(chunk_number).unwrap() == data[offset..offset + CHUNK_SIZE as usize]);
+	}
+	assert!(matches!(reader.reconstruct_chunk(11).unwrap_err().get_kind(), ZffErrorKind::UnrecoverableChunk(11)));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn reconstruct_chunk_fails_on_too_many_corrupt_chunks() {
+	let test_dir = test_directory("chunk_parity_unrecoverable");
+
+	let segment_path = build_parity_container(&test_dir, incompressible_data(4, 0x5851F42D4C957F2D), (4, 1), None);
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	let mut corrupt_offsets = Vec::new();
+	for chunk_number in [1, 2] {
+		corrupt_offsets.push(reader.segment_mut_ref(1).unwrap().get_chunk_offset(&chunk_number).unwrap());
+	}
+	drop(reader);
+	let mut segment = fs::OpenOptions::new().read(true).write(true).open(&segment_path).unwrap();
+	for offset in corrupt_offsets {
+		segment.seek(SeekFrom::Start(offset)).unwrap();
+		segment.write_all(&[0u8; 16]).unwrap();
+	}
+	drop(segment);
+
+	let mut reader = ZffReader::with_reader(vec![File::open(&segment_path).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	assert!(matches!(reader.reconstruct_chunk(1).unwrap_err().get_kind(), ZffErrorKind::UnrecoverableChunk(1)));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn parity_data_over_multiple_segments() {
+	let test_dir = test_directory("chunk_parity_segments");
+
+	let data = incompressible_data(16, 0x2545F4914F6CDD1D);
+	build_parity_container(&test_dir, data.clone(), (3, 1), Some(CHUNK_SIZE * 5));
+	let paths = segment_paths(&test_dir);
+	assert!(paths.len() > 1);
+
+	let mut reader = ZffReader::with_reader(open_segments_from_paths(&paths).unwrap()).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert!(reader.verify_all().unwrap().is_empty());
+	for chunk_number in 1..=16 {
+		let offset = ((chunk_number - 1) * CHUNK_SIZE) as usize;
+		assert!(reader.reconstruct_chunk(chunk_number).unwrap() == data[offset..offset + CHUNK_SIZE as usize]);
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn inspect_segments_of_multi_segment_container() {
+	let test_dir = test_directory("segment_inspection");
+
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(incompressible_data(16, 0x9E3779B97F4A7C15)));
+	let params = ZffCreationParameters {
+		target_segment_size: Some(CHUNK_SIZE * 5),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output)).unwrap();
+	writer.generate_files().unwrap();
+
+	let paths = segment_paths(&test_dir);
+	assert!(paths.len() > 1);
+
+	let mut next_chunk_number = 1;
+	let mut object_numbers = Vec::new();
+	let mut chunk_segments = BTreeSet::new();
+	for (index, path) in paths.iter().enumerate() {
+		let segment = Segment::open(File::open(path).unwrap()).unwrap();
+		assert_eq!(segment.header().segment_number, index as u64 + 1);
+		object_numbers.extend(segment.object_numbers());
+		if let Some(range) = segment.chunk_number_range() {
+			assert_eq!(*range.start(), next_chunk_number);
+			next_chunk_number = range.end() + 1;
+			chunk_segments.insert(segment.header().segment_number);
+		}
+	}
+	assert_eq!(next_chunk_number, 17);
+	object_numbers.dedup();
+	assert_eq!(object_numbers, vec![1]);
+
+	let mut reader = ZffReader::with_reader(open_segments_from_paths(&paths).unwrap()).unwrap();
+	reader.initialize_objects_all().unwrap();
+	let object_segments = reader.object_segments(1).unwrap();
+	assert_eq!(object_segments.header_segment, 1);
+	assert_eq!(object_segments.footer_segment, paths.len() as u64);
+	assert_eq!(object_segments.chunk_segments, chunk_segments);
+	assert!(matches!(reader.object_segments(2).unwrap_err().get_kind(), ZffErrorKind::MissingObjectNumber));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn invalid_parity_shards_are_rejected() {
+	for parity_shards in [(0, 2), (4, 0), (200, 57)] {
+		let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+		let mut physical_objects = HashMap::new();
+		physical_objects.insert(object_header, Cursor::new(incompressible_data(1, 1)));
+		let params = ZffCreationParameters { parity_shards: Some(parity_shards), ..Default::default() };
+		let error = match ZffWriter::with_data(physical_objects, HashMap::new(), Vec::new(), params, ZffFilesOutput::Stream) {
+			Ok(_) => panic!("invalid parity shards {parity_shards:?} accepted"),
+			Err(e) => e,
+		};
+		assert!(matches!(error.get_kind(), ZffErrorKind::InvalidOption));
+	}
+}
+
+#[test]
+fn reconstruct_chunk_without_parity_data() {
+	let (test_dir, mut reader) = build_physical_container("no_chunk_parity", input_data(), vec![HashType::Blake3]);
+	assert!(matches!(reader.reconstruct_chunk(1).unwrap_err().get_kind(), ZffErrorKind::UnrecoverableChunk(1)));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn find_objects_by_description() {
+	let test_dir = test_directory("find_objects");
+
+	let mut physical_objects = HashMap::new();
+	let mut data = HashMap::new();
+	for (object_number, evidence_number) in [(1, "EV-01"), (2, "EV-02"), (3, "EV-01")] {
+		let mut description_header = DescriptionHeader::new_empty();
+		description_header.set_case_number("find_objects");
+		description_header.set_evidence_number(evidence_number);
+		let object_header = ObjectHeader::builder(object_number, ObjectType::Physical)
+			.chunk_size(CHUNK_SIZE)
+			.description(description_header)
+			.build().unwrap();
+		let object_data = incompressible_data(1, object_number);
+		data.insert(object_data.clone(), evidence_number);
+		physical_objects.insert(object_header, Cursor::new(object_data));
+	}
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), Vec::new(), ZffCreationParameters::default(), ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	assert!(reader.find_by_evidence_number("EV-01").is_empty());
+	reader.initialize_objects_all().unwrap();
+	assert_eq!(reader.find_objects(|description_header| description_header.case_number() == Some("find_objects")), vec![1, 2, 3]);
+	assert!(reader.find_by_evidence_number("EV-03").is_empty());
+
+	// the object numbers are reassigned by the writer, so the objects will be identified by their data.
+	let found_objects = reader.find_by_evidence_number("EV-01");
+	assert_eq!(found_objects.len(), 2);
+	for object_number in found_objects {
+		reader.set_active_object(object_number).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert_eq!(data[&read_data], "EV-01");
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn same_bytes_chunks_of_sparse_data() {
+	let test_dir = test_directory("sparse_data");
+
+	// holes (zeros) between some data chunks and a partial (zero filled) last chunk.
+	let mut data = vec![0u8; CHUNK_SIZE as usize];
+	data.extend(incompressible_data(1, 0x4F1BBCDCBFA53E0B));
+	data.extend(vec![0u8; CHUNK_SIZE as usize * 2]);
+	data.extend(vec![0xFF; CHUNK_SIZE as usize]);
+	data.extend(vec![0u8; CHUNK_SIZE as usize / 2]);
+
+	for encoding_threads in [None, Some(2)] {
+		let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+		let mut physical_objects = HashMap::new();
+		physical_objects.insert(object_header, Cursor::new(data.clone()));
+		let params = ZffCreationParameters { encoding_threads, ..Default::default() };
+		let output = test_dir.join(format!("container_{encoding_threads:?}"));
+		let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::SHA256], params, ZffFilesOutput::NewContainer(output.clone())).unwrap();
+		writer.generate_files().unwrap();
+
+		let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+		reader.initialize_objects_all().unwrap();
+		reader.set_active_object(1).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert!(read_data == data);
+		assert!(reader.verify_all().unwrap().is_empty());
+
+		let same_bytes_chunks = (1..=6).filter(|chunk_number| reader.chunk_flags(*chunk_number).unwrap().same_bytes).collect::<Vec<_>>();
+		assert_eq!(same_bytes_chunks, vec![1, 3, 4, 5]);
+
+		let hash_value = match reader.active_object_footer().unwrap() {
+			ObjectFooter::Physical(footer) => footer.hash_header.hashes[0].clone(),
+			_ => panic!("unexpected object footer type"),
+		};
+		assert_eq!(Hash::hash_bytes(&HashType::SHA256, &data), *hash_value.hash());
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn xxhash_diff_reports_differing_chunks() {
+	let test_dir = test_directory("xxhash_diff");
+
+	let data = incompressible_data(6, 0x2F7A1C3B5D4E6F80);
+	let mut changed_data = data.clone();
+	changed_data[CHUNK_SIZE as usize * 2 + 17] ^= 0xFF;
+	let mut extended_data = data.clone();
+	extended_data.extend(vec![0u8; CHUNK_SIZE as usize * 2]);
+
+	let mut readers = Vec::new();
+	for (name, data) in [("original", data.clone()), ("copy", data), ("changed", changed_data), ("extended", extended_data)] {
+		let output = test_dir.join(name);
+		write_physical_object(data, Vec::new(), None, ZffFilesOutput::NewContainer(output.clone()));
+		readers.push(ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap());
+	}
+	let mut original = readers.remove(0);
+	assert_eq!(original.chunk_xxhashes().unwrap().len(), 6);
+	assert!(original.xxhash_diff(&mut readers[0]).unwrap().is_empty());
+	assert_eq!(original.xxhash_diff(&mut readers[1]).unwrap(), vec![3]);
+	assert_eq!(original.xxhash_diff(&mut readers[2]).unwrap(), vec![7, 8]);
+	assert_eq!(readers[2].xxhash_diff(&mut original).unwrap(), vec![7, 8]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn export_chunk_maps_contains_all_chunks() {
+	let (test_dir, mut reader) = build_physical_container("export_chunk_maps", input_data(), Vec::new());
+
+	let export = reader.export_chunk_maps().unwrap();
+	let chunk_numbers = (1..=5).collect::<Vec<u64>>();
+	assert_eq!(export.offsets.keys().copied().collect::<Vec<_>>(), chunk_numbers);
+	assert_eq!(export.sizes.keys().copied().collect::<Vec<_>>(), chunk_numbers);
+	assert_eq!(export.flags.keys().copied().collect::<Vec<_>>(), chunk_numbers);
+	assert_eq!(export.integrity_hashes, reader.chunk_xxhashes().unwrap());
+	for chunk_number in chunk_numbers {
+		let flags = reader.chunk_flags(chunk_number).unwrap();
+		assert_eq!(export.flags[&chunk_number], flags.names());
+	}
+	// the fourth chunk contains only the same bytes.
+	assert!(export.flags[&4].contains(&"same_bytes".to_string()));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn write_to_single_output() {
+	let data = input_data();
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header.clone(), Cursor::new(data.clone()));
+	let mut writer = ZffWriter::with_data(
+		physical_objects, HashMap::new(), vec![HashType::Blake3], ZffCreationParameters::default(), ZffFilesOutput::Stream).unwrap();
+	let mut container = Vec::new();
+	let bytes_written = writer.write_to(&mut container).unwrap();
+	assert_eq!(bytes_written, container.len() as u64);
+
+	let mut reader = ZffReader::with_reader(vec![Cursor::new(container)]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	// segmented containers can't be written to a single output.
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(data));
+	let params = ZffCreationParameters { target_segment_size: Some(CHUNK_SIZE * 2), ..Default::default() };
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::Stream).unwrap();
+	let err = writer.write_to(Vec::new()).unwrap_err();
+	assert!(matches!(err.get_kind(), ZffErrorKind::InvalidOption));
+}
+
+#[test]
+fn container_acquisition_timestamps_are_stored_in_the_main_footer() {
+	let test_dir = test_directory("acquisition_timestamps");
+
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(incompressible_data(16, 0x2545F4914F6CDD1D)));
+	let params = ZffCreationParameters {
+		target_segment_size: Some(CHUNK_SIZE * 5),
+		..Default::default()
+	};
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::NewContainer(output)).unwrap();
+	writer.generate_files().unwrap();
+
+	let paths = segment_paths(&test_dir);
+	// the main footer is part of the length of the last segment.
+	let last_segment = paths.last().unwrap();
+	let segment = Segment::open(File::open(last_segment).unwrap()).unwrap();
+	assert_eq!(segment.footer().length_of_segment, fs::metadata(last_segment).unwrap().len());
+
+	let segments = paths.iter().map(|path| File::open(path).unwrap()).collect::<Vec<_>>();
+	let mut reader = ZffReader::with_reader(segments).unwrap();
+	let main_footer = reader.main_footer_ref().clone();
+	let acquisition_start = main_footer.acquisition_start().unwrap();
+	let acquisition_end = main_footer.acquisition_end().unwrap();
+	assert_eq!(main_footer.acquisition_duration(), Some(acquisition_end - acquisition_start));
+
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let object_footer = match reader.active_object_footer().unwrap() {
+		ObjectFooter::Physical(object_footer) => object_footer,
+		_ => unreachable!(),
+	};
+	assert!(acquisition_start <= object_footer.acquisition_start);
+	assert!(object_footer.acquisition_end <= acquisition_end);
+
+	fs::remove_dir_all(&test_dir).unwrap();
+}
+
+#[test]
+fn peek_container_info_detects_supported_containers() {
+	let data = input_data();
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(data));
+	let mut writer = ZffWriter::with_data(
+		physical_objects, HashMap::new(), vec![HashType::Blake3], ZffCreationParameters::default(), ZffFilesOutput::Stream).unwrap();
+	let mut container = Vec::new();
+	writer.write_to(&mut container).unwrap();
+
+	let mut reader = Cursor::new(container.clone());
+	reader.set_position(7);
+	let container_info = peek_container_info(&mut reader).unwrap();
+	assert_eq!(reader.position(), 7);
+	assert_eq!(container_info.format_version, 3);
+	assert_eq!(container_info.segment_number, 1);
+	let report = ZffReader::inspect(vec![Cursor::new(container.clone())]).unwrap();
+	assert_eq!(report.format_version, container_info.format_version);
+	assert_eq!(container_info.first_object_encrypted, Some(false));
+
+	for length in [0, 10, 20] {
+		let error = peek_container_info(&mut Cursor::new(&container[..length])).unwrap_err();
+		assert!(matches!(error.get_kind(), ZffErrorKind::ReadEOF));
+	}
+
+	let error = peek_container_info(&mut Cursor::new(b"PK\x03\x04 this is not a zff container".to_vec())).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::HeaderDecodeMismatchIdentifier));
+
+	// the version of the segment header is the first byte after the identifier and the header length.
+	let mut legacy_container = container.clone();
+	legacy_container[12] = 2;
+	let error = peek_container_info(&mut Cursor::new(legacy_container.clone())).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+	assert!(error.to_string().contains("zff v2"));
+	// the reader rejects older containers with the same error.
+	let error = ZffReader::with_reader(vec![Cursor::new(legacy_container)]).err().unwrap();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+	assert!(error.to_string().contains("zff v2"));
+
+	let mut unknown_version = container;
+	unknown_version[12] = 42;
+	let error = peek_container_info(&mut Cursor::new(unknown_version)).unwrap_err();
+	assert!(matches!(error.get_kind(), ZffErrorKind::UnsupportedVersion));
+}
+
+#[test]
+fn deduplication_stats_of_writer() {
+	let chunk = incompressible_data(1, 0x94D049BB133111EB);
+	let mut data = Vec::new();
+	for _ in 0..3 {
+		data.extend_from_slice(&chunk);
+	}
+	data.extend(vec![0xAB; CHUNK_SIZE as usize]);
+	data.extend(incompressible_data(1, 0xBF58476D1CE4E5B9));
+
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(data.clone()));
+	let params = ZffCreationParameters {
+		deduplication_chunkmap: Some(DeduplicationChunkMap::new_in_memory_map()),
+		..Default::default()
+	};
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::Stream).unwrap();
+	assert_eq!(writer.deduplication_stats(), DeduplicationStats::default());
+	let mut container = Vec::new();
+	writer.write_to(&mut container).unwrap();
+
+	assert_eq!(writer.deduplication_stats(), DeduplicationStats {
+		total_chunks: 5,
+		deduplicated_chunks: 2,
+		samebytes_chunks: 1,
+		estimated_bytes_saved: CHUNK_SIZE * 2,
+	});
+
+	let mut reader = ZffReader::with_reader(vec![Cursor::new(container)]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+}
+
+#[test]
+fn redb_deduplication_map_behaves_like_in_memory_map() {
+	let test_dir = test_directory("redb_deduplication");
+
+	// the first chunk number of a hash will be kept.
+	let hash = blake3::hash(b"zff");
+	for mut deduplication_map in [DeduplicationChunkMap::new_in_memory_map(), DeduplicationChunkMap::new_from_path(test_dir.join("entries.redb")).unwrap()] {
+		assert!(deduplication_map.get_chunk_number(hash).is_err());
+		deduplication_map.append_entry(3, hash).unwrap();
+		deduplication_map.append_entry(7, hash).unwrap();
+		assert_eq!(deduplication_map.get_chunk_number(hash).unwrap(), 3);
+	}
+
+	let chunk = incompressible_data(1, 0x94D049BB133111EB);
+	let mut data = Vec::new();
+	for _ in 0..4 {
+		data.extend_from_slice(&chunk);
+		data.extend(incompressible_data(1, data.len() as u64));
+	}
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(data.clone()));
+	let params = ZffCreationParameters {
+		deduplication_chunkmap: Some(DeduplicationChunkMap::new_from_path(test_dir.join("deduplication.redb")).unwrap()),
+		..Default::default()
+	};
+	let mut writer = ZffWriter::with_data(physical_objects, HashMap::new(), vec![HashType::Blake3], params, ZffFilesOutput::Stream).unwrap();
+	let mut container = Vec::new();
+	writer.write_to(&mut container).unwrap();
+	assert_eq!(writer.deduplication_stats().deduplicated_chunks, 3);
+
+	let mut reader = ZffReader::with_reader(vec![Cursor::new(container)]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert!(reader.verify_all().unwrap().is_empty());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn preloaded_redb_can_be_taken_and_reused() {
+	let data = input_data();
+	let (test_dir, mut reader) = build_physical_container("take_preloaded_redb", data.clone(), vec![HashType::Blake3]);
+	let db_path = test_dir.join("preloaded.redb");
+	assert!(reader.take_preloaded_redb().is_none());
+
+	// in-memory preloaded chunkmaps will be converted into the Redb.
+	reader.set_preload_chunkmaps_mode_in_memory().unwrap();
+	reader.preload_chunk_offset_map_full().unwrap();
+	reader.preload_chunk_size_map_full().unwrap();
+	reader.preload_chunk_flags_map_full().unwrap();
+	reader.set_preload_chunkmap_mode_redb(redb::Database::create(&db_path).unwrap()).unwrap();
+	let db = reader.take_preloaded_redb().unwrap();
+	assert!(reader.take_preloaded_redb().is_none());
+	drop(db);
+
+	// the chunkmaps are read from the segments again.
+	let mut read_data = Vec::new();
+	reader.rewind().unwrap();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+
+	// the persisted Redb can be reopened and reused.
+	reader.set_preload_chunkmap_mode_redb(redb::Database::create(&db_path).unwrap()).unwrap();
+	let mut read_data = Vec::new();
+	reader.rewind().unwrap();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert!(reader.take_preloaded_redb().is_some());
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn read_at_does_not_change_the_position() {
+	let data = input_data();
+	let (test_dir, mut reader) = build_physical_container("read_at", data.clone(), vec![HashType::Blake3]);
+	reader.seek(SeekFrom::Start(100)).unwrap();
+
+	// across the border of two chunks.
+	let mut buffer = vec![0u8; 200];
+	let offset = CHUNK_SIZE * 2 - 50;
+	assert_eq!(reader.read_at(offset, &mut buffer).unwrap(), buffer.len());
+	assert!(buffer[..] == data[offset as usize..offset as usize + 200]);
+	assert_eq!(reader.stream_position().unwrap(), 100);
+
+	// the read is limited by the end of the data.
+	let mut buffer = vec![0u8; 200];
+	let offset = data.len() as u64 - 10;
+	let read_bytes = reader.read_at(offset, &mut buffer).unwrap();
+	assert!(read_bytes <= 10);
+	assert!(buffer[..read_bytes] == data[offset as usize..offset as usize + read_bytes]);
+	assert_eq!(reader.read_at(data.len() as u64 + 10, &mut buffer).unwrap(), 0);
+
+	let mut buffer = vec![0u8; 10];
+	reader.read_exact(&mut buffer).unwrap();
+	assert!(buffer[..] == data[100..110]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn zff_errors_are_converted_to_the_appropriate_io_error_kind() {
+	let cases = [
+		(ZffError::new(ZffErrorKind::ReadEOF, "eof"), std::io::ErrorKind::UnexpectedEof),
+		(ZffError::new(ZffErrorKind::MissingChunk(1), "missing chunk"), std::io::ErrorKind::NotFound),
+		(ZffError::new_header_decode_error("decode"), std::io::ErrorKind::InvalidData),
+		(ZffError::from(std::io::Error::from(std::io::ErrorKind::PermissionDenied)), std::io::ErrorKind::PermissionDenied),
+		(ZffError::new_custom("custom"), std::io::ErrorKind::Other),
+	];
+	for (zff_error, expected_kind) in cases {
+		let message = zff_error.to_string();
+		let io_error = std::io::Error::from(zff_error);
+		assert_eq!(io_error.kind(), expected_kind);
+		assert_eq!(io_error.to_string(), message);
+	}
+}
+
+#[test]
+fn chunk_cache_serves_repeatedly_read_chunks() {
+	let data = input_data();
+	let (test_dir, mut reader) = build_physical_container("chunk_cache", data.clone(), vec![HashType::Blake3]);
+	assert_eq!(reader.chunk_cache_capacity(), 0);
+	reader.set_chunk_cache_capacity(2);
+
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == data);
+	assert_eq!(reader.cached_chunks(), 2);
+
+	// the same bytes chunk (chunk 4) will be read from the cache, even if the data was changed in the segment.
+	let chunk_range = (CHUNK_SIZE * 3) as usize..(CHUNK_SIZE * 4) as usize;
+	let mut buffer = vec![0u8; CHUNK_SIZE as usize];
+	reader.read_at(CHUNK_SIZE * 3, &mut buffer).unwrap();
+	assert!(buffer[..] == data[chunk_range.clone()]);
+	let offset = reader.segment_mut_ref(1).unwrap().get_chunk_offset(&4).unwrap();
+	let mut segment = fs::OpenOptions::new().read(true).write(true).open(test_dir.join("container.z01")).unwrap();
+	let mut byte = [0u8; 1];
+	segment.seek(SeekFrom::Start(offset)).unwrap();
+	segment.read_exact(&mut byte).unwrap();
+	segment.seek(SeekFrom::Start(offset)).unwrap();
+	segment.write_all(&[!byte[0]]).unwrap();
+	drop(segment);
+	for _ in 0..10 {
+		reader.read_at(CHUNK_SIZE * 3, &mut buffer).unwrap();
+		assert!(buffer[..] == data[chunk_range.clone()]);
+	}
+
+	// disabling the cache removes all cached chunks.
+	reader.set_chunk_cache_capacity(0);
+	assert_eq!(reader.cached_chunks(), 0);
+	reader.read_at(CHUNK_SIZE * 3, &mut buffer).unwrap();
+	assert!(buffer[..] != data[chunk_range]);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "serde")]
+// - STD
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::Cursor;
+
+// - internal
+use zff::{
+	HashType,
+	header::{ObjectHeader, ObjectType, MetadataExtendedValue},
+	io::{
+		ZffCreationParameters,
+		zffwriter::{ZffWriter, ZffFilesOutput},
+		zffreader::ZffReader,
+	},
+};
+
+mod common;
+use common::{CHUNK_SIZE, test_directory};
+
+fn roundtrip(value: &MetadataExtendedValue) -> MetadataExtendedValue {
+	let json = serde_json::to_string(value).unwrap();
+	serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn metadata_extended_values_are_tagged() {
+	assert_eq!(serde_json::to_string(&MetadataExtendedValue::U64(123)).unwrap(), r#"{"type":"u64","value":123}"#);
+	assert_eq!(serde_json::to_string(&MetadataExtendedValue::I16(-1)).unwrap(), r#"{"type":"i16","value":-1}"#);
+	assert_eq!(serde_json::to_string(&MetadataExtendedValue::ByteArray(vec![1, 2])).unwrap(), r#"{"type":"bytearray","value":[1,2]}"#);
+	assert_eq!(
+		serde_json::to_string(&MetadataExtendedValue::Vector(vec![MetadataExtendedValue::Bool(true)])).unwrap(),
+		r#"{"type":"vector","value":[{"type":"bool","value":true}]}"#);
+}
+
+#[test]
+fn metadata_extended_values_roundtrip_losslessly() {
+	let mut btreemap = BTreeMap::new();
+	btreemap.insert("user.comment".to_string(), MetadataExtendedValue::ByteArray(vec![0, 0xFF, 0x7F]));
+	let mut hashmap = HashMap::new();
+	hashmap.insert("acl:u:1000".to_string(), MetadataExtendedValue::String("rwx".to_string()));
+	let values = [
+		MetadataExtendedValue::U8(u8::MAX),
+		MetadataExtendedValue::U16(u16::MAX),
+		// e.g. the dwFileAttributes of windows files.
+		MetadataExtendedValue::U32(0x20),
+		MetadataExtendedValue::U64(u64::MAX),
+		MetadataExtendedValue::I8(i8::MIN),
+		MetadataExtendedValue::I16(i16::MIN),
+		MetadataExtendedValue::I32(i32::MIN),
+		MetadataExtendedValue::I64(i64::MIN),
+		MetadataExtendedValue::String("zff".to_string()),
+		MetadataExtendedValue::Hashmap(hashmap),
+		MetadataExtendedValue::BTreeMap(btreemap),
+		MetadataExtendedValue::ByteArray(Vec::new()),
+		MetadataExtendedValue::from(1.5f32),
+		MetadataExtendedValue::from(-2.25f64),
+		MetadataExtendedValue::Vector(vec![MetadataExtendedValue::U8(1), MetadataExtendedValue::String("a".to_string())]),
+		MetadataExtendedValue::Bool(false),
+	];
+	for value in values {
+		assert_eq!(roundtrip(&value), value);
+	}
+}
+
+#[test]
+fn file_metadata_roundtrip_losslessly() {
+	let test_dir = test_directory("serde_metadata");
+	fs::create_dir_all(test_dir.join("input")).unwrap();
+	fs::write(test_dir.join("input").join("file.txt"), b"zff serde metadata test").unwrap();
+
+	let object_header = ObjectHeader::builder(1, ObjectType::Logical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut logical_objects = HashMap::new();
+	logical_objects.insert(object_header, vec![test_dir.join("input")]);
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::<Cursor<Vec<u8>>>::with_data(
+		HashMap::new(),
+		logical_objects,
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader.set_active_object(1).unwrap();
+	for file_number in reader.file_numbers().unwrap() {
+		reader.set_active_file(file_number).unwrap();
+		let metadata = reader.current_file_metadata().unwrap();
+		assert!(!metadata.is_empty());
+		let json = serde_json::to_string(&metadata).unwrap();
+		let decoded: HashMap<String, MetadataExtendedValue> = serde_json::from_str(&json).unwrap();
+		assert_eq!(decoded, metadata);
+	}
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn chunk_maps_are_exported_as_json() {
+	let test_dir = test_directory("serde_chunk_maps");
+
+	let object_header = ObjectHeader::builder(1, ObjectType::Physical).chunk_size(CHUNK_SIZE).build().unwrap();
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header, Cursor::new(vec![0x5A; CHUNK_SIZE as usize * 2]));
+	let output = test_dir.join("container");
+	let mut writer = ZffWriter::with_data(
+		physical_objects,
+		HashMap::new(),
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(output.clone())).unwrap();
+	writer.generate_files().unwrap();
+
+	let mut reader = ZffReader::with_reader(vec![File::open(output.with_extension("z01")).unwrap()]).unwrap();
+	let export = reader.export_chunk_maps().unwrap();
+	let json: serde_json::Value = serde_json::to_value(&export).unwrap();
+	assert_eq!(json["flags"]["1"], serde_json::json!(["same_bytes"]));
+	assert_eq!(json["offsets"].as_object().unwrap().len(), 2);
+	assert_eq!(json["sizes"]["2"], serde_json::json!(export.sizes[&2]));
+	assert_eq!(json["integrity_hashes"]["1"], serde_json::json!(export.integrity_hashes[&1]));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
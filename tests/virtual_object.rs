@@ -0,0 +1,214 @@
+// - STD
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+// - internal
+use zff::{
+	HashType,
+	VirtualObjectRange,
+	header::ObjectType,
+	io::{
+		ZffCreationParameters,
+		zffwriter::{ZffWriter, ZffFilesOutput},
+		zffreader::{ZffReader, ObjectType as ReaderObjectType},
+	},
+};
+
+mod common;
+use common::{CHUNK_SIZE, object_header, test_directory, segment_paths};
+
+fn object_data(number_of_chunks: u64, seed: u64) -> Vec<u8> {
+	let mut state = seed;
+	(0..CHUNK_SIZE * number_of_chunks).map(|i| {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		// mix some compressible parts into the data.
+		if i % 3 == 0 { 0 } else { state as u8 }
+	}).collect()
+}
+
+fn write_objects(physical_data: Option<Vec<u8>>, ranges: Option<Vec<VirtualObjectRange>>, output: ZffFilesOutput) {
+	let mut physical_objects = HashMap::new();
+	if let Some(data) = physical_data {
+		physical_objects.insert(object_header(1, ObjectType::Physical), Cursor::new(data));
+	}
+	let mut virtual_objects = HashMap::new();
+	if let Some(ranges) = ranges {
+		virtual_objects.insert(object_header(1, ObjectType::Virtual), ranges);
+	}
+	let mut writer = ZffWriter::with_virtual_objects(
+		physical_objects,
+		HashMap::new(),
+		virtual_objects,
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		output).unwrap();
+	writer.generate_files().unwrap();
+}
+
+fn open_reader(directory: &Path) -> ZffReader<File> {
+	let files = segment_paths(directory).iter().map(|path| File::open(path).unwrap()).collect();
+	let mut reader = ZffReader::with_reader(files).unwrap();
+	reader.initialize_objects_all().unwrap();
+	reader
+}
+
+fn expected_data(ranges: &[VirtualObjectRange], objects: &HashMap<u64, Vec<u8>>) -> Vec<u8> {
+	let mut data = Vec::new();
+	for range in ranges {
+		let start = range.offset as usize;
+		data.extend_from_slice(&objects[&range.object_number][start..start + range.length as usize]);
+	}
+	data
+}
+
+#[test]
+fn virtual_object_links_physical_object_of_same_container() {
+	let test_dir = test_directory("virtual_same_container");
+	let physical_data = object_data(6, 0x2545F4914F6CDD1D);
+	let ranges = vec![
+		VirtualObjectRange::new(1, 5000, 3000),
+		VirtualObjectRange::new(1, 0, 100),
+		VirtualObjectRange::new(1, CHUNK_SIZE * 2 - 10, CHUNK_SIZE * 3 + 20),
+	];
+	write_objects(Some(physical_data.clone()), Some(ranges.clone()), ZffFilesOutput::NewContainer(test_dir.join("container")));
+
+	let mut reader = open_reader(&test_dir);
+	let objects = reader.list_objects().unwrap();
+	assert_eq!(objects.get(&1), Some(&ReaderObjectType::Physical));
+	assert_eq!(objects.get(&2), Some(&ReaderObjectType::Virtual));
+
+	// the physical object is still readable.
+	reader.set_active_object(1).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == physical_data);
+
+	// the virtual object only refers to the data of the physical object.
+	assert_eq!(reader.object_data_size(1).unwrap(), physical_data.len() as u64);
+	assert_eq!(reader.container_data_size(), physical_data.len() as u64);
+
+	let expected = expected_data(&ranges, &HashMap::from([(1, physical_data)]));
+	assert_eq!(reader.object_data_size(2).unwrap(), expected.len() as u64);
+	reader.set_active_object(2).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == expected);
+
+	// read a small part across the border of the first and second range.
+	reader.seek(SeekFrom::Start(2990)).unwrap();
+	let mut buffer = vec![0u8; 50];
+	reader.read_exact(&mut buffer).unwrap();
+	assert!(buffer == expected[2990..3040]);
+
+	reader.seek(SeekFrom::End(-20)).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == expected[expected.len() - 20..]);
+
+	// a positioned read does not change the position.
+	reader.seek(SeekFrom::Start(10)).unwrap();
+	let mut buffer = vec![0u8; 50];
+	assert_eq!(reader.read_at(2990, &mut buffer).unwrap(), buffer.len());
+	assert!(buffer == expected[2990..3040]);
+	assert_eq!(reader.stream_position().unwrap(), 10);
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn virtual_object_links_multiple_physical_objects_of_same_container() {
+	let test_dir = test_directory("virtual_multiple_physical_objects");
+	let objects = HashMap::from([(1, object_data(3, 0x9E3779B97F4A7C15)), (2, object_data(4, 0x2545F4914F6CDD1D))]);
+	let ranges = vec![
+		VirtualObjectRange::new(2, CHUNK_SIZE - 10, CHUNK_SIZE * 2),
+		VirtualObjectRange::new(1, 100, CHUNK_SIZE),
+		VirtualObjectRange::new(2, 0, 20),
+	];
+	let mut physical_objects = HashMap::new();
+	for (object_number, data) in &objects {
+		physical_objects.insert(object_header(*object_number, ObjectType::Physical), Cursor::new(data.clone()));
+	}
+	let mut virtual_objects = HashMap::new();
+	virtual_objects.insert(object_header(1, ObjectType::Virtual), ranges.clone());
+	let mut writer = ZffWriter::with_virtual_objects(
+		physical_objects,
+		HashMap::new(),
+		virtual_objects,
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		ZffFilesOutput::NewContainer(test_dir.join("container"))).unwrap();
+	writer.generate_files().unwrap();
+
+	// the physical objects keep the order of the given object numbers.
+	let mut reader = open_reader(&test_dir);
+	for (object_number, data) in &objects {
+		reader.set_active_object(*object_number).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert!(read_data == *data, "data mismatch in object {object_number}");
+	}
+
+	assert_eq!(reader.list_objects().unwrap().get(&3), Some(&ReaderObjectType::Virtual));
+	reader.set_active_object(3).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == expected_data(&ranges, &objects));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn virtual_object_links_objects_of_extended_container() {
+	let test_dir = test_directory("virtual_extended_container");
+	let first_data = object_data(4, 0x9E3779B97F4A7C15);
+	let second_data = object_data(5, 0x2545F4914F6CDD1D);
+	write_objects(Some(first_data.clone()), None, ZffFilesOutput::NewContainer(test_dir.join("container")));
+	write_objects(Some(second_data.clone()), None, ZffFilesOutput::ExtendContainer(segment_paths(&test_dir)));
+
+	let ranges = vec![
+		VirtualObjectRange::new(2, CHUNK_SIZE, CHUNK_SIZE * 2 + 1),
+		VirtualObjectRange::new(1, 17, CHUNK_SIZE * 3),
+		VirtualObjectRange::new(2, 0, 1),
+	];
+	write_objects(None, Some(ranges.clone()), ZffFilesOutput::ExtendContainer(segment_paths(&test_dir)));
+
+	let mut reader = open_reader(&test_dir);
+	let objects = HashMap::from([(1, first_data), (2, second_data)]);
+	for (object_number, data) in &objects {
+		reader.set_active_object(*object_number).unwrap();
+		let mut read_data = Vec::new();
+		reader.read_to_end(&mut read_data).unwrap();
+		assert!(read_data == *data, "data mismatch in object {object_number}");
+	}
+
+	reader.set_active_object(3).unwrap();
+	let mut read_data = Vec::new();
+	reader.read_to_end(&mut read_data).unwrap();
+	assert!(read_data == expected_data(&ranges, &objects));
+
+	fs::remove_dir_all(test_dir).unwrap();
+}
+
+#[test]
+fn virtual_object_with_invalid_ranges_is_rejected() {
+	let test_dir = test_directory("virtual_invalid_ranges");
+	let output = ZffFilesOutput::NewContainer(test_dir.join("container"));
+	let mut physical_objects = HashMap::new();
+	physical_objects.insert(object_header(1, ObjectType::Physical), Cursor::new(object_data(1, 1)));
+	let mut virtual_objects = HashMap::new();
+	virtual_objects.insert(object_header(1, ObjectType::Virtual), vec![VirtualObjectRange::new(1, CHUNK_SIZE - 1, 2)]);
+	let mut writer = ZffWriter::with_virtual_objects(
+		physical_objects,
+		HashMap::new(),
+		virtual_objects,
+		vec![HashType::Blake3],
+		ZffCreationParameters::default(),
+		output).unwrap();
+	assert!(writer.generate_files().is_err());
+
+	let _ = fs::remove_dir_all(test_dir);
+}